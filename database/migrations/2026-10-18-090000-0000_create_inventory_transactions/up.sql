@@ -0,0 +1,11 @@
+-- Your SQL goes here
+CREATE TABLE inventory_transactions (
+  id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+  product_id INTEGER NOT NULL REFERENCES products(id),
+  place_id INTEGER NOT NULL REFERENCES places(id),
+  supplier_id INTEGER REFERENCES suppliers(id),
+  quantity INTEGER NOT NULL,
+  transaction_type TEXT NOT NULL,
+  reason TEXT,
+  created_at TEXT NOT NULL DEFAULT (datetime('now'))
+)
@@ -0,0 +1,12 @@
+-- Your SQL goes here
+CREATE TABLE stock_transfers (
+  id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+  product_id INTEGER NOT NULL REFERENCES products(id),
+  from_place_id INTEGER NOT NULL REFERENCES places(id),
+  to_place_id INTEGER NOT NULL REFERENCES places(id),
+  quantity INTEGER NOT NULL CHECK (quantity > 0),
+  outbound_transaction_id INTEGER NOT NULL REFERENCES inventory_transactions(id),
+  inbound_transaction_id INTEGER NOT NULL REFERENCES inventory_transactions(id),
+  reason TEXT,
+  created_at TEXT NOT NULL DEFAULT (datetime('now'))
+)
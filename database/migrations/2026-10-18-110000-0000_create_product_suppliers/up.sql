@@ -0,0 +1,14 @@
+-- Your SQL goes here
+CREATE TABLE product_suppliers (
+  id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+  product_id INTEGER NOT NULL REFERENCES products(id),
+  supplier_id INTEGER NOT NULL REFERENCES suppliers(id),
+  supplier_sku TEXT,
+  last_unit_cost DOUBLE,
+  currency TEXT NOT NULL DEFAULT 'BRL',
+  min_order_quantity INTEGER NOT NULL DEFAULT 1,
+  lead_time_days INTEGER,
+  created_at TEXT NOT NULL DEFAULT (datetime('now')),
+  updated_at TEXT NOT NULL DEFAULT (datetime('now')),
+  UNIQUE (product_id, supplier_id)
+)
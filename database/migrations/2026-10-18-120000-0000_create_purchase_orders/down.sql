@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE purchase_order_lines;
+DROP TABLE purchase_orders;
@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE goods_receipt_lines;
+DROP TABLE goods_receipts;
@@ -0,0 +1,16 @@
+-- Your SQL goes here
+CREATE TABLE goods_receipts (
+  id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+  purchase_order_id INTEGER NOT NULL REFERENCES purchase_orders(id),
+  place_id INTEGER NOT NULL REFERENCES places(id),
+  notes TEXT,
+  created_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE TABLE goods_receipt_lines (
+  id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+  goods_receipt_id INTEGER NOT NULL REFERENCES goods_receipts(id),
+  purchase_order_line_id INTEGER NOT NULL REFERENCES purchase_order_lines(id),
+  quantity INTEGER NOT NULL CHECK (quantity > 0),
+  inventory_transaction_id INTEGER NOT NULL REFERENCES inventory_transactions(id)
+)
@@ -0,0 +1,6 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE product_suppliers DROP COLUMN preferred;
+
+ALTER TABLE products DROP COLUMN max_stock;
+ALTER TABLE products DROP COLUMN safety_stock;
+ALTER TABLE products DROP COLUMN reorder_point;
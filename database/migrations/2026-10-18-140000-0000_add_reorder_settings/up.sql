@@ -0,0 +1,6 @@
+-- Your SQL goes here
+ALTER TABLE products ADD COLUMN reorder_point INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE products ADD COLUMN safety_stock INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE products ADD COLUMN max_stock INTEGER NOT NULL DEFAULT 0;
+
+ALTER TABLE product_suppliers ADD COLUMN preferred BOOLEAN NOT NULL DEFAULT 0;
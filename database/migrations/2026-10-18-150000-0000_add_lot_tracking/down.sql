@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE inventory_transactions DROP COLUMN expiry_date;
+ALTER TABLE inventory_transactions DROP COLUMN lot_number;
+
+ALTER TABLE products DROP COLUMN tracks_lots;
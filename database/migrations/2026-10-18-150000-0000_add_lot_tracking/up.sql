@@ -0,0 +1,5 @@
+-- Your SQL goes here
+ALTER TABLE products ADD COLUMN tracks_lots BOOLEAN NOT NULL DEFAULT 0;
+
+ALTER TABLE inventory_transactions ADD COLUMN lot_number TEXT;
+ALTER TABLE inventory_transactions ADD COLUMN expiry_date TEXT;
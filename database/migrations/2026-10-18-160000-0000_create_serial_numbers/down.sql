@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE serial_number_movements;
+DROP TABLE serial_numbers;
+
+ALTER TABLE products DROP COLUMN serialized;
@@ -0,0 +1,18 @@
+-- Your SQL goes here
+ALTER TABLE products ADD COLUMN serialized BOOLEAN NOT NULL DEFAULT 0;
+
+CREATE TABLE serial_numbers (
+  id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+  product_id INTEGER NOT NULL REFERENCES products(id),
+  serial_number TEXT NOT NULL,
+  place_id INTEGER REFERENCES places(id),
+  created_at TEXT NOT NULL DEFAULT (datetime('now')),
+  updated_at TEXT NOT NULL DEFAULT (datetime('now')),
+  UNIQUE (product_id, serial_number)
+);
+
+CREATE TABLE serial_number_movements (
+  id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+  serial_number_id INTEGER NOT NULL REFERENCES serial_numbers(id),
+  inventory_transaction_id INTEGER NOT NULL REFERENCES inventory_transactions(id)
+)
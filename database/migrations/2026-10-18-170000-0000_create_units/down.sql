@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE purchase_order_lines DROP COLUMN unit_id;
+ALTER TABLE products DROP COLUMN unit_id;
+
+DROP TABLE units;
@@ -0,0 +1,30 @@
+-- Your SQL goes here
+CREATE TABLE units (
+  id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+  name TEXT NOT NULL,
+  symbol TEXT NOT NULL,
+  base_unit_id INTEGER REFERENCES units(id),
+  factor DOUBLE NOT NULL DEFAULT 1 CHECK (factor > 0),
+  created_at TEXT NOT NULL DEFAULT (datetime('now')),
+  updated_at TEXT NOT NULL DEFAULT (datetime('now')),
+  deleted_at TEXT
+);
+
+INSERT INTO units (name, symbol) VALUES
+  ('Unit', 'un'),
+  ('Kilogram', 'kg'),
+  ('Liter', 'L');
+
+INSERT INTO units (name, symbol, base_unit_id, factor) VALUES
+  ('Gram', 'g', (SELECT id FROM units WHERE symbol = 'kg'), 0.001),
+  ('Milliliter', 'mL', (SELECT id FROM units WHERE symbol = 'L'), 0.001);
+
+-- Keep the units products were already using.
+INSERT INTO units (name, symbol)
+SELECT DISTINCT unity, unity FROM products
+WHERE unity IS NOT NULL AND unity <> '' AND unity NOT IN (SELECT symbol FROM units);
+
+ALTER TABLE products ADD COLUMN unit_id INTEGER REFERENCES units(id);
+UPDATE products SET unit_id = (SELECT id FROM units WHERE units.symbol = products.unity);
+
+ALTER TABLE purchase_order_lines ADD COLUMN unit_id INTEGER REFERENCES units(id);
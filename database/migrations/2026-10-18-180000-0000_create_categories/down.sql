@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE products DROP COLUMN category_id;
+
+DROP TABLE categories;
@@ -0,0 +1,6 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX product_barcodes_barcode_unique;
+DROP TABLE product_barcodes;
+
+DROP INDEX products_sku_unique;
+ALTER TABLE products DROP COLUMN sku;
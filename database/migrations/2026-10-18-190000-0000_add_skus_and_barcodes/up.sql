@@ -0,0 +1,18 @@
+-- Your SQL goes here
+ALTER TABLE products ADD COLUMN sku TEXT;
+
+-- Deleted products give up their SKU so it can be reused.
+CREATE UNIQUE INDEX products_sku_unique ON products (sku) WHERE deleted_at IS NULL;
+
+CREATE TABLE product_barcodes (
+  id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+  product_id INTEGER NOT NULL REFERENCES products(id),
+  barcode TEXT NOT NULL,
+  symbology TEXT NOT NULL,
+  created_at TEXT NOT NULL DEFAULT (datetime('now')),
+  -- Copied from the product when it is deleted or restored, since the index
+  -- below cannot look at the products table.
+  deleted_at TEXT
+);
+
+CREATE UNIQUE INDEX product_barcodes_barcode_unique ON product_barcodes (barcode) WHERE deleted_at IS NULL;
@@ -0,0 +1,22 @@
+-- Your SQL goes here
+CREATE TABLE stocktakes (
+  id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+  place_id INTEGER REFERENCES places(id),
+  status TEXT NOT NULL DEFAULT 'counting',
+  notes TEXT,
+  approved_at TEXT,
+  created_at TEXT NOT NULL DEFAULT (datetime('now')),
+  updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE TABLE stocktake_lines (
+  id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+  stocktake_id INTEGER NOT NULL REFERENCES stocktakes(id),
+  product_id INTEGER NOT NULL REFERENCES products(id),
+  place_id INTEGER NOT NULL REFERENCES places(id),
+  lot_number TEXT,
+  expected_quantity INTEGER NOT NULL,
+  counted_quantity INTEGER,
+  created_at TEXT NOT NULL DEFAULT (datetime('now')),
+  updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE settings;
+
+ALTER TABLE inventory_transactions DROP COLUMN unit_cost;
@@ -0,0 +1,19 @@
+-- Your SQL goes here
+ALTER TABLE inventory_transactions ADD COLUMN unit_cost DOUBLE;
+
+-- Goods already received take the cost of their purchase order line, per unit
+-- of the product (lines can be ordered in another unit).
+UPDATE inventory_transactions
+SET unit_cost = (
+  SELECT purchase_order_lines.unit_cost * goods_receipt_lines.quantity / inventory_transactions.quantity
+  FROM goods_receipt_lines
+  INNER JOIN purchase_order_lines ON purchase_order_lines.id = goods_receipt_lines.purchase_order_line_id
+  WHERE goods_receipt_lines.inventory_transaction_id = inventory_transactions.id
+)
+WHERE id IN (SELECT inventory_transaction_id FROM goods_receipt_lines);
+
+CREATE TABLE settings (
+  key TEXT NOT NULL PRIMARY KEY,
+  value TEXT NOT NULL,
+  updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
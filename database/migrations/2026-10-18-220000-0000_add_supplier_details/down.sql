@@ -0,0 +1,10 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE suppliers DROP COLUMN active;
+ALTER TABLE suppliers DROP COLUMN notes;
+ALTER TABLE suppliers DROP COLUMN payment_terms;
+ALTER TABLE suppliers DROP COLUMN address;
+ALTER TABLE suppliers DROP COLUMN email;
+ALTER TABLE suppliers DROP COLUMN mobile_phone;
+ALTER TABLE suppliers DROP COLUMN phone;
+ALTER TABLE suppliers DROP COLUMN contact_name;
+ALTER TABLE suppliers DROP COLUMN tax_id;
@@ -0,0 +1,10 @@
+-- Your SQL goes here
+ALTER TABLE suppliers ADD COLUMN tax_id TEXT;
+ALTER TABLE suppliers ADD COLUMN contact_name TEXT;
+ALTER TABLE suppliers ADD COLUMN phone TEXT;
+ALTER TABLE suppliers ADD COLUMN mobile_phone TEXT;
+ALTER TABLE suppliers ADD COLUMN email TEXT;
+ALTER TABLE suppliers ADD COLUMN address TEXT;
+ALTER TABLE suppliers ADD COLUMN payment_terms TEXT;
+ALTER TABLE suppliers ADD COLUMN notes TEXT;
+ALTER TABLE suppliers ADD COLUMN active BOOLEAN NOT NULL DEFAULT 1;
@@ -0,0 +1,17 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX places_active_name_idx;
+
+CREATE TABLE places_old (
+  id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+  name TEXT NOT NULL UNIQUE,
+  created_at TEXT NOT NULL DEFAULT (datetime('now')),
+  updated_at TEXT NOT NULL DEFAULT (datetime('now')),
+  deleted_at TEXT
+);
+
+INSERT INTO places_old (id, name, created_at, updated_at, deleted_at)
+SELECT id, name, created_at, updated_at, deleted_at FROM places;
+
+DROP TABLE places;
+
+ALTER TABLE places_old RENAME TO places;
@@ -0,0 +1,23 @@
+-- Your SQL goes here
+-- Names only have to be unique among siblings now ("A" can be an aisle of every
+-- warehouse), so the table is rebuilt without the UNIQUE constraint on name.
+CREATE TABLE places_new (
+  id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+  name TEXT NOT NULL,
+  created_at TEXT NOT NULL DEFAULT (datetime('now')),
+  updated_at TEXT NOT NULL DEFAULT (datetime('now')),
+  deleted_at TEXT,
+  parent_id INTEGER REFERENCES places(id),
+  place_type TEXT NOT NULL DEFAULT 'warehouse'
+);
+
+INSERT INTO places_new (id, name, created_at, updated_at, deleted_at)
+SELECT id, name, created_at, updated_at, deleted_at FROM places;
+
+DROP TABLE places;
+
+ALTER TABLE places_new RENAME TO places;
+
+-- Top level places have no parent, so they are grouped under 0. Deleted places
+-- must not block a new one with the same name.
+CREATE UNIQUE INDEX places_active_name_idx ON places (COALESCE(parent_id, 0), name) WHERE deleted_at IS NULL;
@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE inventory_transactions DROP COLUMN reservation_id;
+
+DROP TABLE reservations;
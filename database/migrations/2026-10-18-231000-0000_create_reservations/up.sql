@@ -0,0 +1,16 @@
+-- Your SQL goes here
+CREATE TABLE reservations (
+  id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+  product_id INTEGER NOT NULL REFERENCES products(id),
+  place_id INTEGER REFERENCES places(id),
+  quantity INTEGER NOT NULL CHECK (quantity > 0),
+  consumed_quantity INTEGER NOT NULL DEFAULT 0,
+  reference TEXT NOT NULL,
+  notes TEXT,
+  expires_on TEXT,
+  status TEXT NOT NULL DEFAULT 'active',
+  created_at TEXT NOT NULL DEFAULT (datetime('now')),
+  updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+ALTER TABLE inventory_transactions ADD COLUMN reservation_id INTEGER REFERENCES reservations(id);
@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE issue_lines;
+DROP TABLE issues;
@@ -0,0 +1,26 @@
+-- Your SQL goes here
+CREATE TABLE issues (
+  id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+  place_id INTEGER NOT NULL REFERENCES places(id),
+  requester TEXT NOT NULL,
+  department TEXT NOT NULL,
+  destination TEXT,
+  status TEXT NOT NULL DEFAULT 'draft',
+  notes TEXT,
+  posted_at TEXT,
+  created_at TEXT NOT NULL DEFAULT (datetime('now')),
+  updated_at TEXT NOT NULL DEFAULT (datetime('now')),
+  deleted_at TEXT
+);
+
+CREATE TABLE issue_lines (
+  id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+  issue_id INTEGER NOT NULL REFERENCES issues(id),
+  product_id INTEGER NOT NULL REFERENCES products(id),
+  quantity INTEGER NOT NULL CHECK (quantity > 0),
+  lot_number TEXT,
+  serial_numbers TEXT,
+  inventory_transaction_id INTEGER REFERENCES inventory_transactions(id),
+  created_at TEXT NOT NULL DEFAULT (datetime('now')),
+  updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
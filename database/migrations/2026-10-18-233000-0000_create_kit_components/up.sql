@@ -0,0 +1,11 @@
+-- Your SQL goes here
+CREATE TABLE kit_components (
+  id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+  kit_product_id INTEGER NOT NULL REFERENCES products(id),
+  component_product_id INTEGER NOT NULL REFERENCES products(id),
+  quantity INTEGER NOT NULL CHECK (quantity > 0),
+  created_at TEXT NOT NULL DEFAULT (datetime('now')),
+  updated_at TEXT NOT NULL DEFAULT (datetime('now')),
+  UNIQUE (kit_product_id, component_product_id),
+  CHECK (kit_product_id <> component_product_id)
+);
@@ -0,0 +1,12 @@
+-- Your SQL goes here
+CREATE TABLE audit_log (
+  id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+  entity TEXT NOT NULL,
+  entity_id INTEGER NOT NULL,
+  action TEXT NOT NULL,
+  -- JSON array of {"field", "before", "after"} objects
+  changes TEXT NOT NULL DEFAULT '[]',
+  created_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX audit_log_entity_idx ON audit_log (entity, entity_id);
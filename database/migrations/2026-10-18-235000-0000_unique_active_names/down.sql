@@ -0,0 +1,26 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX suppliers_active_name_idx;
+
+CREATE TABLE suppliers_old (
+  id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+  name TEXT NOT NULL UNIQUE,
+  created_at TEXT NOT NULL DEFAULT (datetime('now')),
+  updated_at TEXT NOT NULL DEFAULT (datetime('now')),
+  deleted_at TEXT,
+  tax_id TEXT,
+  contact_name TEXT,
+  phone TEXT,
+  mobile_phone TEXT,
+  email TEXT,
+  address TEXT,
+  payment_terms TEXT,
+  notes TEXT,
+  active BOOLEAN NOT NULL DEFAULT 1
+);
+
+INSERT INTO suppliers_old (id, name, created_at, updated_at, deleted_at, tax_id, contact_name, phone, mobile_phone, email, address, payment_terms, notes, active)
+SELECT id, name, created_at, updated_at, deleted_at, tax_id, contact_name, phone, mobile_phone, email, address, payment_terms, notes, active FROM suppliers;
+
+DROP TABLE suppliers;
+
+ALTER TABLE suppliers_old RENAME TO suppliers;
@@ -0,0 +1,30 @@
+-- Your SQL goes here
+-- Deleting is soft, so a name only has to be unique among active rows: a
+-- deleted supplier must not block a new one with the same name. The suppliers
+-- table is rebuilt without the UNIQUE constraint on name; places already got
+-- their index when they were rebuilt for the hierarchy.
+CREATE TABLE suppliers_new (
+  id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+  name TEXT NOT NULL,
+  created_at TEXT NOT NULL DEFAULT (datetime('now')),
+  updated_at TEXT NOT NULL DEFAULT (datetime('now')),
+  deleted_at TEXT,
+  tax_id TEXT,
+  contact_name TEXT,
+  phone TEXT,
+  mobile_phone TEXT,
+  email TEXT,
+  address TEXT,
+  payment_terms TEXT,
+  notes TEXT,
+  active BOOLEAN NOT NULL DEFAULT 1
+);
+
+INSERT INTO suppliers_new (id, name, created_at, updated_at, deleted_at, tax_id, contact_name, phone, mobile_phone, email, address, payment_terms, notes, active)
+SELECT id, name, created_at, updated_at, deleted_at, tax_id, contact_name, phone, mobile_phone, email, address, payment_terms, notes, active FROM suppliers;
+
+DROP TABLE suppliers;
+
+ALTER TABLE suppliers_new RENAME TO suppliers;
+
+CREATE UNIQUE INDEX suppliers_active_name_idx ON suppliers (name) WHERE deleted_at IS NULL;
@@ -0,0 +1,186 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+
+use crate::infra::models::AuditLogRow;
+
+const NAIVE_DATE_TIME_PATTERN: &str =  "%Y-%m-%d %H:%M:%S";
+
+/// A field of a record as it is kept in the audit log, with its value as text.
+pub type AuditField = (&'static str, Option<String>);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuditEntity {
+    Product,
+    Supplier,
+    Place,
+    Category,
+    Unit,
+    PurchaseOrder,
+    Issue,
+    Reservation,
+    Stocktake,
+    /// Settings are kept under entity id 0, one field per key.
+    Setting,
+}
+
+impl AuditEntity {
+    pub const ALL: [AuditEntity; 10] = [
+        AuditEntity::Product,
+        AuditEntity::Supplier,
+        AuditEntity::Place,
+        AuditEntity::Category,
+        AuditEntity::Unit,
+        AuditEntity::PurchaseOrder,
+        AuditEntity::Issue,
+        AuditEntity::Reservation,
+        AuditEntity::Stocktake,
+        AuditEntity::Setting,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuditEntity::Product => "product",
+            AuditEntity::Supplier => "supplier",
+            AuditEntity::Place => "place",
+            AuditEntity::Category => "category",
+            AuditEntity::Unit => "unit",
+            AuditEntity::PurchaseOrder => "purchase_order",
+            AuditEntity::Issue => "issue",
+            AuditEntity::Reservation => "reservation",
+            AuditEntity::Stocktake => "stocktake",
+            AuditEntity::Setting => "setting",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            AuditEntity::Product => "Product",
+            AuditEntity::Supplier => "Supplier",
+            AuditEntity::Place => "Place",
+            AuditEntity::Category => "Category",
+            AuditEntity::Unit => "Unit",
+            AuditEntity::PurchaseOrder => "Purchase order",
+            AuditEntity::Issue => "Issue",
+            AuditEntity::Reservation => "Reservation",
+            AuditEntity::Stocktake => "Stocktake",
+            AuditEntity::Setting => "Setting",
+        }
+    }
+}
+
+impl TryFrom<&str> for AuditEntity {
+    type Error = Box<dyn Error>;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        AuditEntity::ALL
+            .into_iter()
+            .find(|entity| entity.as_str() == value)
+            .ok_or_else(|| format!("Unknown audit entity '{}'", value).into())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuditAction {
+    Create,
+    Edit,
+    Delete,
+    Restore,
+    Purge,
+}
+
+impl AuditAction {
+    pub const ALL: [AuditAction; 5] = [
+        AuditAction::Create,
+        AuditAction::Edit,
+        AuditAction::Delete,
+        AuditAction::Restore,
+        AuditAction::Purge,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuditAction::Create => "create",
+            AuditAction::Edit => "edit",
+            AuditAction::Delete => "delete",
+            AuditAction::Restore => "restore",
+            AuditAction::Purge => "purge",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            AuditAction::Create => "Created",
+            AuditAction::Edit => "Edited",
+            AuditAction::Delete => "Deleted",
+            AuditAction::Restore => "Restored",
+            AuditAction::Purge => "Purged",
+        }
+    }
+}
+
+impl TryFrom<&str> for AuditAction {
+    type Error = Box<dyn Error>;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        AuditAction::ALL
+            .into_iter()
+            .find(|action| action.as_str() == value)
+            .ok_or_else(|| format!("Unknown audit action '{}'", value).into())
+    }
+}
+
+/// One field of a record before and after a change. Creations have no
+/// `before` and deletions no `after`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldChange {
+    pub field: String,
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+/// One change to a record, as listed in its history.
+#[derive(Debug, Clone)]
+pub struct AuditEntry {
+    pub action: AuditAction,
+    pub changes: Vec<FieldChange>,
+    pub created_at: NaiveDateTime,
+}
+
+impl TryFrom<AuditLogRow> for AuditEntry {
+    type Error = Box<dyn Error>;
+
+    fn try_from(row: AuditLogRow) -> Result<Self, Self::Error> {
+        Ok(AuditEntry {
+            action: AuditAction::try_from(row.action.as_str())?,
+            changes: serde_json::from_str(&row.changes)?,
+            created_at: NaiveDateTime::parse_from_str(&row.created_at, NAIVE_DATE_TIME_PATTERN)?,
+        })
+    }
+}
+
+/// Fields whose value differs between two snapshots of a record. A field
+/// missing from one side counts as empty there, so an empty `before` lists
+/// every field of a new record and an empty `after` every field of a deleted one.
+pub fn diff_fields(before: &[AuditField], after: &[AuditField]) -> Vec<FieldChange> {
+    let value = |fields: &[AuditField], name: &str| fields.iter()
+        .find(|(field, _)| *field == name)
+        .and_then(|(_, value)| value.clone());
+
+    let mut names: Vec<&str> = before.iter().map(|(field, _)| *field).collect();
+
+    for (field, _) in after {
+        if !names.contains(field) {
+            names.push(field);
+        }
+    }
+
+    names.into_iter()
+        .map(|name| FieldChange {
+            field: name.to_owned(),
+            before: value(before, name),
+            after: value(after, name),
+        })
+        .filter(|change| change.before != change.after)
+        .collect()
+}
@@ -0,0 +1,185 @@
+use std::error::Error;
+
+use crate::infra::models::ProductBarcodeRow;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Symbology {
+    Ean13,
+    UpcA,
+    Ean8,
+    Code128,
+}
+
+impl Symbology {
+    pub const ALL: [Symbology; 4] = [
+        Symbology::Ean13,
+        Symbology::UpcA,
+        Symbology::Ean8,
+        Symbology::Code128,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Symbology::Ean13 => "ean13",
+            Symbology::UpcA => "upc_a",
+            Symbology::Ean8 => "ean8",
+            Symbology::Code128 => "code128",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Symbology::Ean13 => "EAN-13",
+            Symbology::UpcA => "UPC-A",
+            Symbology::Ean8 => "EAN-8",
+            Symbology::Code128 => "Code 128",
+        }
+    }
+
+    /// Works out the symbology of a typed or scanned code. 13, 12 and 8 digit
+    /// codes are EAN-13, UPC-A and EAN-8 and must carry a valid check digit;
+    /// anything else made of printable ASCII is Code 128.
+    pub fn detect(code: &str) -> Result<Symbology, Box<dyn Error>> {
+        if code.is_empty() {
+            return Err("Barcodes cannot be empty".into());
+        }
+
+        let all_digits = code.chars().all(|char| char.is_ascii_digit());
+
+        let gs1_symbology = match code.len() {
+            13 => Some(Symbology::Ean13),
+            12 => Some(Symbology::UpcA),
+            8 => Some(Symbology::Ean8),
+            _ => None,
+        };
+
+        if all_digits && let Some(symbology) = gs1_symbology {
+            if !has_valid_check_digit(code) {
+                return Err(format!("{} has an invalid check digit", code).into());
+            }
+
+            return Ok(symbology);
+        }
+
+        if !code.chars().all(|char| (' '..='~').contains(&char)) {
+            return Err(format!("{} contains characters that cannot be printed as a barcode", code).into());
+        }
+
+        Ok(Symbology::Code128)
+    }
+}
+
+impl TryFrom<&str> for Symbology {
+    type Error = Box<dyn Error>;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Symbology::ALL
+            .into_iter()
+            .find(|symbology| symbology.as_str() == value)
+            .ok_or_else(|| format!("Unknown barcode symbology '{}'", value).into())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ProductBarcode {
+    pub product_id: i32,
+    pub barcode: String,
+    pub symbology: Symbology,
+}
+
+impl TryFrom<ProductBarcodeRow> for ProductBarcode {
+    type Error = Box<dyn Error>;
+
+    fn try_from(row: ProductBarcodeRow) -> Result<Self, Self::Error> {
+        Ok(ProductBarcode {
+            product_id: row.product_id,
+            barcode: row.barcode,
+            symbology: Symbology::try_from(row.symbology.as_str())?,
+        })
+    }
+}
+
+/// GS1 check digit (EAN-13, UPC-A, EAN-8): weights 3 and 1 alternate from the digit
+/// next to the check digit, and the weighted sum must end in zero.
+fn has_valid_check_digit(code: &str) -> bool {
+    let sum: u32 = code.chars()
+        .rev()
+        .filter_map(|char| char.to_digit(10))
+        .enumerate()
+        .map(|(index, digit)| if index % 2 == 1 { digit * 3 } else { digit })
+        .sum();
+
+    sum.is_multiple_of(10)
+}
+
+/// Splits barcodes typed one per line or separated by commas, dropping blanks.
+pub fn parse_barcodes(text: &str) -> Vec<String> {
+    text.split([',', '\n'])
+        .map(str::trim)
+        .filter(|barcode| !barcode.is_empty())
+        .map(str::to_owned)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_valid_ean13_codes() {
+        assert_eq!(Symbology::detect("4006381333931").unwrap(), Symbology::Ean13);
+        assert_eq!(Symbology::detect("5901234123457").unwrap(), Symbology::Ean13);
+    }
+
+    #[test]
+    fn rejects_ean13_codes_with_wrong_check_digits() {
+        assert!(Symbology::detect("4006381333932").is_err());
+        assert!(Symbology::detect("5901234123450").is_err());
+    }
+
+    #[test]
+    fn accepts_valid_upc_a_codes() {
+        assert_eq!(Symbology::detect("036000291452").unwrap(), Symbology::UpcA);
+    }
+
+    #[test]
+    fn rejects_upc_a_codes_with_wrong_check_digits() {
+        assert!(Symbology::detect("036000291453").is_err());
+    }
+
+    #[test]
+    fn accepts_valid_ean8_codes() {
+        assert_eq!(Symbology::detect("96385074").unwrap(), Symbology::Ean8);
+        assert_eq!(Symbology::detect("73513537").unwrap(), Symbology::Ean8);
+    }
+
+    #[test]
+    fn rejects_ean8_codes_with_wrong_check_digits() {
+        assert!(Symbology::detect("96385075").is_err());
+    }
+
+    #[test]
+    fn treats_codes_with_letters_as_code128() {
+        assert_eq!(Symbology::detect("400638133393A").unwrap(), Symbology::Code128);
+        assert_eq!(Symbology::detect("ABC-123").unwrap(), Symbology::Code128);
+    }
+
+    #[test]
+    fn treats_other_lengths_of_digits_as_code128() {
+        assert_eq!(Symbology::detect("40063813339").unwrap(), Symbology::Code128);
+        assert_eq!(Symbology::detect("40063813339310").unwrap(), Symbology::Code128);
+        assert_eq!(Symbology::detect("9638507").unwrap(), Symbology::Code128);
+    }
+
+    #[test]
+    fn rejects_empty_and_unprintable_codes() {
+        assert!(Symbology::detect("").is_err());
+        assert!(Symbology::detect("ABC\t123").is_err());
+        assert!(Symbology::detect("Código").is_err());
+    }
+
+    #[test]
+    fn splits_barcodes_by_line_or_comma() {
+        assert_eq!(parse_barcodes("4006381333931, 96385074\n\n ABC-123 "), vec!["4006381333931", "96385074", "ABC-123"]);
+    }
+}
@@ -0,0 +1,79 @@
+use crate::infra::models::CategoryRow;
+use crate::domain::audit::AuditField;
+
+/// Separates the levels of a category path, e.g. "Tools > Hand Tools".
+pub const CATEGORY_PATH_SEPARATOR: &str = " > ";
+
+#[derive(Debug, Clone)]
+pub struct Category {
+    pub id: i32,
+    pub name: String,
+    /// `None` for top-level categories.
+    pub parent_id: Option<i32>,
+}
+
+impl From<CategoryRow> for Category {
+    fn from(row: CategoryRow) -> Self {
+        Category {
+            id: row.id,
+            name: row.name,
+            parent_id: row.parent_id,
+        }
+    }
+}
+
+impl Category {
+    /// Fields kept in the audit log when the category changes.
+    pub fn audit_fields(&self) -> Vec<AuditField> {
+        vec![
+            ("name", Some(self.name.clone())),
+            ("parent_id", self.parent_id.map(|id| id.to_string())),
+        ]
+    }
+}
+
+/// Full path of a category from the top level down, e.g. "Tools > Hand Tools".
+pub fn category_path(categories: &[Category], category_id: i32) -> String {
+    let mut names = Vec::new();
+    let mut current = categories.iter().find(|category| category.id == category_id);
+
+    // Bounded by the number of categories in case the tree was corrupted into a cycle.
+    while let Some(category) = current
+        && names.len() < categories.len() {
+        names.push(category.name.as_str());
+        current = category.parent_id
+            .and_then(|parent_id| categories.iter().find(|category| category.id == parent_id));
+    }
+
+    names.reverse();
+    names.join(CATEGORY_PATH_SEPARATOR)
+}
+
+/// The category itself and every category below it.
+pub fn descendant_ids(categories: &[Category], category_id: i32) -> Vec<i32> {
+    let mut ids = vec![category_id];
+    let mut index = 0;
+
+    while index < ids.len() {
+        let parent_id = ids[index];
+
+        for category in categories {
+            if category.parent_id == Some(parent_id) && !ids.contains(&category.id) {
+                ids.push(category.id);
+            }
+        }
+
+        index += 1;
+    }
+
+    ids
+}
+
+/// Splits a "Parent > Child" path into its category names, dropping blanks.
+pub fn parse_category_path(path: &str) -> Vec<String> {
+    path.split('>')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(str::to_owned)
+        .collect()
+}
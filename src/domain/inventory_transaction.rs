@@ -0,0 +1,120 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use std::error::Error;
+use std::fmt;
+
+use crate::infra::models::InventoryTransactionRow;
+
+const NAIVE_DATE_TIME_PATTERN: &str =  "%Y-%m-%d %H:%M:%S";
+pub const NAIVE_DATE_PATTERN: &str = "%Y-%m-%d";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransactionType {
+    Entry,
+    Exit,
+    Adjustment,
+    TransferOut,
+    TransferIn,
+}
+
+impl TransactionType {
+    pub const ALL: [TransactionType; 5] = [
+        TransactionType::Entry,
+        TransactionType::Exit,
+        TransactionType::Adjustment,
+        TransactionType::TransferOut,
+        TransactionType::TransferIn,
+    ];
+
+    /// Types that can be recorded by hand; transfers are always written in pairs
+    /// by `stock_transfer_repository`.
+    pub const MANUAL: [TransactionType; 3] = [
+        TransactionType::Entry,
+        TransactionType::Exit,
+        TransactionType::Adjustment,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TransactionType::Entry => "entry",
+            TransactionType::Exit => "exit",
+            TransactionType::Adjustment => "adjustment",
+            TransactionType::TransferOut => "transfer_out",
+            TransactionType::TransferIn => "transfer_in",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            TransactionType::Entry => "Entry",
+            TransactionType::Exit => "Exit",
+            TransactionType::Adjustment => "Adjustment",
+            TransactionType::TransferOut => "Transfer out",
+            TransactionType::TransferIn => "Transfer in",
+        }
+    }
+
+    /// Applies the sign used by the ledger: entries add stock, exits remove it and
+    /// adjustments keep the sign typed by the user.
+    pub fn signed_quantity(&self, quantity: i32) -> i32 {
+        match self {
+            TransactionType::Entry | TransactionType::TransferIn => quantity.abs(),
+            TransactionType::Exit | TransactionType::TransferOut => -quantity.abs(),
+            TransactionType::Adjustment => quantity,
+        }
+    }
+}
+
+impl fmt::Display for TransactionType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.label())
+    }
+}
+
+impl TryFrom<&str> for TransactionType {
+    type Error = Box<dyn Error>;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        TransactionType::ALL
+            .into_iter()
+            .find(|transaction_type| transaction_type.as_str() == value)
+            .ok_or_else(|| format!("Unknown transaction type '{}'", value).into())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct InventoryTransaction {
+    pub id: i32,
+    pub product_id: i32,
+    pub place_id: i32,
+    pub supplier_id: Option<i32>,
+    pub quantity: i32,
+    pub transaction_type: TransactionType,
+    pub reason: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub lot_number: Option<String>,
+    pub expiry_date: Option<NaiveDate>,
+    pub unit_cost: Option<f64>,
+}
+
+impl TryFrom<InventoryTransactionRow> for InventoryTransaction {
+    type Error = Box<dyn Error>;
+
+    fn try_from(row: InventoryTransactionRow) -> Result<Self, Self::Error> {
+        Ok(InventoryTransaction {
+            id: row.id,
+            product_id: row.product_id,
+            place_id: row.place_id,
+            supplier_id: row.supplier_id,
+            quantity: row.quantity,
+            transaction_type: TransactionType::try_from(row.transaction_type.as_str())?,
+            reason: row.reason,
+            created_at: NaiveDateTime::parse_from_str(&row.created_at, NAIVE_DATE_TIME_PATTERN)?,
+            lot_number: row.lot_number,
+            expiry_date: match row.expiry_date {
+                Some(val) => Some(NaiveDate::parse_from_str(&val, NAIVE_DATE_PATTERN)?),
+                None => None,
+            },
+            unit_cost: row.unit_cost,
+        })
+    }
+}
@@ -0,0 +1,138 @@
+use chrono::NaiveDateTime;
+use std::error::Error;
+
+use crate::infra::models::{IssueLineRow, IssueRow};
+use crate::domain::audit::AuditField;
+use crate::domain::serial_number::parse_serial_numbers;
+
+const NAIVE_DATE_TIME_PATTERN: &str =  "%Y-%m-%d %H:%M:%S";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IssueStatus {
+    Draft,
+    Posted,
+    Cancelled,
+}
+
+impl IssueStatus {
+    pub const ALL: [IssueStatus; 3] = [
+        IssueStatus::Draft,
+        IssueStatus::Posted,
+        IssueStatus::Cancelled,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IssueStatus::Draft => "draft",
+            IssueStatus::Posted => "posted",
+            IssueStatus::Cancelled => "cancelled",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            IssueStatus::Draft => "Draft",
+            IssueStatus::Posted => "Posted",
+            IssueStatus::Cancelled => "Cancelled",
+        }
+    }
+
+    /// Header and lines can only be changed until the material leaves the place.
+    pub fn is_editable(&self) -> bool {
+        *self == IssueStatus::Draft
+    }
+}
+
+impl TryFrom<&str> for IssueStatus {
+    type Error = Box<dyn Error>;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        IssueStatus::ALL
+            .into_iter()
+            .find(|status| status.as_str() == value)
+            .ok_or_else(|| format!("Unknown issue status '{}'", value).into())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Issue {
+    pub id: i32,
+    /// Place the material is taken from.
+    pub place_id: i32,
+    pub requester: String,
+    /// Department or cost center the material is charged to.
+    pub department: String,
+    pub destination: Option<String>,
+    pub status: IssueStatus,
+    pub notes: Option<String>,
+    pub posted_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+    pub deleted_at: Option<NaiveDateTime>,
+}
+
+impl TryFrom<IssueRow> for Issue {
+    type Error = Box<dyn Error>;
+
+    fn try_from(row: IssueRow) -> Result<Self, Self::Error> {
+        Ok(Issue {
+            id: row.id,
+            place_id: row.place_id,
+            requester: row.requester,
+            department: row.department,
+            destination: row.destination,
+            status: IssueStatus::try_from(row.status.as_str())?,
+            notes: row.notes,
+            posted_at: match row.posted_at {
+                Some(val) => Some(NaiveDateTime::parse_from_str(&val, NAIVE_DATE_TIME_PATTERN)?),
+                None => None,
+            },
+            created_at: NaiveDateTime::parse_from_str(&row.created_at, NAIVE_DATE_TIME_PATTERN)?,
+            deleted_at: match row.deleted_at {
+                Some(val) => Some(NaiveDateTime::parse_from_str(&val, NAIVE_DATE_TIME_PATTERN)?),
+                None => None,
+            },
+        })
+    }
+}
+
+impl Issue {
+    /// Fields kept in the audit log when the issue changes.
+    pub fn audit_fields(&self) -> Vec<AuditField> {
+        vec![
+            ("place_id", Some(self.place_id.to_string())),
+            ("requester", Some(self.requester.clone())),
+            ("department", Some(self.department.clone())),
+            ("destination", self.destination.clone()),
+            ("status", Some(self.status.as_str().to_owned())),
+            ("notes", self.notes.clone()),
+            ("posted_at", self.posted_at.map(|posted_at| posted_at.format(NAIVE_DATE_TIME_PATTERN).to_string())),
+        ]
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct IssueLine {
+    pub id: i32,
+    pub issue_id: i32,
+    pub product_id: i32,
+    /// Quantity in the product's unit.
+    pub quantity: i32,
+    pub lot_number: Option<String>,
+    pub serial_numbers: Vec<String>,
+}
+
+impl From<IssueLineRow> for IssueLine {
+    fn from(row: IssueLineRow) -> Self {
+        IssueLine {
+            id: row.id,
+            issue_id: row.issue_id,
+            product_id: row.product_id,
+            quantity: row.quantity,
+            lot_number: row.lot_number,
+            serial_numbers: row.serial_numbers
+                .as_deref()
+                .map(parse_serial_numbers)
+                .unwrap_or_default(),
+        }
+    }
+}
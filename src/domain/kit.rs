@@ -0,0 +1,44 @@
+use std::collections::HashMap;
+
+use crate::infra::models::KitComponentRow;
+
+/// One line of a kit's bill of materials.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KitComponent {
+    pub id: i32,
+    pub kit_product_id: i32,
+    pub component_product_id: i32,
+    /// Units of the component that go into one kit.
+    pub quantity: i32,
+}
+
+impl From<KitComponentRow> for KitComponent {
+    fn from(row: KitComponentRow) -> Self {
+        KitComponent {
+            id: row.id,
+            kit_product_id: row.kit_product_id,
+            component_product_id: row.component_product_id,
+            quantity: row.quantity,
+        }
+    }
+}
+
+/// Kits that can be built from the `available` stock of each component; the
+/// scarcest component sets the limit.
+pub fn buildable_kits(components: &[KitComponent], available: impl Fn(i32) -> i64) -> i64 {
+    components.iter()
+        .map(|component| available(component.component_product_id).max(0) / i64::from(component.quantity))
+        .min()
+        .unwrap_or(0)
+}
+
+/// Whether `product_id` is `kit_product_id` itself or goes into it, directly or
+/// through sub-assemblies. `kits` maps each kit to its components.
+pub fn kit_contains(kits: &HashMap<i32, Vec<KitComponent>>, kit_product_id: i32, product_id: i32) -> bool {
+    if kit_product_id == product_id {
+        return true;
+    }
+
+    kits.get(&kit_product_id)
+        .is_some_and(|components| components.iter().any(|component| kit_contains(kits, component.component_product_id, product_id)))
+}
@@ -1,3 +1,19 @@
+pub mod audit;
+pub mod barcode;
+pub mod category;
+pub mod inventory_transaction;
+pub mod issue;
+pub mod kit;
 pub mod place;
 pub mod product;
-pub mod supplier;
\ No newline at end of file
+pub mod product_supplier;
+pub mod purchase_order;
+pub mod reorder_suggestion;
+pub mod reservation;
+pub mod serial_number;
+pub mod stock_balance;
+pub mod stocktake;
+pub mod supplier;
+pub mod tax_id;
+pub mod unit;
+pub mod valuation;
\ No newline at end of file
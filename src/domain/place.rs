@@ -1,25 +1,101 @@
 use chrono::NaiveDateTime;
+use std::error::Error;
 
 use crate::infra::models::PlaceRow;
+use crate::domain::audit::AuditField;
 
 const NAIVE_DATE_TIME_PATTERN: &str =  "%Y-%m-%d %H:%M:%S";
 
+/// Separates the levels of a place path, e.g. "WH1/A/03".
+pub const PLACE_PATH_SEPARATOR: &str = "/";
+
+/// Levels of the storage hierarchy, from the building down to the bin.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum PlaceType {
+    #[default]
+    Warehouse,
+    Zone,
+    Aisle,
+    Bin,
+}
+
+impl PlaceType {
+    pub const ALL: [PlaceType; 4] = [
+        PlaceType::Warehouse,
+        PlaceType::Zone,
+        PlaceType::Aisle,
+        PlaceType::Bin,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PlaceType::Warehouse => "warehouse",
+            PlaceType::Zone => "zone",
+            PlaceType::Aisle => "aisle",
+            PlaceType::Bin => "bin",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            PlaceType::Warehouse => "Warehouse",
+            PlaceType::Zone => "Zone",
+            PlaceType::Aisle => "Aisle",
+            PlaceType::Bin => "Bin",
+        }
+    }
+
+    /// Depth of the type in the hierarchy; a place can only contain places of a
+    /// deeper type, which also keeps the tree free of cycles. Levels may be
+    /// skipped, e.g. bins directly inside a warehouse.
+    pub fn level(&self) -> usize {
+        PlaceType::ALL.iter()
+            .position(|place_type| place_type == self)
+            .unwrap_or_default()
+    }
+
+    /// The type one level below, used for places created from a path.
+    pub fn child_type(&self) -> PlaceType {
+        PlaceType::ALL.get(self.level() + 1).copied().unwrap_or(PlaceType::Bin)
+    }
+
+    pub fn can_contain(&self, child: PlaceType) -> bool {
+        child.level() > self.level()
+    }
+}
+
+impl TryFrom<&str> for PlaceType {
+    type Error = Box<dyn Error>;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        PlaceType::ALL
+            .into_iter()
+            .find(|place_type| place_type.as_str() == value.trim().to_lowercase())
+            .ok_or_else(|| format!("Unknown place type '{}'", value).into())
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Place {
   	pub id: i32,
   	pub name: String,
+  	/// `None` for top-level places.
+  	pub parent_id: Option<i32>,
+  	pub place_type: PlaceType,
  	pub created_at: NaiveDateTime,
   	pub updated_at: NaiveDateTime,
   	pub deleted_at: Option<NaiveDateTime>,
 }
 
 impl TryFrom<PlaceRow> for Place {
-    type Error = chrono::ParseError;
+    type Error = Box<dyn Error>;
 
     fn try_from(row: PlaceRow) -> Result<Self, Self::Error> {
         Ok(Place {
             id: row.id,
             name: row.name,
+            parent_id: row.parent_id,
+            place_type: PlaceType::try_from(row.place_type.as_str())?,
             created_at: NaiveDateTime::parse_from_str(&row.created_at, NAIVE_DATE_TIME_PATTERN)?,
             updated_at: NaiveDateTime::parse_from_str(&row.updated_at, NAIVE_DATE_TIME_PATTERN)?,
             deleted_at: match row.deleted_at {
@@ -28,4 +104,66 @@ impl TryFrom<PlaceRow> for Place {
             },
         })
     }
-}
\ No newline at end of file
+}
+
+impl Place {
+    /// Text encoded in the QR code of the place's bin label.
+    pub fn label_code(&self) -> String {
+        format!("PLACE-{}", self.id)
+    }
+
+    /// Fields kept in the audit log when the place changes.
+    pub fn audit_fields(&self) -> Vec<AuditField> {
+        vec![
+            ("name", Some(self.name.clone())),
+            ("parent_id", self.parent_id.map(|id| id.to_string())),
+            ("place_type", Some(self.place_type.as_str().to_owned())),
+        ]
+    }
+}
+
+/// Full path of a place from its warehouse down, e.g. "WH1/A/03".
+pub fn place_path(places: &[Place], place_id: i32) -> String {
+    let mut names = Vec::new();
+    let mut current = places.iter().find(|place| place.id == place_id);
+
+    // Bounded by the number of places in case the tree was corrupted into a cycle.
+    while let Some(place) = current
+        && names.len() < places.len() {
+        names.push(place.name.as_str());
+        current = place.parent_id
+            .and_then(|parent_id| places.iter().find(|place| place.id == parent_id));
+    }
+
+    names.reverse();
+    names.join(PLACE_PATH_SEPARATOR)
+}
+
+/// The place itself and every place inside it, so stock can be rolled up.
+pub fn descendant_place_ids(places: &[Place], place_id: i32) -> Vec<i32> {
+    let mut ids = vec![place_id];
+    let mut index = 0;
+
+    while index < ids.len() {
+        let parent_id = ids[index];
+
+        for place in places {
+            if place.parent_id == Some(parent_id) && !ids.contains(&place.id) {
+                ids.push(place.id);
+            }
+        }
+
+        index += 1;
+    }
+
+    ids
+}
+
+/// Splits a "WH1/A/03" path into its place names, dropping blanks.
+pub fn parse_place_path(path: &str) -> Vec<String> {
+    path.split(PLACE_PATH_SEPARATOR)
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(str::to_owned)
+        .collect()
+}
@@ -1,6 +1,7 @@
 use chrono::NaiveDateTime;
 
 use crate::infra::models::{ProductRow};
+use crate::domain::audit::AuditField;
 
 const NAIVE_DATE_TIME_PATTERN: &str =  "%Y-%m-%d %H:%M:%S";
 
@@ -8,9 +9,19 @@ const NAIVE_DATE_TIME_PATTERN: &str =  "%Y-%m-%d %H:%M:%S";
 pub struct Product {
 	pub id: i32,
 	pub name: String,
+	/// Optional stock keeping unit, unique among products that are not deleted.
+	pub sku: Option<String>,
+	/// Symbol of the product's unit, kept in sync with `unit_id`.
 	pub unity: Option<String>,
+	pub unit_id: Option<i32>,
+	pub category_id: Option<i32>,
 	pub brand: Option<String>,
 	pub min_stock: i32,
+	pub reorder_point: i32,
+	pub safety_stock: i32,
+	pub max_stock: i32,
+	pub tracks_lots: bool,
+	pub serialized: bool,
 	pub observation: Option<String>,
 	pub created_at: NaiveDateTime,
 	pub updated_at: NaiveDateTime,
@@ -24,9 +35,17 @@ impl TryFrom<ProductRow> for Product {
         Ok(Product {
             id: row.id,
             name: row.name,
+            sku: row.sku,
             unity: row.unity,
+            unit_id: row.unit_id,
+            category_id: row.category_id,
             brand: row.brand,
             min_stock: row.min_stock,
+            reorder_point: row.reorder_point,
+            safety_stock: row.safety_stock,
+            max_stock: row.max_stock,
+            tracks_lots: row.tracks_lots,
+            serialized: row.serialized,
             observation: row.observation,
             created_at: NaiveDateTime::parse_from_str(&row.created_at, NAIVE_DATE_TIME_PATTERN)?,
             updated_at: NaiveDateTime::parse_from_str(&row.updated_at, NAIVE_DATE_TIME_PATTERN)?,
@@ -36,4 +55,95 @@ impl TryFrom<ProductRow> for Product {
             },
         })
     }
-}
\ No newline at end of file
+}
+
+impl Product {
+    /// Whether `on_hand` is at or below the product's minimum. Products without a
+    /// minimum (zero) never raise an alert.
+    pub fn is_low_stock(&self, on_hand: i64) -> bool {
+        self.min_stock > 0 && on_hand <= i64::from(self.min_stock)
+    }
+
+    /// Quantity to order to bring stock back up to `max_stock`, counting what is
+    /// already on order. Only suggested once on hand plus on order has fallen to the
+    /// reorder point plus the safety stock, so the safety stock is still on hand
+    /// while the order is on its way; products without a max stock are never
+    /// reordered.
+    pub fn reorder_quantity(&self, on_hand: i64, on_order: i64) -> Option<i64> {
+        let position = on_hand + on_order;
+
+        if self.max_stock <= 0 || position > i64::from(self.reorder_point) + i64::from(self.safety_stock) {
+            return None;
+        }
+
+        Some(i64::from(self.max_stock) - position).filter(|quantity| *quantity > 0)
+    }
+
+    /// Fields kept in the audit log when the product changes.
+    pub fn audit_fields(&self) -> Vec<AuditField> {
+        vec![
+            ("name", Some(self.name.clone())),
+            ("sku", self.sku.clone()),
+            ("unit_id", self.unit_id.map(|id| id.to_string())),
+            ("category_id", self.category_id.map(|id| id.to_string())),
+            ("brand", self.brand.clone()),
+            ("min_stock", Some(self.min_stock.to_string())),
+            ("reorder_point", Some(self.reorder_point.to_string())),
+            ("safety_stock", Some(self.safety_stock.to_string())),
+            ("max_stock", Some(self.max_stock.to_string())),
+            ("tracks_lots", Some(self.tracks_lots.to_string())),
+            ("serialized", Some(self.serialized.to_string())),
+            ("observation", self.observation.clone()),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn product(reorder_point: i32, safety_stock: i32, max_stock: i32) -> Product {
+        let now = NaiveDateTime::parse_from_str("2026-01-01 00:00:00", NAIVE_DATE_TIME_PATTERN).unwrap();
+
+        Product {
+            id: 1,
+            name: "Screw".to_owned(),
+            sku: None,
+            unity: None,
+            unit_id: None,
+            category_id: None,
+            brand: None,
+            min_stock: 0,
+            reorder_point,
+            safety_stock,
+            max_stock,
+            tracks_lots: false,
+            serialized: false,
+            observation: None,
+            created_at: now,
+            updated_at: now,
+            deleted_at: None,
+        }
+    }
+
+    #[test]
+    fn reorders_once_stock_reaches_the_reorder_point_plus_safety_stock() {
+        let product = product(10, 5, 40);
+
+        assert_eq!(product.reorder_quantity(16, 0), None);
+        assert_eq!(product.reorder_quantity(15, 0), Some(25));
+        assert_eq!(product.reorder_quantity(8, 4), Some(28));
+    }
+
+    #[test]
+    fn counts_stock_on_order_towards_the_reorder_point() {
+        let product = product(10, 5, 40);
+
+        assert_eq!(product.reorder_quantity(5, 20), None);
+    }
+
+    #[test]
+    fn never_reorders_without_a_max_stock() {
+        assert_eq!(product(10, 5, 0).reorder_quantity(0, 0), None);
+    }
+}
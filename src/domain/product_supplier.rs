@@ -0,0 +1,68 @@
+use chrono::NaiveDateTime;
+
+use crate::infra::models::ProductSupplierRow;
+
+const NAIVE_DATE_TIME_PATTERN: &str =  "%Y-%m-%d %H:%M:%S";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProductSupplier {
+    pub id: i32,
+    pub product_id: i32,
+    pub supplier_id: i32,
+    pub supplier_sku: Option<String>,
+    pub last_unit_cost: Option<f64>,
+    pub currency: String,
+    pub min_order_quantity: i32,
+    pub lead_time_days: Option<i32>,
+    pub preferred: bool,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl TryFrom<ProductSupplierRow> for ProductSupplier {
+    type Error = chrono::ParseError;
+
+    fn try_from(row: ProductSupplierRow) -> Result<Self, Self::Error> {
+        Ok(ProductSupplier {
+            id: row.id,
+            product_id: row.product_id,
+            supplier_id: row.supplier_id,
+            supplier_sku: row.supplier_sku,
+            last_unit_cost: row.last_unit_cost,
+            currency: row.currency,
+            min_order_quantity: row.min_order_quantity,
+            lead_time_days: row.lead_time_days,
+            preferred: row.preferred,
+            created_at: NaiveDateTime::parse_from_str(&row.created_at, NAIVE_DATE_TIME_PATTERN)?,
+            updated_at: NaiveDateTime::parse_from_str(&row.updated_at, NAIVE_DATE_TIME_PATTERN)?,
+        })
+    }
+}
+
+impl ProductSupplier {
+    /// How the link reads in the product's audit log, e.g.
+    /// "Acme (SKU AC-1, 12.50 BRL, min 10, 5 days, preferred)".
+    pub fn audit_summary(&self, supplier_name: &str) -> String {
+        let mut details = Vec::new();
+
+        if let Some(supplier_sku) = &self.supplier_sku {
+            details.push(format!("SKU {}", supplier_sku));
+        }
+
+        if let Some(last_unit_cost) = self.last_unit_cost {
+            details.push(format!("{:.2} {}", last_unit_cost, self.currency));
+        }
+
+        details.push(format!("min {}", self.min_order_quantity));
+
+        if let Some(lead_time_days) = self.lead_time_days {
+            details.push(format!("{} days", lead_time_days));
+        }
+
+        if self.preferred {
+            details.push("preferred".to_owned());
+        }
+
+        format!("{} ({})", supplier_name, details.join(", "))
+    }
+}
@@ -0,0 +1,170 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use std::error::Error;
+
+use crate::infra::models::{PurchaseOrderLineRow, PurchaseOrderRow};
+use crate::domain::audit::AuditField;
+
+const NAIVE_DATE_TIME_PATTERN: &str =  "%Y-%m-%d %H:%M:%S";
+pub const NAIVE_DATE_PATTERN: &str = "%Y-%m-%d";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PurchaseOrderStatus {
+    Draft,
+    Sent,
+    PartiallyReceived,
+    Received,
+    Cancelled,
+}
+
+impl PurchaseOrderStatus {
+    pub const ALL: [PurchaseOrderStatus; 5] = [
+        PurchaseOrderStatus::Draft,
+        PurchaseOrderStatus::Sent,
+        PurchaseOrderStatus::PartiallyReceived,
+        PurchaseOrderStatus::Received,
+        PurchaseOrderStatus::Cancelled,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PurchaseOrderStatus::Draft => "draft",
+            PurchaseOrderStatus::Sent => "sent",
+            PurchaseOrderStatus::PartiallyReceived => "partially_received",
+            PurchaseOrderStatus::Received => "received",
+            PurchaseOrderStatus::Cancelled => "cancelled",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            PurchaseOrderStatus::Draft => "Draft",
+            PurchaseOrderStatus::Sent => "Sent",
+            PurchaseOrderStatus::PartiallyReceived => "Partially received",
+            PurchaseOrderStatus::Received => "Received",
+            PurchaseOrderStatus::Cancelled => "Cancelled",
+        }
+    }
+
+    /// Lifecycle: draft -> sent -> partially received -> received, with
+    /// cancellation allowed until the order is fully received.
+    pub fn can_transition_to(&self, next: PurchaseOrderStatus) -> bool {
+        matches!(
+            (self, next),
+            (PurchaseOrderStatus::Draft, PurchaseOrderStatus::Sent)
+                | (PurchaseOrderStatus::Draft, PurchaseOrderStatus::Cancelled)
+                | (PurchaseOrderStatus::Sent, PurchaseOrderStatus::PartiallyReceived)
+                | (PurchaseOrderStatus::Sent, PurchaseOrderStatus::Received)
+                | (PurchaseOrderStatus::Sent, PurchaseOrderStatus::Cancelled)
+                | (PurchaseOrderStatus::PartiallyReceived, PurchaseOrderStatus::Received)
+                | (PurchaseOrderStatus::PartiallyReceived, PurchaseOrderStatus::Cancelled)
+        )
+    }
+
+    /// Deliveries are accepted once the order is sent and until it is closed.
+    pub fn is_receivable(&self) -> bool {
+        matches!(self, PurchaseOrderStatus::Sent | PurchaseOrderStatus::PartiallyReceived)
+    }
+
+    /// Orders whose outstanding quantities still count as "on order".
+    pub fn is_open(&self) -> bool {
+        self.is_editable() || self.is_receivable()
+    }
+
+    /// Header and lines can only be changed before the order is sent.
+    pub fn is_editable(&self) -> bool {
+        *self == PurchaseOrderStatus::Draft
+    }
+}
+
+impl TryFrom<&str> for PurchaseOrderStatus {
+    type Error = Box<dyn Error>;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        PurchaseOrderStatus::ALL
+            .into_iter()
+            .find(|status| status.as_str() == value)
+            .ok_or_else(|| format!("Unknown purchase order status '{}'", value).into())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PurchaseOrder {
+    pub id: i32,
+    pub supplier_id: i32,
+    pub status: PurchaseOrderStatus,
+    pub expected_date: Option<NaiveDate>,
+    pub notes: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub deleted_at: Option<NaiveDateTime>,
+}
+
+impl TryFrom<PurchaseOrderRow> for PurchaseOrder {
+    type Error = Box<dyn Error>;
+
+    fn try_from(row: PurchaseOrderRow) -> Result<Self, Self::Error> {
+        Ok(PurchaseOrder {
+            id: row.id,
+            supplier_id: row.supplier_id,
+            status: PurchaseOrderStatus::try_from(row.status.as_str())?,
+            expected_date: match row.expected_date {
+                Some(val) => Some(NaiveDate::parse_from_str(&val, NAIVE_DATE_PATTERN)?),
+                None => None,
+            },
+            notes: row.notes,
+            created_at: NaiveDateTime::parse_from_str(&row.created_at, NAIVE_DATE_TIME_PATTERN)?,
+            deleted_at: match row.deleted_at {
+                Some(val) => Some(NaiveDateTime::parse_from_str(&val, NAIVE_DATE_TIME_PATTERN)?),
+                None => None,
+            },
+        })
+    }
+}
+
+impl PurchaseOrder {
+    /// Fields kept in the audit log when the order changes.
+    pub fn audit_fields(&self) -> Vec<AuditField> {
+        vec![
+            ("supplier_id", Some(self.supplier_id.to_string())),
+            ("status", Some(self.status.as_str().to_owned())),
+            ("expected_date", self.expected_date.map(|date| date.format(NAIVE_DATE_PATTERN).to_string())),
+            ("notes", self.notes.clone()),
+        ]
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PurchaseOrderLine {
+    pub id: i32,
+    pub purchase_order_id: i32,
+    pub product_id: i32,
+    pub quantity: i32,
+    pub received_quantity: i32,
+    pub unit_cost: Option<f64>,
+    /// Unit the quantities and cost are expressed in; `None` means the product's unit.
+    pub unit_id: Option<i32>,
+}
+
+impl PurchaseOrderLine {
+    pub fn total_cost(&self) -> Option<f64> {
+        self.unit_cost.map(|cost| cost * self.quantity as f64)
+    }
+
+    /// Quantity still expected from the supplier; never negative after an over-delivery.
+    pub fn remaining_quantity(&self) -> i32 {
+        (self.quantity - self.received_quantity).max(0)
+    }
+}
+
+impl From<PurchaseOrderLineRow> for PurchaseOrderLine {
+    fn from(row: PurchaseOrderLineRow) -> Self {
+        PurchaseOrderLine {
+            id: row.id,
+            purchase_order_id: row.purchase_order_id,
+            product_id: row.product_id,
+            quantity: row.quantity,
+            received_quantity: row.received_quantity,
+            unit_cost: row.unit_cost,
+            unit_id: row.unit_id,
+        }
+    }
+}
@@ -0,0 +1,26 @@
+use crate::domain::product::Product;
+use crate::domain::supplier::Supplier;
+
+#[derive(Debug, Clone)]
+pub struct ReorderSuggestion {
+    pub product: Product,
+    /// Preferred supplier of the product, if one is known.
+    pub supplier: Option<Supplier>,
+    pub supplier_sku: Option<String>,
+    pub on_hand: i64,
+    pub on_order: i64,
+    pub suggested_quantity: i64,
+    pub unit_cost: Option<f64>,
+}
+
+impl ReorderSuggestion {
+    pub fn supplier_name(&self) -> &str {
+        self.supplier.as_ref()
+            .map(|supplier| supplier.name.as_str())
+            .unwrap_or("No preferred supplier")
+    }
+
+    pub fn total_cost(&self) -> Option<f64> {
+        self.unit_cost.map(|cost| cost * self.suggested_quantity as f64)
+    }
+}
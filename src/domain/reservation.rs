@@ -0,0 +1,122 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use std::error::Error;
+
+use crate::infra::models::ReservationRow;
+use crate::domain::audit::AuditField;
+
+const NAIVE_DATE_TIME_PATTERN: &str =  "%Y-%m-%d %H:%M:%S";
+pub const NAIVE_DATE_PATTERN: &str = "%Y-%m-%d";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReservationStatus {
+    Active,
+    /// Every reserved unit was issued.
+    Consumed,
+    /// Given back by hand before being used up.
+    Released,
+    /// Its expiry date passed before it was used up.
+    Expired,
+}
+
+impl ReservationStatus {
+    pub const ALL: [ReservationStatus; 4] = [
+        ReservationStatus::Active,
+        ReservationStatus::Consumed,
+        ReservationStatus::Released,
+        ReservationStatus::Expired,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReservationStatus::Active => "active",
+            ReservationStatus::Consumed => "consumed",
+            ReservationStatus::Released => "released",
+            ReservationStatus::Expired => "expired",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ReservationStatus::Active => "Active",
+            ReservationStatus::Consumed => "Consumed",
+            ReservationStatus::Released => "Released",
+            ReservationStatus::Expired => "Expired",
+        }
+    }
+}
+
+impl TryFrom<&str> for ReservationStatus {
+    type Error = Box<dyn Error>;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        ReservationStatus::ALL
+            .into_iter()
+            .find(|status| status.as_str() == value)
+            .ok_or_else(|| format!("Unknown reservation status '{}'", value).into())
+    }
+}
+
+/// Quantity of a product set aside for a job or customer without moving it.
+#[derive(Debug, Clone)]
+pub struct Reservation {
+    pub id: i32,
+    pub product_id: i32,
+    /// Place the units are held at; `None` holds them at any place.
+    pub place_id: Option<i32>,
+    pub quantity: i32,
+    /// Units already issued through exits against the reservation.
+    pub consumed_quantity: i32,
+    /// Job, order or customer the units are held for.
+    pub reference: String,
+    pub notes: Option<String>,
+    pub expires_on: Option<NaiveDate>,
+    pub status: ReservationStatus,
+    pub created_at: NaiveDateTime,
+}
+
+impl Reservation {
+    /// Units still held back from the available quantity.
+    pub fn remaining(&self) -> i32 {
+        if self.status == ReservationStatus::Active {
+            (self.quantity - self.consumed_quantity).max(0)
+        } else {
+            0
+        }
+    }
+
+    /// Fields kept in the audit log when the reservation changes.
+    pub fn audit_fields(&self) -> Vec<AuditField> {
+        vec![
+            ("product_id", Some(self.product_id.to_string())),
+            ("place_id", self.place_id.map(|id| id.to_string())),
+            ("quantity", Some(self.quantity.to_string())),
+            ("consumed_quantity", Some(self.consumed_quantity.to_string())),
+            ("reference", Some(self.reference.clone())),
+            ("notes", self.notes.clone()),
+            ("expires_on", self.expires_on.map(|date| date.format(NAIVE_DATE_PATTERN).to_string())),
+            ("status", Some(self.status.as_str().to_owned())),
+        ]
+    }
+}
+
+impl TryFrom<ReservationRow> for Reservation {
+    type Error = Box<dyn Error>;
+
+    fn try_from(row: ReservationRow) -> Result<Self, Self::Error> {
+        Ok(Reservation {
+            id: row.id,
+            product_id: row.product_id,
+            place_id: row.place_id,
+            quantity: row.quantity,
+            consumed_quantity: row.consumed_quantity,
+            reference: row.reference,
+            notes: row.notes,
+            expires_on: match row.expires_on {
+                Some(val) => Some(NaiveDate::parse_from_str(&val, NAIVE_DATE_PATTERN)?),
+                None => None,
+            },
+            status: ReservationStatus::try_from(row.status.as_str())?,
+            created_at: NaiveDateTime::parse_from_str(&row.created_at, NAIVE_DATE_TIME_PATTERN)?,
+        })
+    }
+}
@@ -0,0 +1,42 @@
+use chrono::NaiveDateTime;
+
+use crate::infra::models::SerialNumberRow;
+
+const NAIVE_DATE_TIME_PATTERN: &str =  "%Y-%m-%d %H:%M:%S";
+
+/// One individually identified unit of a serialized product.
+#[derive(Debug, Clone)]
+pub struct SerialNumber {
+    pub id: i32,
+    pub product_id: i32,
+    pub serial_number: String,
+    /// Where the unit is stored; `None` once it has left stock.
+    pub place_id: Option<i32>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl TryFrom<SerialNumberRow> for SerialNumber {
+    type Error = chrono::ParseError;
+
+    fn try_from(row: SerialNumberRow) -> Result<Self, Self::Error> {
+        Ok(SerialNumber {
+            id: row.id,
+            product_id: row.product_id,
+            serial_number: row.serial_number,
+            place_id: row.place_id,
+            created_at: NaiveDateTime::parse_from_str(&row.created_at, NAIVE_DATE_TIME_PATTERN)?,
+            updated_at: NaiveDateTime::parse_from_str(&row.updated_at, NAIVE_DATE_TIME_PATTERN)?,
+        })
+    }
+}
+
+/// Splits serial numbers typed one per line or separated by commas, dropping
+/// blanks.
+pub fn parse_serial_numbers(text: &str) -> Vec<String> {
+    text.split([',', '\n'])
+        .map(str::trim)
+        .filter(|serial_number| !serial_number.is_empty())
+        .map(str::to_owned)
+        .collect()
+}
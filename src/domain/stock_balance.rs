@@ -0,0 +1,99 @@
+use chrono::NaiveDate;
+
+use crate::infra::models::{LotBalanceRow, ProductStockRow, StockBalanceRow};
+use crate::domain::inventory_transaction::NAIVE_DATE_PATTERN;
+use crate::domain::product::Product;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StockBalance {
+    pub product_id: i32,
+    pub place_id: i32,
+    pub quantity: i64,
+}
+
+impl From<StockBalanceRow> for StockBalance {
+    fn from(row: StockBalanceRow) -> Self {
+        StockBalance {
+            product_id: row.product_id,
+            place_id: row.place_id,
+            quantity: row.quantity.unwrap_or_default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProductStock {
+    pub product_id: i32,
+    pub quantity: i64,
+}
+
+impl From<ProductStockRow> for ProductStock {
+    fn from(row: ProductStockRow) -> Self {
+        ProductStock {
+            product_id: row.product_id,
+            quantity: row.quantity.unwrap_or_default(),
+        }
+    }
+}
+
+/// Stock of a product split into what is on the shelves and what is already
+/// promised through open reservations.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProductAvailability {
+    pub product_id: i32,
+    pub on_hand: i64,
+    pub reserved: i64,
+}
+
+impl ProductAvailability {
+    /// Quantity that can still be promised or issued freely.
+    pub fn available(&self) -> i64 {
+        self.on_hand - self.reserved
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LotBalance {
+    pub product_id: i32,
+    pub place_id: i32,
+    pub lot_number: String,
+    pub expiry_date: Option<NaiveDate>,
+    pub quantity: i64,
+}
+
+impl LotBalance {
+    /// Days from `today` until the lot expires; negative once it has expired.
+    pub fn days_to_expiry(&self, today: NaiveDate) -> Option<i64> {
+        self.expiry_date.map(|expiry_date| (expiry_date - today).num_days())
+    }
+}
+
+impl TryFrom<LotBalanceRow> for LotBalance {
+    type Error = chrono::ParseError;
+
+    fn try_from(row: LotBalanceRow) -> Result<Self, Self::Error> {
+        Ok(LotBalance {
+            product_id: row.product_id,
+            place_id: row.place_id,
+            lot_number: row.lot_number.unwrap_or_default(),
+            expiry_date: match row.expiry_date {
+                Some(val) => Some(NaiveDate::parse_from_str(&val, NAIVE_DATE_PATTERN)?),
+                None => None,
+            },
+            quantity: row.quantity.unwrap_or_default(),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LowStockAlert {
+    pub product: Product,
+    pub on_hand: i64,
+}
+
+impl LowStockAlert {
+    /// How many units are missing to get back to the minimum.
+    pub fn shortfall(&self) -> i64 {
+        (i64::from(self.product.min_stock) - self.on_hand).max(0)
+    }
+}
@@ -0,0 +1,140 @@
+use chrono::NaiveDateTime;
+use std::error::Error;
+
+use crate::infra::models::{StocktakeLineRow, StocktakeRow};
+use crate::domain::audit::AuditField;
+
+const NAIVE_DATE_TIME_PATTERN: &str =  "%Y-%m-%d %H:%M:%S";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StocktakeStatus {
+    Counting,
+    Approved,
+    Cancelled,
+}
+
+impl StocktakeStatus {
+    pub const ALL: [StocktakeStatus; 3] = [
+        StocktakeStatus::Counting,
+        StocktakeStatus::Approved,
+        StocktakeStatus::Cancelled,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StocktakeStatus::Counting => "counting",
+            StocktakeStatus::Approved => "approved",
+            StocktakeStatus::Cancelled => "cancelled",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            StocktakeStatus::Counting => "Counting",
+            StocktakeStatus::Approved => "Approved",
+            StocktakeStatus::Cancelled => "Cancelled",
+        }
+    }
+
+    /// Counts can only be entered while the session is open.
+    pub fn is_editable(&self) -> bool {
+        *self == StocktakeStatus::Counting
+    }
+}
+
+impl TryFrom<&str> for StocktakeStatus {
+    type Error = Box<dyn Error>;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        StocktakeStatus::ALL
+            .into_iter()
+            .find(|status| status.as_str() == value)
+            .ok_or_else(|| format!("Unknown stocktake status '{}'", value).into())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Stocktake {
+    pub id: i32,
+    /// Place being counted; `None` counts every place.
+    pub place_id: Option<i32>,
+    pub status: StocktakeStatus,
+    pub notes: Option<String>,
+    pub approved_at: Option<NaiveDateTime>,
+    /// Moment the expected quantities were frozen.
+    pub created_at: NaiveDateTime,
+}
+
+impl TryFrom<StocktakeRow> for Stocktake {
+    type Error = Box<dyn Error>;
+
+    fn try_from(row: StocktakeRow) -> Result<Self, Self::Error> {
+        Ok(Stocktake {
+            id: row.id,
+            place_id: row.place_id,
+            status: StocktakeStatus::try_from(row.status.as_str())?,
+            notes: row.notes,
+            approved_at: match row.approved_at {
+                Some(val) => Some(NaiveDateTime::parse_from_str(&val, NAIVE_DATE_TIME_PATTERN)?),
+                None => None,
+            },
+            created_at: NaiveDateTime::parse_from_str(&row.created_at, NAIVE_DATE_TIME_PATTERN)?,
+        })
+    }
+}
+
+impl Stocktake {
+    /// Fields kept in the audit log when the session changes.
+    pub fn audit_fields(&self) -> Vec<AuditField> {
+        vec![
+            ("place_id", self.place_id.map(|id| id.to_string())),
+            ("status", Some(self.status.as_str().to_owned())),
+            ("notes", self.notes.clone()),
+            ("approved_at", self.approved_at.map(|approved_at| approved_at.format(NAIVE_DATE_TIME_PATTERN).to_string())),
+        ]
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct StocktakeLine {
+    pub id: i32,
+    pub stocktake_id: i32,
+    pub product_id: i32,
+    pub place_id: i32,
+    pub lot_number: Option<String>,
+    pub expected_quantity: i32,
+    /// `None` until the product is counted; uncounted lines are left untouched on approval.
+    pub counted_quantity: Option<i32>,
+}
+
+impl StocktakeLine {
+    /// Counted minus expected, i.e. the adjustment posted when the session is approved.
+    pub fn variance(&self) -> Option<i32> {
+        self.counted_quantity.map(|counted| counted - self.expected_quantity)
+    }
+}
+
+impl From<StocktakeLineRow> for StocktakeLine {
+    fn from(row: StocktakeLineRow) -> Self {
+        StocktakeLine {
+            id: row.id,
+            stocktake_id: row.stocktake_id,
+            product_id: row.product_id,
+            place_id: row.place_id,
+            lot_number: row.lot_number,
+            expected_quantity: row.expected_quantity,
+            counted_quantity: row.counted_quantity,
+        }
+    }
+}
+
+/// A row of a filled-in count sheet. Rows exported from the session carry their
+/// line id; rows added by hand name the product, place and lot instead.
+#[derive(Debug, Clone)]
+pub struct StocktakeCount {
+    pub line_id: Option<i32>,
+    pub product_id: Option<i32>,
+    pub place_name: Option<String>,
+    pub lot_number: Option<String>,
+    pub counted_quantity: i32,
+}
@@ -1,6 +1,8 @@
 use chrono::NaiveDateTime;
 
 use crate::infra::models::SupplierRow;
+use crate::domain::audit::AuditField;
+use crate::domain::tax_id::format_tax_id;
 
 const NAIVE_DATE_TIME_PATTERN: &str =  "%Y-%m-%d %H:%M:%S";
 
@@ -8,6 +10,17 @@ const NAIVE_DATE_TIME_PATTERN: &str =  "%Y-%m-%d %H:%M:%S";
 pub struct Supplier {
   	pub id: i32,
   	pub name: String,
+  	/// CNPJ or CPF without punctuation; see `formatted_tax_id` for display.
+  	pub tax_id: Option<String>,
+  	pub contact_name: Option<String>,
+  	pub phone: Option<String>,
+  	pub mobile_phone: Option<String>,
+  	pub email: Option<String>,
+  	pub address: Option<String>,
+  	pub payment_terms: Option<String>,
+  	pub notes: Option<String>,
+  	/// Inactive suppliers are kept for history but not offered for new documents.
+  	pub active: bool,
  	pub created_at: NaiveDateTime,
   	pub updated_at: NaiveDateTime,
   	pub deleted_at: Option<NaiveDateTime>,
@@ -20,6 +33,15 @@ impl TryFrom<SupplierRow> for Supplier {
         Ok(Supplier {
             id: row.id,
             name: row.name,
+            tax_id: row.tax_id,
+            contact_name: row.contact_name,
+            phone: row.phone,
+            mobile_phone: row.mobile_phone,
+            email: row.email,
+            address: row.address,
+            payment_terms: row.payment_terms,
+            notes: row.notes,
+            active: row.active,
             created_at: NaiveDateTime::parse_from_str(&row.created_at, NAIVE_DATE_TIME_PATTERN)?,
             updated_at: NaiveDateTime::parse_from_str(&row.updated_at, NAIVE_DATE_TIME_PATTERN)?,
             deleted_at: match row.deleted_at {
@@ -28,4 +50,26 @@ impl TryFrom<SupplierRow> for Supplier {
             },
         })
     }
-}
\ No newline at end of file
+}
+
+impl Supplier {
+    pub fn formatted_tax_id(&self) -> Option<String> {
+        self.tax_id.as_deref().map(format_tax_id)
+    }
+
+    /// Fields kept in the audit log when the supplier changes.
+    pub fn audit_fields(&self) -> Vec<AuditField> {
+        vec![
+            ("name", Some(self.name.clone())),
+            ("tax_id", self.tax_id.clone()),
+            ("contact_name", self.contact_name.clone()),
+            ("phone", self.phone.clone()),
+            ("mobile_phone", self.mobile_phone.clone()),
+            ("email", self.email.clone()),
+            ("address", self.address.clone()),
+            ("payment_terms", self.payment_terms.clone()),
+            ("notes", self.notes.clone()),
+            ("active", Some(self.active.to_string())),
+        ]
+    }
+}
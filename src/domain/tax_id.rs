@@ -0,0 +1,193 @@
+use std::error::Error;
+
+/// Brazilian taxpayer registries a supplier can be identified by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaxIdKind {
+    /// Companies: 12 base characters (digits, or letters and digits since the
+    /// alphanumeric CNPJ) followed by two check digits.
+    Cnpj,
+    /// Individuals: 9 digits followed by two check digits.
+    Cpf,
+}
+
+impl TaxIdKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            TaxIdKind::Cnpj => "CNPJ",
+            TaxIdKind::Cpf => "CPF",
+        }
+    }
+
+    /// Works out whether a normalized tax ID is a CNPJ or a CPF and checks its
+    /// check digits.
+    pub fn detect(tax_id: &str) -> Result<TaxIdKind, Box<dyn Error>> {
+        let kind = match tax_id.len() {
+            14 => TaxIdKind::Cnpj,
+            11 => TaxIdKind::Cpf,
+            _ => return Err(format!("{} is not a CNPJ (14 characters) or a CPF (11 digits)", tax_id).into()),
+        };
+
+        let (base, check_digits) = tax_id.split_at(tax_id.len() - 2);
+
+        let base_is_valid = match kind {
+            TaxIdKind::Cnpj => base.chars().all(|char| char.is_ascii_digit() || char.is_ascii_uppercase()),
+            TaxIdKind::Cpf => base.chars().all(|char| char.is_ascii_digit()),
+        };
+
+        if !base_is_valid || !check_digits.chars().all(|char| char.is_ascii_digit()) {
+            return Err(format!("{} has characters not allowed in a {}", format_tax_id(tax_id), kind.label()).into());
+        }
+
+        // Sequences like 000.000.000-00 pass the checksum but are never issued.
+        if tax_id.chars().all(|char| tax_id.starts_with(char)) {
+            return Err(format!("{} is not a valid {}", format_tax_id(tax_id), kind.label()).into());
+        }
+
+        let values: Vec<u32> = tax_id.chars()
+            .map(|char| char as u32 - '0' as u32)
+            .collect();
+
+        let expected = match kind {
+            TaxIdKind::Cnpj => {
+                let first = cnpj_check_digit(&values[..12]);
+                let second = cnpj_check_digit(&values[..13]);
+                [first, second]
+            }
+            TaxIdKind::Cpf => {
+                let first = cpf_check_digit(&values[..9]);
+                let second = cpf_check_digit(&values[..10]);
+                [first, second]
+            }
+        };
+
+        if values[values.len() - 2..] != expected {
+            return Err(format!("{} has invalid {} check digits", format_tax_id(tax_id), kind.label()).into());
+        }
+
+        Ok(kind)
+    }
+}
+
+/// Strips the punctuation people type or paste (dots, slash, dash, spaces)
+/// and uppercases letters of alphanumeric CNPJs.
+pub fn normalize_tax_id(value: &str) -> String {
+    value.chars()
+        .filter(|char| char.is_ascii_alphanumeric())
+        .map(|char| char.to_ascii_uppercase())
+        .collect()
+}
+
+/// Formats a normalized tax ID as 00.000.000/0000-00 or 000.000.000-00, leaving
+/// values of any other length as they are.
+pub fn format_tax_id(tax_id: &str) -> String {
+    if !tax_id.is_ascii() {
+        return tax_id.to_owned();
+    }
+
+    match tax_id.len() {
+        14 => format!("{}.{}.{}/{}-{}", &tax_id[..2], &tax_id[2..5], &tax_id[5..8], &tax_id[8..12], &tax_id[12..]),
+        11 => format!("{}.{}.{}-{}", &tax_id[..3], &tax_id[3..6], &tax_id[6..9], &tax_id[9..]),
+        _ => tax_id.to_owned(),
+    }
+}
+
+/// Weights 2 to 9 run from the rightmost character and wrap around; letters
+/// count as their ASCII code minus 48, so digits keep their value.
+fn cnpj_check_digit(values: &[u32]) -> u32 {
+    let sum: u32 = values.iter()
+        .rev()
+        .enumerate()
+        .map(|(index, value)| value * (index as u32 % 8 + 2))
+        .sum();
+
+    match sum % 11 {
+        0 | 1 => 0,
+        remainder => 11 - remainder,
+    }
+}
+
+/// Weights 2 upwards run from the rightmost digit.
+fn cpf_check_digit(values: &[u32]) -> u32 {
+    let sum: u32 = values.iter()
+        .rev()
+        .enumerate()
+        .map(|(index, value)| value * (index as u32 + 2))
+        .sum();
+
+    match sum % 11 {
+        0 | 1 => 0,
+        remainder => 11 - remainder,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detect(value: &str) -> Result<TaxIdKind, Box<dyn Error>> {
+        TaxIdKind::detect(&normalize_tax_id(value))
+    }
+
+    #[test]
+    fn accepts_valid_cpfs() {
+        assert_eq!(detect("529.982.247-25").unwrap(), TaxIdKind::Cpf);
+        assert_eq!(detect("111.444.777-35").unwrap(), TaxIdKind::Cpf);
+    }
+
+    #[test]
+    fn rejects_cpfs_with_wrong_check_digits() {
+        assert!(detect("529.982.247-26").is_err());
+        assert!(detect("111.444.777-53").is_err());
+    }
+
+    #[test]
+    fn accepts_valid_numeric_cnpjs() {
+        assert_eq!(detect("11.222.333/0001-81").unwrap(), TaxIdKind::Cnpj);
+        assert_eq!(detect("11.444.777/0001-61").unwrap(), TaxIdKind::Cnpj);
+    }
+
+    #[test]
+    fn rejects_numeric_cnpjs_with_wrong_check_digits() {
+        assert!(detect("11.222.333/0001-82").is_err());
+        assert!(detect("11.444.777/0001-16").is_err());
+    }
+
+    #[test]
+    fn accepts_valid_alphanumeric_cnpjs() {
+        assert_eq!(detect("12.ABC.345/01DE-35").unwrap(), TaxIdKind::Cnpj);
+        assert_eq!(detect("12abc34501de35").unwrap(), TaxIdKind::Cnpj);
+    }
+
+    #[test]
+    fn rejects_alphanumeric_cnpjs_with_wrong_check_digits() {
+        assert!(detect("12.ABC.345/01DE-36").is_err());
+        assert!(detect("12.ABD.345/01DE-35").is_err());
+    }
+
+    #[test]
+    fn rejects_letters_where_only_digits_are_allowed() {
+        assert!(detect("12.ABC.345/01DE-3A").is_err());
+        assert!(detect("529.982.24A-25").is_err());
+    }
+
+    #[test]
+    fn rejects_repeated_digits() {
+        assert!(detect("000.000.000-00").is_err());
+        assert!(detect("111.111.111-11").is_err());
+        assert!(detect("00.000.000/0000-00").is_err());
+        assert!(detect("99.999.999/9999-99").is_err());
+    }
+
+    #[test]
+    fn rejects_other_lengths() {
+        assert!(detect("529.982.247-2").is_err());
+        assert!(detect("").is_err());
+    }
+
+    #[test]
+    fn formats_by_length() {
+        assert_eq!(format_tax_id("52998224725"), "529.982.247-25");
+        assert_eq!(format_tax_id("12ABC34501DE35"), "12.ABC.345/01DE-35");
+        assert_eq!(format_tax_id("123"), "123");
+    }
+}
@@ -0,0 +1,156 @@
+use std::error::Error;
+
+use crate::infra::models::UnitRow;
+use crate::domain::audit::AuditField;
+
+/// A unit of measure. Units without a base are base units; the others are a
+/// multiple of their base, e.g. "Box of 12" is 12 "un" and "g" is 0.001 "kg".
+#[derive(Debug, Clone)]
+pub struct Unit {
+    pub id: i32,
+    pub name: String,
+    pub symbol: String,
+    pub base_unit_id: Option<i32>,
+    /// How many base units one of this unit holds; 1 for base units.
+    pub factor: f64,
+}
+
+impl From<UnitRow> for Unit {
+    fn from(row: UnitRow) -> Self {
+        Unit {
+            id: row.id,
+            name: row.name,
+            symbol: row.symbol,
+            base_unit_id: row.base_unit_id,
+            factor: row.factor,
+        }
+    }
+}
+
+impl Unit {
+    pub fn label(&self) -> String {
+        format!("{} ({})", self.name, self.symbol)
+    }
+
+    /// Fields kept in the audit log when the unit changes.
+    pub fn audit_fields(&self) -> Vec<AuditField> {
+        vec![
+            ("name", Some(self.name.clone())),
+            ("symbol", Some(self.symbol.clone())),
+            ("base_unit_id", self.base_unit_id.map(|id| id.to_string())),
+            ("factor", Some(self.factor.to_string())),
+        ]
+    }
+
+    /// Id of the base unit this unit is measured in.
+    pub fn base_id(&self) -> i32 {
+        self.base_unit_id.unwrap_or(self.id)
+    }
+
+    pub fn is_convertible_to(&self, other: &Unit) -> bool {
+        self.base_id() == other.base_id()
+    }
+
+    /// How many `target` units one of this unit holds, if they share a base.
+    pub fn ratio_to(&self, target: &Unit) -> Option<f64> {
+        self.is_convertible_to(target).then(|| self.factor / target.factor)
+    }
+
+    /// Converts a whole `quantity` of this unit into `target`. Fails when the units
+    /// have different bases or the result is not a whole number of `target`.
+    pub fn convert(&self, quantity: i32, target: &Unit) -> Result<i32, Box<dyn Error>> {
+        if self.id == target.id {
+            return Ok(quantity);
+        }
+
+        let Some(ratio) = self.ratio_to(target) else {
+            return Err(format!("Cannot convert {} to {}", self.symbol, target.symbol).into());
+        };
+
+        let converted = f64::from(quantity) * ratio;
+        let rounded = converted.round();
+
+        if (converted - rounded).abs() > 1e-6 {
+            return Err(format!("{} {} is not a whole number of {} ({:.3})", quantity, self.symbol, target.symbol, converted).into());
+        }
+
+        Ok(rounded as i32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unit(id: i32, symbol: &str, base_unit_id: Option<i32>, factor: f64) -> Unit {
+        Unit { id, name: symbol.to_owned(), symbol: symbol.to_owned(), base_unit_id, factor }
+    }
+
+    fn piece() -> Unit {
+        unit(1, "un", None, 1.0)
+    }
+
+    fn box_of_12() -> Unit {
+        unit(2, "bx12", Some(1), 12.0)
+    }
+
+    fn pack_of_4() -> Unit {
+        unit(3, "pk4", Some(1), 4.0)
+    }
+
+    fn kilogram() -> Unit {
+        unit(4, "kg", None, 1.0)
+    }
+
+    fn gram() -> Unit {
+        unit(5, "g", Some(4), 0.001)
+    }
+
+    #[test]
+    fn converts_derived_to_base() {
+        assert_eq!(box_of_12().convert(3, &piece()).unwrap(), 36);
+        assert_eq!(kilogram().convert(2, &gram()).unwrap(), 2000);
+    }
+
+    #[test]
+    fn converts_base_to_derived() {
+        assert_eq!(piece().convert(36, &box_of_12()).unwrap(), 3);
+        assert!(gram().convert(2500, &kilogram()).is_err());
+        assert_eq!(gram().convert(3000, &kilogram()).unwrap(), 3);
+    }
+
+    #[test]
+    fn converts_between_derived_units_of_the_same_base() {
+        assert_eq!(box_of_12().convert(2, &pack_of_4()).unwrap(), 6);
+        assert_eq!(pack_of_4().convert(6, &box_of_12()).unwrap(), 2);
+        assert_eq!(box_of_12().ratio_to(&pack_of_4()), Some(3.0));
+    }
+
+    #[test]
+    fn keeps_the_quantity_in_the_same_unit() {
+        assert_eq!(box_of_12().convert(7, &box_of_12()).unwrap(), 7);
+        assert_eq!(piece().ratio_to(&piece()), Some(1.0));
+    }
+
+    #[test]
+    fn rejects_units_with_different_bases() {
+        assert!(piece().convert(1, &kilogram()).is_err());
+        assert_eq!(box_of_12().ratio_to(&gram()), None);
+    }
+
+    #[test]
+    fn rejects_results_that_are_not_whole() {
+        assert!(piece().convert(5, &box_of_12()).is_err());
+        assert!(pack_of_4().convert(1, &box_of_12()).is_err());
+    }
+
+    #[test]
+    fn rounds_away_floating_point_noise() {
+        let bag = unit(6, "bag", Some(4), 0.7);
+
+        // 90 × 0.7 is 62.99999999999999 in floating point.
+        assert_eq!(bag.convert(90, &kilogram()).unwrap(), 63);
+        assert!(bag.convert(1, &kilogram()).is_err());
+        assert_eq!(kilogram().convert(63, &bag).unwrap(), 90);
+    }
+}
@@ -0,0 +1,413 @@
+use std::collections::{HashMap, VecDeque};
+use std::error::Error;
+
+use crate::domain::inventory_transaction::{InventoryTransaction, TransactionType};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum CostingMethod {
+    #[default]
+    WeightedAverage,
+    Fifo,
+}
+
+impl CostingMethod {
+    pub const ALL: [CostingMethod; 2] = [
+        CostingMethod::WeightedAverage,
+        CostingMethod::Fifo,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CostingMethod::WeightedAverage => "weighted_average",
+            CostingMethod::Fifo => "fifo",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            CostingMethod::WeightedAverage => "Moving weighted average",
+            CostingMethod::Fifo => "FIFO",
+        }
+    }
+}
+
+impl TryFrom<&str> for CostingMethod {
+    type Error = Box<dyn Error>;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        CostingMethod::ALL
+            .into_iter()
+            .find(|method| method.as_str() == value)
+            .ok_or_else(|| format!("Unknown costing method '{}'", value).into())
+    }
+}
+
+/// Quantity on hand and its value for a product at a place.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StockValuation {
+    pub product_id: i32,
+    pub place_id: i32,
+    pub quantity: i64,
+    pub value: f64,
+}
+
+impl StockValuation {
+    pub fn unit_cost(&self) -> Option<f64> {
+        (self.quantity != 0).then(|| self.value / self.quantity as f64)
+    }
+}
+
+/// Values the stock left by `transactions`, which must be in ledger order.
+/// `transfer_sources` maps each transfer-in movement to its transfer-out, so
+/// FIFO layers travel with the goods between places.
+///
+/// Entries without a unit cost (and positive adjustments) come in at the
+/// product's current cost: the running average, or the last known cost for FIFO.
+pub fn value_stock(method: CostingMethod, transactions: &[InventoryTransaction], transfer_sources: &HashMap<i32, i32>) -> Vec<StockValuation> {
+    let mut valuations = match method {
+        CostingMethod::WeightedAverage => value_weighted_average(transactions),
+        CostingMethod::Fifo => value_fifo(transactions, transfer_sources),
+    };
+
+    valuations.retain(|valuation| valuation.quantity != 0);
+    valuations.sort_by_key(|valuation| (valuation.product_id, valuation.place_id));
+
+    valuations
+}
+
+#[derive(Default)]
+struct AverageCost {
+    quantity: i64,
+    unit_cost: f64,
+}
+
+fn value_weighted_average(transactions: &[InventoryTransaction]) -> Vec<StockValuation> {
+    let mut costs: HashMap<i32, AverageCost> = HashMap::new();
+    let mut quantities: HashMap<(i32, i32), i64> = HashMap::new();
+
+    for transaction in transactions {
+        let quantity = i64::from(transaction.quantity);
+
+        *quantities.entry((transaction.product_id, transaction.place_id)).or_default() += quantity;
+
+        // Transfers move stock between places without changing the product's cost.
+        if matches!(transaction.transaction_type, TransactionType::TransferIn | TransactionType::TransferOut) {
+            continue;
+        }
+
+        let cost = costs.entry(transaction.product_id).or_default();
+
+        if quantity > 0 {
+            let unit_cost = transaction.unit_cost.unwrap_or(cost.unit_cost);
+            let new_quantity = cost.quantity + quantity;
+
+            cost.unit_cost = if cost.quantity > 0 {
+                (cost.quantity as f64 * cost.unit_cost + quantity as f64 * unit_cost) / new_quantity as f64
+            } else {
+                unit_cost
+            };
+            cost.quantity = new_quantity;
+        } else {
+            cost.quantity += quantity;
+        }
+    }
+
+    quantities.into_iter()
+        .map(|((product_id, place_id), quantity)| {
+            let unit_cost = costs.get(&product_id).map(|cost| cost.unit_cost).unwrap_or_default();
+
+            StockValuation {
+                product_id,
+                place_id,
+                quantity,
+                value: quantity as f64 * unit_cost,
+            }
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, Copy)]
+struct CostLayer {
+    quantity: i64,
+    unit_cost: f64,
+}
+
+fn value_fifo(transactions: &[InventoryTransaction], transfer_sources: &HashMap<i32, i32>) -> Vec<StockValuation> {
+    let mut layers: HashMap<(i32, i32), VecDeque<CostLayer>> = HashMap::new();
+    let mut last_costs: HashMap<i32, f64> = HashMap::new();
+    let mut in_transit: HashMap<i32, Vec<CostLayer>> = HashMap::new();
+
+    for transaction in transactions {
+        let quantity = i64::from(transaction.quantity);
+        let current_cost = last_costs.get(&transaction.product_id).copied().unwrap_or_default();
+        let place_layers = layers.entry((transaction.product_id, transaction.place_id)).or_default();
+
+        match transaction.transaction_type {
+            TransactionType::TransferOut => {
+                let taken = take_layers(place_layers, -quantity, current_cost);
+                in_transit.insert(transaction.id, taken);
+            }
+            TransactionType::TransferIn => {
+                let moved = transfer_sources.get(&transaction.id)
+                    .and_then(|outbound_id| in_transit.remove(outbound_id))
+                    .unwrap_or_else(|| vec![CostLayer { quantity, unit_cost: current_cost }]);
+
+                for layer in moved {
+                    add_layer(place_layers, layer);
+                }
+            }
+            _ if quantity > 0 => {
+                let unit_cost = transaction.unit_cost.unwrap_or(current_cost);
+
+                if transaction.unit_cost.is_some() {
+                    last_costs.insert(transaction.product_id, unit_cost);
+                }
+
+                add_layer(place_layers, CostLayer { quantity, unit_cost });
+            }
+            _ => {
+                take_layers(place_layers, -quantity, current_cost);
+            }
+        }
+    }
+
+    layers.into_iter()
+        .map(|((product_id, place_id), place_layers)| StockValuation {
+            product_id,
+            place_id,
+            quantity: place_layers.iter().map(|layer| layer.quantity).sum(),
+            value: place_layers.iter().map(|layer| layer.quantity as f64 * layer.unit_cost).sum(),
+        })
+        .collect()
+}
+
+/// Takes `quantity` from the oldest layers. Taking more than is on hand leaves a
+/// negative layer at `fallback_cost`, settled by the next receipt.
+fn take_layers(layers: &mut VecDeque<CostLayer>, quantity: i64, fallback_cost: f64) -> Vec<CostLayer> {
+    let mut taken = Vec::new();
+    let mut remaining = quantity;
+
+    while remaining > 0 {
+        let Some(oldest) = layers.front_mut().filter(|layer| layer.quantity > 0) else {
+            break;
+        };
+
+        let quantity_taken = remaining.min(oldest.quantity);
+
+        taken.push(CostLayer { quantity: quantity_taken, unit_cost: oldest.unit_cost });
+        oldest.quantity -= quantity_taken;
+        remaining -= quantity_taken;
+
+        if oldest.quantity == 0 {
+            layers.pop_front();
+        }
+    }
+
+    if remaining > 0 {
+        taken.push(CostLayer { quantity: remaining, unit_cost: fallback_cost });
+        add_layer(layers, CostLayer { quantity: -remaining, unit_cost: fallback_cost });
+    }
+
+    taken
+}
+
+fn add_layer(layers: &mut VecDeque<CostLayer>, layer: CostLayer) {
+    // A negative layer only exists once everything else was taken; receipts settle it first.
+    if let Some(shortage) = layers.front_mut().filter(|oldest| oldest.quantity < 0) {
+        let net = shortage.quantity + layer.quantity;
+
+        if layer.quantity < 0 || net <= 0 {
+            shortage.quantity = net;
+
+            if net == 0 {
+                layers.pop_front();
+            }
+
+            return;
+        }
+
+        layers.pop_front();
+        layers.push_back(CostLayer { quantity: net, unit_cost: layer.unit_cost });
+        return;
+    }
+
+    if layer.quantity < 0 {
+        layers.push_back(layer);
+        return;
+    }
+
+    match layers.back_mut() {
+        Some(newest) if newest.unit_cost == layer.unit_cost => newest.quantity += layer.quantity,
+        _ => layers.push_back(layer),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PRODUCT_ID: i32 = 1;
+
+    fn movement(id: i32, place_id: i32, transaction_type: TransactionType, quantity: i32, unit_cost: Option<f64>) -> InventoryTransaction {
+        InventoryTransaction {
+            id,
+            product_id: PRODUCT_ID,
+            place_id,
+            supplier_id: None,
+            quantity: transaction_type.signed_quantity(quantity),
+            transaction_type,
+            reason: None,
+            created_at: chrono::NaiveDateTime::default(),
+            lot_number: None,
+            expiry_date: None,
+            unit_cost,
+        }
+    }
+
+    fn valuation(place_id: i32, quantity: i64, value: f64) -> StockValuation {
+        StockValuation { product_id: PRODUCT_ID, place_id, quantity, value }
+    }
+
+    #[test]
+    fn weighted_average_blends_receipts() {
+        let transactions = [
+            movement(1, 1, TransactionType::Entry, 10, Some(2.0)),
+            movement(2, 1, TransactionType::Entry, 10, Some(4.0)),
+        ];
+
+        assert_eq!(value_stock(CostingMethod::WeightedAverage, &transactions, &HashMap::new()), vec![valuation(1, 20, 60.0)]);
+    }
+
+    #[test]
+    fn weighted_average_issues_at_the_average() {
+        let transactions = [
+            movement(1, 1, TransactionType::Entry, 10, Some(2.0)),
+            movement(2, 1, TransactionType::Entry, 10, Some(4.0)),
+            movement(3, 1, TransactionType::Exit, 5, None),
+            movement(4, 1, TransactionType::Entry, 5, None),
+        ];
+
+        // The uncosted entry comes in at the running average of 3.
+        assert_eq!(value_stock(CostingMethod::WeightedAverage, &transactions, &HashMap::new()), vec![valuation(1, 20, 60.0)]);
+    }
+
+    #[test]
+    fn weighted_average_restarts_after_a_shortfall() {
+        let transactions = [
+            movement(1, 1, TransactionType::Exit, 4, None),
+            movement(2, 1, TransactionType::Entry, 10, Some(5.0)),
+        ];
+
+        assert_eq!(value_stock(CostingMethod::WeightedAverage, &transactions, &HashMap::new()), vec![valuation(1, 6, 30.0)]);
+    }
+
+    #[test]
+    fn weighted_average_transfers_keep_the_cost() {
+        let transactions = [
+            movement(1, 1, TransactionType::Entry, 10, Some(2.0)),
+            movement(2, 1, TransactionType::TransferOut, 4, None),
+            movement(3, 2, TransactionType::TransferIn, 4, None),
+            movement(4, 1, TransactionType::Entry, 6, Some(7.0)),
+        ];
+
+        // (10 × 2 + 6 × 7) / 16 = 3.875 for the product as a whole.
+        assert_eq!(
+            value_stock(CostingMethod::WeightedAverage, &transactions, &HashMap::from([(3, 2)])),
+            vec![valuation(1, 12, 46.5), valuation(2, 4, 15.5)]
+        );
+    }
+
+    #[test]
+    fn fifo_issues_from_the_oldest_layer() {
+        let transactions = [
+            movement(1, 1, TransactionType::Entry, 10, Some(2.0)),
+            movement(2, 1, TransactionType::Entry, 10, Some(4.0)),
+            movement(3, 1, TransactionType::Exit, 12, None),
+        ];
+
+        assert_eq!(value_stock(CostingMethod::Fifo, &transactions, &HashMap::new()), vec![valuation(1, 8, 32.0)]);
+    }
+
+    #[test]
+    fn fifo_shortfall_is_valued_at_the_last_cost() {
+        let transactions = [
+            movement(1, 1, TransactionType::Entry, 5, Some(2.0)),
+            movement(2, 1, TransactionType::Exit, 8, None),
+        ];
+
+        assert_eq!(value_stock(CostingMethod::Fifo, &transactions, &HashMap::new()), vec![valuation(1, -3, -6.0)]);
+    }
+
+    #[test]
+    fn fifo_shortfall_is_settled_by_the_next_receipt() {
+        let transactions = [
+            movement(1, 1, TransactionType::Entry, 5, Some(2.0)),
+            movement(2, 1, TransactionType::Exit, 8, None),
+            movement(3, 1, TransactionType::Entry, 10, Some(4.0)),
+        ];
+
+        assert_eq!(value_stock(CostingMethod::Fifo, &transactions, &HashMap::new()), vec![valuation(1, 7, 28.0)]);
+    }
+
+    #[test]
+    fn fifo_receipt_smaller_than_the_shortfall_leaves_it_open() {
+        let transactions = [
+            movement(1, 1, TransactionType::Exit, 5, None),
+            movement(2, 1, TransactionType::Entry, 3, Some(4.0)),
+        ];
+
+        // Nothing was ever costed before the shortfall, so it sits at zero.
+        assert_eq!(value_stock(CostingMethod::Fifo, &transactions, &HashMap::new()), vec![valuation(1, -2, 0.0)]);
+    }
+
+    #[test]
+    fn fifo_transfers_carry_their_layers() {
+        let transactions = [
+            movement(1, 1, TransactionType::Entry, 10, Some(2.0)),
+            movement(2, 1, TransactionType::Entry, 10, Some(4.0)),
+            movement(3, 1, TransactionType::TransferOut, 12, None),
+            movement(4, 2, TransactionType::TransferIn, 12, None),
+            movement(5, 2, TransactionType::Exit, 10, None),
+        ];
+
+        // Place 2 received 10 at 2 and 2 at 4, and issued the cheaper ones first.
+        assert_eq!(
+            value_stock(CostingMethod::Fifo, &transactions, &HashMap::from([(4, 3)])),
+            vec![valuation(1, 8, 32.0), valuation(2, 2, 8.0)]
+        );
+    }
+
+    #[test]
+    fn fifo_transfer_without_a_known_source_comes_in_at_the_last_cost() {
+        let transactions = [
+            movement(1, 1, TransactionType::Entry, 10, Some(3.0)),
+            movement(2, 2, TransactionType::TransferIn, 4, None),
+        ];
+
+        assert_eq!(
+            value_stock(CostingMethod::Fifo, &transactions, &HashMap::new()),
+            vec![valuation(1, 10, 30.0), valuation(2, 4, 12.0)]
+        );
+    }
+
+    #[test]
+    fn add_layer_merges_receipts_at_the_same_cost() {
+        let mut layers = VecDeque::new();
+
+        add_layer(&mut layers, CostLayer { quantity: 2, unit_cost: 1.0 });
+        add_layer(&mut layers, CostLayer { quantity: 3, unit_cost: 1.0 });
+        add_layer(&mut layers, CostLayer { quantity: 1, unit_cost: 2.0 });
+
+        assert_eq!(layers.iter().map(|layer| (layer.quantity, layer.unit_cost)).collect::<Vec<_>>(), vec![(5, 1.0), (1, 2.0)]);
+    }
+
+    #[test]
+    fn take_layers_returns_what_it_took() {
+        let mut layers = VecDeque::from([CostLayer { quantity: 2, unit_cost: 1.0 }, CostLayer { quantity: 3, unit_cost: 2.0 }]);
+
+        let taken = take_layers(&mut layers, 6, 5.0);
+
+        assert_eq!(taken.iter().map(|layer| (layer.quantity, layer.unit_cost)).collect::<Vec<_>>(), vec![(2, 1.0), (3, 2.0), (1, 5.0)]);
+        assert_eq!(layers.iter().map(|layer| (layer.quantity, layer.unit_cost)).collect::<Vec<_>>(), vec![(-1, 5.0)]);
+    }
+}
@@ -0,0 +1,122 @@
+use egui::{Id, Modal, RichText, Sides};
+
+use crate::infra::db;
+use crate::infra::repositories::stocktake_repository;
+
+use crate::domain::stocktake::{Stocktake, StocktakeLine};
+
+const DEFAULT_SPACING: f32 = 16.0;
+const FORM_SPACING: f32 = DEFAULT_SPACING / 2.0;
+
+#[derive(Default)]
+pub struct FormErrors {
+    pub save: Option<String>,
+}
+
+/// Summarizes the variances of a stocktake and approves it, posting the
+/// adjustments with the reason typed here.
+pub struct ApproveStocktakeModal {
+    should_close: bool,
+
+    stocktake_id: i32,
+    counted_lines: usize,
+    uncounted_lines: usize,
+    variance_lines: usize,
+    net_variance: i64,
+
+    reason: String,
+
+    errors: FormErrors,
+}
+
+impl ApproveStocktakeModal {
+    pub fn new(stocktake: &Stocktake, lines: &[StocktakeLine]) -> Self {
+        let variances: Vec<i32> = lines.iter()
+            .filter_map(|line| line.variance())
+            .collect();
+
+        Self {
+            should_close: false,
+            stocktake_id: stocktake.id,
+            counted_lines: variances.len(),
+            uncounted_lines: lines.len() - variances.len(),
+            variance_lines: variances.iter().filter(|variance| **variance != 0).count(),
+            net_variance: variances.iter().map(|variance| i64::from(*variance)).sum(),
+            reason: String::new(),
+            errors: FormErrors::default(),
+        }
+    }
+
+    pub fn show(&mut self, ui: &mut egui::Ui) -> (bool, Option<Stocktake>) {
+        let mut approved_stocktake = None;
+
+        let modal = Modal::new(Id::new("Approve Stocktake")).show(ui.ctx(), |ui| {
+            ui.heading(format!("Approve Stocktake #{}", self.stocktake_id));
+            ui.separator();
+            ui.add_space(DEFAULT_SPACING / 2.0);
+
+            egui::Grid::new("approve_stocktake_summary").num_columns(2).show(ui, |ui| {
+                ui.label("Counted lines");
+                ui.label(self.counted_lines.to_string());
+                ui.end_row();
+
+                ui.label("Adjustments to post");
+                ui.label(self.variance_lines.to_string());
+                ui.end_row();
+
+                ui.label("Net variance");
+                ui.label(format!("{:+}", self.net_variance));
+                ui.end_row();
+            });
+
+            if self.uncounted_lines > 0 {
+                ui.add_space(FORM_SPACING);
+                ui.label(RichText::new(format!("{} uncounted line(s) will be left unchanged.", self.uncounted_lines)).weak());
+            }
+
+            ui.add_space(FORM_SPACING);
+
+            ui.label("Reason");
+            ui.text_edit_multiline(&mut self.reason);
+
+            if let Some(error) = &self.errors.save {
+                ui.add_space(FORM_SPACING);
+                ui.label(RichText::new(error).color(ui.visuals().error_fg_color));
+            }
+
+            ui.add_space(DEFAULT_SPACING / 2.0);
+            ui.separator();
+            ui.add_space(FORM_SPACING);
+
+            Sides::new().show(
+                ui,
+                |_ui| {},
+                |ui| {
+                    if ui.button("Approve").clicked() {
+                        let mut connection = db::establish_connection();
+
+                        match stocktake_repository::approve_stocktake(&mut connection, self.stocktake_id, &self.reason) {
+                            Ok(stocktake) => {
+                                approved_stocktake = Some(stocktake);
+                                self.should_close = true;
+                            }
+                            Err(error) => {
+                                self.errors.save = Some(error.to_string());
+                            }
+                        }
+                    }
+
+                    if ui.button("Cancel").clicked() {
+                        self.should_close = true;
+                    }
+                }
+            );
+        });
+
+        if modal.should_close() {
+            self.should_close = true;
+        }
+
+        (self.should_close, approved_stocktake)
+    }
+}
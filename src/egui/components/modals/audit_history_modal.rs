@@ -0,0 +1,132 @@
+use egui::{Id, Modal, RichText, Sides};
+use egui_extras::{Column, TableBuilder};
+
+use crate::infra::db;
+use crate::infra::repositories::audit_repository;
+
+use crate::domain::audit::{AuditEntity, AuditEntry};
+
+const DEFAULT_SPACING: f32 = 16.0;
+const ITEM_HEIGHT: f32 = 24.0;
+const DATE_TIME_DISPLAY_PATTERN: &str = "%Y-%m-%d %H:%M";
+
+/// Every change recorded for one record, newest first, one row per field.
+pub struct AuditHistoryModal {
+    should_close: bool,
+
+    entity: AuditEntity,
+    record_name: String,
+    entries: Vec<AuditEntry>,
+    error: Option<String>,
+}
+
+impl AuditHistoryModal {
+    pub fn new(entity: AuditEntity, entity_id: i32, record_name: &str) -> Self {
+        let mut connection = db::establish_connection();
+
+        let (entries, error) = match audit_repository::list_audit_entries(&mut connection, entity, entity_id) {
+            Ok(entries) => (entries, None),
+            Err(error) => (Vec::new(), Some(error.to_string())),
+        };
+
+        Self {
+            should_close: false,
+            entity,
+            record_name: record_name.to_owned(),
+            entries,
+            error,
+        }
+    }
+
+    pub fn show(&mut self, ui: &mut egui::Ui) -> bool {
+        let modal = Modal::new(Id::new("Audit History")).show(ui.ctx(), |ui| {
+            ui.heading(format!("{} History — {}", self.entity.label(), self.record_name));
+            ui.separator();
+            ui.add_space(DEFAULT_SPACING / 2.0);
+
+            if let Some(error) = &self.error {
+                ui.label(RichText::new(error).color(ui.visuals().error_fg_color));
+            } else if self.entries.is_empty() {
+                ui.label("No changes have been recorded for this record.");
+            } else {
+                self.changes_table(ui);
+            }
+
+            ui.add_space(DEFAULT_SPACING / 2.0);
+            ui.separator();
+
+            Sides::new().show(
+                ui,
+                |_ui| {},
+                |ui| {
+                    if ui.button("Close").clicked() {
+                        self.should_close = true;
+                    }
+                }
+            );
+        });
+
+        if modal.should_close() {
+            self.should_close = true;
+        }
+
+        self.should_close
+    }
+
+    fn changes_table(&self, ui: &mut egui::Ui) {
+        // Restores and purges change no field but still take a row.
+        let rows: Vec<(&AuditEntry, usize)> = self.entries.iter()
+            .flat_map(|entry| (0..entry.changes.len().max(1)).map(move |index| (entry, index)))
+            .collect();
+
+        TableBuilder::new(ui)
+            .striped(true)
+            .resizable(false)
+            .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+            .column(Column::exact(130.0))
+            .column(Column::exact(72.0))
+            .column(Column::exact(120.0))
+            .column(Column::auto().at_least(120.0).at_most(240.0).clip(true))
+            .column(Column::auto().at_least(120.0).at_most(240.0).clip(true))
+            .max_scroll_height(400.0)
+            .header(ITEM_HEIGHT, |mut header| {
+                header.col(|ui| { ui.heading("Date"); });
+                header.col(|ui| { ui.heading("Action"); });
+                header.col(|ui| { ui.heading("Field"); });
+                header.col(|ui| { ui.heading("Before"); });
+                header.col(|ui| { ui.heading("After"); });
+            })
+            .body(|body| {
+                body.rows(ITEM_HEIGHT, rows.len(), |mut row| {
+                    let (entry, index) = rows[row.index()];
+                    let change = entry.changes.get(index);
+
+                    // The date and action are only shown on the first field of each entry.
+                    row.col(|ui| {
+                        if index == 0 {
+                            ui.label(entry.created_at.format(DATE_TIME_DISPLAY_PATTERN).to_string());
+                        }
+                    });
+                    row.col(|ui| {
+                        if index == 0 {
+                            ui.label(entry.action.label());
+                        }
+                    });
+                    row.col(|ui| { ui.label(change.map(|change| field_label(&change.field)).unwrap_or_default()); });
+                    row.col(|ui| { ui.label(RichText::new(change.and_then(|change| change.before.clone()).unwrap_or_default()).weak()); });
+                    row.col(|ui| { ui.label(change.and_then(|change| change.after.clone()).unwrap_or_default()); });
+                });
+            });
+    }
+}
+
+/// "min_stock" reads as "Min stock".
+fn field_label(field: &str) -> String {
+    let label = field.replace('_', " ");
+    let mut chars = label.chars();
+
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => label,
+    }
+}
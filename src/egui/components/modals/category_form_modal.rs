@@ -0,0 +1,143 @@
+use egui::{Id, Modal, RichText, Sides};
+use validator::Validate;
+
+use crate::infra::db;
+use crate::infra::repositories::category_repository;
+use crate::infra::models::{NewCategoryRow, EditCategoryRow};
+
+use crate::domain::category::Category;
+use crate::egui::widgets::category_select::category_select;
+
+const DEFAULT_SPACING: f32 = 16.0;
+const FORM_SPACING: f32 = DEFAULT_SPACING / 2.0;
+
+#[derive(Default)]
+pub struct FormErrors {
+    pub name: Option<String>,
+    pub save: Option<String>,
+}
+
+#[derive(Validate)]
+pub struct CategoryForm {
+    #[validate(length(min = 2, message = "Name must contain at least two characters"))]
+    name: String,
+
+    parent_id: Option<i32>,
+}
+
+pub struct CategoryFormModal {
+    should_close: bool,
+
+    id: Option<i32>,
+    name: String,
+    parent_id: Option<i32>,
+    categories: Vec<Category>,
+
+    errors: FormErrors,
+}
+
+impl CategoryFormModal {
+    /// `parent_id` preselects the parent of a new category.
+    pub fn new(category: Option<&Category>, parent_id: Option<i32>, categories: &[Category]) -> Self {
+        Self {
+            should_close: false,
+            id: category.map(|category| category.id),
+            name: category.map(|category| category.name.clone()).unwrap_or_default(),
+            parent_id: category.map_or(parent_id, |category| category.parent_id),
+            categories: categories.to_vec(),
+            errors: FormErrors::default(),
+        }
+    }
+
+    pub fn show(&mut self, ui: &mut egui::Ui) -> (bool, Option<Category>) {
+        let mut saved_category = None;
+
+        let modal = Modal::new(Id::new("Category Form")).show(ui.ctx(), |ui| {
+            ui.heading(if self.id.is_none() { "New Category" } else { "Edit Category" });
+            ui.separator();
+            ui.add_space(DEFAULT_SPACING / 2.0);
+
+            ui.label("Name");
+            ui.text_edit_singleline(&mut self.name);
+            if let Some(error) = &self.errors.name {
+                ui.label(RichText::new(error).color(ui.visuals().error_fg_color));
+            }
+
+            ui.add_space(FORM_SPACING);
+
+            ui.label("Parent Category");
+            category_select(ui, "category_parent", &mut self.parent_id, &self.categories, self.id);
+
+            if let Some(error) = &self.errors.save {
+                ui.add_space(FORM_SPACING);
+                ui.label(RichText::new(error).color(ui.visuals().error_fg_color));
+            }
+
+            ui.add_space(DEFAULT_SPACING / 2.0);
+            ui.separator();
+            ui.add_space(FORM_SPACING);
+
+            Sides::new().show(
+                ui,
+                |_ui| {},
+                |ui| {
+                    if ui.button("Save").clicked()
+                        && let Some(category) = self.validate_form() {
+                        let mut connection = db::establish_connection();
+
+                        let result = match self.id {
+                            Some(id) => category_repository::edit_category(&mut connection, EditCategoryRow {
+                                id,
+                                name: category.name,
+                                parent_id: category.parent_id,
+                            }),
+                            None => category_repository::create_category(&mut connection, NewCategoryRow {
+                                name: category.name,
+                                parent_id: category.parent_id,
+                            }),
+                        };
+
+                        match result {
+                            Ok(category) => {
+                                saved_category = Some(category);
+                                self.should_close = true;
+                            }
+                            Err(error) => {
+                                self.errors.save = Some(error.to_string());
+                            }
+                        }
+                    }
+
+                    if ui.button("Cancel").clicked() {
+                        self.should_close = true;
+                    }
+                }
+            );
+        });
+
+        if modal.should_close() {
+            self.should_close = true;
+        }
+
+        (self.should_close, saved_category)
+    }
+
+    fn validate_form(&mut self) -> Option<CategoryForm> {
+        self.errors = FormErrors::default();
+
+        let category_data = CategoryForm {
+            name: self.name.trim().to_owned(),
+            parent_id: self.parent_id,
+        };
+
+        match category_data.validate() {
+            Ok(_) => Some(category_data),
+            Err(error) => {
+                if let Some(name_error) = error.field_errors().get("name") {
+                    self.errors.name = Some(name_error[0].clone().message.unwrap_or_default().to_string());
+                }
+                None
+            }
+        }
+    }
+}
@@ -0,0 +1,321 @@
+use chrono::NaiveDate;
+use egui::{ComboBox, Grid, Id, Modal, RichText, Sides};
+
+use crate::infra::db;
+use crate::infra::repositories::{goods_receipt_repository, place_repository, unit_repository};
+use crate::infra::repositories::goods_receipt_repository::{NewGoodsReceipt, NewGoodsReceiptLine};
+
+use crate::domain::inventory_transaction::NAIVE_DATE_PATTERN;
+use crate::domain::place::{Place, place_path};
+use crate::domain::product::Product;
+use crate::domain::purchase_order::{PurchaseOrder, PurchaseOrderLine};
+use crate::domain::serial_number::parse_serial_numbers;
+use crate::domain::unit::Unit;
+
+const DEFAULT_SPACING: f32 = 16.0;
+const FORM_SPACING: f32 = DEFAULT_SPACING / 2.0;
+
+#[derive(Default)]
+pub struct FormErrors {
+    pub place_id: Option<String>,
+    pub lines: Option<String>,
+    pub save: Option<String>,
+}
+
+pub struct GoodsReceiptModal {
+    should_close: bool,
+
+    order: PurchaseOrder,
+    lines: Vec<PurchaseOrderLine>,
+    products: Vec<Product>,
+    places: Vec<Place>,
+    units: Vec<Unit>,
+
+    place_id: Option<i32>,
+    quantities: Vec<String>,
+    lot_numbers: Vec<String>,
+    expiry_dates: Vec<String>,
+    serial_numbers: Vec<String>,
+    notes: String,
+
+    errors: FormErrors,
+}
+
+impl GoodsReceiptModal {
+    pub fn new(order: &PurchaseOrder, lines: &[PurchaseOrderLine], products: &[Product]) -> Self {
+        let mut connection = db::establish_connection();
+
+        Self {
+            should_close: false,
+            order: order.clone(),
+            lines: lines.to_vec(),
+            products: products.to_vec(),
+            places: place_repository::list_places(&mut connection, "").unwrap_or_default(),
+            units: unit_repository::list_units(&mut connection, "").unwrap_or_default(),
+            place_id: None,
+            // Start with whatever is still outstanding so a complete delivery is one click.
+            quantities: lines.iter().map(|line| line.remaining_quantity().to_string()).collect(),
+            lot_numbers: vec![String::new(); lines.len()],
+            expiry_dates: vec![String::new(); lines.len()],
+            serial_numbers: vec![String::new(); lines.len()],
+            notes: String::new(),
+            errors: FormErrors::default(),
+        }
+    }
+
+    pub fn show(&mut self, ui: &mut egui::Ui) -> (bool, Option<PurchaseOrder>) {
+        let mut updated_order = None;
+
+        let modal = Modal::new(Id::new("Goods Receipt")).show(ui.ctx(), |ui| {
+            ui.heading(format!("Receive Purchase Order #{}", self.order.id));
+            ui.separator();
+            ui.add_space(DEFAULT_SPACING / 2.0);
+
+            ui.label("Destination Place");
+            let place_name = self.places.iter()
+                .find(|place| Some(place.id) == self.place_id)
+                .map(|place| place_path(&self.places, place.id))
+                .unwrap_or_default();
+
+            ComboBox::from_id_salt("goods_receipt_place")
+                .selected_text(place_name)
+                .show_ui(ui, |ui| {
+                    for place in &self.places {
+                        ui.selectable_value(&mut self.place_id, Some(place.id), place_path(&self.places, place.id));
+                    }
+                });
+            if let Some(error) = &self.errors.place_id {
+                ui.label(RichText::new(error).color(ui.visuals().error_fg_color));
+            }
+
+            ui.add_space(FORM_SPACING);
+
+            Grid::new("goods_receipt_lines")
+                .striped(true)
+                .num_columns(9)
+                .show(ui, |ui| {
+                    ui.strong("Product");
+                    ui.strong("Ordered");
+                    ui.strong("Received");
+                    ui.strong("Outstanding");
+                    ui.strong("Receive Now");
+                    ui.strong("Lot");
+                    ui.strong("Expiry Date");
+                    ui.strong("Serial Numbers");
+                    ui.label("");
+                    ui.end_row();
+
+                    for (index, line) in self.lines.iter().enumerate() {
+                        let product = self.products.iter().find(|product| product.id == line.product_id);
+
+                        let product_name = product
+                            .map(|product| product.name.clone())
+                            .unwrap_or_else(|| format!("#{}", line.product_id));
+
+                        // Quantities are entered in the unit the line was ordered in.
+                        let unit_symbol = line.unit_id
+                            .and_then(|unit_id| self.units.iter().find(|unit| unit.id == unit_id))
+                            .map(|unit| unit.symbol.clone())
+                            .or_else(|| product.and_then(|product| product.unity.clone()))
+                            .unwrap_or_default();
+
+                        let quantity = &mut self.quantities[index];
+
+                        ui.label(product_name);
+                        ui.label(format!("{} {}", line.quantity, unit_symbol));
+                        ui.label(line.received_quantity.to_string());
+                        ui.label(line.remaining_quantity().to_string());
+                        ui.add(egui::TextEdit::singleline(quantity).desired_width(80.0));
+
+                        if product.is_some_and(|product| product.tracks_lots) {
+                            ui.add(egui::TextEdit::singleline(&mut self.lot_numbers[index]).desired_width(100.0));
+                            ui.add(egui::TextEdit::singleline(&mut self.expiry_dates[index]).desired_width(100.0).hint_text("YYYY-MM-DD"));
+                        } else {
+                            ui.label("");
+                            ui.label("");
+                        }
+
+                        if product.is_some_and(|product| product.serialized) {
+                            ui.add(egui::TextEdit::singleline(&mut self.serial_numbers[index]).desired_width(160.0).hint_text("SN1, SN2, ..."));
+                        } else {
+                            ui.label("");
+                        }
+
+                        match delivery_warning(line, quantity) {
+                            Some(warning) => ui.label(RichText::new(warning).color(ui.visuals().warn_fg_color)),
+                            None => ui.label(""),
+                        };
+
+                        ui.end_row();
+                    }
+                });
+            if let Some(error) = &self.errors.lines {
+                ui.label(RichText::new(error).color(ui.visuals().error_fg_color));
+            }
+
+            ui.add_space(FORM_SPACING);
+
+            ui.label("Notes");
+            ui.text_edit_multiline(&mut self.notes);
+
+            if let Some(error) = &self.errors.save {
+                ui.add_space(FORM_SPACING);
+                ui.label(RichText::new(error).color(ui.visuals().error_fg_color));
+            }
+
+            ui.add_space(DEFAULT_SPACING / 2.0);
+            ui.separator();
+            ui.add_space(FORM_SPACING);
+
+            Sides::new().show(
+                ui,
+                |_ui| {},
+                |ui| {
+                    if ui.button("Receive").clicked()
+                        && let Some(receipt) = self.validate_form() {
+                        let mut connection = db::establish_connection();
+
+                        match goods_receipt_repository::receive_purchase_order(&mut connection, receipt) {
+                            Ok(order) => {
+                                updated_order = Some(order);
+                                self.should_close = true;
+                            }
+                            Err(error) => {
+                                self.errors.save = Some(error.to_string());
+                            }
+                        }
+                    }
+
+                    if ui.button("Cancel").clicked() {
+                        self.should_close = true;
+                    }
+                }
+            );
+        });
+
+        if modal.should_close() {
+            self.should_close = true;
+        }
+
+        (self.should_close, updated_order)
+    }
+
+    fn find_unit(&self, unit_id: Option<i32>) -> Option<&Unit> {
+        unit_id.and_then(|unit_id| self.units.iter().find(|unit| unit.id == unit_id))
+    }
+
+    fn validate_form(&mut self) -> Option<NewGoodsReceipt> {
+        self.errors = FormErrors::default();
+
+        if self.place_id.is_none() {
+            self.errors.place_id = Some("Select where the goods are stored".into());
+        }
+
+        let mut lines = Vec::new();
+
+        for (index, line) in self.lines.iter().enumerate() {
+            let quantity = match self.quantities[index].trim() {
+                "" => 0,
+                value => match value.parse::<i32>() {
+                    Ok(received) if received >= 0 => received,
+                    _ => {
+                        self.errors.lines = Some("Quantities should be positive integers".into());
+                        continue;
+                    }
+                },
+            };
+
+            if quantity == 0 {
+                continue;
+            }
+
+            let tracks_lots = self.products.iter()
+                .any(|product| product.id == line.product_id && product.tracks_lots);
+
+            let lot_number = Some(self.lot_numbers[index].trim().to_owned()).filter(|lot_number| !lot_number.is_empty());
+
+            if tracks_lots && lot_number.is_none() {
+                self.errors.lines = Some("Enter the lot number of every lot-tracked product received".into());
+                continue;
+            }
+
+            let expiry_date = match self.expiry_dates[index].trim() {
+                value if value.is_empty() || !tracks_lots => None,
+                value => match NaiveDate::parse_from_str(value, NAIVE_DATE_PATTERN) {
+                    Ok(date) => Some(date.format(NAIVE_DATE_PATTERN).to_string()),
+                    Err(_) => {
+                        self.errors.lines = Some("Expiry dates should use the YYYY-MM-DD format".into());
+                        continue;
+                    }
+                },
+            };
+
+            let serialized = self.products.iter()
+                .any(|product| product.id == line.product_id && product.serialized);
+
+            let serial_numbers = if serialized {
+                parse_serial_numbers(&self.serial_numbers[index])
+            } else {
+                Vec::new()
+            };
+
+            // Serials are counted per stock unit, e.g. 12 serials for a box of 12.
+            let product_unit_id = self.products.iter()
+                .find(|product| product.id == line.product_id)
+                .and_then(|product| product.unit_id);
+
+            let stock_quantity = match (self.find_unit(line.unit_id), self.find_unit(product_unit_id)) {
+                (Some(line_unit), Some(product_unit)) => match line_unit.convert(quantity, product_unit) {
+                    Ok(stock_quantity) => stock_quantity,
+                    Err(error) => {
+                        self.errors.lines = Some(error.to_string());
+                        continue;
+                    }
+                },
+                _ => quantity,
+            };
+
+            if serialized && serial_numbers.len() != stock_quantity as usize {
+                self.errors.lines = Some("Enter one serial number per unit received of every serialized product".into());
+                continue;
+            }
+
+            lines.push(NewGoodsReceiptLine {
+                purchase_order_line_id: line.id,
+                quantity,
+                lot_number: lot_number.filter(|_| tracks_lots),
+                expiry_date,
+                serial_numbers,
+            });
+        }
+
+        if self.errors.lines.is_none() && lines.is_empty() {
+            self.errors.lines = Some("Enter the received quantity of at least one line".into());
+        }
+
+        if self.errors.place_id.is_some() || self.errors.lines.is_some() {
+            return None;
+        }
+
+        Some(NewGoodsReceipt {
+            purchase_order_id: self.order.id,
+            place_id: self.place_id.unwrap_or_default(),
+            notes: Some(self.notes.trim().to_owned()).filter(|notes| !notes.is_empty()),
+            lines,
+        })
+    }
+}
+
+/// Flags deliveries that differ from what is still outstanding on the line.
+fn delivery_warning(line: &PurchaseOrderLine, quantity: &str) -> Option<String> {
+    let received = quantity.trim().parse::<i32>().ok()?;
+    let outstanding = line.remaining_quantity();
+
+    if received > outstanding {
+        Some(format!("Over-delivery: {} more than ordered", received - outstanding))
+    } else if received < outstanding {
+        Some(format!("Under-delivery: {} still outstanding", outstanding - received))
+    } else {
+        None
+    }
+}
@@ -0,0 +1,541 @@
+use chrono::NaiveDate;
+use egui::{ComboBox, Id, Modal, RichText, Sides};
+use validator::Validate;
+
+use crate::infra::db;
+use crate::infra::repositories::{inventory_transaction_repository, reservation_repository, serial_number_repository, stock_repository, unit_repository};
+use crate::infra::repositories::reservation_repository::ReservationFilter;
+use crate::infra::repositories::serial_number_repository::SerialNumberFilter;
+use crate::infra::repositories::stock_repository::LotBalanceFilter;
+use crate::infra::models::NewInventoryTransactionRow;
+
+use crate::domain::inventory_transaction::{InventoryTransaction, TransactionType, NAIVE_DATE_PATTERN};
+use crate::domain::place::{Place, place_path};
+use crate::domain::product::Product;
+use crate::domain::reservation::{Reservation, ReservationStatus};
+use crate::domain::serial_number::{parse_serial_numbers, SerialNumber};
+use crate::domain::stock_balance::LotBalance;
+use crate::domain::supplier::Supplier;
+use crate::domain::unit::Unit;
+use crate::egui::widgets::lot_input::lot_input;
+use crate::egui::widgets::serial_numbers_input::serial_numbers_input;
+use crate::egui::widgets::unit_select::unit_select;
+
+const DEFAULT_SPACING: f32 = 16.0;
+const FORM_SPACING: f32 = DEFAULT_SPACING / 2.0;
+
+#[derive(Default)]
+pub struct FormErrors {
+    pub product_id: Option<String>,
+    pub place_id: Option<String>,
+    pub quantity: Option<String>,
+    pub unit_cost: Option<String>,
+    pub lot_number: Option<String>,
+    pub expiry_date: Option<String>,
+    pub serial_numbers: Option<String>,
+    pub save: Option<String>,
+}
+
+#[derive(Validate)]
+pub struct InventoryTransactionForm {
+    #[validate(required(message = "Select a product"))]
+    product_id: Option<i32>,
+
+    #[validate(required(message = "Select a place"))]
+    place_id: Option<i32>,
+
+    supplier_id: Option<i32>,
+
+    transaction_type: TransactionType,
+
+    quantity: i32,
+
+    unit_cost: Option<f64>,
+
+    reason: String,
+
+    lot_number: Option<String>,
+
+    expiry_date: Option<NaiveDate>,
+
+    serial_numbers: Vec<String>,
+}
+
+pub struct InventoryTransactionFormModal {
+    should_close: bool,
+
+    products: Vec<Product>,
+    places: Vec<Place>,
+    suppliers: Vec<Supplier>,
+    units: Vec<Unit>,
+
+    product_id: Option<i32>,
+    place_id: Option<i32>,
+    supplier_id: Option<i32>,
+    transaction_type: TransactionType,
+    quantity: String,
+    unit_id: Option<i32>,
+    unit_cost: String,
+    reason: String,
+    lot_number: String,
+    expiry_date: String,
+    lots: Vec<LotBalance>,
+    serial_numbers: String,
+    serials_in_stock: Vec<SerialNumber>,
+    /// Open reservations of the product an exit can be issued against.
+    reservations: Vec<Reservation>,
+    reservation_id: Option<i32>,
+
+    errors: FormErrors,
+}
+
+impl InventoryTransactionFormModal {
+    pub fn new(products: &[Product], places: &[Place], suppliers: &[Supplier]) -> Self {
+        let mut connection = db::establish_connection();
+
+        Self {
+            should_close: false,
+            products: products.to_vec(),
+            places: places.to_vec(),
+            suppliers: suppliers.to_vec(),
+            units: unit_repository::list_units(&mut connection, "").unwrap_or_default(),
+            product_id: None,
+            place_id: None,
+            supplier_id: None,
+            transaction_type: TransactionType::Entry,
+            quantity: String::new(),
+            unit_id: None,
+            unit_cost: String::new(),
+            reason: String::new(),
+            lot_number: String::new(),
+            expiry_date: String::new(),
+            lots: Vec::new(),
+            serial_numbers: String::new(),
+            serials_in_stock: Vec::new(),
+            reservations: Vec::new(),
+            reservation_id: None,
+            errors: FormErrors::default(),
+        }
+    }
+
+    pub fn show(&mut self, ui: &mut egui::Ui) -> (bool, Option<InventoryTransaction>) {
+        let mut created_transaction = None;
+
+        let modal = Modal::new(Id::new("New Inventory Transaction")).show(ui.ctx(), |ui| {
+            ui.heading("New Movement");
+            ui.separator();
+            ui.add_space(DEFAULT_SPACING / 2.0);
+
+            ui.label("Product");
+            let product_name = self.products.iter()
+                .find(|product| Some(product.id) == self.product_id)
+                .map(|product| product.name.clone())
+                .unwrap_or_default();
+
+            let mut lots_changed = false;
+            let mut product_changed = false;
+
+            ComboBox::from_id_salt("transaction_product")
+                .selected_text(product_name)
+                .show_ui(ui, |ui| {
+                    for product in &self.products {
+                        product_changed |= ui.selectable_value(&mut self.product_id, Some(product.id), &product.name).changed();
+                    }
+                });
+
+            if product_changed {
+                self.unit_id = self.product_unit_id();
+                self.refresh_reservations();
+                lots_changed = true;
+            }
+            if let Some(error) = &self.errors.product_id {
+                ui.label(RichText::new(error).color(ui.visuals().error_fg_color));
+            }
+
+            ui.add_space(FORM_SPACING);
+
+            ui.label("Place");
+            let place_name = self.places.iter()
+                .find(|place| Some(place.id) == self.place_id)
+                .map(|place| place_path(&self.places, place.id))
+                .unwrap_or_default();
+
+            ComboBox::from_id_salt("transaction_place")
+                .selected_text(place_name)
+                .show_ui(ui, |ui| {
+                    for place in &self.places {
+                        lots_changed |= ui.selectable_value(&mut self.place_id, Some(place.id), place_path(&self.places, place.id)).changed();
+                    }
+                });
+            if let Some(error) = &self.errors.place_id {
+                ui.label(RichText::new(error).color(ui.visuals().error_fg_color));
+            }
+
+            if lots_changed {
+                self.refresh_lots();
+                self.refresh_serial_numbers();
+            }
+
+            ui.add_space(FORM_SPACING);
+
+            ui.horizontal(|ui| {
+                ui.label("Type");
+
+                ComboBox::from_id_salt("transaction_type")
+                    .selected_text(self.transaction_type.label())
+                    .show_ui(ui, |ui| {
+                        for transaction_type in TransactionType::MANUAL {
+                            ui.selectable_value(&mut self.transaction_type, transaction_type, transaction_type.label());
+                        }
+                    });
+            });
+
+            ui.add_space(FORM_SPACING);
+
+            ui.label("Supplier");
+            let supplier_name = self.suppliers.iter()
+                .find(|supplier| Some(supplier.id) == self.supplier_id)
+                .map(|supplier| supplier.name.clone())
+                .unwrap_or_else(|| "None".to_owned());
+
+            ComboBox::from_id_salt("transaction_supplier")
+                .selected_text(supplier_name)
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut self.supplier_id, None, "None");
+                    let selected_supplier_id = self.supplier_id;
+                    for supplier in self.suppliers.iter().filter(|supplier| supplier.active || Some(supplier.id) == selected_supplier_id) {
+                        ui.selectable_value(&mut self.supplier_id, Some(supplier.id), &supplier.name);
+                    }
+                });
+
+            ui.add_space(FORM_SPACING);
+
+            let product_unit_id = self.product_unit_id();
+
+            ui.label(if self.transaction_type == TransactionType::Adjustment { "Quantity (+/-)" } else { "Quantity" });
+            ui.horizontal(|ui| {
+                ui.add(egui::TextEdit::singleline(&mut self.quantity).desired_width(120.0));
+                unit_select(ui, "transaction_unit", &mut self.unit_id, &self.units, product_unit_id);
+            });
+            if let Some(error) = &self.errors.quantity {
+                ui.label(RichText::new(error).color(ui.visuals().error_fg_color));
+            }
+
+            // Other movements are valued at the product's current cost.
+            if self.transaction_type == TransactionType::Entry {
+                ui.add_space(FORM_SPACING);
+
+                ui.label("Unit Cost");
+                ui.add(egui::TextEdit::singleline(&mut self.unit_cost).desired_width(120.0).hint_text("Optional"));
+                if let Some(error) = &self.errors.unit_cost {
+                    ui.label(RichText::new(error).color(ui.visuals().error_fg_color));
+                }
+            }
+
+            if self.transaction_type == TransactionType::Exit && !self.reservations.is_empty() {
+                ui.add_space(FORM_SPACING);
+
+                ui.label("Reservation");
+                let reservation_text = |reservation: &Reservation| format!("#{} {} ({} left)", reservation.id, reservation.reference, reservation.remaining());
+
+                let selected_reservation = self.reservations.iter()
+                    .find(|reservation| Some(reservation.id) == self.reservation_id)
+                    .map(reservation_text)
+                    .unwrap_or_else(|| "None".to_owned());
+
+                ComboBox::from_id_salt("transaction_reservation")
+                    .selected_text(selected_reservation)
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut self.reservation_id, None, "None");
+                        for reservation in &self.reservations {
+                            ui.selectable_value(&mut self.reservation_id, Some(reservation.id), reservation_text(reservation));
+                        }
+                    });
+            }
+
+            if self.tracks_lots() {
+                ui.add_space(FORM_SPACING);
+
+                ui.horizontal(|ui| {
+                    ui.vertical(|ui| {
+                        ui.label("Lot");
+                        lot_input(ui, "transaction_lot", &mut self.lot_number, &self.lots);
+                    });
+
+                    // Exits take the expiry date already recorded for the lot.
+                    if self.transaction_type != TransactionType::Exit {
+                        ui.vertical(|ui| {
+                            ui.label("Expiry Date");
+                            ui.add(egui::TextEdit::singleline(&mut self.expiry_date).desired_width(100.0).hint_text("YYYY-MM-DD"));
+                        });
+                    }
+                });
+                if let Some(error) = &self.errors.lot_number {
+                    ui.label(RichText::new(error).color(ui.visuals().error_fg_color));
+                }
+                if let Some(error) = &self.errors.expiry_date {
+                    ui.label(RichText::new(error).color(ui.visuals().error_fg_color));
+                }
+            }
+
+            if self.is_serialized() {
+                ui.add_space(FORM_SPACING);
+
+                ui.label("Serial Numbers");
+                // Only units already in stock can leave; entries register new serials.
+                let in_stock: &[SerialNumber] = if self.transaction_type == TransactionType::Entry { &[] } else { &self.serials_in_stock };
+                serial_numbers_input(ui, "transaction_serial_numbers", &mut self.serial_numbers, in_stock);
+                if let Some(error) = &self.errors.serial_numbers {
+                    ui.label(RichText::new(error).color(ui.visuals().error_fg_color));
+                }
+            }
+
+            ui.add_space(FORM_SPACING);
+
+            ui.label("Reason");
+            ui.text_edit_multiline(&mut self.reason);
+
+            if let Some(error) = &self.errors.save {
+                ui.add_space(FORM_SPACING);
+                ui.label(RichText::new(error).color(ui.visuals().error_fg_color));
+            }
+
+            ui.add_space(DEFAULT_SPACING / 2.0);
+            ui.separator();
+            ui.add_space(FORM_SPACING);
+
+            Sides::new().show(
+                ui,
+                |_ui| {},
+                |ui| {
+                    if ui.button("Save").clicked()
+                        && let Some(transaction) = self.validate_form() {
+                        let mut connection = db::establish_connection();
+
+                        let new_transaction = NewInventoryTransactionRow {
+                            product_id: transaction.product_id.unwrap_or_default(),
+                            place_id: transaction.place_id.unwrap_or_default(),
+                            supplier_id: transaction.supplier_id,
+                            quantity: transaction.transaction_type.signed_quantity(transaction.quantity),
+                            transaction_type: transaction.transaction_type.as_str().to_owned(),
+                            reason: Some(transaction.reason).filter(|reason| !reason.is_empty()),
+                            lot_number: transaction.lot_number,
+                            expiry_date: transaction.expiry_date.map(|date| date.format(NAIVE_DATE_PATTERN).to_string()),
+                            unit_cost: transaction.unit_cost,
+                            reservation_id: self.reservation_id.filter(|_| transaction.transaction_type == TransactionType::Exit),
+                        };
+
+                        match inventory_transaction_repository::create_inventory_transaction(&mut connection, new_transaction, &transaction.serial_numbers) {
+                            Ok(created) => {
+                                created_transaction = Some(created);
+                                self.should_close = true;
+                            }
+                            Err(error) => {
+                                self.errors.save = Some(error.to_string());
+                            }
+                        }
+                    }
+
+                    if ui.button("Cancel").clicked() {
+                        self.should_close = true;
+                    }
+                }
+            );
+        });
+
+        if modal.should_close() {
+            self.should_close = true;
+        }
+
+        (self.should_close, created_transaction)
+    }
+
+    fn tracks_lots(&self) -> bool {
+        self.products.iter()
+            .any(|product| Some(product.id) == self.product_id && product.tracks_lots)
+    }
+
+    fn product_unit_id(&self) -> Option<i32> {
+        self.products.iter()
+            .find(|product| Some(product.id) == self.product_id)
+            .and_then(|product| product.unit_id)
+    }
+
+    /// Stock is kept in the product's unit; quantities typed in another unit
+    /// (e.g. boxes) are converted before saving.
+    fn to_product_unit(&self, quantity: i32) -> Result<i32, String> {
+        let find_unit = |unit_id: Option<i32>| unit_id.and_then(|unit_id| self.units.iter().find(|unit| unit.id == unit_id));
+
+        match (find_unit(self.unit_id), find_unit(self.product_unit_id())) {
+            (Some(unit), Some(product_unit)) => unit.convert(quantity, product_unit).map_err(|error| error.to_string()),
+            _ => Ok(quantity),
+        }
+    }
+
+    fn is_serialized(&self) -> bool {
+        self.products.iter()
+            .any(|product| Some(product.id) == self.product_id && product.serialized)
+    }
+
+    fn refresh_serial_numbers(&mut self) {
+        self.serials_in_stock = match (self.product_id, self.place_id) {
+            (Some(product_id), Some(place_id)) if self.is_serialized() => {
+                let mut connection = db::establish_connection();
+
+                let filter = SerialNumberFilter {
+                    product_id: Some(product_id),
+                    place_id: Some(place_id),
+                    ..Default::default()
+                };
+
+                serial_number_repository::list_serial_numbers(&mut connection, &filter).unwrap_or_default()
+            }
+            _ => Vec::new(),
+        };
+    }
+
+    fn refresh_reservations(&mut self) {
+        self.reservation_id = None;
+        self.reservations = match self.product_id {
+            Some(product_id) => {
+                let mut connection = db::establish_connection();
+
+                let filter = ReservationFilter {
+                    product_id: Some(product_id),
+                    status: Some(ReservationStatus::Active),
+                    ..Default::default()
+                };
+
+                reservation_repository::list_reservations(&mut connection, &filter).unwrap_or_default()
+            }
+            None => Vec::new(),
+        };
+    }
+
+    fn refresh_lots(&mut self) {
+        self.lots = match (self.product_id, self.place_id) {
+            (Some(product_id), Some(place_id)) if self.tracks_lots() => {
+                let mut connection = db::establish_connection();
+
+                let filter = LotBalanceFilter {
+                    product_id: Some(product_id),
+                    place_id: Some(place_id),
+                    ..Default::default()
+                };
+
+                stock_repository::list_lot_balances(&mut connection, &filter).unwrap_or_default()
+            }
+            _ => Vec::new(),
+        };
+    }
+
+    fn validate_form(&mut self) -> Option<InventoryTransactionForm> {
+        self.errors = FormErrors::default();
+
+        let quantity = match self.quantity.trim().parse::<i32>() {
+            Ok(0) => {
+                self.errors.quantity = Some("Quantity cannot be zero".into());
+                0
+            }
+            Ok(value) if value < 0 && self.transaction_type != TransactionType::Adjustment => {
+                self.errors.quantity = Some("Quantity should be positive, the type defines the direction".into());
+                value
+            }
+            Ok(value) => value,
+            Err(_) => {
+                self.errors.quantity = Some("Quantity should be a valid integer".into());
+                0
+            }
+        };
+
+        let typed_quantity = quantity;
+
+        let quantity = match self.to_product_unit(quantity) {
+            Ok(quantity) => quantity,
+            Err(error) => {
+                self.errors.quantity.get_or_insert(error);
+                quantity
+            }
+        };
+
+        let unit_cost = match self.unit_cost.trim().replace(',', ".") {
+            value if value.is_empty() || self.transaction_type != TransactionType::Entry => None,
+            value => match value.parse::<f64>() {
+                // The cost is typed per unit entered; stock is costed per unit of the product.
+                Ok(cost) if cost >= 0.0 && quantity != 0 => Some(cost * f64::from(typed_quantity) / f64::from(quantity)),
+                Ok(cost) if cost >= 0.0 => Some(cost),
+                _ => {
+                    self.errors.unit_cost = Some("Unit cost should be a number, zero or more".into());
+                    None
+                }
+            },
+        };
+
+        let tracks_lots = self.tracks_lots();
+
+        let lot_number = Some(self.lot_number.trim().to_owned())
+            .filter(|lot_number| tracks_lots && !lot_number.is_empty());
+
+        if tracks_lots && lot_number.is_none() {
+            self.errors.lot_number = Some("This product is tracked by lot, enter a lot number".into());
+        }
+
+        let expiry_date = match self.expiry_date.trim() {
+            value if value.is_empty() || !tracks_lots || self.transaction_type == TransactionType::Exit => None,
+            value => match NaiveDate::parse_from_str(value, NAIVE_DATE_PATTERN) {
+                Ok(date) => Some(date),
+                Err(_) => {
+                    self.errors.expiry_date = Some("Use the YYYY-MM-DD format".into());
+                    None
+                }
+            },
+        };
+
+        let serial_numbers = if self.is_serialized() {
+            parse_serial_numbers(&self.serial_numbers)
+        } else {
+            Vec::new()
+        };
+
+        if self.is_serialized() && self.errors.quantity.is_none() && serial_numbers.len() != quantity.unsigned_abs() as usize {
+            self.errors.serial_numbers = Some(format!("Enter one serial number per unit: {} expected, {} entered", quantity.abs(), serial_numbers.len()));
+        }
+
+        let transaction_data = InventoryTransactionForm {
+            product_id: self.product_id,
+            place_id: self.place_id,
+            supplier_id: self.supplier_id,
+            transaction_type: self.transaction_type,
+            quantity,
+            unit_cost,
+            reason: self.reason.trim().to_owned(),
+            lot_number,
+            expiry_date,
+            serial_numbers,
+        };
+
+        let input_failed = self.errors.quantity.is_some()
+            || self.errors.unit_cost.is_some()
+            || self.errors.lot_number.is_some()
+            || self.errors.expiry_date.is_some()
+            || self.errors.serial_numbers.is_some();
+
+        match transaction_data.validate() {
+            Ok(_) if !input_failed => Some(transaction_data),
+            Ok(_) => None,
+            Err(error) => {
+                let field_errors = error.field_errors();
+
+                if let Some(product_error) = field_errors.get("product_id") {
+                    self.errors.product_id = Some(product_error[0].clone().message.unwrap_or_default().to_string());
+                }
+
+                if let Some(place_error) = field_errors.get("place_id") {
+                    self.errors.place_id = Some(place_error[0].clone().message.unwrap_or_default().to_string());
+                }
+
+                None
+            }
+        }
+    }
+}
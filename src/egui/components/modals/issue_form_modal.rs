@@ -0,0 +1,211 @@
+use egui::{ComboBox, Id, Modal, RichText, Sides};
+use validator::Validate;
+
+use crate::infra::db;
+use crate::infra::repositories::issue_repository;
+use crate::infra::models::{NewIssueRow, EditIssueRow};
+
+use crate::domain::issue::Issue;
+use crate::domain::place::{Place, place_path};
+
+const DEFAULT_SPACING: f32 = 16.0;
+const FORM_SPACING: f32 = DEFAULT_SPACING / 2.0;
+
+#[derive(Default)]
+pub struct FormErrors {
+    pub requester: Option<String>,
+    pub department: Option<String>,
+    pub place_id: Option<String>,
+    pub save: Option<String>,
+}
+
+#[derive(Validate)]
+pub struct IssueForm {
+    #[validate(length(min = 1, message = "Enter who is requesting the material"))]
+    requester: String,
+
+    #[validate(length(min = 1, message = "Enter the department or cost center"))]
+    department: String,
+
+    destination: Option<String>,
+
+    #[validate(required(message = "Select the place the material leaves from"))]
+    place_id: Option<i32>,
+
+    notes: Option<String>,
+}
+
+pub struct IssueFormModal {
+    should_close: bool,
+
+    places: Vec<Place>,
+
+    id: Option<i32>,
+    requester: String,
+    department: String,
+    destination: String,
+    place_id: Option<i32>,
+    notes: String,
+
+    errors: FormErrors,
+}
+
+impl IssueFormModal {
+    pub fn new(issue: Option<&Issue>, places: &[Place]) -> Self {
+        Self {
+            should_close: false,
+            places: places.to_vec(),
+            id: issue.map(|issue| issue.id),
+            requester: issue.map(|issue| issue.requester.clone()).unwrap_or_default(),
+            department: issue.map(|issue| issue.department.clone()).unwrap_or_default(),
+            destination: issue.and_then(|issue| issue.destination.clone()).unwrap_or_default(),
+            place_id: issue.map(|issue| issue.place_id),
+            notes: issue.and_then(|issue| issue.notes.clone()).unwrap_or_default(),
+            errors: FormErrors::default(),
+        }
+    }
+
+    pub fn show(&mut self, ui: &mut egui::Ui) -> (bool, Option<Issue>) {
+        let mut saved_issue = None;
+
+        let modal = Modal::new(Id::new("Issue Form")).show(ui.ctx(), |ui| {
+            ui.heading(if self.id.is_none() { "New Issue" } else { "Edit Issue" });
+            ui.separator();
+            ui.add_space(DEFAULT_SPACING / 2.0);
+
+            ui.label("Requester");
+            ui.text_edit_singleline(&mut self.requester);
+            if let Some(error) = &self.errors.requester {
+                ui.label(RichText::new(error).color(ui.visuals().error_fg_color));
+            }
+
+            ui.add_space(FORM_SPACING);
+
+            ui.label("Department / Cost Center");
+            ui.text_edit_singleline(&mut self.department);
+            if let Some(error) = &self.errors.department {
+                ui.label(RichText::new(error).color(ui.visuals().error_fg_color));
+            }
+
+            ui.add_space(FORM_SPACING);
+
+            ui.label("Destination");
+            ui.add(egui::TextEdit::singleline(&mut self.destination).hint_text("Optional"));
+
+            ui.add_space(FORM_SPACING);
+
+            ui.label("Issue From");
+            let place_name = self.places.iter()
+                .find(|place| Some(place.id) == self.place_id)
+                .map(|place| place_path(&self.places, place.id))
+                .unwrap_or_default();
+
+            ComboBox::from_id_salt("issue_place")
+                .selected_text(place_name)
+                .show_ui(ui, |ui| {
+                    for place in &self.places {
+                        ui.selectable_value(&mut self.place_id, Some(place.id), place_path(&self.places, place.id));
+                    }
+                });
+            if let Some(error) = &self.errors.place_id {
+                ui.label(RichText::new(error).color(ui.visuals().error_fg_color));
+            }
+
+            ui.add_space(FORM_SPACING);
+
+            ui.label("Notes");
+            ui.text_edit_multiline(&mut self.notes);
+
+            if let Some(error) = &self.errors.save {
+                ui.add_space(FORM_SPACING);
+                ui.label(RichText::new(error).color(ui.visuals().error_fg_color));
+            }
+
+            ui.add_space(DEFAULT_SPACING / 2.0);
+            ui.separator();
+            ui.add_space(FORM_SPACING);
+
+            Sides::new().show(
+                ui,
+                |_ui| {},
+                |ui| {
+                    if ui.button("Save").clicked()
+                        && let Some(issue) = self.validate_form() {
+                        let mut connection = db::establish_connection();
+
+                        let result = match self.id {
+                            Some(id) => issue_repository::edit_issue(&mut connection, EditIssueRow {
+                                id,
+                                place_id: issue.place_id.unwrap_or_default(),
+                                requester: issue.requester,
+                                department: issue.department,
+                                destination: issue.destination,
+                                notes: issue.notes,
+                            }),
+                            None => issue_repository::create_issue(&mut connection, NewIssueRow {
+                                place_id: issue.place_id.unwrap_or_default(),
+                                requester: issue.requester,
+                                department: issue.department,
+                                destination: issue.destination,
+                                notes: issue.notes,
+                            }),
+                        };
+
+                        match result {
+                            Ok(issue) => {
+                                saved_issue = Some(issue);
+                                self.should_close = true;
+                            }
+                            Err(error) => {
+                                self.errors.save = Some(error.to_string());
+                            }
+                        }
+                    }
+
+                    if ui.button("Cancel").clicked() {
+                        self.should_close = true;
+                    }
+                }
+            );
+        });
+
+        if modal.should_close() {
+            self.should_close = true;
+        }
+
+        (self.should_close, saved_issue)
+    }
+
+    fn validate_form(&mut self) -> Option<IssueForm> {
+        self.errors = FormErrors::default();
+
+        let issue_data = IssueForm {
+            requester: self.requester.trim().to_owned(),
+            department: self.department.trim().to_owned(),
+            destination: Some(self.destination.trim().to_owned()).filter(|destination| !destination.is_empty()),
+            place_id: self.place_id,
+            notes: Some(self.notes.trim().to_owned()).filter(|notes| !notes.is_empty()),
+        };
+
+        match issue_data.validate() {
+            Ok(_) => Some(issue_data),
+            Err(error) => {
+                let field_errors = error.field_errors();
+
+                if let Some(requester_error) = field_errors.get("requester") {
+                    self.errors.requester = Some(requester_error[0].clone().message.unwrap_or_default().to_string());
+                }
+
+                if let Some(department_error) = field_errors.get("department") {
+                    self.errors.department = Some(department_error[0].clone().message.unwrap_or_default().to_string());
+                }
+
+                if let Some(place_error) = field_errors.get("place_id") {
+                    self.errors.place_id = Some(place_error[0].clone().message.unwrap_or_default().to_string());
+                }
+
+                None
+            }
+        }
+    }
+}
@@ -0,0 +1,322 @@
+use egui::{ComboBox, Id, Modal, RichText, Sides};
+use validator::Validate;
+
+use crate::infra::db;
+use crate::infra::repositories::{issue_repository, serial_number_repository, stock_repository};
+use crate::infra::repositories::serial_number_repository::SerialNumberFilter;
+use crate::infra::repositories::stock_repository::LotBalanceFilter;
+use crate::infra::models::{NewIssueLineRow, EditIssueLineRow};
+
+use crate::domain::issue::{Issue, IssueLine};
+use crate::domain::product::Product;
+use crate::domain::serial_number::{parse_serial_numbers, SerialNumber};
+use crate::domain::stock_balance::LotBalance;
+use crate::egui::widgets::lot_input::lot_input;
+use crate::egui::widgets::serial_numbers_input::serial_numbers_input;
+
+const DEFAULT_SPACING: f32 = 16.0;
+const FORM_SPACING: f32 = DEFAULT_SPACING / 2.0;
+
+#[derive(Default)]
+pub struct FormErrors {
+    pub product_id: Option<String>,
+    pub quantity: Option<String>,
+    pub lot_number: Option<String>,
+    pub serial_numbers: Option<String>,
+    pub save: Option<String>,
+}
+
+#[derive(Validate)]
+pub struct IssueLineForm {
+    #[validate(required(message = "Select a product"))]
+    product_id: Option<i32>,
+
+    #[validate(range(min = 1, message = "Quantity must be greater than zero"))]
+    quantity: i32,
+
+    lot_number: Option<String>,
+
+    serial_numbers: Vec<String>,
+}
+
+pub struct IssueLineFormModal {
+    should_close: bool,
+
+    issue_id: i32,
+    place_id: i32,
+    products: Vec<Product>,
+
+    id: Option<i32>,
+    product_id: Option<i32>,
+    quantity: String,
+    lot_number: String,
+    lots: Vec<LotBalance>,
+    serial_numbers: String,
+    serials_in_stock: Vec<SerialNumber>,
+    /// On-hand quantity of the selected product at the issuing place.
+    in_stock: Option<i64>,
+
+    errors: FormErrors,
+}
+
+impl IssueLineFormModal {
+    pub fn new(issue: &Issue, line: Option<&IssueLine>, products: &[Product]) -> Self {
+        let mut modal = Self {
+            should_close: false,
+            issue_id: issue.id,
+            place_id: issue.place_id,
+            products: products.to_vec(),
+            id: line.map(|line| line.id),
+            product_id: line.map(|line| line.product_id),
+            quantity: line.map(|line| line.quantity.to_string()).unwrap_or_default(),
+            lot_number: line.and_then(|line| line.lot_number.clone()).unwrap_or_default(),
+            lots: Vec::new(),
+            serial_numbers: line.map(|line| line.serial_numbers.join("\n")).unwrap_or_default(),
+            serials_in_stock: Vec::new(),
+            in_stock: None,
+            errors: FormErrors::default(),
+        };
+
+        modal.refresh_stock();
+
+        modal
+    }
+
+    pub fn show(&mut self, ui: &mut egui::Ui) -> (bool, Option<IssueLine>) {
+        let mut saved_line = None;
+
+        let modal = Modal::new(Id::new("Issue Line Form")).show(ui.ctx(), |ui| {
+            ui.heading(if self.id.is_none() { "New Line" } else { "Edit Line" });
+            ui.separator();
+            ui.add_space(DEFAULT_SPACING / 2.0);
+
+            ui.label("Product");
+            let product_name = self.products.iter()
+                .find(|product| Some(product.id) == self.product_id)
+                .map(|product| product.name.clone())
+                .unwrap_or_default();
+
+            let mut product_changed = false;
+
+            ComboBox::from_id_salt("issue_line_product")
+                .selected_text(product_name)
+                .show_ui(ui, |ui| {
+                    for product in &self.products {
+                        product_changed |= ui.selectable_value(&mut self.product_id, Some(product.id), &product.name).changed();
+                    }
+                });
+            if let Some(error) = &self.errors.product_id {
+                ui.label(RichText::new(error).color(ui.visuals().error_fg_color));
+            }
+
+            if product_changed {
+                self.lot_number.clear();
+                self.serial_numbers.clear();
+                self.refresh_stock();
+            }
+
+            if let Some(in_stock) = self.in_stock {
+                ui.label(RichText::new(format!("In stock at this place: {}", in_stock)).weak());
+            }
+
+            ui.add_space(FORM_SPACING);
+
+            ui.label("Quantity");
+            ui.horizontal(|ui| {
+                ui.add(egui::TextEdit::singleline(&mut self.quantity).desired_width(120.0));
+
+                if let Some(unity) = self.selected_product().and_then(|product| product.unity.clone()) {
+                    ui.label(unity);
+                }
+            });
+            if let Some(error) = &self.errors.quantity {
+                ui.label(RichText::new(error).color(ui.visuals().error_fg_color));
+            }
+
+            if self.selected_product().is_some_and(|product| product.tracks_lots) {
+                ui.add_space(FORM_SPACING);
+
+                ui.label("Lot");
+                lot_input(ui, "issue_line_lot", &mut self.lot_number, &self.lots);
+                if let Some(error) = &self.errors.lot_number {
+                    ui.label(RichText::new(error).color(ui.visuals().error_fg_color));
+                }
+            }
+
+            if self.selected_product().is_some_and(|product| product.serialized) {
+                ui.add_space(FORM_SPACING);
+
+                ui.label("Serial Numbers");
+                serial_numbers_input(ui, "issue_line_serial_numbers", &mut self.serial_numbers, &self.serials_in_stock);
+                if let Some(error) = &self.errors.serial_numbers {
+                    ui.label(RichText::new(error).color(ui.visuals().error_fg_color));
+                }
+            }
+
+            if let Some(error) = &self.errors.save {
+                ui.add_space(FORM_SPACING);
+                ui.label(RichText::new(error).color(ui.visuals().error_fg_color));
+            }
+
+            ui.add_space(DEFAULT_SPACING / 2.0);
+            ui.separator();
+            ui.add_space(FORM_SPACING);
+
+            Sides::new().show(
+                ui,
+                |_ui| {},
+                |ui| {
+                    if ui.button("Save").clicked()
+                        && let Some(line) = self.validate_form() {
+                        let mut connection = db::establish_connection();
+
+                        let serial_numbers = Some(line.serial_numbers.join("\n")).filter(|serial_numbers| !serial_numbers.is_empty());
+
+                        let result = match self.id {
+                            Some(id) => issue_repository::edit_issue_line(&mut connection, EditIssueLineRow {
+                                id,
+                                product_id: line.product_id.unwrap_or_default(),
+                                quantity: line.quantity,
+                                lot_number: line.lot_number,
+                                serial_numbers,
+                            }),
+                            None => issue_repository::create_issue_line(&mut connection, NewIssueLineRow {
+                                issue_id: self.issue_id,
+                                product_id: line.product_id.unwrap_or_default(),
+                                quantity: line.quantity,
+                                lot_number: line.lot_number,
+                                serial_numbers,
+                            }),
+                        };
+
+                        match result {
+                            Ok(line) => {
+                                saved_line = Some(line);
+                                self.should_close = true;
+                            }
+                            Err(error) => {
+                                self.errors.save = Some(error.to_string());
+                            }
+                        }
+                    }
+
+                    if ui.button("Cancel").clicked() {
+                        self.should_close = true;
+                    }
+                }
+            );
+        });
+
+        if modal.should_close() {
+            self.should_close = true;
+        }
+
+        (self.should_close, saved_line)
+    }
+
+    fn selected_product(&self) -> Option<&Product> {
+        self.products.iter().find(|product| Some(product.id) == self.product_id)
+    }
+
+    /// Loads what the issuing place holds of the selected product: the total,
+    /// its lots and its serial numbers.
+    fn refresh_stock(&mut self) {
+        let Some(product) = self.selected_product().cloned() else {
+            self.in_stock = None;
+            self.lots = Vec::new();
+            self.serials_in_stock = Vec::new();
+            return;
+        };
+
+        let mut connection = db::establish_connection();
+
+        self.in_stock = stock_repository::get_stock_balance(&mut connection, product.id, self.place_id).ok();
+
+        self.lots = if product.tracks_lots {
+            let filter = LotBalanceFilter {
+                product_id: Some(product.id),
+                place_id: Some(self.place_id),
+                ..Default::default()
+            };
+
+            stock_repository::list_lot_balances(&mut connection, &filter).unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+
+        self.serials_in_stock = if product.serialized {
+            let filter = SerialNumberFilter {
+                product_id: Some(product.id),
+                place_id: Some(self.place_id),
+                ..Default::default()
+            };
+
+            serial_number_repository::list_serial_numbers(&mut connection, &filter).unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+    }
+
+    fn validate_form(&mut self) -> Option<IssueLineForm> {
+        self.errors = FormErrors::default();
+
+        let quantity = match self.quantity.trim().parse::<i32>() {
+            Ok(value) => value,
+            Err(_) => {
+                self.errors.quantity = Some("Quantity should be a valid integer".into());
+                1
+            }
+        };
+
+        let (tracks_lots, serialized) = self.selected_product()
+            .map(|product| (product.tracks_lots, product.serialized))
+            .unwrap_or_default();
+
+        let lot_number = Some(self.lot_number.trim().to_owned())
+            .filter(|lot_number| tracks_lots && !lot_number.is_empty());
+
+        if tracks_lots && lot_number.is_none() {
+            self.errors.lot_number = Some("This product is tracked by lot, enter a lot number".into());
+        }
+
+        let serial_numbers = if serialized {
+            parse_serial_numbers(&self.serial_numbers)
+        } else {
+            Vec::new()
+        };
+
+        if serialized && self.errors.quantity.is_none() && serial_numbers.len() != quantity.unsigned_abs() as usize {
+            self.errors.serial_numbers = Some(format!("Enter one serial number per unit: {} expected, {} entered", quantity, serial_numbers.len()));
+        }
+
+        let line_data = IssueLineForm {
+            product_id: self.product_id,
+            quantity,
+            lot_number,
+            serial_numbers,
+        };
+
+        let parse_failed = self.errors.quantity.is_some()
+            || self.errors.lot_number.is_some()
+            || self.errors.serial_numbers.is_some();
+
+        match line_data.validate() {
+            Ok(_) if !parse_failed => Some(line_data),
+            Ok(_) => None,
+            Err(error) => {
+                let field_errors = error.field_errors();
+
+                if let Some(product_error) = field_errors.get("product_id") {
+                    self.errors.product_id = Some(product_error[0].clone().message.unwrap_or_default().to_string());
+                }
+
+                if self.errors.quantity.is_none()
+                    && let Some(quantity_error) = field_errors.get("quantity") {
+                    self.errors.quantity = Some(quantity_error[0].clone().message.unwrap_or_default().to_string());
+                }
+
+                None
+            }
+        }
+    }
+}
@@ -1,3 +1,24 @@
+pub mod approve_stocktake_modal;
+pub mod audit_history_modal;
+pub mod category_form_modal;
+pub mod goods_receipt_modal;
+pub mod inventory_transaction_form_modal;
+pub mod issue_form_modal;
+pub mod issue_line_form_modal;
 pub mod place_form_modal;
+pub mod place_stock_modal;
 pub mod product_form_modal;
-pub mod supplier_form_modal;
\ No newline at end of file
+pub mod product_kit_modal;
+pub mod product_lots_modal;
+pub mod print_labels_modal;
+pub mod product_supplier_form_modal;
+pub mod purchase_order_form_modal;
+pub mod purchase_order_line_form_modal;
+pub mod reservation_form_modal;
+pub mod serial_number_history_modal;
+pub mod stock_transfer_form_modal;
+pub mod stocktake_form_modal;
+pub mod stocktake_line_form_modal;
+pub mod supplier_detail_modal;
+pub mod supplier_form_modal;
+pub mod unit_form_modal;
\ No newline at end of file
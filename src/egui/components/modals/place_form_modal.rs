@@ -1,11 +1,11 @@
-use egui::{Id, Modal, RichText, Sides};
+use egui::{ComboBox, Id, Modal, RichText, Sides};
 use validator::Validate;
 
 use crate::infra::db;
 use crate::infra::repositories::place_repository;
 use crate::infra::models::{NewPlaceRow, EditPlaceRow};
 
-use crate::domain::place::Place;
+use crate::domain::place::{Place, PlaceType, descendant_place_ids, place_path};
 
 const DEFAULT_SPACING: f32 = 16.0;
 const FORM_SPACING: f32 = DEFAULT_SPACING / 2.0;
@@ -13,6 +13,7 @@ const FORM_SPACING: f32 = DEFAULT_SPACING / 2.0;
 #[derive(Default)]
 pub struct FormErrors {
     pub name: Option<String>,
+    pub save: Option<String>,
 }
 
 #[derive(Validate)]
@@ -26,28 +27,58 @@ pub struct PlaceFormModal {
 
     id: Option<i32>,
     name: String,
+    parent_id: Option<i32>,
+    place_type: PlaceType,
+
+    /// Places it can be moved into: every place but itself and those inside it.
+    parent_options: Vec<(i32, String)>,
+
+    /// A deleted place with the name being created in the same parent, offered for restoring.
+    deleted_match: Option<Place>,
 
     errors: FormErrors,
 }
 
 impl PlaceFormModal {
-    pub fn new (place: Option<&Place>) -> Self {
+    /// `parent_id` places a new place inside an existing one.
+    pub fn new (place: Option<&Place>, parent_id: Option<i32>, places: &[Place]) -> Self {
         let errors = FormErrors::default();
         let should_close = false;
 
+        let excluded_ids = place
+            .map(|place| descendant_place_ids(places, place.id))
+            .unwrap_or_default();
+
+        let mut parent_options: Vec<(i32, String)> = places.iter()
+            .filter(|option| !excluded_ids.contains(&option.id))
+            .map(|option| (option.id, place_path(places, option.id)))
+            .collect();
+
+        parent_options.sort_by(|a, b| a.1.cmp(&b.1));
+
         match place {
             Some(place) => Self {
                 should_close,
                 errors,
                 id: Some(place.id),
                 name: place.name.clone(),
-                
+                parent_id: place.parent_id,
+                place_type: place.place_type,
+                parent_options,
+                deleted_match: None,
             },
             None => Self {
                 should_close,
                 errors,
                 id: None,
                 name: String::new(),
+                parent_id,
+                place_type: places.iter()
+                    .find(|parent| Some(parent.id) == parent_id)
+                    .map(|parent| parent.place_type.child_type())
+                    .unwrap_or_default(),
+                parent_options,
+                deleted_match: None,
             }
         }
     }
@@ -61,13 +92,64 @@ impl PlaceFormModal {
             ui.add_space(DEFAULT_SPACING / 2.0);
 
             ui.label("Name: ");
-            ui.text_edit_singleline(&mut self.name);
+            if ui.text_edit_singleline(&mut self.name).changed() {
+                self.deleted_match = None;
+            }
             if let Some(error) = &self.errors.name {
                 ui.label(
                     RichText::new(error).color(ui.visuals().error_fg_color)
                 );
             }
 
+            ui.add_space(FORM_SPACING);
+
+            ui.label("Inside: ");
+            let selected_parent = self.parent_options.iter()
+                .find(|(id, _)| Some(*id) == self.parent_id)
+                .map(|(_, path)| path.clone())
+                .unwrap_or_else(|| "(top level)".to_owned());
+
+            ComboBox::from_id_salt("place_parent")
+                .width(260.0)
+                .selected_text(selected_parent)
+                .show_ui(ui, |ui| {
+                    let mut changed = ui.selectable_value(&mut self.parent_id, None, "(top level)").changed();
+
+                    for (id, path) in &self.parent_options {
+                        changed |= ui.selectable_value(&mut self.parent_id, Some(*id), path).changed();
+                    }
+
+                    if changed {
+                        self.deleted_match = None;
+                    }
+                });
+
+            ui.add_space(FORM_SPACING);
+
+            ui.label("Type: ");
+            ComboBox::from_id_salt("place_type")
+                .selected_text(self.place_type.label())
+                .show_ui(ui, |ui| {
+                    for option in PlaceType::ALL {
+                        ui.selectable_value(&mut self.place_type, option, option.label());
+                    }
+                });
+
+            if let Some(error) = &self.errors.save {
+                ui.add_space(FORM_SPACING);
+                ui.label(
+                    RichText::new(error).color(ui.visuals().error_fg_color)
+                );
+            }
+
+            if let Some(deleted) = &self.deleted_match {
+                ui.add_space(FORM_SPACING);
+                ui.label(
+                    RichText::new(format!("A deleted {} named '{}' exists here. Restore it as it was, or create a new one?", deleted.place_type.label().to_lowercase(), deleted.name))
+                        .color(ui.visuals().warn_fg_color)
+                );
+            }
+
             ui.add_space(DEFAULT_SPACING / 2.0);
             ui.separator();
             ui.add_space(FORM_SPACING);
@@ -77,33 +159,35 @@ impl PlaceFormModal {
                 ui,
                 |_ui| {},
                 |ui| {
-                    if ui.button("Save").clicked() {
-                        if let Some(place) = self.validate_form() {
+                    if let Some(deleted_id) = self.deleted_match.as_ref().map(|deleted| deleted.id) {
+                        if ui.button("Restore").clicked() {
                             let mut connection = db::establish_connection();
-                                
-                            match self.id {
-                                Some(id,) => {
-                                    let place = EditPlaceRow {
-                                        id,
-                                        name: place.name,
-                                    };
-
-                                    if let Ok(updated) = place_repository::edit_place(&mut connection, place) {
-                                        created_place = Some(updated);
-                                    }
-                                }
-                                None => {
-                                    let new_place = NewPlaceRow {
-                                        name: place.name,
-                                    };
-
-                                    if let Ok(created) = place_repository::create_place(&mut connection, new_place) {
-                                        created_place = Some(created);
-                                    }
+
+                            match place_repository::restore_place(&mut connection, deleted_id) {
+                                Ok(restored) => {
+                                    created_place = Some(restored);
+                                    self.should_close = true;
                                 }
+                                Err(error) => self.errors.save = Some(error.to_string()),
                             }
+                        }
+
+                        if ui.button("Create New").clicked()
+                            && let Some(place) = self.validate_form() {
+                            created_place = self.save(place);
+                        }
+                    } else if ui.button("Save").clicked()
+                        && let Some(place) = self.validate_form() {
+                        let mut connection = db::establish_connection();
 
-                            self.should_close = true;
+                        // Offer the deleted place back before creating a second one with its name.
+                        match self.id {
+                            None => match place_repository::find_deleted_child_place(&mut connection, self.parent_id, &place.name) {
+                                Ok(Some(deleted)) => self.deleted_match = Some(deleted),
+                                Ok(None) => created_place = self.save(place),
+                                Err(error) => self.errors.save = Some(error.to_string()),
+                            },
+                            Some(_) => created_place = self.save(place),
                         }
                     }
 
@@ -121,11 +205,49 @@ impl PlaceFormModal {
         (self.should_close, created_place)
     }
 
+    fn save(&mut self, place: PlaceForm) -> Option<Place> {
+        let mut connection = db::establish_connection();
+
+        let result = match self.id {
+            Some(id,) => {
+                let place = EditPlaceRow {
+                    id,
+                    name: place.name,
+                    parent_id: self.parent_id,
+                    place_type: self.place_type.as_str().to_owned(),
+                };
+
+                place_repository::edit_place(&mut connection, place)
+            }
+            None => {
+                let new_place = NewPlaceRow {
+                    name: place.name,
+                    parent_id: self.parent_id,
+                    place_type: self.place_type.as_str().to_owned(),
+                };
+
+                place_repository::create_place(&mut connection, new_place)
+            }
+        };
+
+        // Sibling names and the type rules are checked when saving.
+        match result {
+            Ok(saved) => {
+                self.should_close = true;
+                Some(saved)
+            }
+            Err(error) => {
+                self.errors.save = Some(error.to_string());
+                None
+            }
+        }
+    }
+
      fn validate_form(&mut self) -> Option<PlaceForm>{
         self.errors = FormErrors::default();
 
         let place_data = PlaceForm {
-            name: self.name.clone(),
+            name: self.name.trim().to_owned(),
         };
 
 
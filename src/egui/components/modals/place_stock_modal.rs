@@ -0,0 +1,144 @@
+use egui::{Direction, Id, Layout, Modal, Sides};
+use egui_extras::{Column, TableBuilder};
+
+use crate::infra::db;
+use crate::infra::repositories::{place_repository, product_repository, stock_repository};
+use crate::infra::repositories::stock_repository::StockBalanceFilter;
+
+use crate::domain::place::{Place, descendant_place_ids, place_path};
+use crate::domain::product::Product;
+use crate::domain::stock_balance::StockBalance;
+
+const DEFAULT_SPACING: f32 = 16.0;
+const ITEM_HEIGHT: f32 = 24.0;
+
+pub struct PlaceStockModal {
+    should_close: bool,
+
+    place: Place,
+    places: Vec<Place>,
+    products: Vec<Product>,
+    balances: Vec<StockBalance>,
+    /// Rolls up the stock of every place inside this one, per product.
+    include_sub_places: bool,
+}
+
+impl PlaceStockModal {
+    pub fn new(place: &Place) -> Self {
+        let mut connection = db::establish_connection();
+
+        let mut modal = Self {
+            should_close: false,
+            place: place.clone(),
+            places: place_repository::list_places(&mut connection, "").unwrap_or_default(),
+            products: product_repository::list_products(&mut connection, "").unwrap_or_default(),
+            balances: Vec::new(),
+            include_sub_places: true,
+        };
+
+        modal.load_balances();
+
+        modal
+    }
+
+    fn load_balances(&mut self) {
+        let mut connection = db::establish_connection();
+
+        let place_ids = if self.include_sub_places {
+            descendant_place_ids(&self.places, self.place.id)
+        } else {
+            vec![self.place.id]
+        };
+
+        let filter = StockBalanceFilter {
+            place_ids: Some(place_ids),
+            ..Default::default()
+        };
+
+        let balances = stock_repository::list_stock_balances(&mut connection, &filter).unwrap_or_default();
+
+        self.balances = Vec::new();
+
+        for balance in balances {
+            match self.balances.iter_mut().find(|existing| existing.product_id == balance.product_id) {
+                Some(existing) => existing.quantity += balance.quantity,
+                None => self.balances.push(StockBalance { place_id: self.place.id, ..balance }),
+            }
+        }
+
+        self.balances.retain(|balance| balance.quantity != 0);
+    }
+
+    pub fn show(&mut self, ui: &mut egui::Ui) -> bool {
+        let modal = Modal::new(Id::new("Place Stock")).show(ui.ctx(), |ui| {
+            ui.heading(format!("Contents of '{}'", place_path(&self.places, self.place.id)));
+            ui.separator();
+            ui.add_space(DEFAULT_SPACING / 2.0);
+
+            if ui.checkbox(&mut self.include_sub_places, "Include places inside it").changed() {
+                self.load_balances();
+            }
+
+            ui.add_space(DEFAULT_SPACING / 2.0);
+
+            if self.balances.is_empty() {
+                ui.label("There is no stock recorded at this place.");
+            } else {
+                TableBuilder::new(ui)
+                    .striped(true)
+                    .resizable(false)
+                    .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+                    .column(Column::exact(64.0))
+                    .column(Column::remainder().at_least(200.0).clip(true))
+                    .column(Column::exact(88.0))
+                    .max_scroll_height(400.0)
+                    .header(ITEM_HEIGHT, |mut header| {
+                        header.col(|ui| { ui.heading("ID"); });
+                        header.col(|ui| { ui.heading("Product"); });
+                        header.col(|ui| {
+                            ui.with_layout(Layout::centered_and_justified(Direction::LeftToRight), |ui| {
+                                ui.heading("Stock");
+                            });
+                        });
+                    })
+                    .body(|body| {
+                        body.rows(ITEM_HEIGHT, self.balances.len(), |mut row| {
+                            let balance = &self.balances[row.index()];
+
+                            let product_name = self.products.iter()
+                                .find(|product| product.id == balance.product_id)
+                                .map(|product| product.name.clone())
+                                .unwrap_or_else(|| format!("#{}", balance.product_id));
+
+                            row.col(|ui| { ui.label(balance.product_id.to_string()); });
+                            row.col(|ui| { ui.label(product_name); });
+                            row.col(|ui| {
+                                ui.with_layout(Layout::centered_and_justified(Direction::LeftToRight), |ui| {
+                                    ui.label(balance.quantity.to_string());
+                                });
+                            });
+                        });
+                    });
+            }
+
+            ui.add_space(DEFAULT_SPACING / 2.0);
+            ui.separator();
+
+            Sides::new().show(
+                ui,
+                |_ui| {},
+                |ui| {
+                    if ui.button("Close").clicked() {
+                        self.should_close = true;
+                    }
+                }
+            );
+        });
+
+        if modal.should_close() {
+            self.should_close = true;
+        }
+
+        self.should_close
+    }
+}
@@ -0,0 +1,193 @@
+use egui::{ComboBox, DragValue, Grid, Id, Modal, RichText, Sides};
+use rfd::FileDialog;
+use std::error::Error;
+use std::path::PathBuf;
+
+use crate::infra::db;
+use crate::infra::repositories::product_barcode_repository;
+use crate::domain::place::Place;
+use crate::domain::product::Product;
+use crate::services::labels::label_sheet::LabelLayout;
+use crate::services::labels::print_place_labels::print_place_labels;
+use crate::services::labels::print_product_labels::print_product_labels;
+
+const DEFAULT_SPACING: f32 = 16.0;
+const FORM_SPACING: f32 = DEFAULT_SPACING / 2.0;
+
+/// What the labels are printed for.
+pub enum LabelTarget {
+    Products(Vec<Product>),
+    Places(Vec<Place>),
+}
+
+impl LabelTarget {
+    fn len(&self) -> usize {
+        match self {
+            LabelTarget::Products(products) => products.len(),
+            LabelTarget::Places(places) => places.len(),
+        }
+    }
+
+    fn file_stem(&self) -> &'static str {
+        match self {
+            LabelTarget::Products(_) => "product_labels",
+            LabelTarget::Places(_) => "place_labels",
+        }
+    }
+}
+
+pub struct PrintLabelsModal {
+    should_close: bool,
+
+    target: LabelTarget,
+    layout: LabelLayout,
+
+    error: Option<String>,
+}
+
+impl PrintLabelsModal {
+    pub fn new(target: LabelTarget) -> Self {
+        Self {
+            should_close: false,
+            target,
+            layout: LabelLayout::PRESETS[0].1,
+            error: None,
+        }
+    }
+
+    pub fn show(&mut self, ui: &mut egui::Ui) -> bool {
+        let modal = Modal::new(Id::new("Print Labels")).show(ui.ctx(), |ui| {
+            ui.heading("Print Labels");
+            ui.separator();
+            ui.add_space(DEFAULT_SPACING / 2.0);
+
+            let pages = self.target.len().div_ceil(self.layout.labels_per_page().max(1));
+            ui.label(format!("{} label(s) on {} page(s)", self.target.len(), pages));
+
+            ui.add_space(FORM_SPACING);
+
+            ui.label("Label Stock");
+            let preset_name = LabelLayout::PRESETS.iter()
+                .find(|(_, layout)| *layout == self.layout)
+                .map(|(name, _)| *name)
+                .unwrap_or("Custom");
+
+            ComboBox::from_id_salt("label_preset")
+                .selected_text(preset_name)
+                .width(240.0)
+                .show_ui(ui, |ui| {
+                    for (name, layout) in LabelLayout::PRESETS {
+                        ui.selectable_value(&mut self.layout, layout, name);
+                    }
+                });
+
+            ui.add_space(FORM_SPACING);
+
+            Grid::new("label_layout")
+                .num_columns(3)
+                .spacing([DEFAULT_SPACING, FORM_SPACING / 2.0])
+                .show(ui, |ui| {
+                    ui.label("");
+                    ui.strong("Width / Columns");
+                    ui.strong("Height / Rows");
+                    ui.end_row();
+
+                    ui.label("Page");
+                    ui.add(millimetres(&mut self.layout.page_width));
+                    ui.add(millimetres(&mut self.layout.page_height));
+                    ui.end_row();
+
+                    ui.label("Label");
+                    ui.add(millimetres(&mut self.layout.label_width));
+                    ui.add(millimetres(&mut self.layout.label_height));
+                    ui.end_row();
+
+                    ui.label("Labels per page");
+                    ui.add(DragValue::new(&mut self.layout.columns).range(1..=20));
+                    ui.add(DragValue::new(&mut self.layout.rows).range(1..=40));
+                    ui.end_row();
+
+                    ui.label("Margin");
+                    ui.add(millimetres(&mut self.layout.margin_left));
+                    ui.add(millimetres(&mut self.layout.margin_top));
+                    ui.end_row();
+
+                    ui.label("Gap");
+                    ui.add(millimetres(&mut self.layout.gap_x));
+                    ui.add(millimetres(&mut self.layout.gap_y));
+                    ui.end_row();
+                });
+
+            let layout_error = self.layout.validate().err();
+
+            if let Some(error) = layout_error.as_ref().or(self.error.as_ref()) {
+                ui.add_space(FORM_SPACING);
+                ui.label(RichText::new(error).color(ui.visuals().error_fg_color));
+            }
+
+            ui.add_space(DEFAULT_SPACING / 2.0);
+            ui.separator();
+            ui.add_space(FORM_SPACING);
+
+            Sides::new().show(
+                ui,
+                |_ui| {},
+                |ui| {
+                    let can_save = layout_error.is_none();
+
+                    if ui.add_enabled(can_save, egui::Button::new("Save SVG")).clicked() {
+                        self.save("svg");
+                    }
+
+                    if ui.add_enabled(can_save, egui::Button::new("Save PDF")).clicked() {
+                        self.save("pdf");
+                    }
+
+                    if ui.button("Cancel").clicked() {
+                        self.should_close = true;
+                    }
+                }
+            );
+        });
+
+        if modal.should_close() {
+            self.should_close = true;
+        }
+
+        self.should_close
+    }
+
+    fn save(&mut self, extension: &str) {
+        let Some(path) = FileDialog::new()
+            .set_file_name(format!("{}.{}", self.target.file_stem(), extension))
+            .add_filter(extension.to_uppercase(), &[extension])
+            .save_file() else {
+            return;
+        };
+
+        match self.print(path) {
+            Ok(_) => self.should_close = true,
+            Err(error) => self.error = Some(error.to_string()),
+        }
+    }
+
+    fn print(&self, path: PathBuf) -> Result<(), Box<dyn Error>> {
+        match &self.target {
+            LabelTarget::Products(products) => {
+                let mut connection = db::establish_connection();
+                let mut barcodes = Vec::new();
+
+                for product in products {
+                    barcodes.extend(product_barcode_repository::list_product_barcodes(&mut connection, product.id)?);
+                }
+
+                print_product_labels(products, &barcodes, &self.layout, path)
+            }
+            LabelTarget::Places(places) => print_place_labels(places, &self.layout, path),
+        }
+    }
+}
+
+fn millimetres(value: &mut f64) -> DragValue<'_> {
+    DragValue::new(value).speed(0.1).range(0.0..=1000.0).suffix(" mm")
+}
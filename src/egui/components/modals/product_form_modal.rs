@@ -1,12 +1,18 @@
 use eframe::egui;
 use egui::{ComboBox, Id, Modal, RichText, Sides};
-use validator::{Validate};
+use validator::{Validate, ValidationError};
 
 use crate::infra::db;
-use crate::infra::repositories::product_repository;
+use crate::infra::repositories::{category_repository, product_barcode_repository, product_repository, unit_repository};
 use crate::infra::models::{NewProductRow, EditProductRow};
 
+use crate::domain::barcode::{Symbology, parse_barcodes};
+use crate::domain::category::Category;
 use crate::domain::product::Product;
+use crate::domain::unit::Unit;
+use crate::egui::components::modals::product_supplier_form_modal::LinkOwner;
+use crate::egui::widgets::category_select::category_select;
+use crate::egui::widgets::product_supplier_links::ProductSupplierLinks;
 
 const DEFAULT_SPACING: f32 = 16.0;
 const FORM_SPACING: f32 = DEFAULT_SPACING / 2.0;
@@ -14,32 +20,103 @@ const FORM_SPACING: f32 = DEFAULT_SPACING / 2.0;
 #[derive(Debug, Default)]
 pub struct FormErrors {
     pub name: Option<String>,
+    pub sku: Option<String>,
+    pub barcodes: Option<String>,
+    pub save: Option<String>,
+    pub unit_id: Option<String>,
     pub min_stock: Option<String>,
+    pub safety_stock: Option<String>,
+    pub reorder_point: Option<String>,
+    pub max_stock: Option<String>,
 }
 
 #[derive(Validate)]
+#[validate(schema(function = "validate_stock_levels"))]
 pub struct ProductForm { 
     #[validate(length(min = 2, message = "Name must contain at least two characters"))]
     name: String,
+
+    #[validate(length(max = 64, message = "SKU must contain at most 64 characters"))]
+    sku: String,
+
+    barcodes: Vec<String>,
     
-    unity: &'static str,
+    #[validate(required(message = "Select a unit"))]
+    unit_id: Option<i32>,
 
     brand: String,
+
+    category_id: Option<i32>,
     
     #[validate(range(min = 0))]
     min_stock: i32,
+
+    #[validate(range(min = 0, message = "Safety stock cannot be negative"))]
+    safety_stock: i32,
+
+    #[validate(range(min = 0, message = "Reorder point cannot be negative"))]
+    reorder_point: i32,
+
+    #[validate(range(min = 0, message = "Max stock cannot be negative"))]
+    max_stock: i32,
+
+    tracks_lots: bool,
+
+    serialized: bool,
+
     observation: String,
 }
-#[derive(Debug)]
+
+/// Cross-field checks of the stock levels. Zero means "not set" for every level.
+/// The error code names the field the message is shown under.
+fn validate_stock_levels(form: &ProductForm) -> Result<(), ValidationError> {
+    let level_error = |field: &'static str, message: &'static str| {
+        Err(ValidationError::new(field).with_message(message.into()))
+    };
+
+    if form.reorder_point > 0 && form.safety_stock > form.reorder_point {
+        return level_error("safety_stock", "Safety stock cannot exceed the reorder point");
+    }
+
+    if form.reorder_point > 0 && form.max_stock == 0 {
+        return level_error("max_stock", "Set a max stock to get reorder suggestions");
+    }
+
+    if form.max_stock > 0 && form.reorder_point >= form.max_stock {
+        return level_error("max_stock", "Max stock must be greater than the reorder point");
+    }
+
+    if form.max_stock > 0 && i64::from(form.reorder_point) + i64::from(form.safety_stock) >= i64::from(form.max_stock) {
+        return level_error("max_stock", "Max stock must be greater than the reorder point plus the safety stock");
+    }
+
+    if form.max_stock > 0 && form.min_stock > form.max_stock {
+        return level_error("max_stock", "Max stock cannot be below the min stock");
+    }
+
+    Ok(())
+}
+
 pub struct ProductFormModal {
 	should_close: bool,
 
     id: Option<i32>,
     name: String,
+    sku: String,
+    barcodes: String,
     brand: String,
-    unity: &'static str,
+    unit_id: Option<i32>,
+    units: Vec<Unit>,
+    category_id: Option<i32>,
+    categories: Vec<Category>,
     min_stock: String,
+    safety_stock: String,
+    reorder_point: String,
+    max_stock: String,
+    tracks_lots: bool,
+    serialized: bool,
     observation: String,
+    supplier_links: Option<ProductSupplierLinks>,
     errors: FormErrors,
 }
 
@@ -50,26 +127,62 @@ impl ProductFormModal {
         let errors = FormErrors::default();
         let should_close = false;
 
+        let mut connection = db::establish_connection();
+        let units = unit_repository::list_units(&mut connection, "").unwrap_or_default();
+        let categories = category_repository::list_categories(&mut connection).unwrap_or_default();
+
         match product {
             Some(prod) => Self { 
                 should_close,
                 errors,
                 id: Some(prod.id),
                 name: prod.name.clone(),
+                sku: prod.sku.clone().unwrap_or_default(),
+                barcodes: product_barcode_repository::list_product_barcodes(&mut connection, prod.id)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|barcode| barcode.barcode)
+                    .collect::<Vec<_>>()
+                    .join("\n"),
                 brand: prod.brand.clone().unwrap_or_default(),
-                unity: "un",
+                // Products imported before units existed only carry the symbol.
+                unit_id: prod.unit_id.or_else(|| {
+                    units.iter()
+                        .find(|unit| Some(&unit.symbol) == prod.unity.as_ref())
+                        .map(|unit| unit.id)
+                }),
+                units,
+                category_id: prod.category_id,
+                categories,
                 min_stock: prod.min_stock.to_string(),
+                safety_stock: prod.safety_stock.to_string(),
+                reorder_point: prod.reorder_point.to_string(),
+                max_stock: prod.max_stock.to_string(),
+                tracks_lots: prod.tracks_lots,
+                serialized: prod.serialized,
                 observation: prod.observation.clone().unwrap_or_default(),
+                supplier_links: Some(ProductSupplierLinks::new(LinkOwner::Product(prod.id))),
             },
             None => Self { 
                 should_close,
                 errors,
                 id: None,
                 name: "".to_owned(),
+                sku: "".to_owned(),
+                barcodes: "".to_owned(),
                 brand: "".to_owned(),
-                unity: "un",
+                unit_id: units.iter().find(|unit| unit.symbol == "un").map(|unit| unit.id),
+                units,
+                category_id: None,
+                categories,
                 min_stock: "".to_owned(),
+                safety_stock: "".to_owned(),
+                reorder_point: "".to_owned(),
+                max_stock: "".to_owned(),
+                tracks_lots: false,
+                serialized: false,
                 observation: "".to_owned(),
+                supplier_links: None,
             }
         }
 	}
@@ -93,27 +206,97 @@ impl ProductFormModal {
 
                 ui.add_space(FORM_SPACING);
 
+                ui.horizontal(|ui| {
+                    ui.vertical(|ui| {
+                        ui.label("SKU");
+                        ui.add(egui::TextEdit::singleline(&mut self.sku).desired_width(160.0));
+                    });
+                    ui.vertical(|ui| {
+                        ui.label("Barcodes");
+                        ui.add(
+                            egui::TextEdit::multiline(&mut self.barcodes)
+                                .desired_rows(2)
+                                .desired_width(200.0)
+                                .hint_text("One per line")
+                        );
+                    });
+                });
+                let symbologies: Vec<&str> = parse_barcodes(&self.barcodes).iter()
+                    .filter_map(|barcode| Symbology::detect(barcode).ok())
+                    .map(|symbology| symbology.label())
+                    .collect();
+                if !symbologies.is_empty() {
+                    ui.label(RichText::new(symbologies.join(", ")).weak());
+                }
+                for error in [&self.errors.sku, &self.errors.barcodes].into_iter().flatten() {
+                    ui.label(
+                        RichText::new(error).color(ui.visuals().error_fg_color)
+                    );
+                }
+
+                ui.add_space(FORM_SPACING);
+
                 ui.label("Brand");
                 ui.text_edit_singleline(&mut self.brand);
 
                 ui.add_space(FORM_SPACING);
 
+                ui.label("Category");
+                category_select(ui, "product_category", &mut self.category_id, &self.categories, None);
+
+                ui.add_space(FORM_SPACING);
+
                 ui.horizontal(|ui| {
-                    ui.label("Unity");
-                    
-                    ComboBox::new("unity", "")
-                    .selected_text(self.unity)
+                    ui.label("Unit");
+
+                    let unit_name = self.units.iter()
+                        .find(|unit| Some(unit.id) == self.unit_id)
+                        .map(|unit| unit.label())
+                        .unwrap_or_default();
+
+                    ComboBox::from_id_salt("product_unit")
+                    .selected_text(unit_name)
                     .show_ui(ui, |ui| {
-                        ui.selectable_value(
-                            &mut self.unity, "un", "un");
+                        for unit in &self.units {
+                            ui.selectable_value(&mut self.unit_id, Some(unit.id), unit.label());
+                        }
                     });
                 });
+                if let Some(error) = &self.errors.unit_id {
+                    ui.label(
+                        RichText::new(error).color(ui.visuals().error_fg_color)
+                    );
+                }
 
                 ui.add_space(FORM_SPACING);
 
-                ui.label("Min Stock");
-                ui.text_edit_singleline(&mut self.min_stock);
-                if let Some(error) = &self.errors.min_stock {
+                ui.horizontal(|ui| {
+                    ui.vertical(|ui| {
+                        ui.label("Min Stock");
+                        ui.add(egui::TextEdit::singleline(&mut self.min_stock).desired_width(80.0));
+                    });
+                    ui.vertical(|ui| {
+                        ui.label("Safety Stock");
+                        ui.add(egui::TextEdit::singleline(&mut self.safety_stock).desired_width(80.0));
+                    });
+                    ui.vertical(|ui| {
+                        ui.label("Reorder Point");
+                        ui.add(egui::TextEdit::singleline(&mut self.reorder_point).desired_width(80.0));
+                    });
+                    ui.vertical(|ui| {
+                        ui.label("Max Stock");
+                        ui.add(egui::TextEdit::singleline(&mut self.max_stock).desired_width(80.0));
+                    });
+                });
+
+                let level_errors = [
+                    &self.errors.min_stock,
+                    &self.errors.safety_stock,
+                    &self.errors.reorder_point,
+                    &self.errors.max_stock,
+                ];
+
+                for error in level_errors.into_iter().flatten() {
                     ui.label(
                         RichText::new(error).color(ui.visuals().error_fg_color)
                     );
@@ -121,9 +304,31 @@ impl ProductFormModal {
 
                 ui.add_space(FORM_SPACING);
 
+                ui.checkbox(&mut self.tracks_lots, "Track lots and expiry dates");
+                ui.checkbox(&mut self.serialized, "Track serial numbers (one per unit)");
+
+                ui.add_space(FORM_SPACING);
+
                 ui.label("Observation");
                 ui.text_edit_multiline(&mut self.observation);
 
+                ui.add_space(FORM_SPACING);
+
+                ui.label("Suppliers");
+                match self.supplier_links.as_mut() {
+                    Some(supplier_links) => supplier_links.ui(ui),
+                    None => {
+                        ui.label(RichText::new("Save the product to link suppliers.").weak());
+                    }
+                }
+
+                if let Some(error) = &self.errors.save {
+                    ui.add_space(FORM_SPACING);
+                    ui.label(
+                        RichText::new(error).color(ui.visuals().error_fg_color)
+                    );
+                }
+
                 ui.add_space(DEFAULT_SPACING / 2.0);
                 ui.separator();
                 ui.add_space(FORM_SPACING);
@@ -134,39 +339,59 @@ impl ProductFormModal {
                     |ui| {
                         if ui.button("Save").clicked() {
                            if let Some(product) = self.validate_form() {
+                                let product_data_barcodes = product.barcodes.clone();
                                 let mut connection = db::establish_connection();
                                 
-                                match self.id {
+                                let result = match self.id {
                                     Some(id,) => {
                                         let product = EditProductRow {
                                             id,
                                             name: product.name,
-                                            unity: Some(product.unity.into()),
+                                            unity: None,
+                                            unit_id: product.unit_id,
+                                            category_id: Some(product.category_id),
+                                            sku: Some(Some(product.sku)),
                                             brand: Some(product.brand),
                                             min_stock: Some(product.min_stock),
                                             observation: Some(product.observation),
+                                            reorder_point: Some(product.reorder_point),
+                                            safety_stock: Some(product.safety_stock),
+                                            max_stock: Some(product.max_stock),
+                                            tracks_lots: Some(product.tracks_lots),
+                                            serialized: Some(product.serialized),
                                         };
 
-                                        if let Ok(updated) = product_repository::edit_product(&mut connection, product) {
-                                            created_product = Some(updated);
-                                        }
+                                        product_repository::edit_product(&mut connection, product, &product_data_barcodes)
                                     }
                                     None => {
                                         let new_product = NewProductRow {
                                             name: product.name,
-                                            unity: Some(product.unity.into()),
+                                            unity: None,
+                                            unit_id: product.unit_id,
+                                            category_id: product.category_id,
+                                            sku: Some(product.sku),
                                             brand: Some(product.brand),
                                             min_stock: Some(product.min_stock),
                                             observation: Some(product.observation),
+                                            reorder_point: Some(product.reorder_point),
+                                            safety_stock: Some(product.safety_stock),
+                                            max_stock: Some(product.max_stock),
+                                            tracks_lots: Some(product.tracks_lots),
+                                            serialized: Some(product.serialized),
                                         };
 
-                                        if let Ok(created) = product_repository::create_product(&mut connection, new_product) {
-                                            created_product = Some(created);
-                                        }
+                                        product_repository::create_product(&mut connection, new_product, &product_data_barcodes)
+                                    }
+                                };
+
+                                // SKU and barcode clashes are only known once saving.
+                                match result {
+                                    Ok(saved) => {
+                                        created_product = Some(saved);
+                                        self.should_close = true;
                                     }
+                                    Err(error) => self.errors.save = Some(error.to_string()),
                                 }
-                                
-                                self.should_close = true;
                            }
                         }
 
@@ -195,23 +420,94 @@ impl ProductFormModal {
             }
         };
 
+        let safety_stock = Self::parse_level(&self.safety_stock, "Safety stock", &mut self.errors.safety_stock);
+        let reorder_point = Self::parse_level(&self.reorder_point, "Reorder point", &mut self.errors.reorder_point);
+        let max_stock = Self::parse_level(&self.max_stock, "Max stock", &mut self.errors.max_stock);
+
+        let barcodes = parse_barcodes(&self.barcodes);
+
+        if let Some(error) = barcodes.iter().find_map(|barcode| Symbology::detect(barcode).err()) {
+            self.errors.barcodes = Some(error.to_string());
+        }
+
         let product_data = ProductForm {
             name: self.name.clone(),
+            sku: self.sku.trim().to_owned(),
+            barcodes,
             brand: self.brand.clone(),
+            category_id: self.category_id,
             min_stock,
-            unity: self.unity,
+            safety_stock,
+            reorder_point,
+            max_stock,
+            tracks_lots: self.tracks_lots,
+            serialized: self.serialized,
+            unit_id: self.unit_id,
             observation: self.observation.clone()
         };
 
+        let parse_failed = self.errors.barcodes.is_some()
+            || self.errors.safety_stock.is_some()
+            || self.errors.reorder_point.is_some()
+            || self.errors.max_stock.is_some();
 
         match product_data.validate() {
-            Ok(_) => Some(product_data),
+            Ok(_) if !parse_failed => Some(product_data),
+            Ok(_) => None,
             Err(error)=> {
-                if let Some(name_error)= error.field_errors().get("name") {
+                let field_errors = error.field_errors();
+
+                if let Some(name_error)= field_errors.get("name") {
                     self.errors.name = Some(name_error[0].clone().message.unwrap_or_default().to_string());
                 }
+
+                if let Some(sku_error) = field_errors.get("sku") {
+                    self.errors.sku = Some(sku_error[0].clone().message.unwrap_or_default().to_string());
+                }
+
+                if let Some(unit_error) = field_errors.get("unit_id") {
+                    self.errors.unit_id = Some(unit_error[0].clone().message.unwrap_or_default().to_string());
+                }
+
+                if parse_failed {
+                    return None;
+                }
+
+                let level_errors = field_errors.get("__all__").into_iter().flat_map(|errors| errors.iter());
+
+                for level_error in level_errors {
+                    let message = Some(level_error.message.clone().unwrap_or_default().to_string());
+
+                    match level_error.code.as_ref() {
+                        "safety_stock" => self.errors.safety_stock = message,
+                        "reorder_point" => self.errors.reorder_point = message,
+                        _ => self.errors.max_stock = message,
+                    }
+                }
+
+                for (field, slot) in [
+                    ("safety_stock", &mut self.errors.safety_stock),
+                    ("reorder_point", &mut self.errors.reorder_point),
+                    ("max_stock", &mut self.errors.max_stock),
+                ] {
+                    if let Some(field_error) = field_errors.get(field) {
+                        *slot = Some(field_error[0].clone().message.unwrap_or_default().to_string());
+                    }
+                }
+
                 None
             }
         }
     }
-}
\ No newline at end of file
+
+    /// Optional stock levels: blank means zero (not set).
+    fn parse_level(value: &str, label: &str, error: &mut Option<String>) -> i32 {
+        match value.trim() {
+            "" => 0,
+            value => value.parse::<i32>().unwrap_or_else(|_| {
+                *error = Some(format!("{} should be a valid integer", label));
+                0
+            }),
+        }
+    }
+}
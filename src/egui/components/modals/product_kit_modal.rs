@@ -0,0 +1,318 @@
+use egui::{ComboBox, Direction, Id, Layout, Modal, RichText, Sides};
+use egui_extras::{Column, TableBuilder};
+use std::collections::HashMap;
+
+use crate::infra::db;
+use crate::infra::repositories::{kit_repository, place_repository, product_repository, reservation_repository};
+use crate::infra::repositories::kit_repository::KitAssembly;
+use crate::infra::models::NewKitComponentRow;
+
+use crate::domain::kit::{KitComponent, buildable_kits};
+use crate::domain::place::{Place, place_path};
+use crate::domain::product::Product;
+use crate::domain::stock_balance::ProductAvailability;
+
+const DEFAULT_SPACING: f32 = 16.0;
+const FORM_SPACING: f32 = DEFAULT_SPACING / 2.0;
+const ITEM_HEIGHT: f32 = 24.0;
+
+/// Bill of materials of a product, with assembly and disassembly at a place.
+pub struct ProductKitModal {
+    should_close: bool,
+
+    product: Product,
+    products: Vec<Product>,
+    places: Vec<Place>,
+    components: Vec<KitComponent>,
+    stock: HashMap<i32, ProductAvailability>,
+
+    component_product_id: Option<i32>,
+    component_quantity: String,
+
+    place_id: Option<i32>,
+    kit_quantity: String,
+
+    message: Option<String>,
+    error: Option<String>,
+}
+
+impl ProductKitModal {
+    pub fn new(product: &Product) -> Self {
+        let mut connection = db::establish_connection();
+
+        let mut modal = Self {
+            should_close: false,
+            product: product.clone(),
+            products: product_repository::list_products(&mut connection, "").unwrap_or_default(),
+            places: place_repository::list_places(&mut connection, "").unwrap_or_default(),
+            components: kit_repository::list_kit_components(&mut connection, product.id).unwrap_or_default(),
+            stock: HashMap::new(),
+            component_product_id: None,
+            component_quantity: "1".to_owned(),
+            place_id: None,
+            kit_quantity: String::new(),
+            message: None,
+            error: None,
+        };
+
+        modal.refresh_stock();
+
+        modal
+    }
+
+    pub fn show(&mut self, ui: &mut egui::Ui) -> bool {
+        let modal = Modal::new(Id::new("Product Kit")).show(ui.ctx(), |ui| {
+            ui.heading(format!("Kit '{}'", self.product.name));
+            ui.separator();
+            ui.add_space(DEFAULT_SPACING / 2.0);
+
+            if self.components.is_empty() {
+                ui.label("This product has no components. Add the products that go into one kit.");
+            } else {
+                self.components_table(ui);
+            }
+
+            ui.add_space(FORM_SPACING);
+
+            ui.horizontal(|ui| {
+                let component_name = self.products.iter()
+                    .find(|product| Some(product.id) == self.component_product_id)
+                    .map(|product| product.name.clone())
+                    .unwrap_or_else(|| "Component".to_owned());
+
+                ComboBox::from_id_salt("kit_component_product")
+                    .selected_text(component_name)
+                    .show_ui(ui, |ui| {
+                        for product in self.products.iter().filter(|product| product.id != self.product.id) {
+                            ui.selectable_value(&mut self.component_product_id, Some(product.id), &product.name);
+                        }
+                    });
+
+                ui.add(egui::TextEdit::singleline(&mut self.component_quantity).desired_width(60.0))
+                    .on_hover_text("Units per kit");
+
+                if ui.add_enabled(self.component_product_id.is_some(), egui::Button::new("Set Component")).clicked() {
+                    self.set_component();
+                }
+            });
+
+            ui.add_space(FORM_SPACING);
+
+            if !self.components.is_empty() {
+                let buildable = buildable_kits(&self.components, |product_id| self.available(product_id));
+
+                ui.label(RichText::new(format!("Can be built from available stock: {}", buildable)).strong());
+
+                ui.add_space(FORM_SPACING);
+                ui.separator();
+                ui.add_space(FORM_SPACING);
+
+                ui.horizontal(|ui| {
+                    let place_name = self.places.iter()
+                        .find(|place| Some(place.id) == self.place_id)
+                        .map(|place| place_path(&self.places, place.id))
+                        .unwrap_or_else(|| "Place".to_owned());
+
+                    ComboBox::from_id_salt("kit_assembly_place")
+                        .selected_text(place_name)
+                        .show_ui(ui, |ui| {
+                            for place in &self.places {
+                                ui.selectable_value(&mut self.place_id, Some(place.id), place_path(&self.places, place.id));
+                            }
+                        });
+
+                    ui.add(egui::TextEdit::singleline(&mut self.kit_quantity).desired_width(60.0).hint_text("Kits"));
+
+                    if ui.button("Assemble").on_hover_text("Take the components out and put the kits in").clicked() {
+                        self.assemble(true);
+                    }
+
+                    if ui.button("Disassemble").on_hover_text("Take the kits out and put the components back").clicked() {
+                        self.assemble(false);
+                    }
+                });
+            }
+
+            if let Some(message) = &self.message {
+                ui.add_space(FORM_SPACING);
+                ui.label(RichText::new(message).weak());
+            }
+
+            if let Some(error) = &self.error {
+                ui.add_space(FORM_SPACING);
+                ui.label(RichText::new(error).color(ui.visuals().error_fg_color));
+            }
+
+            ui.add_space(DEFAULT_SPACING / 2.0);
+            ui.separator();
+
+            Sides::new().show(
+                ui,
+                |_ui| {},
+                |ui| {
+                    if ui.button("Close").clicked() {
+                        self.should_close = true;
+                    }
+                }
+            );
+        });
+
+        if modal.should_close() {
+            self.should_close = true;
+        }
+
+        self.should_close
+    }
+
+    fn components_table(&mut self, ui: &mut egui::Ui) {
+        let mut component_to_remove = None;
+
+        TableBuilder::new(ui)
+            .striped(true)
+            .resizable(false)
+            .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+            .column(Column::remainder().at_least(200.0).clip(true))
+            .column(Column::exact(88.0))
+            .column(Column::exact(88.0))
+            .column(Column::exact(88.0))
+            .max_scroll_height(300.0)
+            .header(ITEM_HEIGHT, |mut header| {
+                header.col(|ui| { ui.heading("Component"); });
+                header.col(|ui| {
+                    ui.with_layout(Layout::centered_and_justified(Direction::LeftToRight), |ui| {
+                        ui.heading("Per Kit");
+                    });
+                });
+                header.col(|ui| {
+                    ui.with_layout(Layout::centered_and_justified(Direction::LeftToRight), |ui| {
+                        ui.heading("Available");
+                    });
+                });
+                header.col(|_ui| {});
+            })
+            .body(|body| {
+                body.rows(ITEM_HEIGHT, self.components.len(), |mut row| {
+                    let component = &self.components[row.index()];
+
+                    let component_name = self.products.iter()
+                        .find(|product| product.id == component.component_product_id)
+                        .map(|product| product.name.clone())
+                        .unwrap_or_else(|| format!("#{}", component.component_product_id));
+
+                    row.col(|ui| { ui.label(component_name); });
+                    row.col(|ui| {
+                        ui.with_layout(Layout::centered_and_justified(Direction::LeftToRight), |ui| {
+                            ui.label(component.quantity.to_string());
+                        });
+                    });
+                    row.col(|ui| {
+                        ui.with_layout(Layout::centered_and_justified(Direction::LeftToRight), |ui| {
+                            ui.label(self.available(component.component_product_id).to_string());
+                        });
+                    });
+                    row.col(|ui| {
+                        if ui.button("Remove").clicked() {
+                            component_to_remove = Some(component.id);
+                        }
+                    });
+                });
+            });
+
+        if let Some(component_id) = component_to_remove {
+            let mut connection = db::establish_connection();
+
+            match kit_repository::delete_kit_component(&mut connection, component_id) {
+                Ok(_) => self.components.retain(|component| component.id != component_id),
+                Err(error) => self.error = Some(error.to_string()),
+            }
+        }
+    }
+
+    fn set_component(&mut self) {
+        self.message = None;
+        self.error = None;
+
+        let Some(component_product_id) = self.component_product_id else {
+            return;
+        };
+
+        let Ok(quantity) = self.component_quantity.trim().parse::<i32>() else {
+            self.error = Some("Units per kit should be a valid integer".into());
+            return;
+        };
+
+        let mut connection = db::establish_connection();
+
+        let new_component = NewKitComponentRow {
+            kit_product_id: self.product.id,
+            component_product_id,
+            quantity,
+        };
+
+        match kit_repository::set_kit_component(&mut connection, new_component) {
+            Ok(component) => {
+                if let Some(existing) = self.components.iter_mut().find(|existing| existing.id == component.id) {
+                    *existing = component;
+                } else {
+                    self.components.push(component);
+                }
+
+                self.component_product_id = None;
+                self.component_quantity = "1".to_owned();
+            }
+            Err(error) => self.error = Some(error.to_string()),
+        }
+    }
+
+    fn assemble(&mut self, assemble: bool) {
+        self.message = None;
+        self.error = None;
+
+        let Some(place_id) = self.place_id else {
+            self.error = Some("Select the place where the kits are put together".into());
+            return;
+        };
+
+        let Ok(quantity) = self.kit_quantity.trim().parse::<i32>() else {
+            self.error = Some("Number of kits should be a valid integer".into());
+            return;
+        };
+
+        let mut connection = db::establish_connection();
+
+        let assembly = KitAssembly {
+            kit_product_id: self.product.id,
+            place_id,
+            quantity,
+        };
+
+        let result = if assemble {
+            kit_repository::assemble_kits(&mut connection, assembly)
+        } else {
+            kit_repository::disassemble_kits(&mut connection, assembly)
+        };
+
+        match result {
+            Ok(_) => {
+                self.message = Some(format!("{} {} kits", if assemble { "Assembled" } else { "Disassembled" }, quantity));
+                self.kit_quantity.clear();
+                self.refresh_stock();
+            }
+            Err(error) => self.error = Some(error.to_string()),
+        }
+    }
+
+    fn available(&self, product_id: i32) -> i64 {
+        self.stock.get(&product_id).map(|stock| stock.available()).unwrap_or_default()
+    }
+
+    fn refresh_stock(&mut self) {
+        let mut connection = db::establish_connection();
+
+        self.stock = reservation_repository::list_product_availability(&mut connection)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|stock| (stock.product_id, stock))
+            .collect();
+    }
+}
@@ -0,0 +1,120 @@
+use chrono::Local;
+use egui::{Direction, Id, Layout, Modal, RichText, Sides};
+use egui_extras::{Column, TableBuilder};
+
+use crate::infra::db;
+use crate::infra::repositories::{place_repository, stock_repository};
+use crate::infra::repositories::stock_repository::LotBalanceFilter;
+
+use crate::domain::place::{Place, place_path};
+use crate::domain::product::Product;
+use crate::domain::stock_balance::LotBalance;
+
+const DEFAULT_SPACING: f32 = 16.0;
+const ITEM_HEIGHT: f32 = 24.0;
+
+pub struct ProductLotsModal {
+    should_close: bool,
+
+    product: Product,
+    places: Vec<Place>,
+    lots: Vec<LotBalance>,
+}
+
+impl ProductLotsModal {
+    pub fn new(product: &Product) -> Self {
+        let mut connection = db::establish_connection();
+
+        let filter = LotBalanceFilter {
+            product_id: Some(product.id),
+            ..Default::default()
+        };
+
+        Self {
+            should_close: false,
+            product: product.clone(),
+            places: place_repository::list_places(&mut connection, "").unwrap_or_default(),
+            lots: stock_repository::list_lot_balances(&mut connection, &filter).unwrap_or_default(),
+        }
+    }
+
+    pub fn show(&mut self, ui: &mut egui::Ui) -> bool {
+        let today = Local::now().date_naive();
+
+        let modal = Modal::new(Id::new("Product Lots")).show(ui.ctx(), |ui| {
+            ui.heading(format!("Lots of '{}'", self.product.name));
+            ui.separator();
+            ui.add_space(DEFAULT_SPACING / 2.0);
+
+            if self.lots.is_empty() {
+                ui.label("There are no lots of this product in stock.");
+            } else {
+                TableBuilder::new(ui)
+                    .striped(true)
+                    .resizable(false)
+                    .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+                    .column(Column::remainder().at_least(160.0).clip(true))
+                    .column(Column::exact(120.0))
+                    .column(Column::exact(100.0))
+                    .column(Column::exact(88.0))
+                    .max_scroll_height(400.0)
+                    .header(ITEM_HEIGHT, |mut header| {
+                        header.col(|ui| { ui.heading("Place"); });
+                        header.col(|ui| { ui.heading("Lot"); });
+                        header.col(|ui| { ui.heading("Expiry"); });
+                        header.col(|ui| {
+                            ui.with_layout(Layout::centered_and_justified(Direction::LeftToRight), |ui| {
+                                ui.heading("Stock");
+                            });
+                        });
+                    })
+                    .body(|body| {
+                        body.rows(ITEM_HEIGHT, self.lots.len(), |mut row| {
+                            let lot = &self.lots[row.index()];
+
+                            let place_name = self.places.iter()
+                                .find(|place| place.id == lot.place_id)
+                                .map(|place| place_path(&self.places, place.id))
+                                .unwrap_or_else(|| format!("#{}", lot.place_id));
+
+                            row.col(|ui| { ui.label(place_name); });
+                            row.col(|ui| { ui.label(&lot.lot_number); });
+                            row.col(|ui| {
+                                let expiry = lot.expiry_date.map(|date| date.to_string()).unwrap_or_default();
+
+                                if lot.days_to_expiry(today).is_some_and(|days| days < 0) {
+                                    ui.label(RichText::new(expiry).color(ui.visuals().error_fg_color));
+                                } else {
+                                    ui.label(expiry);
+                                }
+                            });
+                            row.col(|ui| {
+                                ui.with_layout(Layout::centered_and_justified(Direction::LeftToRight), |ui| {
+                                    ui.label(lot.quantity.to_string());
+                                });
+                            });
+                        });
+                    });
+            }
+
+            ui.add_space(DEFAULT_SPACING / 2.0);
+            ui.separator();
+
+            Sides::new().show(
+                ui,
+                |_ui| {},
+                |ui| {
+                    if ui.button("Close").clicked() {
+                        self.should_close = true;
+                    }
+                }
+            );
+        });
+
+        if modal.should_close() {
+            self.should_close = true;
+        }
+
+        self.should_close
+    }
+}
@@ -0,0 +1,352 @@
+use egui::{ComboBox, Id, Modal, RichText, Sides};
+use validator::{Validate, ValidationErrors};
+
+use crate::infra::db;
+use crate::infra::repositories::product_supplier_repository;
+use crate::infra::models::{NewProductSupplierRow, EditProductSupplierRow};
+
+use crate::domain::product::Product;
+use crate::domain::product_supplier::ProductSupplier;
+use crate::domain::supplier::Supplier;
+
+const DEFAULT_SPACING: f32 = 16.0;
+const FORM_SPACING: f32 = DEFAULT_SPACING / 2.0;
+const DEFAULT_CURRENCY: &str = "BRL";
+
+#[derive(Default)]
+pub struct FormErrors {
+    pub product_id: Option<String>,
+    pub supplier_id: Option<String>,
+    pub last_unit_cost: Option<String>,
+    pub currency: Option<String>,
+    pub min_order_quantity: Option<String>,
+    pub lead_time_days: Option<String>,
+    pub save: Option<String>,
+}
+
+#[derive(Validate)]
+pub struct ProductSupplierForm {
+    #[validate(required(message = "Select a product"))]
+    product_id: Option<i32>,
+
+    #[validate(required(message = "Select a supplier"))]
+    supplier_id: Option<i32>,
+
+    supplier_sku: String,
+
+    #[validate(range(min = 0.0, message = "Cost cannot be negative"))]
+    last_unit_cost: Option<f64>,
+
+    #[validate(length(equal = 3, message = "Use a three letter currency code"))]
+    currency: String,
+
+    #[validate(range(min = 1, message = "Minimum order must be at least one unit"))]
+    min_order_quantity: i32,
+
+    #[validate(range(min = 0, message = "Lead time cannot be negative"))]
+    lead_time_days: Option<i32>,
+
+    preferred: bool,
+}
+
+/// Which side of the link is already known when the form is opened: the product
+/// form links suppliers to its product and the supplier view links products.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinkOwner {
+    Product(i32),
+    Supplier(i32),
+}
+
+pub struct ProductSupplierFormModal {
+    should_close: bool,
+
+    owner: LinkOwner,
+    products: Vec<Product>,
+    suppliers: Vec<Supplier>,
+
+    id: Option<i32>,
+    product_id: Option<i32>,
+    supplier_id: Option<i32>,
+    supplier_sku: String,
+    last_unit_cost: String,
+    currency: String,
+    min_order_quantity: String,
+    lead_time_days: String,
+    preferred: bool,
+
+    errors: FormErrors,
+}
+
+impl ProductSupplierFormModal {
+    pub fn new(link: Option<&ProductSupplier>, owner: LinkOwner, products: &[Product], suppliers: &[Supplier]) -> Self {
+        let (product_id, supplier_id) = match owner {
+            LinkOwner::Product(product_id) => (Some(product_id), link.map(|link| link.supplier_id)),
+            LinkOwner::Supplier(supplier_id) => (link.map(|link| link.product_id), Some(supplier_id)),
+        };
+
+        Self {
+            should_close: false,
+            owner,
+            products: products.to_vec(),
+            suppliers: suppliers.to_vec(),
+            id: link.map(|link| link.id),
+            product_id,
+            supplier_id,
+            supplier_sku: link.and_then(|link| link.supplier_sku.clone()).unwrap_or_default(),
+            last_unit_cost: link.and_then(|link| link.last_unit_cost).map(|cost| format!("{:.2}", cost)).unwrap_or_default(),
+            currency: link.map(|link| link.currency.clone()).unwrap_or_else(|| DEFAULT_CURRENCY.to_owned()),
+            min_order_quantity: link.map(|link| link.min_order_quantity).unwrap_or(1).to_string(),
+            lead_time_days: link.and_then(|link| link.lead_time_days).map(|days| days.to_string()).unwrap_or_default(),
+            preferred: link.map(|link| link.preferred).unwrap_or_default(),
+            errors: FormErrors::default(),
+        }
+    }
+
+    pub fn show(&mut self, ui: &mut egui::Ui) -> (bool, Option<ProductSupplier>) {
+        let mut saved_link = None;
+
+        let modal = Modal::new(Id::new("Product Supplier Link")).show(ui.ctx(), |ui| {
+            ui.heading(if self.id.is_none() { "New Supplier Link" } else { "Edit Supplier Link" });
+            ui.separator();
+            ui.add_space(DEFAULT_SPACING / 2.0);
+
+            // Only the side that is not fixed by the owner can be picked, and neither
+            // side can change once the link exists.
+            let editable = self.id.is_none();
+
+            ui.label("Product");
+            let product_name = self.products.iter()
+                .find(|product| Some(product.id) == self.product_id)
+                .map(|product| product.name.clone())
+                .unwrap_or_default();
+
+            if editable && matches!(self.owner, LinkOwner::Supplier(_)) {
+                ComboBox::from_id_salt("link_product")
+                    .selected_text(product_name)
+                    .show_ui(ui, |ui| {
+                        for product in &self.products {
+                            ui.selectable_value(&mut self.product_id, Some(product.id), &product.name);
+                        }
+                    });
+            } else {
+                ui.label(RichText::new(product_name).strong());
+            }
+            if let Some(error) = &self.errors.product_id {
+                ui.label(RichText::new(error).color(ui.visuals().error_fg_color));
+            }
+
+            ui.add_space(FORM_SPACING);
+
+            ui.label("Supplier");
+            let supplier_name = self.suppliers.iter()
+                .find(|supplier| Some(supplier.id) == self.supplier_id)
+                .map(|supplier| supplier.name.clone())
+                .unwrap_or_default();
+
+            if editable && matches!(self.owner, LinkOwner::Product(_)) {
+                ComboBox::from_id_salt("link_supplier")
+                    .selected_text(supplier_name)
+                    .show_ui(ui, |ui| {
+                        let selected_supplier_id = self.supplier_id;
+                        for supplier in self.suppliers.iter().filter(|supplier| supplier.active || Some(supplier.id) == selected_supplier_id) {
+                            ui.selectable_value(&mut self.supplier_id, Some(supplier.id), &supplier.name);
+                        }
+                    });
+            } else {
+                ui.label(RichText::new(supplier_name).strong());
+            }
+            if let Some(error) = &self.errors.supplier_id {
+                ui.label(RichText::new(error).color(ui.visuals().error_fg_color));
+            }
+
+            ui.add_space(FORM_SPACING);
+
+            ui.label("Supplier SKU");
+            ui.text_edit_singleline(&mut self.supplier_sku);
+
+            ui.add_space(FORM_SPACING);
+
+            ui.horizontal(|ui| {
+                ui.vertical(|ui| {
+                    ui.label("Last Unit Cost");
+                    ui.add(egui::TextEdit::singleline(&mut self.last_unit_cost).desired_width(120.0));
+                });
+                ui.vertical(|ui| {
+                    ui.label("Currency");
+                    ui.add(egui::TextEdit::singleline(&mut self.currency).desired_width(60.0));
+                });
+            });
+            if let Some(error) = &self.errors.last_unit_cost {
+                ui.label(RichText::new(error).color(ui.visuals().error_fg_color));
+            }
+            if let Some(error) = &self.errors.currency {
+                ui.label(RichText::new(error).color(ui.visuals().error_fg_color));
+            }
+
+            ui.add_space(FORM_SPACING);
+
+            ui.horizontal(|ui| {
+                ui.vertical(|ui| {
+                    ui.label("Min Order Qty");
+                    ui.add(egui::TextEdit::singleline(&mut self.min_order_quantity).desired_width(120.0));
+                });
+                ui.vertical(|ui| {
+                    ui.label("Lead Time (days)");
+                    ui.add(egui::TextEdit::singleline(&mut self.lead_time_days).desired_width(120.0));
+                });
+            });
+            if let Some(error) = &self.errors.min_order_quantity {
+                ui.label(RichText::new(error).color(ui.visuals().error_fg_color));
+            }
+            if let Some(error) = &self.errors.lead_time_days {
+                ui.label(RichText::new(error).color(ui.visuals().error_fg_color));
+            }
+
+            ui.add_space(FORM_SPACING);
+
+            ui.checkbox(&mut self.preferred, "Preferred supplier for this product");
+
+            if let Some(error) = &self.errors.save {
+                ui.add_space(FORM_SPACING);
+                ui.label(RichText::new(error).color(ui.visuals().error_fg_color));
+            }
+
+            ui.add_space(DEFAULT_SPACING / 2.0);
+            ui.separator();
+            ui.add_space(FORM_SPACING);
+
+            Sides::new().show(
+                ui,
+                |_ui| {},
+                |ui| {
+                    if ui.button("Save").clicked()
+                        && let Some(link) = self.validate_form() {
+                        let mut connection = db::establish_connection();
+
+                        let result = match self.id {
+                            Some(id) => {
+                                let link = EditProductSupplierRow {
+                                    id,
+                                    supplier_sku: Some(link.supplier_sku).filter(|sku| !sku.is_empty()),
+                                    last_unit_cost: link.last_unit_cost,
+                                    currency: link.currency,
+                                    min_order_quantity: link.min_order_quantity,
+                                    lead_time_days: link.lead_time_days,
+                                    preferred: link.preferred,
+                                };
+
+                                product_supplier_repository::edit_product_supplier(&mut connection, link)
+                            }
+                            None => {
+                                let new_link = NewProductSupplierRow {
+                                    product_id: link.product_id.unwrap_or_default(),
+                                    supplier_id: link.supplier_id.unwrap_or_default(),
+                                    supplier_sku: Some(link.supplier_sku).filter(|sku| !sku.is_empty()),
+                                    last_unit_cost: link.last_unit_cost,
+                                    currency: link.currency,
+                                    min_order_quantity: link.min_order_quantity,
+                                    lead_time_days: link.lead_time_days,
+                                    preferred: link.preferred,
+                                };
+
+                                product_supplier_repository::create_product_supplier(&mut connection, new_link)
+                            }
+                        };
+
+                        match result {
+                            Ok(link) => {
+                                saved_link = Some(link);
+                                self.should_close = true;
+                            }
+                            Err(error) => {
+                                self.errors.save = Some(error.to_string());
+                            }
+                        }
+                    }
+
+                    if ui.button("Cancel").clicked() {
+                        self.should_close = true;
+                    }
+                }
+            );
+        });
+
+        if modal.should_close() {
+            self.should_close = true;
+        }
+
+        (self.should_close, saved_link)
+    }
+
+    fn validate_form(&mut self) -> Option<ProductSupplierForm> {
+        self.errors = FormErrors::default();
+
+        let last_unit_cost = match self.last_unit_cost.trim().replace(',', ".") {
+            value if value.is_empty() => None,
+            value => match value.parse::<f64>() {
+                Ok(cost) => Some(cost),
+                Err(_) => {
+                    self.errors.last_unit_cost = Some("Cost should be a valid number".into());
+                    None
+                }
+            },
+        };
+
+        let min_order_quantity = match self.min_order_quantity.trim().parse::<i32>() {
+            Ok(value) => value,
+            Err(_) => {
+                self.errors.min_order_quantity = Some("Minimum order should be a valid integer".into());
+                1
+            }
+        };
+
+        let lead_time_days = match self.lead_time_days.trim() {
+            "" => None,
+            value => match value.parse::<i32>() {
+                Ok(days) => Some(days),
+                Err(_) => {
+                    self.errors.lead_time_days = Some("Lead time should be a valid integer".into());
+                    None
+                }
+            },
+        };
+
+        let link_data = ProductSupplierForm {
+            product_id: self.product_id,
+            supplier_id: self.supplier_id,
+            supplier_sku: self.supplier_sku.trim().to_owned(),
+            last_unit_cost,
+            currency: self.currency.trim().to_uppercase(),
+            min_order_quantity,
+            lead_time_days,
+            preferred: self.preferred,
+        };
+
+        let parse_failed = self.errors.last_unit_cost.is_some()
+            || self.errors.min_order_quantity.is_some()
+            || self.errors.lead_time_days.is_some();
+
+        match link_data.validate() {
+            Ok(_) if !parse_failed => Some(link_data),
+            Ok(_) => None,
+            Err(error) => {
+                let message = |field: &str| first_error_message(&error, field);
+
+                self.errors.product_id = message("product_id");
+                self.errors.supplier_id = message("supplier_id");
+                self.errors.currency = message("currency");
+                self.errors.last_unit_cost = self.errors.last_unit_cost.take().or_else(|| message("last_unit_cost"));
+                self.errors.min_order_quantity = self.errors.min_order_quantity.take().or_else(|| message("min_order_quantity"));
+                self.errors.lead_time_days = self.errors.lead_time_days.take().or_else(|| message("lead_time_days"));
+
+                None
+            }
+        }
+    }
+}
+
+fn first_error_message(errors: &ValidationErrors, field: &str) -> Option<String> {
+    errors.field_errors()
+        .get(field)
+        .map(|field_errors| field_errors[0].clone().message.unwrap_or_default().to_string())
+}
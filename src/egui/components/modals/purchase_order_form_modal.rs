@@ -0,0 +1,190 @@
+use chrono::NaiveDate;
+use egui::{ComboBox, Id, Modal, RichText, Sides};
+use validator::Validate;
+
+use crate::infra::db;
+use crate::infra::repositories::purchase_order_repository;
+use crate::infra::models::{NewPurchaseOrderRow, EditPurchaseOrderRow};
+
+use crate::domain::purchase_order::{PurchaseOrder, NAIVE_DATE_PATTERN};
+use crate::domain::supplier::Supplier;
+
+const DEFAULT_SPACING: f32 = 16.0;
+const FORM_SPACING: f32 = DEFAULT_SPACING / 2.0;
+
+#[derive(Default)]
+pub struct FormErrors {
+    pub supplier_id: Option<String>,
+    pub expected_date: Option<String>,
+    pub save: Option<String>,
+}
+
+#[derive(Validate)]
+pub struct PurchaseOrderForm {
+    #[validate(required(message = "Select a supplier"))]
+    supplier_id: Option<i32>,
+
+    expected_date: Option<NaiveDate>,
+
+    notes: String,
+}
+
+pub struct PurchaseOrderFormModal {
+    should_close: bool,
+
+    suppliers: Vec<Supplier>,
+
+    id: Option<i32>,
+    supplier_id: Option<i32>,
+    expected_date: String,
+    notes: String,
+
+    errors: FormErrors,
+}
+
+impl PurchaseOrderFormModal {
+    pub fn new(order: Option<&PurchaseOrder>, suppliers: &[Supplier]) -> Self {
+        Self {
+            should_close: false,
+            suppliers: suppliers.to_vec(),
+            id: order.map(|order| order.id),
+            supplier_id: order.map(|order| order.supplier_id),
+            expected_date: order
+                .and_then(|order| order.expected_date)
+                .map(|date| date.format(NAIVE_DATE_PATTERN).to_string())
+                .unwrap_or_default(),
+            notes: order.and_then(|order| order.notes.clone()).unwrap_or_default(),
+            errors: FormErrors::default(),
+        }
+    }
+
+    pub fn show(&mut self, ui: &mut egui::Ui) -> (bool, Option<PurchaseOrder>) {
+        let mut saved_order = None;
+
+        let modal = Modal::new(Id::new("Purchase Order Form")).show(ui.ctx(), |ui| {
+            ui.heading(if self.id.is_none() { "New Purchase Order" } else { "Edit Purchase Order" });
+            ui.separator();
+            ui.add_space(DEFAULT_SPACING / 2.0);
+
+            ui.label("Supplier");
+            let supplier_name = self.suppliers.iter()
+                .find(|supplier| Some(supplier.id) == self.supplier_id)
+                .map(|supplier| supplier.name.clone())
+                .unwrap_or_default();
+
+            ComboBox::from_id_salt("purchase_order_supplier")
+                .selected_text(supplier_name)
+                .show_ui(ui, |ui| {
+                    let selected_supplier_id = self.supplier_id;
+                    for supplier in self.suppliers.iter().filter(|supplier| supplier.active || Some(supplier.id) == selected_supplier_id) {
+                        ui.selectable_value(&mut self.supplier_id, Some(supplier.id), &supplier.name);
+                    }
+                });
+            if let Some(error) = &self.errors.supplier_id {
+                ui.label(RichText::new(error).color(ui.visuals().error_fg_color));
+            }
+
+            ui.add_space(FORM_SPACING);
+
+            ui.label("Expected Date (YYYY-MM-DD)");
+            ui.text_edit_singleline(&mut self.expected_date);
+            if let Some(error) = &self.errors.expected_date {
+                ui.label(RichText::new(error).color(ui.visuals().error_fg_color));
+            }
+
+            ui.add_space(FORM_SPACING);
+
+            ui.label("Notes");
+            ui.text_edit_multiline(&mut self.notes);
+
+            if let Some(error) = &self.errors.save {
+                ui.add_space(FORM_SPACING);
+                ui.label(RichText::new(error).color(ui.visuals().error_fg_color));
+            }
+
+            ui.add_space(DEFAULT_SPACING / 2.0);
+            ui.separator();
+            ui.add_space(FORM_SPACING);
+
+            Sides::new().show(
+                ui,
+                |_ui| {},
+                |ui| {
+                    if ui.button("Save").clicked()
+                        && let Some(order) = self.validate_form() {
+                        let mut connection = db::establish_connection();
+
+                        let expected_date = order.expected_date.map(|date| date.format(NAIVE_DATE_PATTERN).to_string());
+                        let notes = Some(order.notes).filter(|notes| !notes.is_empty());
+
+                        let result = match self.id {
+                            Some(id) => purchase_order_repository::edit_purchase_order(&mut connection, EditPurchaseOrderRow {
+                                id,
+                                supplier_id: order.supplier_id.unwrap_or_default(),
+                                expected_date,
+                                notes,
+                            }),
+                            None => purchase_order_repository::create_purchase_order(&mut connection, NewPurchaseOrderRow {
+                                supplier_id: order.supplier_id.unwrap_or_default(),
+                                expected_date,
+                                notes,
+                            }),
+                        };
+
+                        match result {
+                            Ok(order) => {
+                                saved_order = Some(order);
+                                self.should_close = true;
+                            }
+                            Err(error) => {
+                                self.errors.save = Some(error.to_string());
+                            }
+                        }
+                    }
+
+                    if ui.button("Cancel").clicked() {
+                        self.should_close = true;
+                    }
+                }
+            );
+        });
+
+        if modal.should_close() {
+            self.should_close = true;
+        }
+
+        (self.should_close, saved_order)
+    }
+
+    fn validate_form(&mut self) -> Option<PurchaseOrderForm> {
+        self.errors = FormErrors::default();
+
+        let expected_date = match self.expected_date.trim() {
+            "" => None,
+            value => match NaiveDate::parse_from_str(value, NAIVE_DATE_PATTERN) {
+                Ok(date) => Some(date),
+                Err(_) => {
+                    self.errors.expected_date = Some("Date should follow the YYYY-MM-DD format".into());
+                    None
+                }
+            },
+        };
+
+        let order_data = PurchaseOrderForm {
+            supplier_id: self.supplier_id,
+            expected_date,
+            notes: self.notes.trim().to_owned(),
+        };
+
+        match order_data.validate() {
+            Ok(_) if self.errors.expected_date.is_none() => Some(order_data),
+            Ok(_) => None,
+            Err(error) => {
+                if let Some(supplier_error) = error.field_errors().get("supplier_id") {
+                    self.errors.supplier_id = Some(supplier_error[0].clone().message.unwrap_or_default().to_string());
+                }
+                None
+            }
+        }
+    }
+}
@@ -0,0 +1,277 @@
+use egui::{ComboBox, Id, Modal, RichText, Sides};
+use validator::Validate;
+
+use crate::infra::db;
+use crate::infra::repositories::{product_supplier_repository, purchase_order_repository, unit_repository};
+use crate::infra::repositories::product_supplier_repository::ProductSupplierFilter;
+use crate::infra::models::{NewPurchaseOrderLineRow, EditPurchaseOrderLineRow};
+
+use crate::domain::product::Product;
+use crate::domain::purchase_order::{PurchaseOrder, PurchaseOrderLine};
+use crate::domain::unit::Unit;
+use crate::egui::widgets::unit_select::unit_select;
+
+const DEFAULT_SPACING: f32 = 16.0;
+const FORM_SPACING: f32 = DEFAULT_SPACING / 2.0;
+
+#[derive(Default)]
+pub struct FormErrors {
+    pub product_id: Option<String>,
+    pub quantity: Option<String>,
+    pub unit_cost: Option<String>,
+    pub save: Option<String>,
+}
+
+#[derive(Validate)]
+pub struct PurchaseOrderLineForm {
+    #[validate(required(message = "Select a product"))]
+    product_id: Option<i32>,
+
+    #[validate(range(min = 1, message = "Quantity must be greater than zero"))]
+    quantity: i32,
+
+    #[validate(range(min = 0.0, message = "Cost cannot be negative"))]
+    unit_cost: Option<f64>,
+}
+
+pub struct PurchaseOrderLineFormModal {
+    should_close: bool,
+
+    order_id: i32,
+    supplier_id: i32,
+    products: Vec<Product>,
+    units: Vec<Unit>,
+
+    id: Option<i32>,
+    product_id: Option<i32>,
+    quantity: String,
+    unit_id: Option<i32>,
+    unit_cost: String,
+
+    errors: FormErrors,
+}
+
+impl PurchaseOrderLineFormModal {
+    pub fn new(order: &PurchaseOrder, line: Option<&PurchaseOrderLine>, products: &[Product]) -> Self {
+        let mut connection = db::establish_connection();
+
+        let product_unit_id = line
+            .and_then(|line| products.iter().find(|product| product.id == line.product_id))
+            .and_then(|product| product.unit_id);
+
+        Self {
+            should_close: false,
+            order_id: order.id,
+            supplier_id: order.supplier_id,
+            products: products.to_vec(),
+            units: unit_repository::list_units(&mut connection, "").unwrap_or_default(),
+            id: line.map(|line| line.id),
+            product_id: line.map(|line| line.product_id),
+            quantity: line.map(|line| line.quantity.to_string()).unwrap_or_default(),
+            unit_id: line.and_then(|line| line.unit_id).or(product_unit_id),
+            unit_cost: line.and_then(|line| line.unit_cost).map(|cost| format!("{:.2}", cost)).unwrap_or_default(),
+            errors: FormErrors::default(),
+        }
+    }
+
+    pub fn show(&mut self, ui: &mut egui::Ui) -> (bool, Option<PurchaseOrderLine>) {
+        let mut saved_line = None;
+
+        let modal = Modal::new(Id::new("Purchase Order Line Form")).show(ui.ctx(), |ui| {
+            ui.heading(if self.id.is_none() { "New Line" } else { "Edit Line" });
+            ui.separator();
+            ui.add_space(DEFAULT_SPACING / 2.0);
+
+            ui.label("Product");
+            let product_name = self.products.iter()
+                .find(|product| Some(product.id) == self.product_id)
+                .map(|product| product.name.clone())
+                .unwrap_or_default();
+
+            let mut product_changed = false;
+
+            ComboBox::from_id_salt("purchase_order_line_product")
+                .selected_text(product_name)
+                .show_ui(ui, |ui| {
+                    for product in &self.products {
+                        product_changed |= ui.selectable_value(&mut self.product_id, Some(product.id), &product.name).changed();
+                    }
+                });
+            if let Some(error) = &self.errors.product_id {
+                ui.label(RichText::new(error).color(ui.visuals().error_fg_color));
+            }
+
+            if product_changed {
+                self.unit_id = self.product_unit_id();
+                self.fill_supplier_terms();
+            }
+
+            ui.add_space(FORM_SPACING);
+
+            let product_unit_id = self.product_unit_id();
+
+            ui.label("Quantity");
+            ui.horizontal(|ui| {
+                ui.add(egui::TextEdit::singleline(&mut self.quantity).desired_width(120.0));
+                unit_select(ui, "purchase_order_line_unit", &mut self.unit_id, &self.units, product_unit_id);
+            });
+            if let Some(error) = &self.errors.quantity {
+                ui.label(RichText::new(error).color(ui.visuals().error_fg_color));
+            }
+
+            ui.add_space(FORM_SPACING);
+
+            ui.label("Unit Cost");
+            ui.text_edit_singleline(&mut self.unit_cost);
+            if let Some(error) = &self.errors.unit_cost {
+                ui.label(RichText::new(error).color(ui.visuals().error_fg_color));
+            }
+
+            if let Some(error) = &self.errors.save {
+                ui.add_space(FORM_SPACING);
+                ui.label(RichText::new(error).color(ui.visuals().error_fg_color));
+            }
+
+            ui.add_space(DEFAULT_SPACING / 2.0);
+            ui.separator();
+            ui.add_space(FORM_SPACING);
+
+            Sides::new().show(
+                ui,
+                |_ui| {},
+                |ui| {
+                    if ui.button("Save").clicked()
+                        && let Some(line) = self.validate_form() {
+                        let mut connection = db::establish_connection();
+
+                        let result = match self.id {
+                            Some(id) => purchase_order_repository::edit_purchase_order_line(&mut connection, EditPurchaseOrderLineRow {
+                                id,
+                                product_id: line.product_id.unwrap_or_default(),
+                                quantity: line.quantity,
+                                unit_cost: line.unit_cost,
+                                unit_id: self.unit_id,
+                            }),
+                            None => purchase_order_repository::create_purchase_order_line(&mut connection, NewPurchaseOrderLineRow {
+                                purchase_order_id: self.order_id,
+                                product_id: line.product_id.unwrap_or_default(),
+                                quantity: line.quantity,
+                                unit_cost: line.unit_cost,
+                                unit_id: self.unit_id,
+                            }),
+                        };
+
+                        match result {
+                            Ok(line) => {
+                                saved_line = Some(line);
+                                self.should_close = true;
+                            }
+                            Err(error) => {
+                                self.errors.save = Some(error.to_string());
+                            }
+                        }
+                    }
+
+                    if ui.button("Cancel").clicked() {
+                        self.should_close = true;
+                    }
+                }
+            );
+        });
+
+        if modal.should_close() {
+            self.should_close = true;
+        }
+
+        (self.should_close, saved_line)
+    }
+
+    fn product_unit_id(&self) -> Option<i32> {
+        self.products.iter()
+            .find(|product| Some(product.id) == self.product_id)
+            .and_then(|product| product.unit_id)
+    }
+
+    /// Pre-fills cost and minimum quantity from the product/supplier link, if any.
+    fn fill_supplier_terms(&mut self) {
+        let Some(product_id) = self.product_id else {
+            return;
+        };
+
+        let mut connection = db::establish_connection();
+
+        let filter = ProductSupplierFilter {
+            product_id: Some(product_id),
+            supplier_id: Some(self.supplier_id),
+        };
+
+        let link = product_supplier_repository::list_product_suppliers(&mut connection, &filter)
+            .ok()
+            .and_then(|links| links.into_iter().next());
+
+        if let Some(link) = link {
+            if let Some(cost) = link.last_unit_cost {
+                self.unit_cost = format!("{:.2}", cost);
+            }
+
+            if self.quantity.trim().is_empty() {
+                self.quantity = link.min_order_quantity.to_string();
+            }
+        }
+    }
+
+    fn validate_form(&mut self) -> Option<PurchaseOrderLineForm> {
+        self.errors = FormErrors::default();
+
+        let quantity = match self.quantity.trim().parse::<i32>() {
+            Ok(value) => value,
+            Err(_) => {
+                self.errors.quantity = Some("Quantity should be a valid integer".into());
+                1
+            }
+        };
+
+        let unit_cost = match self.unit_cost.trim().replace(',', ".") {
+            value if value.is_empty() => None,
+            value => match value.parse::<f64>() {
+                Ok(cost) => Some(cost),
+                Err(_) => {
+                    self.errors.unit_cost = Some("Cost should be a valid number".into());
+                    None
+                }
+            },
+        };
+
+        let line_data = PurchaseOrderLineForm {
+            product_id: self.product_id,
+            quantity,
+            unit_cost,
+        };
+
+        let parse_failed = self.errors.quantity.is_some() || self.errors.unit_cost.is_some();
+
+        match line_data.validate() {
+            Ok(_) if !parse_failed => Some(line_data),
+            Ok(_) => None,
+            Err(error) => {
+                let field_errors = error.field_errors();
+
+                if let Some(product_error) = field_errors.get("product_id") {
+                    self.errors.product_id = Some(product_error[0].clone().message.unwrap_or_default().to_string());
+                }
+
+                if self.errors.quantity.is_none()
+                    && let Some(quantity_error) = field_errors.get("quantity") {
+                    self.errors.quantity = Some(quantity_error[0].clone().message.unwrap_or_default().to_string());
+                }
+
+                if self.errors.unit_cost.is_none()
+                    && let Some(cost_error) = field_errors.get("unit_cost") {
+                    self.errors.unit_cost = Some(cost_error[0].clone().message.unwrap_or_default().to_string());
+                }
+
+                None
+            }
+        }
+    }
+}
@@ -0,0 +1,278 @@
+use chrono::NaiveDate;
+use egui::{ComboBox, Id, Modal, RichText, Sides};
+use validator::Validate;
+
+use crate::infra::db;
+use crate::infra::repositories::reservation_repository;
+use crate::infra::models::NewReservationRow;
+
+use crate::domain::place::{Place, place_path};
+use crate::domain::product::Product;
+use crate::domain::reservation::{NAIVE_DATE_PATTERN, Reservation};
+
+const DEFAULT_SPACING: f32 = 16.0;
+const FORM_SPACING: f32 = DEFAULT_SPACING / 2.0;
+
+#[derive(Default)]
+pub struct FormErrors {
+    pub product_id: Option<String>,
+    pub quantity: Option<String>,
+    pub reference: Option<String>,
+    pub expires_on: Option<String>,
+    pub save: Option<String>,
+}
+
+#[derive(Validate)]
+pub struct ReservationForm {
+    #[validate(required(message = "Select a product"))]
+    product_id: Option<i32>,
+
+    place_id: Option<i32>,
+
+    #[validate(range(min = 1, message = "Quantity must be greater than zero"))]
+    quantity: i32,
+
+    #[validate(length(min = 1, message = "Enter the job or customer the units are for"))]
+    reference: String,
+
+    notes: Option<String>,
+
+    expires_on: Option<NaiveDate>,
+}
+
+pub struct ReservationFormModal {
+    should_close: bool,
+
+    products: Vec<Product>,
+    places: Vec<Place>,
+
+    product_id: Option<i32>,
+    place_id: Option<i32>,
+    quantity: String,
+    reference: String,
+    notes: String,
+    expires_on: String,
+    /// Available to promise for the selected product and place.
+    available: Option<i64>,
+
+    errors: FormErrors,
+}
+
+impl ReservationFormModal {
+    pub fn new(products: &[Product], places: &[Place]) -> Self {
+        Self {
+            should_close: false,
+            products: products.to_vec(),
+            places: places.to_vec(),
+            product_id: None,
+            place_id: None,
+            quantity: String::new(),
+            reference: String::new(),
+            notes: String::new(),
+            expires_on: String::new(),
+            available: None,
+            errors: FormErrors::default(),
+        }
+    }
+
+    pub fn show(&mut self, ui: &mut egui::Ui) -> (bool, Option<Reservation>) {
+        let mut created_reservation = None;
+
+        let modal = Modal::new(Id::new("Reservation Form")).show(ui.ctx(), |ui| {
+            ui.heading("New Reservation");
+            ui.separator();
+            ui.add_space(DEFAULT_SPACING / 2.0);
+
+            let mut availability_changed = false;
+
+            ui.label("Product");
+            let product_name = self.products.iter()
+                .find(|product| Some(product.id) == self.product_id)
+                .map(|product| product.name.clone())
+                .unwrap_or_default();
+
+            ComboBox::from_id_salt("reservation_product")
+                .selected_text(product_name)
+                .show_ui(ui, |ui| {
+                    for product in &self.products {
+                        availability_changed |= ui.selectable_value(&mut self.product_id, Some(product.id), &product.name).changed();
+                    }
+                });
+            if let Some(error) = &self.errors.product_id {
+                ui.label(RichText::new(error).color(ui.visuals().error_fg_color));
+            }
+
+            ui.add_space(FORM_SPACING);
+
+            ui.label("Place");
+            let place_name = self.places.iter()
+                .find(|place| Some(place.id) == self.place_id)
+                .map(|place| place_path(&self.places, place.id))
+                .unwrap_or_else(|| "Any place".to_owned());
+
+            ComboBox::from_id_salt("reservation_place")
+                .selected_text(place_name)
+                .show_ui(ui, |ui| {
+                    availability_changed |= ui.selectable_value(&mut self.place_id, None, "Any place").changed();
+                    for place in &self.places {
+                        availability_changed |= ui.selectable_value(&mut self.place_id, Some(place.id), place_path(&self.places, place.id)).changed();
+                    }
+                });
+
+            if availability_changed {
+                self.refresh_available();
+            }
+
+            if let Some(available) = self.available {
+                ui.label(RichText::new(format!("Available to promise: {}", available.max(0))).weak());
+            }
+
+            ui.add_space(FORM_SPACING);
+
+            ui.horizontal(|ui| {
+                ui.vertical(|ui| {
+                    ui.label("Quantity");
+                    ui.add(egui::TextEdit::singleline(&mut self.quantity).desired_width(100.0));
+                });
+                ui.vertical(|ui| {
+                    ui.label("Expires On");
+                    ui.add(egui::TextEdit::singleline(&mut self.expires_on).desired_width(100.0).hint_text("Optional"));
+                });
+            });
+            if let Some(error) = &self.errors.quantity {
+                ui.label(RichText::new(error).color(ui.visuals().error_fg_color));
+            }
+            if let Some(error) = &self.errors.expires_on {
+                ui.label(RichText::new(error).color(ui.visuals().error_fg_color));
+            }
+
+            ui.add_space(FORM_SPACING);
+
+            ui.label("Reserved For");
+            ui.add(egui::TextEdit::singleline(&mut self.reference).hint_text("Job, order or customer"));
+            if let Some(error) = &self.errors.reference {
+                ui.label(RichText::new(error).color(ui.visuals().error_fg_color));
+            }
+
+            ui.add_space(FORM_SPACING);
+
+            ui.label("Notes");
+            ui.text_edit_multiline(&mut self.notes);
+
+            if let Some(error) = &self.errors.save {
+                ui.add_space(FORM_SPACING);
+                ui.label(RichText::new(error).color(ui.visuals().error_fg_color));
+            }
+
+            ui.add_space(DEFAULT_SPACING / 2.0);
+            ui.separator();
+            ui.add_space(FORM_SPACING);
+
+            Sides::new().show(
+                ui,
+                |_ui| {},
+                |ui| {
+                    if ui.button("Reserve").clicked()
+                        && let Some(reservation) = self.validate_form() {
+                        let mut connection = db::establish_connection();
+
+                        let new_reservation = NewReservationRow {
+                            product_id: reservation.product_id.unwrap_or_default(),
+                            place_id: reservation.place_id,
+                            quantity: reservation.quantity,
+                            reference: reservation.reference,
+                            notes: reservation.notes,
+                            expires_on: reservation.expires_on.map(|date| date.format(NAIVE_DATE_PATTERN).to_string()),
+                        };
+
+                        // Someone else may have promised the units since the form was opened.
+                        match reservation_repository::create_reservation(&mut connection, new_reservation) {
+                            Ok(created) => {
+                                created_reservation = Some(created);
+                                self.should_close = true;
+                            }
+                            Err(error) => {
+                                self.errors.save = Some(error.to_string());
+                                self.refresh_available();
+                            }
+                        }
+                    }
+
+                    if ui.button("Cancel").clicked() {
+                        self.should_close = true;
+                    }
+                }
+            );
+        });
+
+        if modal.should_close() {
+            self.should_close = true;
+        }
+
+        (self.should_close, created_reservation)
+    }
+
+    fn refresh_available(&mut self) {
+        self.available = self.product_id.and_then(|product_id| {
+            let mut connection = db::establish_connection();
+
+            reservation_repository::get_available_quantity(&mut connection, product_id, self.place_id).ok()
+        });
+    }
+
+    fn validate_form(&mut self) -> Option<ReservationForm> {
+        self.errors = FormErrors::default();
+
+        let quantity = match self.quantity.trim().parse::<i32>() {
+            Ok(value) => value,
+            Err(_) => {
+                self.errors.quantity = Some("Quantity should be a valid integer".into());
+                0
+            }
+        };
+
+        let expires_on = match self.expires_on.trim() {
+            "" => None,
+            value => match NaiveDate::parse_from_str(value, NAIVE_DATE_PATTERN) {
+                Ok(date) => Some(date),
+                Err(_) => {
+                    self.errors.expires_on = Some("Use the YYYY-MM-DD format".into());
+                    None
+                }
+            },
+        };
+
+        let reservation_data = ReservationForm {
+            product_id: self.product_id,
+            place_id: self.place_id,
+            quantity,
+            reference: self.reference.trim().to_owned(),
+            notes: Some(self.notes.trim().to_owned()).filter(|notes| !notes.is_empty()),
+            expires_on,
+        };
+
+        let parse_failed = self.errors.quantity.is_some() || self.errors.expires_on.is_some();
+
+        match reservation_data.validate() {
+            Ok(_) if !parse_failed => Some(reservation_data),
+            Ok(_) => None,
+            Err(error) => {
+                let field_errors = error.field_errors();
+
+                if let Some(product_error) = field_errors.get("product_id") {
+                    self.errors.product_id = Some(product_error[0].clone().message.unwrap_or_default().to_string());
+                }
+
+                if let Some(quantity_error) = field_errors.get("quantity") {
+                    self.errors.quantity.get_or_insert(quantity_error[0].clone().message.unwrap_or_default().to_string());
+                }
+
+                if let Some(reference_error) = field_errors.get("reference") {
+                    self.errors.reference = Some(reference_error[0].clone().message.unwrap_or_default().to_string());
+                }
+
+                None
+            }
+        }
+    }
+}
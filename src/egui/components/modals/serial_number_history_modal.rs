@@ -0,0 +1,109 @@
+use egui::{Id, Modal, Sides};
+use egui_extras::{Column, TableBuilder};
+
+use crate::infra::db;
+use crate::infra::repositories::{place_repository, serial_number_repository, supplier_repository};
+
+use crate::domain::inventory_transaction::InventoryTransaction;
+use crate::domain::place::{Place, place_path};
+use crate::domain::serial_number::SerialNumber;
+use crate::domain::supplier::Supplier;
+
+const DEFAULT_SPACING: f32 = 16.0;
+const ITEM_HEIGHT: f32 = 24.0;
+const DATE_TIME_DISPLAY_PATTERN: &str = "%Y-%m-%d %H:%M";
+
+pub struct SerialNumberHistoryModal {
+    should_close: bool,
+
+    serial_number: SerialNumber,
+    product_name: String,
+    places: Vec<Place>,
+    suppliers: Vec<Supplier>,
+    movements: Vec<InventoryTransaction>,
+}
+
+impl SerialNumberHistoryModal {
+    pub fn new(serial_number: &SerialNumber, product_name: &str) -> Self {
+        let mut connection = db::establish_connection();
+
+        Self {
+            should_close: false,
+            serial_number: serial_number.clone(),
+            product_name: product_name.to_owned(),
+            places: place_repository::list_places(&mut connection, "").unwrap_or_default(),
+            suppliers: supplier_repository::list_suppliers(&mut connection, "").unwrap_or_default(),
+            movements: serial_number_repository::list_serial_number_history(&mut connection, serial_number.id).unwrap_or_default(),
+        }
+    }
+
+    pub fn show(&mut self, ui: &mut egui::Ui) -> bool {
+        let modal = Modal::new(Id::new("Serial Number History")).show(ui.ctx(), |ui| {
+            ui.heading(format!("{} — {}", self.serial_number.serial_number, self.product_name));
+            ui.separator();
+            ui.add_space(DEFAULT_SPACING / 2.0);
+
+            if self.movements.is_empty() {
+                ui.label("This serial number has no movements.");
+            } else {
+                TableBuilder::new(ui)
+                    .striped(true)
+                    .resizable(false)
+                    .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+                    .column(Column::exact(130.0))
+                    .column(Column::exact(100.0))
+                    .column(Column::auto().at_least(100.0).at_most(200.0).clip(true))
+                    .column(Column::auto().at_least(100.0).at_most(200.0).clip(true))
+                    .column(Column::remainder().at_least(120.0).clip(true))
+                    .max_scroll_height(400.0)
+                    .header(ITEM_HEIGHT, |mut header| {
+                        header.col(|ui| { ui.heading("Date"); });
+                        header.col(|ui| { ui.heading("Type"); });
+                        header.col(|ui| { ui.heading("Place"); });
+                        header.col(|ui| { ui.heading("Supplier"); });
+                        header.col(|ui| { ui.heading("Reason"); });
+                    })
+                    .body(|body| {
+                        body.rows(ITEM_HEIGHT, self.movements.len(), |mut row| {
+                            let movement = &self.movements[row.index()];
+
+                            let place_name = self.places.iter()
+                                .find(|place| place.id == movement.place_id)
+                                .map(|place| place_path(&self.places, place.id))
+                                .unwrap_or_else(|| format!("#{}", movement.place_id));
+
+                            let supplier_name = movement.supplier_id
+                                .and_then(|supplier_id| self.suppliers.iter().find(|supplier| supplier.id == supplier_id))
+                                .map(|supplier| supplier.name.clone())
+                                .unwrap_or_default();
+
+                            row.col(|ui| { ui.label(movement.created_at.format(DATE_TIME_DISPLAY_PATTERN).to_string()); });
+                            row.col(|ui| { ui.label(movement.transaction_type.label()); });
+                            row.col(|ui| { ui.label(place_name); });
+                            row.col(|ui| { ui.label(supplier_name); });
+                            row.col(|ui| { ui.label(movement.reason.clone().unwrap_or_default()); });
+                        });
+                    });
+            }
+
+            ui.add_space(DEFAULT_SPACING / 2.0);
+            ui.separator();
+
+            Sides::new().show(
+                ui,
+                |_ui| {},
+                |ui| {
+                    if ui.button("Close").clicked() {
+                        self.should_close = true;
+                    }
+                }
+            );
+        });
+
+        if modal.should_close() {
+            self.should_close = true;
+        }
+
+        self.should_close
+    }
+}
@@ -0,0 +1,365 @@
+use egui::{ComboBox, Id, Modal, RichText, Sides};
+use validator::Validate;
+
+use crate::infra::db;
+use crate::infra::repositories::{serial_number_repository, stock_repository, stock_transfer_repository};
+use crate::infra::repositories::serial_number_repository::SerialNumberFilter;
+use crate::infra::repositories::stock_repository::LotBalanceFilter;
+use crate::infra::repositories::stock_transfer_repository::NewStockTransfer;
+
+use crate::domain::place::{Place, place_path};
+use crate::domain::product::Product;
+use crate::domain::serial_number::{parse_serial_numbers, SerialNumber};
+use crate::domain::stock_balance::LotBalance;
+use crate::egui::widgets::lot_input::lot_input;
+use crate::egui::widgets::serial_numbers_input::serial_numbers_input;
+
+const DEFAULT_SPACING: f32 = 16.0;
+const FORM_SPACING: f32 = DEFAULT_SPACING / 2.0;
+
+#[derive(Default)]
+pub struct FormErrors {
+    pub product_id: Option<String>,
+    pub from_place_id: Option<String>,
+    pub to_place_id: Option<String>,
+    pub quantity: Option<String>,
+    pub lot_number: Option<String>,
+    pub serial_numbers: Option<String>,
+    pub save: Option<String>,
+}
+
+#[derive(Validate)]
+pub struct StockTransferForm {
+    #[validate(required(message = "Select a product"))]
+    product_id: Option<i32>,
+
+    #[validate(required(message = "Select the source place"))]
+    from_place_id: Option<i32>,
+
+    #[validate(required(message = "Select the destination place"))]
+    to_place_id: Option<i32>,
+
+    #[validate(range(min = 1, message = "Quantity must be greater than zero"))]
+    quantity: i32,
+
+    reason: String,
+}
+
+pub struct StockTransferFormModal {
+    should_close: bool,
+
+    products: Vec<Product>,
+    places: Vec<Place>,
+
+    product_id: Option<i32>,
+    from_place_id: Option<i32>,
+    to_place_id: Option<i32>,
+    quantity: String,
+    lot_number: String,
+    reason: String,
+    available: Option<i64>,
+    lots: Vec<LotBalance>,
+    serial_numbers: String,
+    serials_in_stock: Vec<SerialNumber>,
+
+    errors: FormErrors,
+}
+
+impl StockTransferFormModal {
+    pub fn new(products: &[Product], places: &[Place]) -> Self {
+        Self {
+            should_close: false,
+            products: products.to_vec(),
+            places: places.to_vec(),
+            product_id: None,
+            from_place_id: None,
+            to_place_id: None,
+            quantity: String::new(),
+            lot_number: String::new(),
+            reason: String::new(),
+            available: None,
+            lots: Vec::new(),
+            serial_numbers: String::new(),
+            serials_in_stock: Vec::new(),
+            errors: FormErrors::default(),
+        }
+    }
+
+    pub fn show(&mut self, ui: &mut egui::Ui) -> (bool, bool) {
+        let mut transferred = false;
+
+        let modal = Modal::new(Id::new("New Stock Transfer")).show(ui.ctx(), |ui| {
+            ui.heading("Transfer Stock");
+            ui.separator();
+            ui.add_space(DEFAULT_SPACING / 2.0);
+
+            let mut source_changed = false;
+
+            ui.label("Product");
+            let product_name = self.products.iter()
+                .find(|product| Some(product.id) == self.product_id)
+                .map(|product| product.name.clone())
+                .unwrap_or_default();
+
+            ComboBox::from_id_salt("transfer_product")
+                .selected_text(product_name)
+                .show_ui(ui, |ui| {
+                    for product in &self.products {
+                        source_changed |= ui.selectable_value(&mut self.product_id, Some(product.id), &product.name).changed();
+                    }
+                });
+            if let Some(error) = &self.errors.product_id {
+                ui.label(RichText::new(error).color(ui.visuals().error_fg_color));
+            }
+
+            ui.add_space(FORM_SPACING);
+
+            ui.label("From");
+            source_changed |= Self::place_combo(ui, "transfer_from_place", &self.places, &mut self.from_place_id);
+            if let Some(error) = &self.errors.from_place_id {
+                ui.label(RichText::new(error).color(ui.visuals().error_fg_color));
+            }
+
+            if self.tracks_lots() {
+                ui.add_space(FORM_SPACING);
+
+                ui.label("Lot");
+                source_changed |= lot_input(ui, "transfer_lot", &mut self.lot_number, &self.lots);
+                if let Some(error) = &self.errors.lot_number {
+                    ui.label(RichText::new(error).color(ui.visuals().error_fg_color));
+                }
+            }
+
+            if source_changed {
+                self.refresh_available();
+            }
+
+            if let Some(available) = self.available {
+                ui.label(format!("Available: {}", available));
+            }
+
+            ui.add_space(FORM_SPACING);
+
+            ui.label("To");
+            Self::place_combo(ui, "transfer_to_place", &self.places, &mut self.to_place_id);
+            if let Some(error) = &self.errors.to_place_id {
+                ui.label(RichText::new(error).color(ui.visuals().error_fg_color));
+            }
+
+            ui.add_space(FORM_SPACING);
+
+            ui.label("Quantity");
+            ui.text_edit_singleline(&mut self.quantity);
+            if let Some(error) = &self.errors.quantity {
+                ui.label(RichText::new(error).color(ui.visuals().error_fg_color));
+            }
+
+            if self.is_serialized() {
+                ui.add_space(FORM_SPACING);
+
+                ui.label("Serial Numbers");
+                serial_numbers_input(ui, "transfer_serial_numbers", &mut self.serial_numbers, &self.serials_in_stock);
+                if let Some(error) = &self.errors.serial_numbers {
+                    ui.label(RichText::new(error).color(ui.visuals().error_fg_color));
+                }
+            }
+
+            ui.add_space(FORM_SPACING);
+
+            ui.label("Reason");
+            ui.text_edit_multiline(&mut self.reason);
+
+            if let Some(error) = &self.errors.save {
+                ui.add_space(FORM_SPACING);
+                ui.label(RichText::new(error).color(ui.visuals().error_fg_color));
+            }
+
+            ui.add_space(DEFAULT_SPACING / 2.0);
+            ui.separator();
+            ui.add_space(FORM_SPACING);
+
+            Sides::new().show(
+                ui,
+                |_ui| {},
+                |ui| {
+                    if ui.button("Transfer").clicked()
+                        && let Some(transfer) = self.validate_form() {
+                        let mut connection = db::establish_connection();
+
+                        let new_transfer = NewStockTransfer {
+                            product_id: transfer.product_id.unwrap_or_default(),
+                            from_place_id: transfer.from_place_id.unwrap_or_default(),
+                            to_place_id: transfer.to_place_id.unwrap_or_default(),
+                            quantity: transfer.quantity,
+                            lot_number: Some(self.lot_number.trim().to_owned()).filter(|lot_number| !lot_number.is_empty()),
+                            serial_numbers: if self.is_serialized() { parse_serial_numbers(&self.serial_numbers) } else { Vec::new() },
+                            reason: Some(transfer.reason).filter(|reason| !reason.is_empty()),
+                        };
+
+                        match stock_transfer_repository::transfer_stock(&mut connection, new_transfer) {
+                            Ok(_) => {
+                                transferred = true;
+                                self.should_close = true;
+                            }
+                            Err(error) => {
+                                self.errors.save = Some(error.to_string());
+                            }
+                        }
+                    }
+
+                    if ui.button("Cancel").clicked() {
+                        self.should_close = true;
+                    }
+                }
+            );
+        });
+
+        if modal.should_close() {
+            self.should_close = true;
+        }
+
+        (self.should_close, transferred)
+    }
+
+    fn place_combo(ui: &mut egui::Ui, id_salt: &str, places: &[Place], selected: &mut Option<i32>) -> bool {
+        let mut changed = false;
+
+        let place_name = places.iter()
+            .find(|place| Some(place.id) == *selected)
+            .map(|place| place_path(places, place.id))
+            .unwrap_or_default();
+
+        ComboBox::from_id_salt(id_salt)
+            .selected_text(place_name)
+            .show_ui(ui, |ui| {
+                for place in places {
+                    changed |= ui.selectable_value(selected, Some(place.id), place_path(places, place.id)).changed();
+                }
+            });
+
+        changed
+    }
+
+    fn tracks_lots(&self) -> bool {
+        self.products.iter()
+            .any(|product| Some(product.id) == self.product_id && product.tracks_lots)
+    }
+
+    fn is_serialized(&self) -> bool {
+        self.products.iter()
+            .any(|product| Some(product.id) == self.product_id && product.serialized)
+    }
+
+    /// Available quantity at the source, narrowed to the typed lot for products
+    /// tracked by lot.
+    fn refresh_available(&mut self) {
+        let (Some(product_id), Some(place_id)) = (self.product_id, self.from_place_id) else {
+            self.available = None;
+            self.lots.clear();
+            self.serials_in_stock.clear();
+            return;
+        };
+
+        let mut connection = db::establish_connection();
+
+        self.serials_in_stock = if self.is_serialized() {
+            let filter = SerialNumberFilter {
+                product_id: Some(product_id),
+                place_id: Some(place_id),
+                ..Default::default()
+            };
+
+            serial_number_repository::list_serial_numbers(&mut connection, &filter).unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+
+        if !self.tracks_lots() {
+            self.available = stock_repository::get_stock_balance(&mut connection, product_id, place_id).ok();
+            return;
+        }
+
+        let filter = LotBalanceFilter {
+            product_id: Some(product_id),
+            place_id: Some(place_id),
+            ..Default::default()
+        };
+
+        self.lots = stock_repository::list_lot_balances(&mut connection, &filter).unwrap_or_default();
+
+        let lot_number = self.lot_number.trim();
+
+        self.available = if lot_number.is_empty() {
+            None
+        } else {
+            stock_repository::get_lot_balance(&mut connection, product_id, place_id, lot_number).ok()
+        };
+    }
+
+    fn validate_form(&mut self) -> Option<StockTransferForm> {
+        self.errors = FormErrors::default();
+
+        let quantity = match self.quantity.trim().parse::<i32>() {
+            Ok(value) => value,
+            Err(_) => {
+                self.errors.quantity = Some("Quantity should be a valid integer".into());
+                0
+            }
+        };
+
+        let transfer_data = StockTransferForm {
+            product_id: self.product_id,
+            from_place_id: self.from_place_id,
+            to_place_id: self.to_place_id,
+            quantity,
+            reason: self.reason.trim().to_owned(),
+        };
+
+        if self.tracks_lots() && self.lot_number.trim().is_empty() {
+            self.errors.lot_number = Some("Pick the lot to transfer".into());
+        }
+
+        let serial_count = parse_serial_numbers(&self.serial_numbers).len();
+
+        if self.is_serialized() && self.errors.quantity.is_none() && serial_count != quantity.unsigned_abs() as usize {
+            self.errors.serial_numbers = Some(format!("Enter one serial number per unit: {} expected, {} entered", quantity, serial_count));
+        }
+
+        if transfer_data.from_place_id.is_some() && transfer_data.from_place_id == transfer_data.to_place_id {
+            self.errors.to_place_id = Some("Destination must differ from the source".into());
+        }
+
+        if let Some(available) = self.available
+            && self.errors.quantity.is_none()
+            && (quantity as i64) > available {
+            self.errors.quantity = Some(format!("Only {} available at the source place", available));
+        }
+
+        match transfer_data.validate() {
+            Ok(_) if self.errors.quantity.is_none() && self.errors.to_place_id.is_none() && self.errors.lot_number.is_none() && self.errors.serial_numbers.is_none() => Some(transfer_data),
+            Ok(_) => None,
+            Err(error) => {
+                let field_errors = error.field_errors();
+
+                if let Some(product_error) = field_errors.get("product_id") {
+                    self.errors.product_id = Some(product_error[0].clone().message.unwrap_or_default().to_string());
+                }
+
+                if let Some(from_error) = field_errors.get("from_place_id") {
+                    self.errors.from_place_id = Some(from_error[0].clone().message.unwrap_or_default().to_string());
+                }
+
+                if let Some(to_error) = field_errors.get("to_place_id") {
+                    self.errors.to_place_id = Some(to_error[0].clone().message.unwrap_or_default().to_string());
+                }
+
+                if self.errors.quantity.is_none()
+                    && let Some(quantity_error) = field_errors.get("quantity") {
+                    self.errors.quantity = Some(quantity_error[0].clone().message.unwrap_or_default().to_string());
+                }
+
+                None
+            }
+        }
+    }
+}
@@ -0,0 +1,116 @@
+use egui::{ComboBox, Id, Modal, RichText, Sides};
+
+use crate::infra::db;
+use crate::infra::repositories::stocktake_repository;
+use crate::infra::models::NewStocktakeRow;
+
+use crate::domain::place::{Place, place_path};
+use crate::domain::stocktake::Stocktake;
+
+const DEFAULT_SPACING: f32 = 16.0;
+const FORM_SPACING: f32 = DEFAULT_SPACING / 2.0;
+
+#[derive(Default)]
+pub struct FormErrors {
+    pub save: Option<String>,
+}
+
+pub struct StocktakeFormModal {
+    should_close: bool,
+
+    places: Vec<Place>,
+
+    place_id: Option<i32>,
+    notes: String,
+
+    errors: FormErrors,
+}
+
+impl StocktakeFormModal {
+    pub fn new(places: &[Place]) -> Self {
+        Self {
+            should_close: false,
+            places: places.to_vec(),
+            place_id: None,
+            notes: String::new(),
+            errors: FormErrors::default(),
+        }
+    }
+
+    pub fn show(&mut self, ui: &mut egui::Ui) -> (bool, Option<Stocktake>) {
+        let mut created_stocktake = None;
+
+        let modal = Modal::new(Id::new("Stocktake Form")).show(ui.ctx(), |ui| {
+            ui.heading("New Stocktake");
+            ui.separator();
+            ui.add_space(DEFAULT_SPACING / 2.0);
+
+            ui.label("Place");
+            let place_name = self.places.iter()
+                .find(|place| Some(place.id) == self.place_id)
+                .map(|place| place_path(&self.places, place.id))
+                .unwrap_or_else(|| "All places".to_owned());
+
+            ComboBox::from_id_salt("stocktake_place")
+                .selected_text(place_name)
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut self.place_id, None, "All places");
+                    for place in &self.places {
+                        ui.selectable_value(&mut self.place_id, Some(place.id), place_path(&self.places, place.id));
+                    }
+                });
+
+            ui.add_space(FORM_SPACING);
+
+            ui.label("Notes");
+            ui.text_edit_multiline(&mut self.notes);
+
+            ui.add_space(FORM_SPACING);
+            ui.label(RichText::new("The expected quantities are frozen when the stocktake starts.").weak());
+
+            if let Some(error) = &self.errors.save {
+                ui.add_space(FORM_SPACING);
+                ui.label(RichText::new(error).color(ui.visuals().error_fg_color));
+            }
+
+            ui.add_space(DEFAULT_SPACING / 2.0);
+            ui.separator();
+            ui.add_space(FORM_SPACING);
+
+            Sides::new().show(
+                ui,
+                |_ui| {},
+                |ui| {
+                    if ui.button("Start").clicked() {
+                        let mut connection = db::establish_connection();
+
+                        let new_stocktake = NewStocktakeRow {
+                            place_id: self.place_id,
+                            notes: Some(self.notes.trim().to_owned()).filter(|notes| !notes.is_empty()),
+                        };
+
+                        match stocktake_repository::create_stocktake(&mut connection, new_stocktake) {
+                            Ok(stocktake) => {
+                                created_stocktake = Some(stocktake);
+                                self.should_close = true;
+                            }
+                            Err(error) => {
+                                self.errors.save = Some(error.to_string());
+                            }
+                        }
+                    }
+
+                    if ui.button("Cancel").clicked() {
+                        self.should_close = true;
+                    }
+                }
+            );
+        });
+
+        if modal.should_close() {
+            self.should_close = true;
+        }
+
+        (self.should_close, created_stocktake)
+    }
+}
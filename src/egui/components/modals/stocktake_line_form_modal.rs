@@ -0,0 +1,225 @@
+use egui::{ComboBox, Id, Modal, RichText, Sides};
+use validator::Validate;
+
+use crate::infra::db;
+use crate::infra::repositories::stocktake_repository;
+use crate::infra::models::NewStocktakeLineRow;
+
+use crate::domain::place::{Place, descendant_place_ids, place_path};
+use crate::domain::product::Product;
+use crate::domain::stocktake::{Stocktake, StocktakeLine};
+
+const DEFAULT_SPACING: f32 = 16.0;
+const FORM_SPACING: f32 = DEFAULT_SPACING / 2.0;
+
+#[derive(Default)]
+pub struct FormErrors {
+    pub product_id: Option<String>,
+    pub place_id: Option<String>,
+    pub counted_quantity: Option<String>,
+    pub save: Option<String>,
+}
+
+#[derive(Validate)]
+pub struct StocktakeLineForm {
+    #[validate(required(message = "Select a product"))]
+    product_id: Option<i32>,
+
+    #[validate(required(message = "Select a place"))]
+    place_id: Option<i32>,
+
+    lot_number: String,
+
+    counted_quantity: i32,
+}
+
+/// Adds a product found during the count that is not on the stocktake yet.
+pub struct StocktakeLineFormModal {
+    should_close: bool,
+
+    stocktake_id: i32,
+    products: Vec<Product>,
+    places: Vec<Place>,
+
+    product_id: Option<i32>,
+    place_id: Option<i32>,
+    lot_number: String,
+    counted_quantity: String,
+
+    errors: FormErrors,
+}
+
+impl StocktakeLineFormModal {
+    pub fn new(stocktake: &Stocktake, products: &[Product], places: &[Place]) -> Self {
+        Self {
+            should_close: false,
+            stocktake_id: stocktake.id,
+            products: products.to_vec(),
+            // Single-place sessions only take lines for their place and the places inside it.
+            places: match stocktake.place_id {
+                Some(place_id) => {
+                    let place_ids = descendant_place_ids(places, place_id);
+                    places.iter().filter(|place| place_ids.contains(&place.id)).cloned().collect()
+                }
+                None => places.to_vec(),
+            },
+            product_id: None,
+            place_id: stocktake.place_id,
+            lot_number: String::new(),
+            counted_quantity: String::new(),
+            errors: FormErrors::default(),
+        }
+    }
+
+    pub fn show(&mut self, ui: &mut egui::Ui) -> (bool, Option<StocktakeLine>) {
+        let mut created_line = None;
+
+        let modal = Modal::new(Id::new("Stocktake Line Form")).show(ui.ctx(), |ui| {
+            ui.heading("Add Counted Product");
+            ui.separator();
+            ui.add_space(DEFAULT_SPACING / 2.0);
+
+            ui.label("Product");
+            let product_name = self.products.iter()
+                .find(|product| Some(product.id) == self.product_id)
+                .map(|product| product.name.clone())
+                .unwrap_or_default();
+
+            ComboBox::from_id_salt("stocktake_line_product")
+                .selected_text(product_name)
+                .show_ui(ui, |ui| {
+                    for product in &self.products {
+                        ui.selectable_value(&mut self.product_id, Some(product.id), &product.name);
+                    }
+                });
+            if let Some(error) = &self.errors.product_id {
+                ui.label(RichText::new(error).color(ui.visuals().error_fg_color));
+            }
+
+            ui.add_space(FORM_SPACING);
+
+            ui.label("Place");
+            let place_name = self.places.iter()
+                .find(|place| Some(place.id) == self.place_id)
+                .map(|place| place_path(&self.places, place.id))
+                .unwrap_or_default();
+
+            ComboBox::from_id_salt("stocktake_line_place")
+                .selected_text(place_name)
+                .show_ui(ui, |ui| {
+                    for place in &self.places {
+                        ui.selectable_value(&mut self.place_id, Some(place.id), place_path(&self.places, place.id));
+                    }
+                });
+            if let Some(error) = &self.errors.place_id {
+                ui.label(RichText::new(error).color(ui.visuals().error_fg_color));
+            }
+
+            if self.tracks_lots() {
+                ui.add_space(FORM_SPACING);
+
+                ui.label("Lot");
+                ui.add(egui::TextEdit::singleline(&mut self.lot_number).desired_width(140.0).hint_text("Lot number"));
+            }
+
+            ui.add_space(FORM_SPACING);
+
+            ui.label("Counted Quantity");
+            ui.add(egui::TextEdit::singleline(&mut self.counted_quantity).desired_width(120.0));
+            if let Some(error) = &self.errors.counted_quantity {
+                ui.label(RichText::new(error).color(ui.visuals().error_fg_color));
+            }
+
+            if let Some(error) = &self.errors.save {
+                ui.add_space(FORM_SPACING);
+                ui.label(RichText::new(error).color(ui.visuals().error_fg_color));
+            }
+
+            ui.add_space(DEFAULT_SPACING / 2.0);
+            ui.separator();
+            ui.add_space(FORM_SPACING);
+
+            Sides::new().show(
+                ui,
+                |_ui| {},
+                |ui| {
+                    if ui.button("Save").clicked()
+                        && let Some(line) = self.validate_form() {
+                        let mut connection = db::establish_connection();
+
+                        let new_line = NewStocktakeLineRow {
+                            stocktake_id: self.stocktake_id,
+                            product_id: line.product_id.unwrap_or_default(),
+                            place_id: line.place_id.unwrap_or_default(),
+                            lot_number: Some(line.lot_number).filter(|lot_number| !lot_number.is_empty()),
+                            expected_quantity: 0,
+                            counted_quantity: Some(line.counted_quantity),
+                        };
+
+                        match stocktake_repository::create_stocktake_line(&mut connection, new_line) {
+                            Ok(line) => {
+                                created_line = Some(line);
+                                self.should_close = true;
+                            }
+                            Err(error) => {
+                                self.errors.save = Some(error.to_string());
+                            }
+                        }
+                    }
+
+                    if ui.button("Cancel").clicked() {
+                        self.should_close = true;
+                    }
+                }
+            );
+        });
+
+        if modal.should_close() {
+            self.should_close = true;
+        }
+
+        (self.should_close, created_line)
+    }
+
+    fn tracks_lots(&self) -> bool {
+        self.products.iter()
+            .any(|product| Some(product.id) == self.product_id && product.tracks_lots)
+    }
+
+    fn validate_form(&mut self) -> Option<StocktakeLineForm> {
+        self.errors = FormErrors::default();
+
+        let counted_quantity = match self.counted_quantity.trim().parse::<i32>() {
+            Ok(value) if value >= 0 => value,
+            _ => {
+                self.errors.counted_quantity = Some("Counted quantity should be a whole number, zero or more".into());
+                0
+            }
+        };
+
+        let line_data = StocktakeLineForm {
+            product_id: self.product_id,
+            place_id: self.place_id,
+            lot_number: if self.tracks_lots() { self.lot_number.trim().to_owned() } else { String::new() },
+            counted_quantity,
+        };
+
+        match line_data.validate() {
+            Ok(_) if self.errors.counted_quantity.is_none() => Some(line_data),
+            Ok(_) => None,
+            Err(error) => {
+                let field_errors = error.field_errors();
+
+                if let Some(product_error) = field_errors.get("product_id") {
+                    self.errors.product_id = Some(product_error[0].clone().message.unwrap_or_default().to_string());
+                }
+
+                if let Some(place_error) = field_errors.get("place_id") {
+                    self.errors.place_id = Some(place_error[0].clone().message.unwrap_or_default().to_string());
+                }
+
+                None
+            }
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use egui::{Id, Modal, Sides};
+
+use crate::domain::supplier::Supplier;
+use crate::egui::components::modals::product_supplier_form_modal::LinkOwner;
+use crate::egui::widgets::product_supplier_links::ProductSupplierLinks;
+
+const DEFAULT_SPACING: f32 = 16.0;
+
+pub struct SupplierDetailModal {
+    should_close: bool,
+
+    supplier: Supplier,
+    product_links: ProductSupplierLinks,
+}
+
+impl SupplierDetailModal {
+    pub fn new(supplier: &Supplier) -> Self {
+        Self {
+            should_close: false,
+            supplier: supplier.clone(),
+            product_links: ProductSupplierLinks::new(LinkOwner::Supplier(supplier.id)),
+        }
+    }
+
+    pub fn show(&mut self, ui: &mut egui::Ui) -> bool {
+        let modal = Modal::new(Id::new("Supplier Details")).show(ui.ctx(), |ui| {
+            ui.heading(&self.supplier.name);
+            ui.separator();
+            ui.add_space(DEFAULT_SPACING / 2.0);
+
+            self.details_grid(ui);
+
+            ui.add_space(DEFAULT_SPACING / 2.0);
+
+            ui.label(egui::RichText::new("Products").strong());
+            ui.add_space(DEFAULT_SPACING / 4.0);
+            self.product_links.ui(ui);
+
+            ui.add_space(DEFAULT_SPACING / 2.0);
+            ui.separator();
+
+            Sides::new().show(
+                ui,
+                |_ui| {},
+                |ui| {
+                    if ui.button("Close").clicked() {
+                        self.should_close = true;
+                    }
+                }
+            );
+        });
+
+        if modal.should_close() {
+            self.should_close = true;
+        }
+
+        self.should_close
+    }
+
+    fn details_grid(&self, ui: &mut egui::Ui) {
+        let supplier = &self.supplier;
+
+        let details = [
+            ("Tax ID", supplier.formatted_tax_id()),
+            ("Contact", supplier.contact_name.clone()),
+            ("Phone", supplier.phone.clone()),
+            ("Mobile", supplier.mobile_phone.clone()),
+            ("Email", supplier.email.clone()),
+            ("Address", supplier.address.clone()),
+            ("Payment terms", supplier.payment_terms.clone()),
+            ("Notes", supplier.notes.clone()),
+            ("Status", Some((if supplier.active { "Active" } else { "Inactive" }).to_owned())),
+        ];
+
+        egui::Grid::new("supplier_details").num_columns(2).show(ui, |ui| {
+            for (label, value) in details {
+                let Some(value) = value else {
+                    continue;
+                };
+
+                ui.label(egui::RichText::new(label).weak());
+                ui.label(value);
+                ui.end_row();
+            }
+        });
+    }
+}
@@ -6,6 +6,7 @@ use crate::infra::repositories::supplier_repository;
 use crate::infra::models::{NewSupplierRow, EditSupplierRow};
 
 use crate::domain::supplier::Supplier;
+use crate::domain::tax_id::{format_tax_id, normalize_tax_id, TaxIdKind};
 
 const DEFAULT_SPACING: f32 = 16.0;
 const FORM_SPACING: f32 = DEFAULT_SPACING / 2.0;
@@ -13,12 +14,35 @@ const FORM_SPACING: f32 = DEFAULT_SPACING / 2.0;
 #[derive(Default)]
 pub struct FormErrors {
     pub name: Option<String>,
+    pub tax_id: Option<String>,
+    pub phones: Option<String>,
+    pub email: Option<String>,
+    pub save: Option<String>,
 }
 
 #[derive(Validate)]
 pub struct SupplierForm {
     #[validate(length(min = 2, message = "Name must contain at least two characters"))]
     name: String,
+
+    tax_id: Option<String>,
+
+    contact_name: Option<String>,
+
+    phone: Option<String>,
+
+    mobile_phone: Option<String>,
+
+    #[validate(email(message = "Enter a valid email address"))]
+    email: Option<String>,
+
+    address: Option<String>,
+
+    payment_terms: Option<String>,
+
+    notes: Option<String>,
+
+    active: bool,
 }
 
 pub struct SupplierFormModal {
@@ -26,6 +50,18 @@ pub struct SupplierFormModal {
 
     id: Option<i32>,
     name: String,
+    tax_id: String,
+    contact_name: String,
+    phone: String,
+    mobile_phone: String,
+    email: String,
+    address: String,
+    payment_terms: String,
+    notes: String,
+    active: bool,
+
+    /// A deleted supplier with the name being created, offered for restoring.
+    deleted_match: Option<Supplier>,
 
     errors: FormErrors,
 }
@@ -41,13 +77,32 @@ impl SupplierFormModal {
                 errors,
                 id: Some(supplier.id),
                 name: supplier.name.clone(),
-                
+                tax_id: supplier.formatted_tax_id().unwrap_or_default(),
+                contact_name: supplier.contact_name.clone().unwrap_or_default(),
+                phone: supplier.phone.clone().unwrap_or_default(),
+                mobile_phone: supplier.mobile_phone.clone().unwrap_or_default(),
+                email: supplier.email.clone().unwrap_or_default(),
+                address: supplier.address.clone().unwrap_or_default(),
+                payment_terms: supplier.payment_terms.clone().unwrap_or_default(),
+                notes: supplier.notes.clone().unwrap_or_default(),
+                active: supplier.active,
+                deleted_match: None,
             },
             None => Self {
                 should_close,
                 errors,
                 id: None,
                 name: String::new(),
+                tax_id: String::new(),
+                contact_name: String::new(),
+                phone: String::new(),
+                mobile_phone: String::new(),
+                email: String::new(),
+                address: String::new(),
+                payment_terms: String::new(),
+                notes: String::new(),
+                active: true,
+                deleted_match: None,
             }
         }
     }
@@ -61,13 +116,99 @@ impl SupplierFormModal {
             ui.add_space(DEFAULT_SPACING / 2.0);
 
             ui.label("Name: ");
-            ui.text_edit_singleline(&mut self.name);
+            if ui.text_edit_singleline(&mut self.name).changed() {
+                self.deleted_match = None;
+            }
             if let Some(error) = &self.errors.name {
                 ui.label(
                     RichText::new(error).color(ui.visuals().error_fg_color)
                 );
             }
 
+            ui.add_space(FORM_SPACING);
+
+            ui.horizontal(|ui| {
+                ui.vertical(|ui| {
+                    ui.label("Tax ID (CNPJ/CPF)");
+                    ui.add(egui::TextEdit::singleline(&mut self.tax_id).desired_width(180.0));
+                });
+                ui.vertical(|ui| {
+                    ui.label("Contact");
+                    ui.add(egui::TextEdit::singleline(&mut self.contact_name).desired_width(180.0));
+                });
+            });
+            let tax_id = normalize_tax_id(&self.tax_id);
+            if let Ok(kind) = TaxIdKind::detect(&tax_id) {
+                ui.label(RichText::new(format!("{} {}", kind.label(), format_tax_id(&tax_id))).weak());
+            }
+            if let Some(error) = &self.errors.tax_id {
+                ui.label(
+                    RichText::new(error).color(ui.visuals().error_fg_color)
+                );
+            }
+
+            ui.add_space(FORM_SPACING);
+
+            ui.horizontal(|ui| {
+                ui.vertical(|ui| {
+                    ui.label("Phone");
+                    ui.add(egui::TextEdit::singleline(&mut self.phone).desired_width(180.0));
+                });
+                ui.vertical(|ui| {
+                    ui.label("Mobile");
+                    ui.add(egui::TextEdit::singleline(&mut self.mobile_phone).desired_width(180.0));
+                });
+            });
+            if let Some(error) = &self.errors.phones {
+                ui.label(
+                    RichText::new(error).color(ui.visuals().error_fg_color)
+                );
+            }
+
+            ui.add_space(FORM_SPACING);
+
+            ui.label("Email");
+            ui.text_edit_singleline(&mut self.email);
+            if let Some(error) = &self.errors.email {
+                ui.label(
+                    RichText::new(error).color(ui.visuals().error_fg_color)
+                );
+            }
+
+            ui.add_space(FORM_SPACING);
+
+            ui.label("Address");
+            ui.add(egui::TextEdit::multiline(&mut self.address).desired_rows(2));
+
+            ui.add_space(FORM_SPACING);
+
+            ui.label("Payment Terms");
+            ui.add(egui::TextEdit::singleline(&mut self.payment_terms).hint_text("e.g. 30/60/90 days"));
+
+            ui.add_space(FORM_SPACING);
+
+            ui.label("Notes");
+            ui.text_edit_multiline(&mut self.notes);
+
+            ui.add_space(FORM_SPACING);
+
+            ui.checkbox(&mut self.active, "Active (offered on new purchase orders and movements)");
+
+            if let Some(error) = &self.errors.save {
+                ui.add_space(FORM_SPACING);
+                ui.label(
+                    RichText::new(error).color(ui.visuals().error_fg_color)
+                );
+            }
+
+            if let Some(deleted) = &self.deleted_match {
+                ui.add_space(FORM_SPACING);
+                ui.label(
+                    RichText::new(format!("A deleted supplier named '{}' exists. Restore it as it was, or create a new one?", deleted.name))
+                        .color(ui.visuals().warn_fg_color)
+                );
+            }
+
             ui.add_space(DEFAULT_SPACING / 2.0);
             ui.separator();
             ui.add_space(FORM_SPACING);
@@ -77,33 +218,35 @@ impl SupplierFormModal {
                 ui,
                 |_ui| {},
                 |ui| {
-                    if ui.button("Save").clicked() {
-                        if let Some(supplier) = self.validate_form() {
+                    if let Some(deleted_id) = self.deleted_match.as_ref().map(|deleted| deleted.id) {
+                        if ui.button("Restore").clicked() {
                             let mut connection = db::establish_connection();
-                                
-                            match self.id {
-                                Some(id,) => {
-                                    let supplier = EditSupplierRow {
-                                        id,
-                                        name: supplier.name,
-                                    };
-
-                                    if let Ok(updated) = supplier_repository::edit_supplier(&mut connection, supplier) {
-                                        created_supplier = Some(updated);
-                                    }
-                                }
-                                None => {
-                                    let new_supplier = NewSupplierRow {
-                                        name: supplier.name,
-                                    };
-
-                                    if let Ok(created) = supplier_repository::create_supplier(&mut connection, new_supplier) {
-                                        created_supplier = Some(created);
-                                    }
+
+                            match supplier_repository::restore_supplier(&mut connection, deleted_id) {
+                                Ok(restored) => {
+                                    created_supplier = Some(restored);
+                                    self.should_close = true;
                                 }
+                                Err(error) => self.errors.save = Some(error.to_string()),
                             }
+                        }
+
+                        if ui.button("Create New").clicked()
+                            && let Some(supplier) = self.validate_form() {
+                            created_supplier = self.save(supplier);
+                        }
+                    } else if ui.button("Save").clicked()
+                        && let Some(supplier) = self.validate_form() {
+                        let mut connection = db::establish_connection();
 
-                            self.should_close = true;
+                        // Offer the deleted supplier back before creating a second one with its name.
+                        match self.id {
+                            None => match supplier_repository::find_deleted_supplier_by_name(&mut connection, &supplier.name) {
+                                Ok(Some(deleted)) => self.deleted_match = Some(deleted),
+                                Ok(None) => created_supplier = self.save(supplier),
+                                Err(error) => self.errors.save = Some(error.to_string()),
+                            },
+                            Some(_) => created_supplier = self.save(supplier),
                         }
                     }
 
@@ -121,22 +264,106 @@ impl SupplierFormModal {
         (self.should_close, created_supplier)
     }
 
+    fn save(&mut self, supplier: SupplierForm) -> Option<Supplier> {
+        let mut connection = db::establish_connection();
+
+        let result = match self.id {
+            Some(id,) => {
+                let supplier = EditSupplierRow {
+                    id,
+                    name: supplier.name,
+                    tax_id: supplier.tax_id,
+                    contact_name: supplier.contact_name,
+                    phone: supplier.phone,
+                    mobile_phone: supplier.mobile_phone,
+                    email: supplier.email,
+                    address: supplier.address,
+                    payment_terms: supplier.payment_terms,
+                    notes: supplier.notes,
+                    active: supplier.active,
+                };
+
+                supplier_repository::edit_supplier(&mut connection, supplier)
+            }
+            None => {
+                let new_supplier = NewSupplierRow {
+                    name: supplier.name,
+                    tax_id: supplier.tax_id,
+                    contact_name: supplier.contact_name,
+                    phone: supplier.phone,
+                    mobile_phone: supplier.mobile_phone,
+                    email: supplier.email,
+                    address: supplier.address,
+                    payment_terms: supplier.payment_terms,
+                    notes: supplier.notes,
+                    active: Some(supplier.active),
+                };
+
+                supplier_repository::create_supplier(&mut connection, new_supplier)
+            }
+        };
+
+        // Names and tax IDs already used by another supplier are only known once saving.
+        match result {
+            Ok(saved) => {
+                self.should_close = true;
+                Some(saved)
+            }
+            Err(error) => {
+                self.errors.save = Some(error.to_string());
+                None
+            }
+        }
+    }
+
      fn validate_form(&mut self) -> Option<SupplierForm>{
         self.errors = FormErrors::default();
 
+        let optional = |value: &str| Some(value.trim().to_owned()).filter(|value| !value.is_empty());
+
+        let tax_id = Some(normalize_tax_id(&self.tax_id)).filter(|tax_id| !tax_id.is_empty());
+
+        if let Some(error) = tax_id.as_deref().and_then(|tax_id| TaxIdKind::detect(tax_id).err()) {
+            self.errors.tax_id = Some(error.to_string());
+        }
+
+        let phone_chars = |char: char| char.is_ascii_digit() || " +-()".contains(char);
+
+        if ![&self.phone, &self.mobile_phone].iter().all(|phone| phone.trim().chars().all(phone_chars)) {
+            self.errors.phones = Some("Phones may only contain digits, spaces and + - ( )".into());
+        }
+
         let supplier_data = SupplierForm {
             name: self.name.clone(),
+            tax_id,
+            contact_name: optional(&self.contact_name),
+            phone: optional(&self.phone),
+            mobile_phone: optional(&self.mobile_phone),
+            email: optional(&self.email),
+            address: optional(&self.address),
+            payment_terms: optional(&self.payment_terms),
+            notes: optional(&self.notes),
+            active: self.active,
         };
 
+        let parse_failed = self.errors.tax_id.is_some() || self.errors.phones.is_some();
 
         match supplier_data.validate() {
-            Ok(_) => Some(supplier_data),
+            Ok(_) if !parse_failed => Some(supplier_data),
+            Ok(_) => None,
             Err(error)=> {
-                if let Some(name_error)= error.field_errors().get("name") {
+                let field_errors = error.field_errors();
+
+                if let Some(name_error)= field_errors.get("name") {
                     self.errors.name = Some(name_error[0].clone().message.unwrap_or_default().to_string());
                 }
+
+                if let Some(email_error) = field_errors.get("email") {
+                    self.errors.email = Some(email_error[0].clone().message.unwrap_or_default().to_string());
+                }
+
                 None
             }
         }
     }
-}
\ No newline at end of file
+}
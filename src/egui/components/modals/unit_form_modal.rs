@@ -0,0 +1,223 @@
+use egui::{ComboBox, Id, Modal, RichText, Sides};
+use validator::Validate;
+
+use crate::infra::db;
+use crate::infra::repositories::unit_repository;
+use crate::infra::models::{NewUnitRow, EditUnitRow};
+
+use crate::domain::unit::Unit;
+
+const DEFAULT_SPACING: f32 = 16.0;
+const FORM_SPACING: f32 = DEFAULT_SPACING / 2.0;
+
+#[derive(Default)]
+pub struct FormErrors {
+    pub name: Option<String>,
+    pub symbol: Option<String>,
+    pub factor: Option<String>,
+    pub save: Option<String>,
+}
+
+#[derive(Validate)]
+pub struct UnitForm {
+    #[validate(length(min = 2, message = "Name must contain at least two characters"))]
+    name: String,
+
+    #[validate(length(min = 1, max = 16, message = "Symbol must contain between 1 and 16 characters"))]
+    symbol: String,
+
+    base_unit_id: Option<i32>,
+
+    #[validate(range(exclusive_min = 0.0, message = "Factor must be greater than zero"))]
+    factor: f64,
+}
+
+pub struct UnitFormModal {
+    should_close: bool,
+
+    id: Option<i32>,
+    name: String,
+    symbol: String,
+    base_unit_id: Option<i32>,
+    factor: String,
+    base_units: Vec<Unit>,
+
+    errors: FormErrors,
+}
+
+impl UnitFormModal {
+    pub fn new(unit: Option<&Unit>, units: &[Unit]) -> Self {
+        // Derived units point at a base unit, so only base units can be picked.
+        let base_units = units.iter()
+            .filter(|candidate| candidate.base_unit_id.is_none() && Some(candidate.id) != unit.map(|unit| unit.id))
+            .cloned()
+            .collect();
+
+        Self {
+            should_close: false,
+            id: unit.map(|unit| unit.id),
+            name: unit.map(|unit| unit.name.clone()).unwrap_or_default(),
+            symbol: unit.map(|unit| unit.symbol.clone()).unwrap_or_default(),
+            base_unit_id: unit.and_then(|unit| unit.base_unit_id),
+            factor: unit.map(|unit| unit.factor.to_string()).unwrap_or_else(|| "1".to_owned()),
+            base_units,
+            errors: FormErrors::default(),
+        }
+    }
+
+    pub fn show(&mut self, ui: &mut egui::Ui) -> (bool, Option<Unit>) {
+        let mut saved_unit = None;
+
+        let modal = Modal::new(Id::new("Unit Form")).show(ui.ctx(), |ui| {
+            ui.heading(if self.id.is_none() { "New Unit" } else { "Edit Unit" });
+            ui.separator();
+            ui.add_space(DEFAULT_SPACING / 2.0);
+
+            ui.label("Name");
+            ui.text_edit_singleline(&mut self.name);
+            if let Some(error) = &self.errors.name {
+                ui.label(RichText::new(error).color(ui.visuals().error_fg_color));
+            }
+
+            ui.add_space(FORM_SPACING);
+
+            ui.label("Symbol");
+            ui.text_edit_singleline(&mut self.symbol);
+            if let Some(error) = &self.errors.symbol {
+                ui.label(RichText::new(error).color(ui.visuals().error_fg_color));
+            }
+
+            ui.add_space(FORM_SPACING);
+
+            ui.label("Base Unit");
+            let base_unit = self.base_units.iter().find(|unit| Some(unit.id) == self.base_unit_id);
+
+            ComboBox::from_id_salt("unit_base_unit")
+                .selected_text(base_unit.map(|unit| unit.label()).unwrap_or_else(|| "None (this is a base unit)".to_owned()))
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut self.base_unit_id, None, "None (this is a base unit)");
+                    for unit in &self.base_units {
+                        ui.selectable_value(&mut self.base_unit_id, Some(unit.id), unit.label());
+                    }
+                });
+
+            if let Some(base_unit) = self.base_units.iter().find(|unit| Some(unit.id) == self.base_unit_id) {
+                ui.add_space(FORM_SPACING);
+
+                ui.horizontal(|ui| {
+                    ui.label(format!("1 {} =", if self.symbol.trim().is_empty() { "unit" } else { self.symbol.trim() }));
+                    ui.add(egui::TextEdit::singleline(&mut self.factor).desired_width(80.0));
+                    ui.label(&base_unit.symbol);
+                });
+                if let Some(error) = &self.errors.factor {
+                    ui.label(RichText::new(error).color(ui.visuals().error_fg_color));
+                }
+            }
+
+            if let Some(error) = &self.errors.save {
+                ui.add_space(FORM_SPACING);
+                ui.label(RichText::new(error).color(ui.visuals().error_fg_color));
+            }
+
+            ui.add_space(DEFAULT_SPACING / 2.0);
+            ui.separator();
+            ui.add_space(FORM_SPACING);
+
+            Sides::new().show(
+                ui,
+                |_ui| {},
+                |ui| {
+                    if ui.button("Save").clicked()
+                        && let Some(unit) = self.validate_form() {
+                        let mut connection = db::establish_connection();
+
+                        let result = match self.id {
+                            Some(id) => unit_repository::edit_unit(&mut connection, EditUnitRow {
+                                id,
+                                name: unit.name,
+                                symbol: unit.symbol,
+                                base_unit_id: unit.base_unit_id,
+                                factor: unit.factor,
+                            }),
+                            None => unit_repository::create_unit(&mut connection, NewUnitRow {
+                                name: unit.name,
+                                symbol: unit.symbol,
+                                base_unit_id: unit.base_unit_id,
+                                factor: unit.factor,
+                            }),
+                        };
+
+                        match result {
+                            Ok(unit) => {
+                                saved_unit = Some(unit);
+                                self.should_close = true;
+                            }
+                            Err(error) => {
+                                self.errors.save = Some(error.to_string());
+                            }
+                        }
+                    }
+
+                    if ui.button("Cancel").clicked() {
+                        self.should_close = true;
+                    }
+                }
+            );
+        });
+
+        if modal.should_close() {
+            self.should_close = true;
+        }
+
+        (self.should_close, saved_unit)
+    }
+
+    fn validate_form(&mut self) -> Option<UnitForm> {
+        self.errors = FormErrors::default();
+
+        // Base units are their own measure.
+        let factor = if self.base_unit_id.is_none() {
+            1.0
+        } else {
+            match self.factor.trim().replace(',', ".").parse::<f64>() {
+                Ok(factor) => factor,
+                Err(_) => {
+                    self.errors.factor = Some("Factor should be a valid number".into());
+                    0.0
+                }
+            }
+        };
+
+        let unit_data = UnitForm {
+            name: self.name.trim().to_owned(),
+            symbol: self.symbol.trim().to_owned(),
+            base_unit_id: self.base_unit_id,
+            factor,
+        };
+
+        let parse_failed = self.errors.factor.is_some();
+
+        match unit_data.validate() {
+            Ok(_) if !parse_failed => Some(unit_data),
+            Ok(_) => None,
+            Err(error) => {
+                let field_errors = error.field_errors();
+
+                if let Some(name_error) = field_errors.get("name") {
+                    self.errors.name = Some(name_error[0].clone().message.unwrap_or_default().to_string());
+                }
+
+                if let Some(symbol_error) = field_errors.get("symbol") {
+                    self.errors.symbol = Some(symbol_error[0].clone().message.unwrap_or_default().to_string());
+                }
+
+                if self.errors.factor.is_none()
+                    && let Some(factor_error) = field_errors.get("factor") {
+                    self.errors.factor = Some(factor_error[0].clone().message.unwrap_or_default().to_string());
+                }
+
+                None
+            }
+        }
+    }
+}
@@ -3,11 +3,30 @@ pub mod screens;
 pub mod widgets;
 
 use eframe::egui;
+use egui::Sides;
+use std::error::Error;
+
+use crate::infra::db;
+use crate::infra::repositories::{reservation_repository, trash_repository};
 use crate::egui::screens::dashboard_screen::DashboardScreen;
+use crate::egui::screens::inventory_transactions_screen::InventoryTransactionsScreen;
+use crate::egui::screens::issues_screen::IssuesScreen;
+use crate::egui::screens::lots_screen::LotsScreen;
 use crate::egui::screens::places_screen::PlacesScreen;
+use crate::egui::screens::scan_screen::ScanScreen;
+use crate::egui::screens::stocktakes_screen::StocktakesScreen;
 use crate::egui::screens::suppliers_screen::SuppliersScreen;
+use crate::egui::screens::units_screen::UnitsScreen;
+use crate::egui::screens::valuation_screen::ValuationScreen;
 use crate::egui::widgets::sidebar::SideBar;
+use crate::egui::widgets::trash_retention::TrashRetention;
 use crate::egui::screens::products_screen::ProductsScreen;
+use crate::egui::screens::purchase_orders_screen::PurchaseOrdersScreen;
+use crate::egui::screens::reorder_suggestions_screen::ReorderSuggestionsScreen;
+use crate::egui::screens::reservations_screen::ReservationsScreen;
+use crate::egui::screens::serial_numbers_screen::SerialNumbersScreen;
+
+const DEFAULT_SPACING: f32 = 16.0;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ScreenId {
@@ -15,16 +34,42 @@ pub enum ScreenId {
     Products,
     Suppliers,
     Places,
+    Units,
     InventoryTransactions,
+    Scan,
+    Stocktakes,
+    Reservations,
+    Issues,
+    Lots,
+    SerialNumbers,
+    PurchaseOrders,
+    ReorderSuggestions,
+    Valuation,
 }
 
 pub struct StockManagement {
     pub active_screen: ScreenId,
     pub dashboard_screen: Option<DashboardScreen>,
+    pub inventory_transactions_screen: Option<InventoryTransactionsScreen>,
+    pub issues_screen: Option<IssuesScreen>,
+    pub lots_screen: Option<LotsScreen>,
     pub places_screen: Option<PlacesScreen>,
     pub products_screen: Option<ProductsScreen>,
+    pub purchase_orders_screen: Option<PurchaseOrdersScreen>,
+    pub reorder_suggestions_screen: Option<ReorderSuggestionsScreen>,
+    pub reservations_screen: Option<ReservationsScreen>,
+    pub scan_screen: Option<ScanScreen>,
+    pub serial_numbers_screen: Option<SerialNumbersScreen>,
+    pub stocktakes_screen: Option<StocktakesScreen>,
     pub suppliers_screen: Option<SuppliersScreen>,
+    pub units_screen: Option<UnitsScreen>,
+    pub valuation_screen: Option<ValuationScreen>,
     pub sidebar: SideBar,
+    /// Trash retention setting, shared by the screens that show a trash.
+    pub trash_retention: TrashRetention,
+    /// Errors from work done before the first screen is shown, such as
+    /// purging the expired trash.
+    pub error: Option<Box<dyn Error>>,
 }
 
 impl Default for StockManagement {
@@ -32,10 +77,23 @@ impl Default for StockManagement {
         Self {
             active_screen: ScreenId::Dashboard,
             dashboard_screen: Some(DashboardScreen::new()),
+            inventory_transactions_screen: Some(InventoryTransactionsScreen::new()),
+            issues_screen: Some(IssuesScreen::new()),
+            lots_screen: Some(LotsScreen::new()),
             places_screen: Some(PlacesScreen::new()),
             products_screen: Some(ProductsScreen::new()),
+            purchase_orders_screen: Some(PurchaseOrdersScreen::new()),
+            reorder_suggestions_screen: Some(ReorderSuggestionsScreen::new()),
+            reservations_screen: Some(ReservationsScreen::new()),
+            scan_screen: Some(ScanScreen::new()),
+            serial_numbers_screen: Some(SerialNumbersScreen::new()),
+            stocktakes_screen: Some(StocktakesScreen::new()),
             suppliers_screen: Some(SuppliersScreen::new()),
+            units_screen: Some(UnitsScreen::new()),
+            valuation_screen: Some(ValuationScreen::new()),
             sidebar: SideBar::new(),
+            trash_retention: TrashRetention::new(),
+            error: None,
         }
     }
 }
@@ -43,10 +101,16 @@ impl Default for StockManagement {
 
 impl eframe::App for StockManagement {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        let previous_screen = self.active_screen;
+
         egui::SidePanel::left("side_panel").resizable(false).show(ctx, |ui| {
             self.sidebar.ui(ui, &mut self.active_screen);
         });
 
+        if self.active_screen != previous_screen {
+            self.reload_screen(self.active_screen);
+        }
+
         egui::CentralPanel::default().show(ctx, |ui| {
             match self.active_screen {
                 ScreenId::Dashboard => {
@@ -56,27 +120,155 @@ impl eframe::App for StockManagement {
                 }
                 ScreenId::Products => {
                     if let Some(screen) = &mut self.products_screen {
-                        screen.ui(ui);
+                        screen.ui(ui, &mut self.trash_retention);
                     }
                 }
                 ScreenId::Suppliers => { 
                     if let Some(screen) = &mut self.suppliers_screen {
-                        screen.ui(ui);
+                        screen.ui(ui, &mut self.trash_retention);
                     }
                 }
                 ScreenId::Places => { 
                     if let Some(screen) = &mut self.places_screen {
-                        screen.ui(ui);
+                        screen.ui(ui, &mut self.trash_retention);
                     }
                  }
-                ScreenId::InventoryTransactions => { ui.label("Inventory content"); }
+                ScreenId::Units => {
+                    if let Some(screen) = &mut self.units_screen {
+                        screen.ui(ui);
+                    }
+                }
+                ScreenId::InventoryTransactions => {
+                    if let Some(screen) = &mut self.inventory_transactions_screen {
+                        screen.ui(ui);
+                    }
+                }
+                ScreenId::Scan => {
+                    if let Some(screen) = &mut self.scan_screen {
+                        screen.ui(ui);
+                    }
+                }
+                ScreenId::Stocktakes => {
+                    if let Some(screen) = &mut self.stocktakes_screen {
+                        screen.ui(ui);
+                    }
+                }
+                ScreenId::Reservations => {
+                    if let Some(screen) = &mut self.reservations_screen {
+                        screen.ui(ui);
+                    }
+                }
+                ScreenId::Issues => {
+                    if let Some(screen) = &mut self.issues_screen {
+                        screen.ui(ui);
+                    }
+                }
+                ScreenId::Lots => {
+                    if let Some(screen) = &mut self.lots_screen {
+                        screen.ui(ui);
+                    }
+                }
+                ScreenId::SerialNumbers => {
+                    if let Some(screen) = &mut self.serial_numbers_screen {
+                        screen.ui(ui);
+                    }
+                }
+                ScreenId::PurchaseOrders => {
+                    if let Some(screen) = &mut self.purchase_orders_screen {
+                        screen.ui(ui);
+                    }
+                }
+                ScreenId::ReorderSuggestions => {
+                    if let Some(screen) = &mut self.reorder_suggestions_screen {
+                        screen.ui(ui);
+                    }
+                }
+                ScreenId::Valuation => {
+                    if let Some(screen) = &mut self.valuation_screen {
+                        screen.ui(ui);
+                    }
+                }
             }
+
+            self.show_error_message(ui);
         });
     }
 }
 
+impl StockManagement {
+    /// Screens keep their data between frames, so they are rebuilt when entered
+    /// to pick up changes made from other screens (e.g. new stock movements).
+    fn reload_screen(&mut self, screen: ScreenId) {
+        match screen {
+            ScreenId::Dashboard => self.dashboard_screen = Some(DashboardScreen::new()),
+            ScreenId::Products => self.products_screen = Some(ProductsScreen::new()),
+            ScreenId::Suppliers => self.suppliers_screen = Some(SuppliersScreen::new()),
+            ScreenId::Places => self.places_screen = Some(PlacesScreen::new()),
+            ScreenId::Units => self.units_screen = Some(UnitsScreen::new()),
+            ScreenId::InventoryTransactions => self.inventory_transactions_screen = Some(InventoryTransactionsScreen::new()),
+            ScreenId::Scan => self.scan_screen = Some(ScanScreen::new()),
+            ScreenId::Stocktakes => self.stocktakes_screen = Some(StocktakesScreen::new()),
+            ScreenId::Reservations => self.reservations_screen = Some(ReservationsScreen::new()),
+            ScreenId::Issues => self.issues_screen = Some(IssuesScreen::new()),
+            ScreenId::Lots => self.lots_screen = Some(LotsScreen::new()),
+            ScreenId::SerialNumbers => self.serial_numbers_screen = Some(SerialNumbersScreen::new()),
+            ScreenId::PurchaseOrders => self.purchase_orders_screen = Some(PurchaseOrdersScreen::new()),
+            ScreenId::ReorderSuggestions => self.reorder_suggestions_screen = Some(ReorderSuggestionsScreen::new()),
+            ScreenId::Valuation => self.valuation_screen = Some(ValuationScreen::new()),
+        }
+    }
+
+    fn show_error_message(&mut self, ui: &mut egui::Ui){
+        if let Some(error) = &self.error {
+            let error_message = error.to_string();
+
+            let error_alert = egui::Modal::new(egui::Id::new("App Error Message"))
+                .show(ui.ctx(), |ui| {
+                    ui.heading("Error");
+
+                    ui.separator();
+                    ui.add_space(DEFAULT_SPACING / 2.0);
+
+                    ui.label(error_message);
+
+                    ui.add_space(DEFAULT_SPACING);
+                    ui.separator();
+
+
+                    Sides::new().show(
+                        ui,
+                        |_ui| {},
+                        |ui| {
+                            if ui.button("OK").clicked() {
+                                self.error = None;
+                            }
+                        }
+                    );
+                });
+
+            if error_alert.should_close() {
+                self.error = None;
+            }
+        }
+    }
+}
+
 pub fn run() {
-    let app = StockManagement::default();
+    let mut connection = db::establish_connection();
+
+    // Purged before the screens load, so they never list what is about to go.
+    let purge_result = trash_repository::purge_expired_trash(&mut connection);
+    // Expired here rather than on every read, so listing reservations never writes.
+    let expire_result = reservation_repository::expire_reservations(&mut connection);
+
+    let mut app = StockManagement::default();
+
+    if let Err(error) = purge_result {
+        app.error = Some(format!("Could not purge the expired trash: {}", error).into());
+    } else if let Err(error) = expire_result {
+        app.error = Some(format!("Could not expire the past reservations: {}", error).into());
+    }
+
     let native_options = eframe::NativeOptions::default();
 
     eframe::run_native(
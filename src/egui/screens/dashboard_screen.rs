@@ -1,123 +1,82 @@
 use eframe::egui;
-use egui::{Direction, Label, Layout, Sides};
-use egui_extras::{Column, Size, StripBuilder, TableBuilder};
-use rfd::FileDialog;
+use egui::{Frame, RichText, Sides};
+use egui_extras::{Column, TableBuilder};
 use std::error::Error;
 
 use crate::infra::db;
-use crate::infra::repositories::supplier_repository;
-use crate::domain::supplier::Supplier;
-use crate::egui::components::modals::supplier_form_modal::SupplierFormModal;
-use crate::services::export::export_suppliers::export_suppliers;
-use crate::services::import::import_suppliers::import_suppliers;
+use crate::infra::repositories::stock_repository;
+use crate::domain::stock_balance::LowStockAlert;
 
 const DEFAULT_SPACING: f32 = 16.0;
 const ITEM_HEIGHT: f32 = 24.0;
 
 pub struct DashboardScreen {
-    pub entry_form_modal: Option<SupplierFormModal>,
+    pub low_stock_alerts: Vec<LowStockAlert>,
     pub error: Option<Box<dyn Error>>,
 }
 
 impl DashboardScreen {
     pub fn new() -> Self {
-        let suppliers = DashboardScreen::get_suppliers_list("");
+        let mut connection = db::establish_connection();
+
+        let (low_stock_alerts, error) = match stock_repository::list_low_stock_alerts(&mut connection) {
+            Ok(alerts) => (alerts, None),
+            Err(error) => (Vec::new(), Some(error)),
+        };
 
         Self {
-            entry_form_modal: None,
-            error: None,
+            low_stock_alerts,
+            error,
         }
     }
 
     pub fn ui(&mut self, ui: &mut egui::Ui) {
-        let add_supplier_btn = egui::Button::new(
-            egui::RichText::new("Add Supplier").color(egui::Color32::WHITE)
-        ).fill(ui.visuals().selection.bg_fill);
-
         ui.horizontal(|ui| {
             ui.heading("Dashboard");
 
             ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                // if ui.add(add_supplier_btn).clicked() {
-                //     self.supplier_form_modal = Some(SupplierFormModal::new(None));
-                // }
-
-                // if ui.add(egui::Button::new("Export")).clicked() {
-                //     match FileDialog::new().set_file_name("suppliers.xlsx").save_file() {
-                //         Some(path) => {
-                //             let _ = export_suppliers(&self.suppliers, path);
-                //         },
-                //         None => {}
-                //     };
-                // }
-
-                // if ui.add(egui::Button::new("Import")).clicked() {
-                //     if let Some(path) = FileDialog::new().pick_file() {
-                //         match import_suppliers(path) {
-                //             Ok(new_suppliers_row) => {
-                //                 let mut conn = db::establish_connection();
-
-                //                 let creation_result = supplier_repository::create_suppliers(
-                //                     &mut conn,
-                //                     &new_suppliers_row
-                //                 );
-
-                //                 if let Ok(mut created_suppliers) = creation_result {
-                //                     self.suppliers.append(&mut created_suppliers);
-                //                 }
-                //             },
-                //             Err(error) => {
-                //                 self.error = Some(error);
-                //             }
-                //         }
-                //     }
-                // }
-            
-                // if ui.add(egui::TextEdit::singleline(&mut self.search).hint_text("Search for supplier...")).changed() {
-                //     let filtered_suppliers = SuppliersScreen::get_suppliers_list(&self.search);
-                //     self.suppliers = filtered_suppliers;
-                // };
+                if ui.add(egui::Button::new("Refresh")).clicked() {
+                    *self = DashboardScreen::new();
+                }
             });
         });
 
         ui.add_space(DEFAULT_SPACING);
 
-        StripBuilder::new(ui)
-            .size(Size::remainder().at_least(100.0))
-            .vertical(|mut strip| {
-                strip.cell(|ui| {
-                    egui::ScrollArea::horizontal().show(ui, |ui| {
-                        self.suppliers_table(ui);
-                    });
-                });
-            });
+        self.low_stock_section(ui);
 
-        // if let Some(modal) = self.supplier_form_modal.as_mut() {
-        //     let (should_close, upserted_supplier) = modal.show(ui);
+        if self.error.is_some() {
+           self.show_error_message(ui);
+        }
+    }
+
+    fn low_stock_section(&mut self, ui: &mut egui::Ui) {
+        let alert_color = ui.visuals().error_fg_color;
 
-        //     if should_close {
-        //         self.supplier_form_modal = None;
+        Frame::group(ui.style())
+            .stroke(egui::Stroke::new(1.0, alert_color))
+            .fill(alert_color.gamma_multiply(0.08))
+            .inner_margin(DEFAULT_SPACING / 2.0)
+            .show(ui, |ui| {
+                ui.set_width(ui.available_width());
 
-        //         if let Some(supplier) = upserted_supplier {
-        //             if let Some(existing_supplier) = self.suppliers.iter_mut().find(|p| p.id == supplier.id) {
-        //                 *existing_supplier = supplier;
-        //             } else {
-        //                 self.suppliers.push(supplier);
-        //             }
-        //         }
-        //     }
-        // }
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new("Low Stock").heading().color(alert_color));
+                    ui.label(format!("{} product(s) at or below minimum stock", self.low_stock_alerts.len()));
+                });
+
+                ui.add_space(DEFAULT_SPACING / 2.0);
 
-        // if self.supplier_to_delete.is_some() {
-        //     self.show_confirm_delete_alert(ui);
-        // }
+                if self.low_stock_alerts.is_empty() {
+                    ui.label("Every product is above its minimum stock.");
+                    return;
+                }
 
-        // if self.error.is_some() {
-        //    self.show_error_message(ui);
-        // }
+                self.low_stock_table(ui);
+            });
     }
 
-    fn suppliers_table(&mut self, ui: &mut egui::Ui) {
+    fn low_stock_table(&mut self, ui: &mut egui::Ui) {
         let avaiable_height = ui.available_height();
 
         TableBuilder::new(ui)
@@ -131,95 +90,40 @@ impl DashboardScreen {
                 .clip(true)
                 .resizable(false)
             )
-            .column(Column::exact(100.0))     
+            .column(Column::exact(88.0))
+            .column(Column::exact(88.0))
+            .column(Column::exact(88.0))
+            .column(Column::exact(88.0))
             .min_scrolled_height(0.0)
             .max_scroll_height(avaiable_height)
-            .header(ITEM_HEIGHT,|mut header| {
+            .header(ITEM_HEIGHT, |mut header| {
                 header.col(|ui| { ui.heading("ID"); });
-                header.col(|ui| { ui.heading("Name"); });
-                header.col(|ui| { 
-                    ui.with_layout(Layout::centered_and_justified(Direction::LeftToRight), |ui| {
-                        ui.heading("Actions");
-                    });
-                });
+                header.col(|ui| { ui.heading("Product"); });
+                header.col(|ui| { ui.heading("Unity"); });
+                header.col(|ui| { ui.heading("Min Stock"); });
+                header.col(|ui| { ui.heading("On Hand"); });
+                header.col(|ui| { ui.heading("Missing"); });
             })
             .body(|body| {
                 body.rows(
                     ITEM_HEIGHT,
-                    // self.suppliers.len(),
-                    0,
+                    self.low_stock_alerts.len(),
                     |mut row| {
-                        // let supplier_index = row.index();
-                        // let supplier = &self.suppliers[supplier_index];
-
-
-                        // row.col(|ui| { ui.label(supplier.id.to_string()); });
-                        // row.col(|ui| { ui.label(supplier.name.clone()); });
-                        // row.col(|ui| {
-                        //     let delete_button = egui::Button::new(
-                        //     egui::RichText::new("Delete").color(egui::Color32::WHITE)
-                        //     ).fill(ui.visuals().error_fg_color);
-
-                        //      if ui.add(delete_button).clicked() {
-                        //         self.supplier_to_delete = Some(supplier.clone());
-                        //     }
-
-                        //     if ui.add(egui::Button::new("Edit")).clicked() {
-                        //         self.supplier_form_modal = Some(SupplierFormModal::new(Some(&supplier)));
-                        //     }
-                        // });
+                        let alert = &self.low_stock_alerts[row.index()];
+
+                        row.col(|ui| { ui.label(alert.product.id.to_string()); });
+                        row.col(|ui| { ui.label(&alert.product.name); });
+                        row.col(|ui| { ui.label(alert.product.unity.clone().unwrap_or_default()); });
+                        row.col(|ui| { ui.label(alert.product.min_stock.to_string()); });
+                        row.col(|ui| {
+                            ui.label(RichText::new(alert.on_hand.to_string()).color(ui.visuals().error_fg_color));
+                        });
+                        row.col(|ui| { ui.label(alert.shortfall().to_string()); });
                     }
                 );
             });
     }
 
-    fn show_confirm_delete_alert(&mut self, ui: &mut egui::Ui) {
-        let alert = egui::Modal::new(egui::Id::new("Delete Supplier"))
-            .show(ui.ctx(), |ui| {
-                ui.heading("Delete Supplier");
-
-                ui.separator();
-                ui.add_space(DEFAULT_SPACING / 2.0);
-
-                // ui.add(
-                // Label::new(
-                //     format!("Are you sure you want to delete supplier '{}'?",
-                //             self.supplier_to_delete.as_ref().unwrap().name)
-                //         )
-                // );
-
-                ui.add_space(DEFAULT_SPACING * 2.0);
-                ui.separator();
-                
-                // Sides::new().show(
-                //     ui,
-                //     |_ui| {},
-                //     |ui| {
-                //         if ui.add(egui::Button::new("Confirm")).clicked() {
-                //            let mut connection = db::establish_connection();
-
-                //             if supplier_repository::delete_supplier(&mut connection, self.supplier_to_delete.as_ref().unwrap().id).is_ok() {
-                //                 if let Some(pos) = self.suppliers.iter().position(|p| p.id == self.supplier_to_delete.as_ref().unwrap().id) {
-                //                     self.suppliers.remove(pos);
-                //                 }
-                //             }
-                           
-                //             self.supplier_to_delete = None;
-                //         }
-
-                //         if ui.add(egui::Button::new("Cancel")).clicked() {
-                //             self.supplier_to_delete = None;
-                //         }
-                //     }
-                // );
-
-            });
-
-        // if alert.should_close() {
-        //     self.supplier_to_delete = None;
-        // }
-    }
-
     fn show_error_message(&mut self, ui: &mut egui::Ui){
         if let Some(error) = &self.error {
             let error_message = error.to_string();
@@ -253,13 +157,4 @@ impl DashboardScreen {
             }
         }
     }
-
-    fn get_suppliers_list(search: &str) -> Vec<Supplier> {
-        let mut connection = db::establish_connection();
-
-        let suppliers = supplier_repository::list_suppliers(&mut connection, search)
-            .unwrap_or_default();
-
-        suppliers
-    }
-}
\ No newline at end of file
+}
@@ -0,0 +1,359 @@
+use eframe::egui;
+use egui::{ComboBox, Direction, Layout, Sides};
+use egui_extras::{Column, Size, StripBuilder, TableBuilder};
+use std::error::Error;
+
+use crate::infra::db;
+use crate::infra::repositories::{inventory_transaction_repository, place_repository, product_repository, supplier_repository};
+use crate::infra::repositories::inventory_transaction_repository::InventoryTransactionFilter;
+use crate::domain::inventory_transaction::{InventoryTransaction, TransactionType};
+use crate::domain::place::{Place, descendant_place_ids, place_path};
+use crate::domain::product::Product;
+use crate::domain::supplier::Supplier;
+use crate::egui::components::modals::inventory_transaction_form_modal::InventoryTransactionFormModal;
+use crate::egui::components::modals::stock_transfer_form_modal::StockTransferFormModal;
+
+const DEFAULT_SPACING: f32 = 16.0;
+const ITEM_HEIGHT: f32 = 24.0;
+const DATE_TIME_DISPLAY_PATTERN: &str = "%Y-%m-%d %H:%M";
+
+pub struct InventoryTransactionsScreen {
+    pub transactions: Vec<InventoryTransaction>,
+    pub products: Vec<Product>,
+    pub places: Vec<Place>,
+    pub suppliers: Vec<Supplier>,
+    pub transaction_form_modal: Option<InventoryTransactionFormModal>,
+    pub stock_transfer_modal: Option<StockTransferFormModal>,
+    pub error: Option<Box<dyn Error>>,
+    pub filter: InventoryTransactionFilter,
+    /// Place picked in the filter; its movements include the places inside it.
+    pub place_id: Option<i32>,
+}
+
+impl InventoryTransactionsScreen {
+    pub fn new() -> Self {
+        let filter = InventoryTransactionFilter::default();
+        let mut connection = db::establish_connection();
+
+        Self {
+            transactions: InventoryTransactionsScreen::get_transactions_list(&filter),
+            products: product_repository::list_products(&mut connection, "").unwrap_or_default(),
+            places: place_repository::list_places(&mut connection, "").unwrap_or_default(),
+            suppliers: supplier_repository::list_suppliers(&mut connection, "").unwrap_or_default(),
+            transaction_form_modal: None,
+            stock_transfer_modal: None,
+            error: None,
+            filter,
+            place_id: None,
+        }
+    }
+
+    pub fn ui(&mut self, ui: &mut egui::Ui) {
+        let add_transaction_btn = egui::Button::new(
+            egui::RichText::new("New Movement").color(egui::Color32::WHITE)
+        ).fill(ui.visuals().selection.bg_fill);
+
+        ui.horizontal(|ui| {
+            ui.heading("Inventory Transactions");
+
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if ui.add(add_transaction_btn).clicked() {
+                    self.refresh_catalogs();
+                    self.transaction_form_modal = Some(InventoryTransactionFormModal::new(
+                        &self.products,
+                        &self.places,
+                        &self.suppliers,
+                    ));
+                }
+
+                if ui.add(egui::Button::new("Transfer")).clicked() {
+                    self.refresh_catalogs();
+                    self.stock_transfer_modal = Some(StockTransferFormModal::new(&self.products, &self.places));
+                }
+
+                if ui.add(egui::TextEdit::singleline(&mut self.filter.search).hint_text("Search by reason or lot...")).changed() {
+                    self.transactions = InventoryTransactionsScreen::get_transactions_list(&self.filter);
+                };
+            });
+        });
+
+        ui.add_space(DEFAULT_SPACING / 2.0);
+
+        if self.filters_bar(ui) {
+            self.transactions = InventoryTransactionsScreen::get_transactions_list(&self.filter);
+        }
+
+        ui.add_space(DEFAULT_SPACING);
+
+        StripBuilder::new(ui)
+            .size(Size::remainder().at_least(100.0))
+            .vertical(|mut strip| {
+                strip.cell(|ui| {
+                    egui::ScrollArea::horizontal().show(ui, |ui| {
+                        self.transactions_table(ui);
+                    });
+                });
+            });
+
+        if let Some(modal) = self.transaction_form_modal.as_mut() {
+            let (should_close, created_transaction) = modal.show(ui);
+
+            if should_close {
+                self.transaction_form_modal = None;
+
+                if created_transaction.is_some() {
+                    self.transactions = InventoryTransactionsScreen::get_transactions_list(&self.filter);
+                }
+            }
+        }
+
+        if let Some(modal) = self.stock_transfer_modal.as_mut() {
+            let (should_close, transferred) = modal.show(ui);
+
+            if should_close {
+                self.stock_transfer_modal = None;
+
+                if transferred {
+                    self.transactions = InventoryTransactionsScreen::get_transactions_list(&self.filter);
+                }
+            }
+        }
+
+        if self.error.is_some() {
+           self.show_error_message(ui);
+        }
+    }
+
+    fn filters_bar(&mut self, ui: &mut egui::Ui) -> bool {
+        let mut changed = false;
+
+        ui.horizontal(|ui| {
+            ui.label("Product");
+            let product_name = self.products.iter()
+                .find(|product| Some(product.id) == self.filter.product_id)
+                .map(|product| product.name.clone())
+                .unwrap_or_else(|| "All".to_owned());
+
+            ComboBox::from_id_salt("filter_product")
+                .selected_text(product_name)
+                .show_ui(ui, |ui| {
+                    changed |= ui.selectable_value(&mut self.filter.product_id, None, "All").changed();
+                    for product in &self.products {
+                        changed |= ui.selectable_value(&mut self.filter.product_id, Some(product.id), &product.name).changed();
+                    }
+                });
+
+            ui.add_space(DEFAULT_SPACING);
+
+            ui.label("Place");
+            let place_name = self.places.iter()
+                .find(|place| Some(place.id) == self.place_id)
+                .map(|place| place_path(&self.places, place.id))
+                .unwrap_or_else(|| "All".to_owned());
+
+            let mut place_changed = false;
+
+            ComboBox::from_id_salt("filter_place")
+                .selected_text(place_name)
+                .show_ui(ui, |ui| {
+                    place_changed |= ui.selectable_value(&mut self.place_id, None, "All").changed();
+                    for place in &self.places {
+                        place_changed |= ui.selectable_value(&mut self.place_id, Some(place.id), place_path(&self.places, place.id)).changed();
+                    }
+                });
+
+            if place_changed {
+                self.filter.place_ids = self.place_id.map(|place_id| descendant_place_ids(&self.places, place_id));
+                changed = true;
+            }
+
+            ui.add_space(DEFAULT_SPACING);
+
+            ui.label("Type");
+            ComboBox::from_id_salt("filter_type")
+                .selected_text(self.filter.transaction_type.map(|t| t.label()).unwrap_or("All"))
+                .show_ui(ui, |ui| {
+                    changed |= ui.selectable_value(&mut self.filter.transaction_type, None, "All").changed();
+                    for transaction_type in TransactionType::ALL {
+                        changed |= ui.selectable_value(&mut self.filter.transaction_type, Some(transaction_type), transaction_type.label()).changed();
+                    }
+                });
+        });
+
+        changed
+    }
+
+    fn transactions_table(&mut self, ui: &mut egui::Ui) {
+        let avaiable_height = ui.available_height();
+
+        TableBuilder::new(ui)
+            .striped(true)
+            .resizable(false)
+            .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+            .column(Column::exact(64.0))
+            .column(Column::exact(140.0))
+            .column(
+                Column::remainder()
+                .at_least(120.0)
+                .clip(true)
+                .resizable(false)
+            )
+            .column(
+                Column::auto()
+                .at_least(80.0)
+                .at_most(200.0)
+                .clip(true)
+            )
+            .column(Column::exact(100.0))
+            .column(Column::exact(88.0))
+            .column(
+                Column::auto()
+                .at_least(80.0)
+                .at_most(200.0)
+                .clip(true)
+            )
+            .column(
+                Column::auto()
+                .at_least(60.0)
+                .at_most(200.0)
+                .clip(true)
+            )
+            .column(
+                Column::auto()
+                .at_least(60.0)
+                .at_most(240.0)
+                .clip(true)
+            )
+            .min_scrolled_height(0.0)
+            .max_scroll_height(avaiable_height)
+            .header(ITEM_HEIGHT, |mut header| {
+                header.col(|ui| { ui.heading("ID"); });
+                header.col(|ui| { ui.heading("Date"); });
+                header.col(|ui| { ui.heading("Product"); });
+                header.col(|ui| { ui.heading("Place"); });
+                header.col(|ui| { ui.heading("Type"); });
+                header.col(|ui| {
+                    ui.with_layout(Layout::centered_and_justified(Direction::LeftToRight), |ui| {
+                        ui.heading("Quantity");
+                    });
+                });
+                header.col(|ui| { ui.heading("Supplier"); });
+                header.col(|ui| { ui.heading("Lot"); });
+                header.col(|ui| { ui.heading("Reason"); });
+            })
+            .body(|body| {
+                body.rows(
+                    ITEM_HEIGHT,
+                    self.transactions.len(),
+                    |mut row| {
+                        let transaction = &self.transactions[row.index()];
+
+                        let product_name = self.products.iter()
+                            .find(|product| product.id == transaction.product_id)
+                            .map(|product| product.name.clone())
+                            .unwrap_or_else(|| format!("#{}", transaction.product_id));
+
+                        let place_name = self.places.iter()
+                            .find(|place| place.id == transaction.place_id)
+                            .map(|place| place_path(&self.places, place.id))
+                            .unwrap_or_else(|| format!("#{}", transaction.place_id));
+
+                        let supplier_name = transaction.supplier_id
+                            .map(|supplier_id| {
+                                self.suppliers.iter()
+                                    .find(|supplier| supplier.id == supplier_id)
+                                    .map(|supplier| supplier.name.clone())
+                                    .unwrap_or_else(|| format!("#{}", supplier_id))
+                            })
+                            .unwrap_or_default();
+
+                        row.col(|ui| { ui.label(transaction.id.to_string()); });
+                        row.col(|ui| { ui.label(transaction.created_at.format(DATE_TIME_DISPLAY_PATTERN).to_string()); });
+                        row.col(|ui| { ui.label(product_name); });
+                        row.col(|ui| { ui.label(place_name); });
+                        row.col(|ui| { ui.label(transaction.transaction_type.label()); });
+                        row.col(|ui| {
+                            ui.with_layout(Layout::centered_and_justified(Direction::LeftToRight), |ui| {
+                                let mut quantity = egui::RichText::new(format!("{:+}", transaction.quantity));
+
+                                if transaction.quantity < 0 {
+                                    quantity = quantity.color(ui.visuals().error_fg_color);
+                                }
+
+                                ui.label(quantity);
+                            });
+                        });
+                        let lot = match (&transaction.lot_number, transaction.expiry_date) {
+                            (Some(lot_number), Some(expiry_date)) => format!("{} (exp. {})", lot_number, expiry_date),
+                            (Some(lot_number), None) => lot_number.clone(),
+                            _ => String::new(),
+                        };
+
+                        row.col(|ui| { ui.label(supplier_name); });
+                        row.col(|ui| { ui.label(lot); });
+                        row.col(|ui| { ui.label(transaction.reason.clone().unwrap_or_default()); });
+                    }
+                );
+            });
+    }
+
+    fn show_error_message(&mut self, ui: &mut egui::Ui){
+        if let Some(error) = &self.error {
+            let error_message = error.to_string();
+
+            let error_alert = egui::Modal::new(egui::Id::new("Error Message"))
+                .show(ui.ctx(), |ui| {
+                    ui.heading("Error");
+
+                    ui.separator();
+                    ui.add_space(DEFAULT_SPACING / 2.0);
+
+                    ui.label(error_message);
+
+                    ui.add_space(DEFAULT_SPACING);
+                    ui.separator();
+
+
+                    Sides::new().show(
+                        ui,
+                        |_ui| {},
+                        |ui| {
+                            if ui.button("OK").clicked() {
+                                self.error = None;
+                            }
+                        }
+                    );
+                });
+
+            if error_alert.should_close() {
+                self.error = None;
+            }
+        }
+    }
+
+    fn refresh_catalogs(&mut self) {
+        let mut connection = db::establish_connection();
+
+        match product_repository::list_products(&mut connection, "") {
+            Ok(products) => self.products = products,
+            Err(error) => self.error = Some(error),
+        }
+
+        match place_repository::list_places(&mut connection, "") {
+            Ok(places) => self.places = places,
+            Err(error) => self.error = Some(error),
+        }
+
+        match supplier_repository::list_suppliers(&mut connection, "") {
+            Ok(suppliers) => self.suppliers = suppliers,
+            Err(error) => self.error = Some(error),
+        }
+    }
+
+    fn get_transactions_list(filter: &InventoryTransactionFilter) -> Vec<InventoryTransaction> {
+        let mut connection = db::establish_connection();
+
+        inventory_transaction_repository::list_inventory_transactions(&mut connection, filter)
+            .unwrap_or_default()
+    }
+}
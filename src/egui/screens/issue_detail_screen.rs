@@ -0,0 +1,369 @@
+use eframe::egui;
+use egui::{Direction, Label, Layout, RichText, Sides};
+use egui_extras::{Column, Size, StripBuilder, TableBuilder};
+use rfd::FileDialog;
+use std::error::Error;
+
+use crate::infra::db;
+use crate::infra::repositories::{issue_repository, place_repository, product_repository};
+use crate::domain::issue::{Issue, IssueLine};
+use crate::domain::place::{Place, place_path};
+use crate::domain::product::Product;
+use crate::egui::components::modals::issue_form_modal::IssueFormModal;
+use crate::egui::components::modals::issue_line_form_modal::IssueLineFormModal;
+use crate::services::export::export_issue::export_issue;
+use crate::services::labels::print_issue_receipt::print_issue_receipt;
+
+const DEFAULT_SPACING: f32 = 16.0;
+const ITEM_HEIGHT: f32 = 24.0;
+const DATE_TIME_DISPLAY_PATTERN: &str = "%Y-%m-%d %H:%M";
+
+pub struct IssueDetailScreen {
+    pub issue: Issue,
+    pub lines: Vec<IssueLine>,
+    pub products: Vec<Product>,
+    pub places: Vec<Place>,
+    pub issue_form_modal: Option<IssueFormModal>,
+    pub line_form_modal: Option<IssueLineFormModal>,
+    pub confirm_post: bool,
+    pub error: Option<Box<dyn Error>>,
+}
+
+impl IssueDetailScreen {
+    pub fn new(issue: Issue) -> Self {
+        let mut connection = db::establish_connection();
+
+        Self {
+            lines: issue_repository::list_issue_lines(&mut connection, issue.id).unwrap_or_default(),
+            products: product_repository::list_products(&mut connection, "").unwrap_or_default(),
+            places: place_repository::list_places(&mut connection, "").unwrap_or_default(),
+            issue,
+            issue_form_modal: None,
+            line_form_modal: None,
+            confirm_post: false,
+            error: None,
+        }
+    }
+
+    /// Renders the issue and returns `true` when the user asks to go back to the list.
+    pub fn ui(&mut self, ui: &mut egui::Ui) -> bool {
+        let mut go_back = false;
+        let editable = self.issue.status.is_editable();
+
+        ui.horizontal(|ui| {
+            if ui.button("Back").clicked() {
+                go_back = true;
+            }
+
+            ui.heading(format!("Issue #{}", self.issue.id));
+            ui.label(RichText::new(self.issue.status.label()).strong());
+
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if editable {
+                    let add_line_btn = egui::Button::new(
+                        egui::RichText::new("Add Line").color(egui::Color32::WHITE)
+                    ).fill(ui.visuals().selection.bg_fill);
+
+                    if ui.add(add_line_btn).clicked() {
+                        self.line_form_modal = Some(IssueLineFormModal::new(&self.issue, None, &self.products));
+                    }
+
+                    let post_btn = egui::Button::new(
+                        egui::RichText::new("Post").color(egui::Color32::WHITE)
+                    ).fill(ui.visuals().selection.bg_fill);
+
+                    if ui.add(post_btn).on_hover_text("Take the material out of the place").clicked() {
+                        if self.lines.is_empty() {
+                            self.error = Some("Add at least one line before posting the issue".into());
+                        } else {
+                            self.confirm_post = true;
+                        }
+                    }
+                }
+
+                if ui.add(egui::Button::new("Print Receipt")).clicked()
+                    && let Some(path) = FileDialog::new()
+                        .add_filter("PDF", &["pdf"])
+                        .add_filter("SVG", &["svg"])
+                        .set_file_name(format!("issue_{}.pdf", self.issue.id))
+                        .save_file()
+                    && let Err(error) = print_issue_receipt(&self.issue, &self.place_name(), &self.lines, &self.products, path) {
+                    self.error = Some(error);
+                }
+
+                if ui.add(egui::Button::new("Export")).clicked()
+                    && let Some(path) = FileDialog::new().set_file_name(format!("issue_{}.xlsx", self.issue.id)).save_file() {
+                    let _ = export_issue(&self.issue, &self.place_name(), &self.lines, &self.products, path);
+                }
+
+                if editable && ui.add(egui::Button::new("Cancel Issue")).clicked() {
+                    let mut connection = db::establish_connection();
+
+                    match issue_repository::cancel_issue(&mut connection, self.issue.id) {
+                        Ok(issue) => self.issue = issue,
+                        Err(error) => self.error = Some(error),
+                    }
+                }
+
+                if editable && ui.add(egui::Button::new("Edit")).clicked() {
+                    self.issue_form_modal = Some(IssueFormModal::new(Some(&self.issue), &self.places));
+                }
+            });
+        });
+
+        ui.add_space(DEFAULT_SPACING / 2.0);
+
+        egui::Grid::new("issue_header").num_columns(2).show(ui, |ui| {
+            ui.label("Requester");
+            ui.label(&self.issue.requester);
+            ui.end_row();
+
+            ui.label("Department");
+            ui.label(&self.issue.department);
+            ui.end_row();
+
+            ui.label("Destination");
+            ui.label(self.issue.destination.clone().unwrap_or_default());
+            ui.end_row();
+
+            ui.label("Issued From");
+            ui.label(self.place_name());
+            ui.end_row();
+
+            ui.label("Date");
+            ui.label(self.issue.created_at.format(DATE_TIME_DISPLAY_PATTERN).to_string());
+            ui.end_row();
+
+            ui.label("Posted");
+            ui.label(self.issue.posted_at.map(|date| date.format(DATE_TIME_DISPLAY_PATTERN).to_string()).unwrap_or_default());
+            ui.end_row();
+
+            ui.label("Notes");
+            ui.label(self.issue.notes.clone().unwrap_or_default());
+            ui.end_row();
+        });
+
+        ui.add_space(DEFAULT_SPACING);
+
+        StripBuilder::new(ui)
+            .size(Size::remainder().at_least(100.0))
+            .vertical(|mut strip| {
+                strip.cell(|ui| {
+                    egui::ScrollArea::horizontal().show(ui, |ui| {
+                        self.lines_table(ui, editable);
+                    });
+                });
+            });
+
+        if let Some(modal) = self.issue_form_modal.as_mut() {
+            let (should_close, updated_issue) = modal.show(ui);
+
+            if should_close {
+                self.issue_form_modal = None;
+
+                if let Some(issue) = updated_issue {
+                    self.issue = issue;
+                }
+            }
+        }
+
+        if let Some(modal) = self.line_form_modal.as_mut() {
+            let (should_close, saved_line) = modal.show(ui);
+
+            if should_close {
+                self.line_form_modal = None;
+
+                if let Some(line) = saved_line {
+                    if let Some(existing_line) = self.lines.iter_mut().find(|l| l.id == line.id) {
+                        *existing_line = line;
+                    } else {
+                        self.lines.push(line);
+                    }
+                }
+            }
+        }
+
+        if self.confirm_post {
+            self.show_confirm_post_alert(ui);
+        }
+
+        if self.error.is_some() {
+           self.show_error_message(ui);
+        }
+
+        go_back
+    }
+
+    fn lines_table(&mut self, ui: &mut egui::Ui, editable: bool) {
+        let avaiable_height = ui.available_height();
+        let mut line_to_remove = None;
+
+        TableBuilder::new(ui)
+            .striped(true)
+            .resizable(false)
+            .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+            .column(
+                Column::remainder()
+                .at_least(160.0)
+                .clip(true)
+                .resizable(false)
+            )
+            .column(Column::exact(88.0))
+            .column(Column::exact(64.0))
+            .column(Column::exact(120.0))
+            .column(
+                Column::auto()
+                .at_least(120.0)
+                .at_most(260.0)
+                .clip(true)
+            )
+            .column(Column::exact(120.0))
+            .min_scrolled_height(0.0)
+            .max_scroll_height(avaiable_height)
+            .header(ITEM_HEIGHT, |mut header| {
+                header.col(|ui| { ui.heading("Product"); });
+                header.col(|ui| { ui.heading("Quantity"); });
+                header.col(|ui| { ui.heading("Unit"); });
+                header.col(|ui| { ui.heading("Lot"); });
+                header.col(|ui| { ui.heading("Serial Numbers"); });
+                header.col(|ui| {
+                    ui.with_layout(Layout::centered_and_justified(Direction::LeftToRight), |ui| {
+                        ui.heading("Actions");
+                    });
+                });
+            })
+            .body(|body| {
+                body.rows(
+                    ITEM_HEIGHT,
+                    self.lines.len(),
+                    |mut row| {
+                        let line = &self.lines[row.index()];
+
+                        let product = self.products.iter().find(|product| product.id == line.product_id);
+
+                        let product_name = product
+                            .map(|product| product.name.clone())
+                            .unwrap_or_else(|| format!("#{}", line.product_id));
+
+                        row.col(|ui| { ui.label(product_name); });
+                        row.col(|ui| { ui.label(line.quantity.to_string()); });
+                        row.col(|ui| { ui.label(product.and_then(|product| product.unity.clone()).unwrap_or_default()); });
+                        row.col(|ui| { ui.label(line.lot_number.clone().unwrap_or_default()); });
+                        row.col(|ui| { ui.label(line.serial_numbers.join(", ")); });
+                        row.col(|ui| {
+                            if !editable {
+                                return;
+                            }
+
+                            let remove_button = egui::Button::new(
+                                egui::RichText::new("Remove").color(egui::Color32::WHITE)
+                            ).fill(ui.visuals().error_fg_color);
+
+                            if ui.add(remove_button).clicked() {
+                                line_to_remove = Some(line.id);
+                            }
+
+                            if ui.add(egui::Button::new("Edit")).clicked() {
+                                self.line_form_modal = Some(IssueLineFormModal::new(&self.issue, Some(line), &self.products));
+                            }
+                        });
+                    }
+                );
+            });
+
+        if let Some(line_id) = line_to_remove {
+            let mut connection = db::establish_connection();
+
+            match issue_repository::delete_issue_line(&mut connection, line_id) {
+                Ok(_) => self.lines.retain(|line| line.id != line_id),
+                Err(error) => self.error = Some(error),
+            }
+        }
+    }
+
+    fn place_name(&self) -> String {
+        self.places.iter()
+            .find(|place| place.id == self.issue.place_id)
+            .map(|place| place_path(&self.places, place.id))
+            .unwrap_or_else(|| format!("#{}", self.issue.place_id))
+    }
+
+    fn show_confirm_post_alert(&mut self, ui: &mut egui::Ui) {
+        let alert = egui::Modal::new(egui::Id::new("Post Issue"))
+            .show(ui.ctx(), |ui| {
+                ui.heading("Post Issue");
+
+                ui.separator();
+                ui.add_space(DEFAULT_SPACING / 2.0);
+
+                ui.add(
+                Label::new(
+                    format!("Post issue #{}? The material will be taken out of {} and the issue can no longer be changed.",
+                            self.issue.id, self.place_name())
+                        )
+                );
+
+                ui.add_space(DEFAULT_SPACING * 2.0);
+                ui.separator();
+
+                Sides::new().show(
+                    ui,
+                    |_ui| {},
+                    |ui| {
+                        if ui.add(egui::Button::new("Confirm")).clicked() {
+                            let mut connection = db::establish_connection();
+
+                            match issue_repository::post_issue(&mut connection, self.issue.id) {
+                                Ok(issue) => self.issue = issue,
+                                Err(error) => self.error = Some(error),
+                            }
+
+                            self.confirm_post = false;
+                        }
+
+                        if ui.add(egui::Button::new("Cancel")).clicked() {
+                            self.confirm_post = false;
+                        }
+                    }
+                );
+            });
+
+        if alert.should_close() {
+            self.confirm_post = false;
+        }
+    }
+
+    fn show_error_message(&mut self, ui: &mut egui::Ui){
+        if let Some(error) = &self.error {
+            let error_message = error.to_string();
+
+            let error_alert = egui::Modal::new(egui::Id::new("Error Message"))
+                .show(ui.ctx(), |ui| {
+                    ui.heading("Error");
+
+                    ui.separator();
+                    ui.add_space(DEFAULT_SPACING / 2.0);
+
+                    ui.label(error_message);
+
+                    ui.add_space(DEFAULT_SPACING);
+                    ui.separator();
+
+
+                    Sides::new().show(
+                        ui,
+                        |_ui| {},
+                        |ui| {
+                            if ui.button("OK").clicked() {
+                                self.error = None;
+                            }
+                        }
+                    );
+                });
+
+            if error_alert.should_close() {
+                self.error = None;
+            }
+        }
+    }
+}
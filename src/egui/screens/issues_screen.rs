@@ -0,0 +1,335 @@
+use eframe::egui;
+use egui::{ComboBox, Direction, Label, Layout, Sides};
+use egui_extras::{Column, Size, StripBuilder, TableBuilder};
+use std::error::Error;
+
+use crate::infra::db;
+use crate::infra::repositories::{issue_repository, place_repository};
+use crate::infra::repositories::issue_repository::IssueFilter;
+use crate::domain::audit::AuditEntity;
+use crate::domain::issue::{Issue, IssueStatus};
+use crate::domain::place::{Place, place_path};
+use crate::egui::components::modals::audit_history_modal::AuditHistoryModal;
+use crate::egui::components::modals::issue_form_modal::IssueFormModal;
+use crate::egui::screens::issue_detail_screen::IssueDetailScreen;
+
+const DEFAULT_SPACING: f32 = 16.0;
+const ITEM_HEIGHT: f32 = 24.0;
+const DATE_TIME_DISPLAY_PATTERN: &str = "%Y-%m-%d %H:%M";
+
+pub struct IssuesScreen {
+    pub issues: Vec<Issue>,
+    pub places: Vec<Place>,
+    pub filter: IssueFilter,
+    pub issue_form_modal: Option<IssueFormModal>,
+    pub audit_history_modal: Option<AuditHistoryModal>,
+    pub issue_to_delete: Option<Issue>,
+    pub detail_screen: Option<IssueDetailScreen>,
+    pub error: Option<Box<dyn Error>>,
+}
+
+impl IssuesScreen {
+    pub fn new() -> Self {
+        let mut connection = db::establish_connection();
+
+        let mut screen = Self {
+            issues: Vec::new(),
+            places: place_repository::list_places(&mut connection, "").unwrap_or_default(),
+            filter: IssueFilter::default(),
+            issue_form_modal: None,
+            audit_history_modal: None,
+            issue_to_delete: None,
+            detail_screen: None,
+            error: None,
+        };
+
+        screen.refresh_issues();
+
+        screen
+    }
+
+    pub fn ui(&mut self, ui: &mut egui::Ui) {
+        if let Some(detail_screen) = self.detail_screen.as_mut() {
+            if detail_screen.ui(ui) {
+                self.detail_screen = None;
+                self.refresh_issues();
+            }
+
+            return;
+        }
+
+        let new_issue_btn = egui::Button::new(
+            egui::RichText::new("New Issue").color(egui::Color32::WHITE)
+        ).fill(ui.visuals().selection.bg_fill);
+
+        ui.horizontal(|ui| {
+            ui.heading("Issues");
+
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if ui.add(new_issue_btn).clicked() {
+                    self.issue_form_modal = Some(IssueFormModal::new(None, &self.places));
+                }
+
+                if ui.add(egui::TextEdit::singleline(&mut self.filter.search).hint_text("Search by requester, department or destination...")).changed() {
+                    self.refresh_issues();
+                }
+            });
+        });
+
+        ui.add_space(DEFAULT_SPACING / 2.0);
+
+        if self.filters_bar(ui) {
+            self.refresh_issues();
+        }
+
+        ui.add_space(DEFAULT_SPACING);
+
+        StripBuilder::new(ui)
+            .size(Size::remainder().at_least(100.0))
+            .vertical(|mut strip| {
+                strip.cell(|ui| {
+                    egui::ScrollArea::horizontal().show(ui, |ui| {
+                        self.issues_table(ui);
+                    });
+                });
+            });
+
+        if let Some(modal) = self.issue_form_modal.as_mut() {
+            let (should_close, created_issue) = modal.show(ui);
+
+            if should_close {
+                self.issue_form_modal = None;
+
+                if let Some(issue) = created_issue {
+                    self.detail_screen = Some(IssueDetailScreen::new(issue));
+                }
+            }
+        }
+
+        if let Some(modal) = self.audit_history_modal.as_mut()
+            && modal.show(ui) {
+            self.audit_history_modal = None;
+        }
+
+        if self.issue_to_delete.is_some() {
+            self.show_confirm_delete_alert(ui);
+        }
+
+        if self.error.is_some() {
+           self.show_error_message(ui);
+        }
+    }
+
+    fn filters_bar(&mut self, ui: &mut egui::Ui) -> bool {
+        let mut changed = false;
+
+        ui.horizontal(|ui| {
+            ui.label("Place");
+            let place_name = self.places.iter()
+                .find(|place| Some(place.id) == self.filter.place_id)
+                .map(|place| place_path(&self.places, place.id))
+                .unwrap_or_else(|| "All".to_owned());
+
+            ComboBox::from_id_salt("issues_filter_place")
+                .selected_text(place_name)
+                .show_ui(ui, |ui| {
+                    changed |= ui.selectable_value(&mut self.filter.place_id, None, "All").changed();
+                    for place in &self.places {
+                        changed |= ui.selectable_value(&mut self.filter.place_id, Some(place.id), place_path(&self.places, place.id)).changed();
+                    }
+                });
+
+            ui.add_space(DEFAULT_SPACING);
+
+            ui.label("Status");
+            ComboBox::from_id_salt("issues_filter_status")
+                .selected_text(self.filter.status.map(|status| status.label()).unwrap_or("All"))
+                .show_ui(ui, |ui| {
+                    changed |= ui.selectable_value(&mut self.filter.status, None, "All").changed();
+                    for status in IssueStatus::ALL {
+                        changed |= ui.selectable_value(&mut self.filter.status, Some(status), status.label()).changed();
+                    }
+                });
+        });
+
+        changed
+    }
+
+    fn issues_table(&mut self, ui: &mut egui::Ui) {
+        let avaiable_height = ui.available_height();
+
+        TableBuilder::new(ui)
+            .striped(true)
+            .resizable(false)
+            .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+            .column(Column::exact(64.0))
+            .column(Column::exact(130.0))
+            .column(
+                Column::remainder()
+                .at_least(120.0)
+                .clip(true)
+                .resizable(false)
+            )
+            .column(
+                Column::auto()
+                .at_least(100.0)
+                .at_most(200.0)
+                .clip(true)
+            )
+            .column(
+                Column::auto()
+                .at_least(80.0)
+                .at_most(200.0)
+                .clip(true)
+            )
+            .column(Column::exact(88.0))
+            .column(Column::exact(190.0))
+            .min_scrolled_height(0.0)
+            .max_scroll_height(avaiable_height)
+            .header(ITEM_HEIGHT, |mut header| {
+                header.col(|ui| { ui.heading("ID"); });
+                header.col(|ui| { ui.heading("Date"); });
+                header.col(|ui| { ui.heading("Requester"); });
+                header.col(|ui| { ui.heading("Department"); });
+                header.col(|ui| { ui.heading("Place"); });
+                header.col(|ui| { ui.heading("Status"); });
+                header.col(|ui| {
+                    ui.with_layout(Layout::centered_and_justified(Direction::LeftToRight), |ui| {
+                        ui.heading("Actions");
+                    });
+                });
+            })
+            .body(|body| {
+                body.rows(
+                    ITEM_HEIGHT,
+                    self.issues.len(),
+                    |mut row| {
+                        let issue = &self.issues[row.index()];
+
+                        let place_name = self.places.iter()
+                            .find(|place| place.id == issue.place_id)
+                            .map(|place| place_path(&self.places, place.id))
+                            .unwrap_or_else(|| format!("#{}", issue.place_id));
+
+                        row.col(|ui| { ui.label(issue.id.to_string()); });
+                        row.col(|ui| { ui.label(issue.created_at.format(DATE_TIME_DISPLAY_PATTERN).to_string()); });
+                        row.col(|ui| {
+                            ui.label(&issue.requester)
+                                .on_hover_text(issue.destination.clone().unwrap_or_default());
+                        });
+                        row.col(|ui| { ui.label(&issue.department); });
+                        row.col(|ui| { ui.label(place_name); });
+                        row.col(|ui| { ui.label(issue.status.label()); });
+                        row.col(|ui| {
+                            if issue.status.is_editable() {
+                                let delete_button = egui::Button::new(
+                                    egui::RichText::new("Delete").color(egui::Color32::WHITE)
+                                ).fill(ui.visuals().error_fg_color);
+
+                                if ui.add(delete_button).clicked() {
+                                    self.issue_to_delete = Some(issue.clone());
+                                }
+                            }
+
+                            if ui.add(egui::Button::new("Open")).clicked() {
+                                self.detail_screen = Some(IssueDetailScreen::new(issue.clone()));
+                            }
+
+                            if ui.add(egui::Button::new("History")).clicked() {
+                                self.audit_history_modal = Some(AuditHistoryModal::new(AuditEntity::Issue, issue.id, &format!("#{}", issue.id)));
+                            }
+                        });
+                    }
+                );
+            });
+    }
+
+    fn show_confirm_delete_alert(&mut self, ui: &mut egui::Ui) {
+        let alert = egui::Modal::new(egui::Id::new("Delete Issue"))
+            .show(ui.ctx(), |ui| {
+                ui.heading("Delete Issue");
+
+                ui.separator();
+                ui.add_space(DEFAULT_SPACING / 2.0);
+
+                ui.add(
+                Label::new(
+                    format!("Are you sure you want to delete issue #{}?",
+                            self.issue_to_delete.as_ref().unwrap().id)
+                        )
+                );
+
+                ui.add_space(DEFAULT_SPACING * 2.0);
+                ui.separator();
+
+                Sides::new().show(
+                    ui,
+                    |_ui| {},
+                    |ui| {
+                        if ui.add(egui::Button::new("Confirm")).clicked() {
+                            let mut connection = db::establish_connection();
+                            let issue_id = self.issue_to_delete.as_ref().unwrap().id;
+
+                            match issue_repository::delete_issue(&mut connection, issue_id) {
+                                Ok(_) => self.issues.retain(|issue| issue.id != issue_id),
+                                Err(error) => self.error = Some(error),
+                            }
+
+                            self.issue_to_delete = None;
+                        }
+
+                        if ui.add(egui::Button::new("Cancel")).clicked() {
+                            self.issue_to_delete = None;
+                        }
+                    }
+                );
+            });
+
+        if alert.should_close() {
+            self.issue_to_delete = None;
+        }
+    }
+
+    fn refresh_issues(&mut self) {
+        let mut connection = db::establish_connection();
+
+        match issue_repository::list_issues(&mut connection, &self.filter) {
+            Ok(issues) => self.issues = issues,
+            Err(error) => self.error = Some(error),
+        }
+    }
+
+    fn show_error_message(&mut self, ui: &mut egui::Ui){
+        if let Some(error) = &self.error {
+            let error_message = error.to_string();
+
+            let error_alert = egui::Modal::new(egui::Id::new("Error Message"))
+                .show(ui.ctx(), |ui| {
+                    ui.heading("Error");
+
+                    ui.separator();
+                    ui.add_space(DEFAULT_SPACING / 2.0);
+
+                    ui.label(error_message);
+
+                    ui.add_space(DEFAULT_SPACING);
+                    ui.separator();
+
+
+                    Sides::new().show(
+                        ui,
+                        |_ui| {},
+                        |ui| {
+                            if ui.button("OK").clicked() {
+                                self.error = None;
+                            }
+                        }
+                    );
+                });
+
+            if error_alert.should_close() {
+                self.error = None;
+            }
+        }
+    }
+}
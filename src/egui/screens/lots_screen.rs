@@ -0,0 +1,257 @@
+use chrono::{Local, NaiveDate};
+use eframe::egui;
+use egui::{ComboBox, Direction, Layout, RichText, Sides};
+use egui_extras::{Column, Size, StripBuilder, TableBuilder};
+use std::error::Error;
+
+use crate::infra::db;
+use crate::infra::repositories::{place_repository, product_repository, stock_repository};
+use crate::infra::repositories::stock_repository::LotBalanceFilter;
+use crate::domain::place::{Place, descendant_place_ids, place_path};
+use crate::domain::product::Product;
+use crate::domain::stock_balance::LotBalance;
+
+const DEFAULT_SPACING: f32 = 16.0;
+const ITEM_HEIGHT: f32 = 24.0;
+const DEFAULT_EXPIRY_WINDOW_DAYS: i64 = 30;
+
+pub struct LotsScreen {
+    pub lots: Vec<LotBalance>,
+    pub products: Vec<Product>,
+    pub places: Vec<Place>,
+    pub error: Option<Box<dyn Error>>,
+    pub filter: LotBalanceFilter,
+    /// Place picked in the filter; its lots include the places inside it.
+    pub place_id: Option<i32>,
+    pub only_expiring: bool,
+    pub expiry_window_days: i64,
+    pub today: NaiveDate,
+}
+
+impl LotsScreen {
+    pub fn new() -> Self {
+        let mut connection = db::establish_connection();
+
+        let mut screen = Self {
+            lots: Vec::new(),
+            products: product_repository::list_products(&mut connection, "").unwrap_or_default(),
+            places: place_repository::list_places(&mut connection, "").unwrap_or_default(),
+            error: None,
+            filter: LotBalanceFilter::default(),
+            place_id: None,
+            only_expiring: false,
+            expiry_window_days: DEFAULT_EXPIRY_WINDOW_DAYS,
+            today: Local::now().date_naive(),
+        };
+
+        screen.refresh_lots();
+
+        screen
+    }
+
+    pub fn ui(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.heading("Lots & Expiry");
+        });
+
+        ui.add_space(DEFAULT_SPACING / 2.0);
+
+        if self.filters_bar(ui) {
+            self.refresh_lots();
+        }
+
+        ui.add_space(DEFAULT_SPACING);
+
+        StripBuilder::new(ui)
+            .size(Size::remainder().at_least(100.0))
+            .vertical(|mut strip| {
+                strip.cell(|ui| {
+                    egui::ScrollArea::horizontal().show(ui, |ui| {
+                        self.lots_table(ui);
+                    });
+                });
+            });
+
+        if self.error.is_some() {
+           self.show_error_message(ui);
+        }
+    }
+
+    fn filters_bar(&mut self, ui: &mut egui::Ui) -> bool {
+        let mut changed = false;
+
+        ui.horizontal(|ui| {
+            ui.label("Product");
+            let product_name = self.products.iter()
+                .find(|product| Some(product.id) == self.filter.product_id)
+                .map(|product| product.name.clone())
+                .unwrap_or_else(|| "All".to_owned());
+
+            ComboBox::from_id_salt("lots_filter_product")
+                .selected_text(product_name)
+                .show_ui(ui, |ui| {
+                    changed |= ui.selectable_value(&mut self.filter.product_id, None, "All").changed();
+                    for product in self.products.iter().filter(|product| product.tracks_lots) {
+                        changed |= ui.selectable_value(&mut self.filter.product_id, Some(product.id), &product.name).changed();
+                    }
+                });
+
+            ui.add_space(DEFAULT_SPACING);
+
+            ui.label("Place");
+            let place_name = self.places.iter()
+                .find(|place| Some(place.id) == self.place_id)
+                .map(|place| place_path(&self.places, place.id))
+                .unwrap_or_else(|| "All".to_owned());
+
+            ComboBox::from_id_salt("lots_filter_place")
+                .selected_text(place_name)
+                .show_ui(ui, |ui| {
+                    changed |= ui.selectable_value(&mut self.place_id, None, "All").changed();
+                    for place in &self.places {
+                        changed |= ui.selectable_value(&mut self.place_id, Some(place.id), place_path(&self.places, place.id)).changed();
+                    }
+                });
+
+            ui.add_space(DEFAULT_SPACING);
+
+            changed |= ui.checkbox(&mut self.only_expiring, "Expiring within").changed();
+            changed |= ui.add(egui::DragValue::new(&mut self.expiry_window_days).range(0..=3650)).changed();
+            ui.label("days");
+        });
+
+        changed
+    }
+
+    fn lots_table(&mut self, ui: &mut egui::Ui) {
+        let avaiable_height = ui.available_height();
+
+        TableBuilder::new(ui)
+            .striped(true)
+            .resizable(false)
+            .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+            .column(
+                Column::remainder()
+                .at_least(120.0)
+                .clip(true)
+                .resizable(false)
+            )
+            .column(
+                Column::auto()
+                .at_least(80.0)
+                .at_most(200.0)
+                .clip(true)
+            )
+            .column(Column::exact(120.0))
+            .column(Column::exact(100.0))
+            .column(Column::exact(100.0))
+            .column(Column::exact(88.0))
+            .min_scrolled_height(0.0)
+            .max_scroll_height(avaiable_height)
+            .header(ITEM_HEIGHT, |mut header| {
+                header.col(|ui| { ui.heading("Product"); });
+                header.col(|ui| { ui.heading("Place"); });
+                header.col(|ui| { ui.heading("Lot"); });
+                header.col(|ui| { ui.heading("Expiry"); });
+                header.col(|ui| { ui.heading("Days Left"); });
+                header.col(|ui| {
+                    ui.with_layout(Layout::centered_and_justified(Direction::LeftToRight), |ui| {
+                        ui.heading("Quantity");
+                    });
+                });
+            })
+            .body(|body| {
+                body.rows(
+                    ITEM_HEIGHT,
+                    self.lots.len(),
+                    |mut row| {
+                        let lot = &self.lots[row.index()];
+
+                        let product_name = self.products.iter()
+                            .find(|product| product.id == lot.product_id)
+                            .map(|product| product.name.clone())
+                            .unwrap_or_else(|| format!("#{}", lot.product_id));
+
+                        let place_name = self.places.iter()
+                            .find(|place| place.id == lot.place_id)
+                            .map(|place| place_path(&self.places, place.id))
+                            .unwrap_or_else(|| format!("#{}", lot.place_id));
+
+                        let days_left = lot.days_to_expiry(self.today);
+
+                        row.col(|ui| { ui.label(product_name); });
+                        row.col(|ui| { ui.label(place_name); });
+                        row.col(|ui| { ui.label(&lot.lot_number); });
+                        row.col(|ui| { ui.label(lot.expiry_date.map(|date| date.to_string()).unwrap_or_default()); });
+                        row.col(|ui| {
+                            let Some(days_left) = days_left else {
+                                return;
+                            };
+
+                            let text = if days_left < 0 {
+                                RichText::new("Expired").color(ui.visuals().error_fg_color)
+                            } else if days_left <= self.expiry_window_days {
+                                RichText::new(days_left.to_string()).color(ui.visuals().warn_fg_color)
+                            } else {
+                                RichText::new(days_left.to_string())
+                            };
+
+                            ui.label(text);
+                        });
+                        row.col(|ui| {
+                            ui.with_layout(Layout::centered_and_justified(Direction::LeftToRight), |ui| {
+                                ui.label(lot.quantity.to_string());
+                            });
+                        });
+                    }
+                );
+            });
+    }
+
+    fn refresh_lots(&mut self) {
+        let mut connection = db::establish_connection();
+
+        self.filter.place_ids = self.place_id.map(|place_id| descendant_place_ids(&self.places, place_id));
+        self.filter.expires_until = self.only_expiring
+            .then(|| self.today + chrono::Duration::days(self.expiry_window_days));
+
+        match stock_repository::list_lot_balances(&mut connection, &self.filter) {
+            Ok(lots) => self.lots = lots,
+            Err(error) => self.error = Some(error),
+        }
+    }
+
+    fn show_error_message(&mut self, ui: &mut egui::Ui){
+        if let Some(error) = &self.error {
+            let error_message = error.to_string();
+
+            let error_alert = egui::Modal::new(egui::Id::new("Error Message"))
+                .show(ui.ctx(), |ui| {
+                    ui.heading("Error");
+
+                    ui.separator();
+                    ui.add_space(DEFAULT_SPACING / 2.0);
+
+                    ui.label(error_message);
+
+                    ui.add_space(DEFAULT_SPACING);
+                    ui.separator();
+
+
+                    Sides::new().show(
+                        ui,
+                        |_ui| {},
+                        |ui| {
+                            if ui.button("OK").clicked() {
+                                self.error = None;
+                            }
+                        }
+                    );
+                });
+
+            if error_alert.should_close() {
+                self.error = None;
+            }
+        }
+    }
+}
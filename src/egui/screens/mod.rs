@@ -1,4 +1,18 @@
 pub mod dashboard_screen;
+pub mod inventory_transactions_screen;
+pub mod issue_detail_screen;
+pub mod issues_screen;
+pub mod lots_screen;
 pub mod places_screen;
 pub mod products_screen;
-pub mod suppliers_screen;
\ No newline at end of file
+pub mod purchase_order_detail_screen;
+pub mod purchase_orders_screen;
+pub mod reorder_suggestions_screen;
+pub mod reservations_screen;
+pub mod scan_screen;
+pub mod serial_numbers_screen;
+pub mod stocktake_detail_screen;
+pub mod stocktakes_screen;
+pub mod suppliers_screen;
+pub mod units_screen;
+pub mod valuation_screen;
\ No newline at end of file
@@ -2,22 +2,40 @@ use eframe::egui;
 use egui::{Direction, Label, Layout, Sides};
 use egui_extras::{Column, Size, StripBuilder, TableBuilder};
 use rfd::FileDialog;
+use std::collections::HashSet;
 use std::error::Error;
 
 use crate::infra::db;
 use crate::infra::repositories::place_repository;
-use crate::domain::place::Place;
+use crate::domain::audit::AuditEntity;
+use crate::domain::place::{Place, PlaceType, place_path};
+use crate::egui::components::modals::audit_history_modal::AuditHistoryModal;
 use crate::egui::components::modals::place_form_modal::PlaceFormModal;
+use crate::egui::components::modals::place_stock_modal::PlaceStockModal;
+use crate::egui::components::modals::print_labels_modal::{LabelTarget, PrintLabelsModal};
+use crate::egui::widgets::trash_retention::TrashRetention;
 use crate::services::export::export_places::export_places;
 use crate::services::import::import_places::import_places;
 
 const DEFAULT_SPACING: f32 = 16.0;
 const ITEM_HEIGHT: f32 = 24.0;
+const DATE_TIME_DISPLAY_PATTERN: &str = "%Y-%m-%d %H:%M";
 
 pub struct PlacesScreen {
     pub places: Vec<Place>,
+    /// Every place, to build paths and the tree while searching.
+    pub all_places: Vec<Place>,
+    /// Places whose contents are hidden in the tree.
+    pub collapsed_places: HashSet<i32>,
     pub place_form_modal: Option<PlaceFormModal>,
+    pub place_stock_modal: Option<PlaceStockModal>,
+    pub print_labels_modal: Option<PrintLabelsModal>,
+    pub audit_history_modal: Option<AuditHistoryModal>,
+    pub selected_places: HashSet<i32>,
     pub place_to_delete: Option<Place>,
+    pub show_trash: bool,
+    pub deleted_places: Vec<Place>,
+    pub place_to_purge: Option<Place>,
     pub error: Option<Box<dyn Error>>,
     pub search: String,
 }
@@ -27,15 +45,24 @@ impl PlacesScreen {
         let places = PlacesScreen::get_places_list("");
 
         Self {
+            all_places: places.clone(),
+            collapsed_places: HashSet::new(),
             places,
             place_form_modal: None,
+            place_stock_modal: None,
+            print_labels_modal: None,
+            audit_history_modal: None,
+            selected_places: HashSet::new(),
             place_to_delete: None,
+            show_trash: false,
+            deleted_places: Vec::new(),
+            place_to_purge: None,
             error: None,
             search: String::new(),
         }
     }
 
-    pub fn ui(&mut self, ui: &mut egui::Ui) {
+    pub fn ui(&mut self, ui: &mut egui::Ui, trash_retention: &mut TrashRetention) {
         let add_place_btn = egui::Button::new(
             egui::RichText::new("Add Place").color(egui::Color32::WHITE)
         ).fill(ui.visuals().selection.bg_fill);
@@ -45,13 +72,30 @@ impl PlacesScreen {
 
             ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                 if ui.add(add_place_btn).clicked() {
-                    self.place_form_modal = Some(PlaceFormModal::new(None));
+                    self.place_form_modal = Some(PlaceFormModal::new(None, None, &self.all_places));
+                }
+
+                if ui.toggle_value(&mut self.show_trash, "Trash").on_hover_text("Deleted places").changed() && self.show_trash {
+                    self.reload_deleted_places();
+                }
+
+                // Labels carry the full path so bins with the same name can be told apart.
+                let selected: Vec<Place> = self.places.iter()
+                    .filter(|place| self.selected_places.contains(&place.id))
+                    .map(|place| Place { name: place_path(&self.all_places, place.id), ..place.clone() })
+                    .collect();
+
+                let print_labels_button = ui.add_enabled(!selected.is_empty(), egui::Button::new("Print Labels"))
+                    .on_disabled_hover_text("Select the places to print bin labels for");
+
+                if print_labels_button.clicked() {
+                    self.print_labels_modal = Some(PrintLabelsModal::new(LabelTarget::Places(selected)));
                 }
 
                 if ui.add(egui::Button::new("Export")).clicked() {
                     match FileDialog::new().set_file_name("places.xlsx").save_file() {
                         Some(path) => {
-                            let _ = export_places(&self.places, path);
+                            let _ = export_places(&self.places, &self.all_places, path);
                         },
                         None => {}
                     };
@@ -60,17 +104,17 @@ impl PlacesScreen {
                 if ui.add(egui::Button::new("Import")).clicked() {
                     if let Some(path) = FileDialog::new().pick_file() {
                         match import_places(path) {
-                            Ok(new_places_row) => {
+                            Ok(imported_places) => {
                                 let mut conn = db::establish_connection();
 
-                                let creation_result = place_repository::create_places(
-                                    &mut conn,
-                                    &new_places_row
-                                );
-
-                                if let Ok(mut created_places) = creation_result {
-                                    self.places.append(&mut created_places);
+                                for imported in imported_places {
+                                    if let Err(error) = place_repository::find_or_create_place_path(&mut conn, &imported.path, imported.place_type) {
+                                        self.error = Some(format!("{}: {}", imported.path, error).into());
+                                        break;
+                                    }
                                 }
+
+                                self.reload_places();
                             },
                             Err(error) => {
                                 self.error = Some(error);
@@ -80,20 +124,35 @@ impl PlacesScreen {
                 }
             
                 if ui.add(egui::TextEdit::singleline(&mut self.search).hint_text("Search for place...")).changed() {
-                    let filtered_places = PlacesScreen::get_places_list(&self.search);
-                    self.places = filtered_places;
+                    self.reload_places();
+
+                    if self.show_trash {
+                        self.reload_deleted_places();
+                    }
                 };
             });
         });
 
         ui.add_space(DEFAULT_SPACING);
 
+        if self.show_trash {
+            if trash_retention.ui(ui) {
+                self.reload_deleted_places();
+            }
+
+            ui.add_space(DEFAULT_SPACING / 2.0);
+        }
+
         StripBuilder::new(ui)
             .size(Size::remainder().at_least(100.0))
             .vertical(|mut strip| {
                 strip.cell(|ui| {
                     egui::ScrollArea::horizontal().show(ui, |ui| {
-                        self.places_table(ui);
+                        if self.show_trash {
+                            self.trash_table(ui);
+                        } else {
+                            self.places_table(ui);
+                        }
                     });
                 });
             });
@@ -104,20 +163,41 @@ impl PlacesScreen {
             if should_close {
                 self.place_form_modal = None;
 
-                if let Some(place) = upserted_place {
-                    if let Some(existing_place) = self.places.iter_mut().find(|p| p.id == place.id) {
-                        *existing_place = place;
-                    } else {
-                        self.places.push(place);
+                // Moving a place changes the paths and order of everything inside it.
+                if upserted_place.is_some() {
+                    self.reload_places();
+
+                    // The place may have been restored from the trash instead of created.
+                    if self.show_trash {
+                        self.reload_deleted_places();
                     }
                 }
             }
         }
 
+        if let Some(modal) = self.place_stock_modal.as_mut()
+            && modal.show(ui) {
+            self.place_stock_modal = None;
+        }
+
+        if let Some(modal) = self.print_labels_modal.as_mut()
+            && modal.show(ui) {
+            self.print_labels_modal = None;
+        }
+
+        if let Some(modal) = self.audit_history_modal.as_mut()
+            && modal.show(ui) {
+            self.audit_history_modal = None;
+        }
+
         if self.place_to_delete.is_some() {
             self.show_confirm_delete_alert(ui);
         }
 
+        if self.place_to_purge.is_some() {
+            self.show_confirm_purge_alert(ui);
+        }
+
         if self.error.is_some() {
            self.show_error_message(ui);
         }
@@ -125,11 +205,13 @@ impl PlacesScreen {
 
     fn places_table(&mut self, ui: &mut egui::Ui) {
         let avaiable_height = ui.available_height();
+        let visible_rows = self.visible_rows();
 
         TableBuilder::new(ui)
             .striped(true)
             .resizable(false)
             .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+            .column(Column::exact(24.0))
             .column(Column::exact(64.0))
             .column(
                 Column::remainder()
@@ -137,12 +219,28 @@ impl PlacesScreen {
                 .clip(true)
                 .resizable(false)
             )
-            .column(Column::exact(100.0))     
+            .column(Column::exact(88.0))
+            .column(Column::exact(350.0))
             .min_scrolled_height(0.0)
             .max_scroll_height(avaiable_height)
             .header(ITEM_HEIGHT,|mut header| {
+                header.col(|ui| {
+                    let mut all_selected = !self.places.is_empty()
+                        && self.places.iter().all(|place| self.selected_places.contains(&place.id));
+
+                    if ui.checkbox(&mut all_selected, "").on_hover_text("Select all").changed() {
+                        for place in &self.places {
+                            if all_selected {
+                                self.selected_places.insert(place.id);
+                            } else {
+                                self.selected_places.remove(&place.id);
+                            }
+                        }
+                    }
+                });
                 header.col(|ui| { ui.heading("ID"); });
                 header.col(|ui| { ui.heading("Name"); });
+                header.col(|ui| { ui.heading("Type"); });
                 header.col(|ui| { 
                     ui.with_layout(Layout::centered_and_justified(Direction::LeftToRight), |ui| {
                         ui.heading("Actions");
@@ -152,14 +250,51 @@ impl PlacesScreen {
             .body(|body| {
                 body.rows(
                     ITEM_HEIGHT,
-                    self.places.len(),
+                    visible_rows.len(),
                     |mut row| {
-                        let place_index = row.index();
+                        let (place_index, depth) = visible_rows[row.index()];
                         let place = &self.places[place_index];
 
 
+                        row.col(|ui| {
+                            let mut selected = self.selected_places.contains(&place.id);
+
+                            if ui.checkbox(&mut selected, "").changed() {
+                                if selected {
+                                    self.selected_places.insert(place.id);
+                                } else {
+                                    self.selected_places.remove(&place.id);
+                                }
+                            }
+                        });
                         row.col(|ui| { ui.label(place.id.to_string()); });
-                        row.col(|ui| { ui.label(place.name.clone()); });
+                        row.col(|ui| {
+                            if !self.search.is_empty() {
+                                ui.label(place_path(&self.all_places, place.id));
+                                return;
+                            }
+
+                            ui.add_space(depth as f32 * DEFAULT_SPACING);
+
+                            let has_children = self.all_places.iter().any(|child| child.parent_id == Some(place.id));
+
+                            if has_children {
+                                let collapsed = self.collapsed_places.contains(&place.id);
+
+                                if ui.small_button(if collapsed { "▸" } else { "▾" }).clicked() {
+                                    if collapsed {
+                                        self.collapsed_places.remove(&place.id);
+                                    } else {
+                                        self.collapsed_places.insert(place.id);
+                                    }
+                                }
+                            } else {
+                                ui.add_space(DEFAULT_SPACING + 2.0);
+                            }
+
+                            ui.label(place.name.clone());
+                        });
+                        row.col(|ui| { ui.label(place.place_type.label()); });
                         row.col(|ui| {
                             let delete_button = egui::Button::new(
                             egui::RichText::new("Delete").color(egui::Color32::WHITE)
@@ -170,7 +305,24 @@ impl PlacesScreen {
                             }
 
                             if ui.add(egui::Button::new("Edit")).clicked() {
-                                self.place_form_modal = Some(PlaceFormModal::new(Some(&place)));
+                                self.place_form_modal = Some(PlaceFormModal::new(Some(place), None, &self.all_places));
+                            }
+
+                            let add_inside_button = ui.add_enabled(
+                                PlaceType::ALL.iter().any(|child_type| place.place_type.can_contain(*child_type)),
+                                egui::Button::new("Add Inside")
+                            );
+
+                            if add_inside_button.clicked() {
+                                self.place_form_modal = Some(PlaceFormModal::new(None, Some(place.id), &self.all_places));
+                            }
+
+                            if ui.add(egui::Button::new("Contents")).clicked() {
+                                self.place_stock_modal = Some(PlaceStockModal::new(place));
+                            }
+
+                            if ui.add(egui::Button::new("History")).clicked() {
+                                self.audit_history_modal = Some(AuditHistoryModal::new(AuditEntity::Place, place.id, &place_path(&self.all_places, place.id)));
                             }
                         });
                     }
@@ -203,10 +355,9 @@ impl PlacesScreen {
                         if ui.add(egui::Button::new("Confirm")).clicked() {
                            let mut connection = db::establish_connection();
 
-                            if place_repository::delete_place(&mut connection, self.place_to_delete.as_ref().unwrap().id).is_ok() {
-                                if let Some(pos) = self.places.iter().position(|p| p.id == self.place_to_delete.as_ref().unwrap().id) {
-                                    self.places.remove(pos);
-                                }
+                            match place_repository::delete_place(&mut connection, self.place_to_delete.as_ref().unwrap().id) {
+                                Ok(_) => self.reload_places(),
+                                Err(error) => self.error = Some(error),
                             }
                            
                             self.place_to_delete = None;
@@ -225,6 +376,140 @@ impl PlacesScreen {
         }
     }
 
+    fn trash_table(&mut self, ui: &mut egui::Ui) {
+        let avaiable_height = ui.available_height();
+        let mut place_to_restore = None;
+
+        TableBuilder::new(ui)
+            .striped(true)
+            .resizable(false)
+            .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+            .column(Column::exact(64.0))
+            .column(
+                Column::remainder()
+                .at_least(120.0)
+                .clip(true)
+                .resizable(false)
+            )
+            .column(
+                Column::auto()
+                .at_least(120.0)
+                .at_most(260.0)
+                .clip(true)
+            )
+            .column(Column::exact(130.0))
+            .column(Column::exact(160.0))
+            .min_scrolled_height(0.0)
+            .max_scroll_height(avaiable_height)
+            .header(ITEM_HEIGHT, |mut header| {
+                header.col(|ui| { ui.heading("ID"); });
+                header.col(|ui| { ui.heading("Name"); });
+                header.col(|ui| { ui.heading("Inside"); });
+                header.col(|ui| { ui.heading("Deleted"); });
+                header.col(|ui| {
+                    ui.with_layout(Layout::centered_and_justified(Direction::LeftToRight), |ui| {
+                        ui.heading("Actions");
+                    });
+                });
+            })
+            .body(|body| {
+                body.rows(
+                    ITEM_HEIGHT,
+                    self.deleted_places.len(),
+                    |mut row| {
+                        let place = &self.deleted_places[row.index()];
+
+                        row.col(|ui| { ui.label(place.id.to_string()); });
+                        row.col(|ui| { ui.label(&place.name); });
+                        row.col(|ui| { ui.label(place.parent_id.map(|parent_id| place_path(&self.all_places, parent_id)).unwrap_or_default()); });
+                        row.col(|ui| {
+                            ui.label(place.deleted_at.map(|date| date.format(DATE_TIME_DISPLAY_PATTERN).to_string()).unwrap_or_default());
+                        });
+                        row.col(|ui| {
+                            let purge_button = egui::Button::new(
+                                egui::RichText::new("Purge").color(egui::Color32::WHITE)
+                            ).fill(ui.visuals().error_fg_color);
+
+                            if ui.add(purge_button).on_hover_text("Delete permanently").clicked() {
+                                self.place_to_purge = Some(place.clone());
+                            }
+
+                            if ui.add(egui::Button::new("Restore")).clicked() {
+                                place_to_restore = Some(place.id);
+                            }
+                        });
+                    }
+                );
+            });
+
+        if let Some(place_id) = place_to_restore {
+            let mut connection = db::establish_connection();
+
+            match place_repository::restore_place(&mut connection, place_id) {
+                Ok(_) => {
+                    self.deleted_places.retain(|place| place.id != place_id);
+                    self.reload_places();
+                }
+                Err(error) => self.error = Some(error),
+            }
+        }
+    }
+
+    fn show_confirm_purge_alert(&mut self, ui: &mut egui::Ui) {
+        let alert = egui::Modal::new(egui::Id::new("Purge Place"))
+            .show(ui.ctx(), |ui| {
+                ui.heading("Purge Place");
+
+                ui.separator();
+                ui.add_space(DEFAULT_SPACING / 2.0);
+
+                ui.add(
+                Label::new(
+                    format!("Permanently delete place '{}'? It cannot be restored afterwards.",
+                            self.place_to_purge.as_ref().unwrap().name)
+                        )
+                );
+
+                ui.add_space(DEFAULT_SPACING * 2.0);
+                ui.separator();
+
+                Sides::new().show(
+                    ui,
+                    |_ui| {},
+                    |ui| {
+                        if ui.add(egui::Button::new("Confirm")).clicked() {
+                            let mut connection = db::establish_connection();
+                            let place_id = self.place_to_purge.as_ref().unwrap().id;
+
+                            match place_repository::purge_place(&mut connection, place_id) {
+                                Ok(_) => self.deleted_places.retain(|place| place.id != place_id),
+                                Err(error) => self.error = Some(error),
+                            }
+
+                            self.place_to_purge = None;
+                        }
+
+                        if ui.add(egui::Button::new("Cancel")).clicked() {
+                            self.place_to_purge = None;
+                        }
+                    }
+                );
+            });
+
+        if alert.should_close() {
+            self.place_to_purge = None;
+        }
+    }
+
+    fn reload_deleted_places(&mut self) {
+        let mut connection = db::establish_connection();
+
+        match place_repository::list_deleted_places(&mut connection, &self.search) {
+            Ok(places) => self.deleted_places = places,
+            Err(error) => self.error = Some(error),
+        }
+    }
+
     fn show_error_message(&mut self, ui: &mut egui::Ui){
         if let Some(error) = &self.error {
             let error_message = error.to_string();
@@ -259,6 +544,48 @@ impl PlacesScreen {
         }
     }
 
+    /// Rows of the table as (index into `places`, depth). While searching the
+    /// matches are listed flat with their paths; otherwise places inside a
+    /// collapsed place are hidden.
+    fn visible_rows(&self) -> Vec<(usize, usize)> {
+        let mut rows = Vec::with_capacity(self.places.len());
+
+        for (index, place) in self.places.iter().enumerate() {
+            if !self.search.is_empty() {
+                rows.push((index, 0));
+                continue;
+            }
+
+            let mut depth = 0;
+            let mut hidden = false;
+            let mut parent_id = place.parent_id;
+
+            while let Some(id) = parent_id
+                && depth < self.all_places.len() {
+                hidden |= self.collapsed_places.contains(&id);
+                depth += 1;
+                parent_id = self.all_places.iter()
+                    .find(|parent| parent.id == id)
+                    .and_then(|parent| parent.parent_id);
+            }
+
+            if !hidden {
+                rows.push((index, depth));
+            }
+        }
+
+        rows
+    }
+
+    fn reload_places(&mut self) {
+        self.all_places = PlacesScreen::get_places_list("");
+        self.places = if self.search.is_empty() {
+            self.all_places.clone()
+        } else {
+            PlacesScreen::get_places_list(&self.search)
+        };
+    }
+
     fn get_places_list(search: &str) -> Vec<Place> {
         let mut connection = db::establish_connection();
 
@@ -1,41 +1,76 @@
 use eframe::egui;
-use egui::{Direction, Label, Layout, Sides};
+use egui::{Direction, Label, Layout, RichText, Sides};
 use egui_extras::{Size, StripBuilder, Column, TableBuilder};
 use rfd::FileDialog;
+use std::collections::{HashMap, HashSet};
 use std::error::Error;
 
 use crate::infra::db;
-use crate::infra::repositories::product_repository;
+use crate::infra::repositories::{category_repository, kit_repository, product_repository, reservation_repository};
+use crate::domain::audit::AuditEntity;
+use crate::domain::category::category_path;
+use crate::domain::kit::{KitComponent, buildable_kits};
 use crate::domain::product::Product;
+use crate::domain::stock_balance::ProductAvailability;
+use crate::egui::components::modals::audit_history_modal::AuditHistoryModal;
 use crate::egui::components::modals::product_form_modal::ProductFormModal;
+use crate::egui::components::modals::product_kit_modal::ProductKitModal;
+use crate::egui::components::modals::print_labels_modal::{LabelTarget, PrintLabelsModal};
+use crate::egui::components::modals::product_lots_modal::ProductLotsModal;
+use crate::egui::widgets::trash_retention::TrashRetention;
 use crate::services::export::export_products::export_products;
-use crate::services::import::import_products::import_products;
+use crate::egui::widgets::category_tree::CategoryTree;
+use crate::services::import::import_products::{ImportedProduct, import_products};
 
 const DEFAULT_SPACING: f32 = 16.0;
 const ITEM_HEIGHT: f32 = 24.0;
+const DATE_TIME_DISPLAY_PATTERN: &str = "%Y-%m-%d %H:%M";
 
 pub struct ProductsScreen {
     pub products: Vec<Product>,
+    pub stock: HashMap<i32, ProductAvailability>,
+    /// Components of each kit, keyed by the kit's product id.
+    pub kits: HashMap<i32, Vec<KitComponent>>,
+    pub category_tree: CategoryTree,
     pub product_form_modal: Option<ProductFormModal>,
+    pub product_lots_modal: Option<ProductLotsModal>,
+    pub product_kit_modal: Option<ProductKitModal>,
+    pub print_labels_modal: Option<PrintLabelsModal>,
+    pub audit_history_modal: Option<AuditHistoryModal>,
+    pub selected_products: HashSet<i32>,
     pub product_to_delete: Option<Product>,
+    pub show_trash: bool,
+    pub deleted_products: Vec<Product>,
+    pub product_to_purge: Option<Product>,
     pub error: Option<Box<dyn Error>>,
     pub search: String,
 }
 
 impl ProductsScreen {
     pub fn new() -> Self {
-        let products = ProductsScreen::get_products_list("");
+        let products = ProductsScreen::get_products_list("", None);
 
         Self {
             products: products,
+            stock: ProductsScreen::get_stock_map(),
+            kits: ProductsScreen::get_kits_map(),
+            category_tree: CategoryTree::new(),
             product_form_modal: None,
+            product_lots_modal: None,
+            product_kit_modal: None,
+            print_labels_modal: None,
+            audit_history_modal: None,
+            selected_products: HashSet::new(),
             product_to_delete: None,
+            show_trash: false,
+            deleted_products: Vec::new(),
+            product_to_purge: None,
             error: None,
             search: String::new(),
         }
     }
 
-    pub fn ui(&mut self, ui: &mut egui::Ui) {
+    pub fn ui(&mut self, ui: &mut egui::Ui, trash_retention: &mut TrashRetention) {
         let add_product_btn = egui::Button::new(
             egui::RichText::new("Add Product").color(egui::Color32::WHITE)
         ).fill(ui.visuals().selection.bg_fill);
@@ -47,10 +82,26 @@ impl ProductsScreen {
                     self.product_form_modal = Some(ProductFormModal::new(None));
                 }
 
+                if ui.toggle_value(&mut self.show_trash, "Trash").on_hover_text("Deleted products").changed() && self.show_trash {
+                    self.reload_deleted_products();
+                }
+
+                let selected: Vec<Product> = self.products.iter()
+                    .filter(|product| self.selected_products.contains(&product.id))
+                    .cloned()
+                    .collect();
+
+                let print_labels_button = ui.add_enabled(!selected.is_empty(), egui::Button::new("Print Labels"))
+                    .on_disabled_hover_text("Select the products to print labels for");
+
+                if print_labels_button.clicked() {
+                    self.print_labels_modal = Some(PrintLabelsModal::new(LabelTarget::Products(selected)));
+                }
+
                 if ui.add(egui::Button::new("Export")).clicked() {
                     match FileDialog::new().set_file_name("products.xlsx").save_file() {
                         Some(path) => {
-                            let _ = export_products(&self.products, path);
+                            let _ = export_products(&self.products, self.category_tree.categories(), path);
                         },
                         None => {}
                     };
@@ -59,16 +110,13 @@ impl ProductsScreen {
                 if ui.add(egui::Button::new("Import")).clicked() {
                     if let Some(path) = FileDialog::new().pick_file() {
                         match import_products(path) {
-                            Ok(new_products_row) => {
-                                let mut conn = db::establish_connection();
-
-                                let creation_result = product_repository::create_products(
-                                    &mut conn,
-                                    &new_products_row
-                                );
-
-                                if let Ok(mut created_products) = creation_result {
-                                    self.products.append(&mut created_products);
+                            Ok(imported_products) => {
+                                match ProductsScreen::create_imported_products(imported_products) {
+                                    Ok(_) => {
+                                        self.category_tree.reload();
+                                        self.reload_products();
+                                    }
+                                    Err(error) => self.error = Some(error),
                                 }
                             },
                             Err(error) => {
@@ -78,21 +126,55 @@ impl ProductsScreen {
                     }
                 }
             
-                if ui.add(egui::TextEdit::singleline(&mut self.search).hint_text("Search for product...")).changed() {
-                    let filtered_products = ProductsScreen::get_products_list(&self.search);
-                    self.products = filtered_products;
+                let search_input = ui.add(egui::TextEdit::singleline(&mut self.search).hint_text("Search by name, SKU or barcode..."));
+
+                if search_input.changed() {
+                    self.reload_products();
+
+                    if self.show_trash {
+                        self.reload_deleted_products();
+                    }
                 };
+
+                // Scanners type the code and press Enter: open the matching product.
+                if search_input.lost_focus() && ui.input(|input| input.key_pressed(egui::Key::Enter)) {
+                    let mut connection = db::establish_connection();
+
+                    match product_repository::find_product_by_code(&mut connection, &self.search) {
+                        Ok(Some(product)) => self.product_form_modal = Some(ProductFormModal::new(Some(&product))),
+                        Ok(None) => {}
+                        Err(error) => self.error = Some(error),
+                    }
+                }
             });
         });
 
         ui.add_space(DEFAULT_SPACING);
 
+        if self.show_trash {
+            if trash_retention.ui(ui) {
+                self.reload_deleted_products();
+            }
+
+            ui.add_space(DEFAULT_SPACING / 2.0);
+        }
+
         StripBuilder::new(ui)
+            .size(Size::exact(200.0))
             .size(Size::remainder().at_least(100.0))
-            .vertical(|mut strip| {
+            .horizontal(|mut strip| {
+                strip.cell(|ui| {
+                    if self.category_tree.ui(ui) {
+                        self.reload_products();
+                    }
+                });
                 strip.cell(|ui| {
                     egui::ScrollArea::horizontal().show(ui, |ui| {
-                        self.products_table(ui);
+                        if self.show_trash {
+                            self.trash_table(ui);
+                        } else {
+                            self.products_table(ui);
+                        }
                     });
                 });
             });
@@ -113,10 +195,36 @@ impl ProductsScreen {
             }
         }
 
+        if let Some(modal) = self.product_lots_modal.as_mut()
+            && modal.show(ui) {
+            self.product_lots_modal = None;
+        }
+
+        if let Some(modal) = self.product_kit_modal.as_mut()
+            && modal.show(ui) {
+            self.product_kit_modal = None;
+            self.stock = ProductsScreen::get_stock_map();
+            self.kits = ProductsScreen::get_kits_map();
+        }
+
+        if let Some(modal) = self.print_labels_modal.as_mut()
+            && modal.show(ui) {
+            self.print_labels_modal = None;
+        }
+
+        if let Some(modal) = self.audit_history_modal.as_mut()
+            && modal.show(ui) {
+            self.audit_history_modal = None;
+        }
+
         if self.product_to_delete.is_some() {
             self.show_confirm_delete_alert(ui);
         }
 
+        if self.product_to_purge.is_some() {
+            self.show_confirm_purge_alert(ui);
+        }
+
         if self.error.is_some() {
            self.show_error_message(ui);
         }
@@ -130,7 +238,14 @@ impl ProductsScreen {
             .resizable(false) 
             .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
             .min_scrolled_height(200.0) 
+            .column(Column::exact(24.0))
             .column(Column::exact(64.0))      
+            .column(
+                Column::auto()
+                .at_least(60.0)
+                .at_most(160.0)
+                .clip(true)
+            )
             .column(
                 Column::remainder()
                     .at_least(120.0)
@@ -143,27 +258,57 @@ impl ProductsScreen {
                 .at_most(240.0)
                 .clip(true)
             )      
+            .column(
+                Column::auto()
+                .at_least(80.0)
+                .at_most(240.0)
+                .clip(true)
+            )
+            .column(Column::exact(88.0))
+            .column(Column::exact(88.0))
+            .column(Column::exact(88.0))
+            .column(Column::exact(88.0))
+            .column(Column::exact(88.0))
             .column(Column::exact(88.0))
-            .column(Column::exact(88.0))        
             .column(
                 Column::auto()
                     .at_least(60.0)
                     .at_most(240.0)
                     .clip(true)
             )    
-            .column(Column::exact(100.0))     
+            .column(Column::exact(280.0))     
             .min_scrolled_height(0.0)
             .max_scroll_height(available_height)      
             .header(ITEM_HEIGHT, |mut header| {
+                header.col(|ui| {
+                    let mut all_selected = !self.products.is_empty()
+                        && self.products.iter().all(|product| self.selected_products.contains(&product.id));
+
+                    if ui.checkbox(&mut all_selected, "").on_hover_text("Select all").changed() {
+                        for product in &self.products {
+                            if all_selected {
+                                self.selected_products.insert(product.id);
+                            } else {
+                                self.selected_products.remove(&product.id);
+                            }
+                        }
+                    }
+                });
                 header.col(|ui| { ui.heading("ID"); });
+                header.col(|ui| { ui.heading("SKU"); });
                 header.col(|ui| { ui.heading("Name"); });
                 header.col(|ui| { ui.heading("Brand"); });
+                header.col(|ui| { ui.heading("Category"); });
                 header.col(|ui| {
                     ui.with_layout(Layout::centered_and_justified(Direction::LeftToRight), |ui| {
                         ui.heading("Unity");
                     });
                 });
                 header.col(|ui| { ui.heading("Min Stock"); });
+                header.col(|ui| { ui.heading("On Hand"); });
+                header.col(|ui| { ui.heading("Reserved"); });
+                header.col(|ui| { ui.heading("Available"); });
+                header.col(|ui| { ui.heading("Buildable"); });
                 header.col(|ui| { ui.heading("Observation"); });
                 header.col(|ui| { 
                     ui.with_layout(Layout::centered_and_justified(Direction::LeftToRight), |ui| {
@@ -179,16 +324,60 @@ impl ProductsScreen {
                         let product_index = row.index();
                         let product = &self.products[product_index];
 
-                        row.col(|ui| { ui.label(product.id.to_string()); });
-                        row.col(|ui| { ui.label(&product.name); });
-                        row.col(|ui| { ui.label(product.brand.clone().unwrap_or_default()); });
+                        let stock = self.stock.get(&product.id).cloned().unwrap_or_default();
+                        let low_stock = product.is_low_stock(stock.on_hand);
+
+                        // Products at or below their minimum are shown in red.
+                        let cell_text = |ui: &egui::Ui, text: String| {
+                            if low_stock {
+                                RichText::new(text).color(ui.visuals().error_fg_color)
+                            } else {
+                                RichText::new(text)
+                            }
+                        };
+
+                        row.col(|ui| {
+                            let mut selected = self.selected_products.contains(&product.id);
+
+                            if ui.checkbox(&mut selected, "").changed() {
+                                if selected {
+                                    self.selected_products.insert(product.id);
+                                } else {
+                                    self.selected_products.remove(&product.id);
+                                }
+                            }
+                        });
+                        row.col(|ui| { ui.label(cell_text(ui, product.id.to_string())); });
+                        row.col(|ui| { ui.label(cell_text(ui, product.sku.clone().unwrap_or_default())); });
+                        row.col(|ui| { ui.label(cell_text(ui, product.name.clone())); });
+                        row.col(|ui| { ui.label(cell_text(ui, product.brand.clone().unwrap_or_default())); });
+                        row.col(|ui| {
+                            let path = product.category_id
+                                .map(|category_id| category_path(self.category_tree.categories(), category_id))
+                                .unwrap_or_default();
+
+                            ui.label(cell_text(ui, path));
+                        });
                         row.col(|ui| {
                             ui.with_layout(Layout::centered_and_justified(Direction::LeftToRight), |ui| {
-                                ui.label(product.unity.clone().unwrap_or_default());
+                                ui.label(cell_text(ui, product.unity.clone().unwrap_or_default()));
                             });
                         });
-                        row.col(|ui| { ui.label(product.min_stock.to_string()); });
-                        row.col(|ui| { ui.label(product.observation.clone().unwrap_or_default()); });
+                        row.col(|ui| { ui.label(cell_text(ui, product.min_stock.to_string())); });
+                        row.col(|ui| { ui.label(cell_text(ui, stock.on_hand.to_string())); });
+                        row.col(|ui| { ui.label(cell_text(ui, stock.reserved.to_string())); });
+                        row.col(|ui| { ui.label(cell_text(ui, stock.available().to_string())); });
+                        row.col(|ui| {
+                            let buildable = self.kits.get(&product.id).map(|components| {
+                                buildable_kits(components, |product_id| {
+                                    self.stock.get(&product_id).map(|stock| stock.available()).unwrap_or_default()
+                                })
+                            });
+
+                            ui.label(cell_text(ui, buildable.map(|kits| kits.to_string()).unwrap_or_default()))
+                                .on_hover_text("Kits that can be assembled from the available components");
+                        });
+                        row.col(|ui| { ui.label(cell_text(ui, product.observation.clone().unwrap_or_default())); });
                         row.col(|ui| {
                             let delete_button = egui::Button::new(
                                 egui::RichText::new("Delete").color(egui::Color32::WHITE)
@@ -201,6 +390,18 @@ impl ProductsScreen {
                             if ui.add(egui::Button::new("Edit")).clicked() {
                                 self.product_form_modal = Some(ProductFormModal::new(Some(&product)));
                             }
+
+                            if product.tracks_lots && ui.add(egui::Button::new("Lots")).clicked() {
+                                self.product_lots_modal = Some(ProductLotsModal::new(product));
+                            }
+
+                            if ui.add(egui::Button::new("Kit")).on_hover_text("Components, assembly and disassembly").clicked() {
+                                self.product_kit_modal = Some(ProductKitModal::new(product));
+                            }
+
+                            if ui.add(egui::Button::new("History")).clicked() {
+                                self.audit_history_modal = Some(AuditHistoryModal::new(AuditEntity::Product, product.id, &product.name));
+                            }
                         });
                     }
                 );          
@@ -253,6 +454,140 @@ impl ProductsScreen {
         }
     }
 
+    fn trash_table(&mut self, ui: &mut egui::Ui) {
+        let avaiable_height = ui.available_height();
+        let mut product_to_restore = None;
+
+        TableBuilder::new(ui)
+            .striped(true)
+            .resizable(false)
+            .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+            .column(Column::exact(64.0))
+            .column(
+                Column::remainder()
+                .at_least(120.0)
+                .clip(true)
+                .resizable(false)
+            )
+            .column(
+                Column::auto()
+                .at_least(120.0)
+                .at_most(260.0)
+                .clip(true)
+            )
+            .column(Column::exact(130.0))
+            .column(Column::exact(160.0))
+            .min_scrolled_height(0.0)
+            .max_scroll_height(avaiable_height)
+            .header(ITEM_HEIGHT, |mut header| {
+                header.col(|ui| { ui.heading("ID"); });
+                header.col(|ui| { ui.heading("Name"); });
+                header.col(|ui| { ui.heading("SKU"); });
+                header.col(|ui| { ui.heading("Deleted"); });
+                header.col(|ui| {
+                    ui.with_layout(Layout::centered_and_justified(Direction::LeftToRight), |ui| {
+                        ui.heading("Actions");
+                    });
+                });
+            })
+            .body(|body| {
+                body.rows(
+                    ITEM_HEIGHT,
+                    self.deleted_products.len(),
+                    |mut row| {
+                        let product = &self.deleted_products[row.index()];
+
+                        row.col(|ui| { ui.label(product.id.to_string()); });
+                        row.col(|ui| { ui.label(&product.name); });
+                        row.col(|ui| { ui.label(product.sku.clone().unwrap_or_default()); });
+                        row.col(|ui| {
+                            ui.label(product.deleted_at.map(|date| date.format(DATE_TIME_DISPLAY_PATTERN).to_string()).unwrap_or_default());
+                        });
+                        row.col(|ui| {
+                            let purge_button = egui::Button::new(
+                                egui::RichText::new("Purge").color(egui::Color32::WHITE)
+                            ).fill(ui.visuals().error_fg_color);
+
+                            if ui.add(purge_button).on_hover_text("Delete permanently").clicked() {
+                                self.product_to_purge = Some(product.clone());
+                            }
+
+                            if ui.add(egui::Button::new("Restore")).clicked() {
+                                product_to_restore = Some(product.id);
+                            }
+                        });
+                    }
+                );
+            });
+
+        if let Some(product_id) = product_to_restore {
+            let mut connection = db::establish_connection();
+
+            match product_repository::restore_product(&mut connection, product_id) {
+                Ok(_) => {
+                    self.deleted_products.retain(|product| product.id != product_id);
+                    self.reload_products();
+                }
+                Err(error) => self.error = Some(error),
+            }
+        }
+    }
+
+    fn show_confirm_purge_alert(&mut self, ui: &mut egui::Ui) {
+        let alert = egui::Modal::new(egui::Id::new("Purge Product"))
+            .show(ui.ctx(), |ui| {
+                ui.heading("Purge Product");
+
+                ui.separator();
+                ui.add_space(DEFAULT_SPACING / 2.0);
+
+                ui.add(
+                Label::new(
+                    format!("Permanently delete product '{}'? It cannot be restored afterwards.",
+                            self.product_to_purge.as_ref().unwrap().name)
+                        )
+                );
+
+                ui.add_space(DEFAULT_SPACING * 2.0);
+                ui.separator();
+
+                Sides::new().show(
+                    ui,
+                    |_ui| {},
+                    |ui| {
+                        if ui.add(egui::Button::new("Confirm")).clicked() {
+                            let mut connection = db::establish_connection();
+                            let product_id = self.product_to_purge.as_ref().unwrap().id;
+
+                            match product_repository::purge_product(&mut connection, product_id) {
+                                Ok(_) => self.deleted_products.retain(|product| product.id != product_id),
+                                Err(error) => self.error = Some(error),
+                            }
+
+                            self.product_to_purge = None;
+                        }
+
+                        if ui.add(egui::Button::new("Cancel")).clicked() {
+                            self.product_to_purge = None;
+                        }
+                    }
+                );
+            });
+
+        if alert.should_close() {
+            self.product_to_purge = None;
+        }
+    }
+
+    fn reload_deleted_products(&mut self) {
+        let mut connection = db::establish_connection();
+
+        match product_repository::list_deleted_products(&mut connection, &self.search) {
+            Ok(products) => self.deleted_products = products,
+            Err(error) => self.error = Some(error),
+        }
+    }
+
     fn show_error_message(&mut self, ui: &mut egui::Ui){
         if let Some(error) = &self.error {
             let error_message = error.to_string();
@@ -287,12 +622,53 @@ impl ProductsScreen {
         }
     }
 
-    fn get_products_list(search: &str) -> Vec<Product> {
+    fn reload_products(&mut self) {
+        let category_ids = self.category_tree.selected_category_ids();
+
+        self.products = ProductsScreen::get_products_list(&self.search, category_ids.as_deref());
+    }
+
+    fn get_products_list(search: &str, category_ids: Option<&[i32]>) -> Vec<Product> {
         let mut connection = db::establish_connection();
 
-        let products = product_repository::list_products(&mut connection, search)
+        let products = product_repository::list_products_in_categories(&mut connection, search, category_ids)
             .unwrap_or_default();
 
         products
     }
+
+    /// Creates the categories named in the imported paths before the products.
+    fn create_imported_products(imported_products: Vec<ImportedProduct>) -> Result<Vec<Product>, Box<dyn Error>> {
+        let mut connection = db::establish_connection();
+
+        let mut new_products_row = Vec::with_capacity(imported_products.len());
+
+        for imported in imported_products {
+            let mut product = imported.product;
+
+            if let Some(path) = &imported.category_path {
+                product.category_id = category_repository::find_or_create_category_path(&mut connection, path)?;
+            }
+
+            new_products_row.push(product);
+        }
+
+        product_repository::create_products(&mut connection, new_products_row)
+    }
+
+    fn get_stock_map() -> HashMap<i32, ProductAvailability> {
+        let mut connection = db::establish_connection();
+
+        reservation_repository::list_product_availability(&mut connection)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|stock| (stock.product_id, stock))
+            .collect()
+    }
+
+    fn get_kits_map() -> HashMap<i32, Vec<KitComponent>> {
+        let mut connection = db::establish_connection();
+
+        kit_repository::list_kits(&mut connection).unwrap_or_default()
+    }
 }
@@ -0,0 +1,331 @@
+use eframe::egui;
+use egui::{Direction, Layout, RichText, Sides};
+use egui_extras::{Column, Size, StripBuilder, TableBuilder};
+use rfd::FileDialog;
+use std::error::Error;
+
+use crate::infra::db;
+use crate::infra::repositories::{product_repository, purchase_order_repository, supplier_repository, unit_repository};
+use crate::domain::product::Product;
+use crate::domain::purchase_order::{PurchaseOrder, PurchaseOrderLine, PurchaseOrderStatus};
+use crate::domain::supplier::Supplier;
+use crate::domain::unit::Unit;
+use crate::egui::components::modals::goods_receipt_modal::GoodsReceiptModal;
+use crate::egui::components::modals::purchase_order_form_modal::PurchaseOrderFormModal;
+use crate::egui::components::modals::purchase_order_line_form_modal::PurchaseOrderLineFormModal;
+use crate::services::export::export_purchase_order::export_purchase_order;
+
+const DEFAULT_SPACING: f32 = 16.0;
+const ITEM_HEIGHT: f32 = 24.0;
+
+pub struct PurchaseOrderDetailScreen {
+    pub order: PurchaseOrder,
+    pub supplier: Option<Supplier>,
+    pub lines: Vec<PurchaseOrderLine>,
+    pub products: Vec<Product>,
+    pub suppliers: Vec<Supplier>,
+    pub units: Vec<Unit>,
+    pub order_form_modal: Option<PurchaseOrderFormModal>,
+    pub line_form_modal: Option<PurchaseOrderLineFormModal>,
+    pub goods_receipt_modal: Option<GoodsReceiptModal>,
+    pub error: Option<Box<dyn Error>>,
+}
+
+impl PurchaseOrderDetailScreen {
+    pub fn new(order: PurchaseOrder) -> Self {
+        let mut connection = db::establish_connection();
+
+        Self {
+            supplier: supplier_repository::get_supplier_by_id(&mut connection, order.supplier_id).ok(),
+            lines: purchase_order_repository::list_purchase_order_lines(&mut connection, order.id).unwrap_or_default(),
+            products: product_repository::list_products(&mut connection, "").unwrap_or_default(),
+            suppliers: supplier_repository::list_suppliers(&mut connection, "").unwrap_or_default(),
+            units: unit_repository::list_units(&mut connection, "").unwrap_or_default(),
+            order,
+            order_form_modal: None,
+            line_form_modal: None,
+            goods_receipt_modal: None,
+            error: None,
+        }
+    }
+
+    /// Renders the order and returns `true` when the user asks to go back to the list.
+    pub fn ui(&mut self, ui: &mut egui::Ui) -> bool {
+        let mut go_back = false;
+        let editable = self.order.status.is_editable();
+
+        ui.horizontal(|ui| {
+            if ui.button("Back").clicked() {
+                go_back = true;
+            }
+
+            ui.heading(format!("Purchase Order #{}", self.order.id));
+            ui.label(RichText::new(self.order.status.label()).strong());
+
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if editable {
+                    let add_line_btn = egui::Button::new(
+                        egui::RichText::new("Add Line").color(egui::Color32::WHITE)
+                    ).fill(ui.visuals().selection.bg_fill);
+
+                    if ui.add(add_line_btn).clicked() {
+                        self.line_form_modal = Some(PurchaseOrderLineFormModal::new(&self.order, None, &self.products));
+                    }
+                }
+
+                if self.order.status.is_receivable() {
+                    let receive_btn = egui::Button::new(
+                        egui::RichText::new("Receive").color(egui::Color32::WHITE)
+                    ).fill(ui.visuals().selection.bg_fill);
+
+                    if ui.add(receive_btn).clicked() {
+                        self.goods_receipt_modal = Some(GoodsReceiptModal::new(&self.order, &self.lines, &self.products));
+                    }
+                }
+
+                if ui.add(egui::Button::new("Export")).clicked()
+                    && let Some(supplier) = &self.supplier
+                    && let Some(path) = FileDialog::new().set_file_name(format!("purchase_order_{}.xlsx", self.order.id)).save_file() {
+                    let _ = export_purchase_order(&self.order, supplier, &self.lines, &self.products, &self.units, path);
+                }
+
+                if self.order.status.can_transition_to(PurchaseOrderStatus::Cancelled)
+                    && ui.add(egui::Button::new("Cancel Order")).clicked() {
+                    self.change_status(PurchaseOrderStatus::Cancelled);
+                }
+
+                if self.order.status.can_transition_to(PurchaseOrderStatus::Sent)
+                    && ui.add(egui::Button::new("Mark as Sent")).clicked() {
+                    self.change_status(PurchaseOrderStatus::Sent);
+                }
+
+                if editable && ui.add(egui::Button::new("Edit")).clicked() {
+                    self.order_form_modal = Some(PurchaseOrderFormModal::new(Some(&self.order), &self.suppliers));
+                }
+            });
+        });
+
+        ui.add_space(DEFAULT_SPACING / 2.0);
+
+        egui::Grid::new("purchase_order_header").num_columns(2).show(ui, |ui| {
+            ui.label("Supplier");
+            ui.label(self.supplier.as_ref().map(|supplier| supplier.name.clone()).unwrap_or_default());
+            ui.end_row();
+
+            ui.label("Date");
+            ui.label(self.order.created_at.format("%Y-%m-%d").to_string());
+            ui.end_row();
+
+            ui.label("Expected");
+            ui.label(self.order.expected_date.map(|date| date.to_string()).unwrap_or_default());
+            ui.end_row();
+
+            ui.label("Notes");
+            ui.label(self.order.notes.clone().unwrap_or_default());
+            ui.end_row();
+
+            ui.label("Total");
+            let order_total: f64 = self.lines.iter().filter_map(|line| line.total_cost()).sum();
+            ui.label(RichText::new(format!("{:.2}", order_total)).strong());
+            ui.end_row();
+        });
+
+        ui.add_space(DEFAULT_SPACING);
+
+        StripBuilder::new(ui)
+            .size(Size::remainder().at_least(100.0))
+            .vertical(|mut strip| {
+                strip.cell(|ui| {
+                    egui::ScrollArea::horizontal().show(ui, |ui| {
+                        self.lines_table(ui, editable);
+                    });
+                });
+            });
+
+        if let Some(modal) = self.order_form_modal.as_mut() {
+            let (should_close, updated_order) = modal.show(ui);
+
+            if should_close {
+                self.order_form_modal = None;
+
+                if let Some(order) = updated_order {
+                    let mut connection = db::establish_connection();
+
+                    self.supplier = supplier_repository::get_supplier_by_id(&mut connection, order.supplier_id).ok();
+                    self.order = order;
+                }
+            }
+        }
+
+        if let Some(modal) = self.line_form_modal.as_mut() {
+            let (should_close, saved_line) = modal.show(ui);
+
+            if should_close {
+                self.line_form_modal = None;
+
+                if let Some(line) = saved_line {
+                    if let Some(existing_line) = self.lines.iter_mut().find(|l| l.id == line.id) {
+                        *existing_line = line;
+                    } else {
+                        self.lines.push(line);
+                    }
+                }
+            }
+        }
+
+        if let Some(modal) = self.goods_receipt_modal.as_mut() {
+            let (should_close, updated_order) = modal.show(ui);
+
+            if should_close {
+                self.goods_receipt_modal = None;
+
+                if let Some(order) = updated_order {
+                    let mut connection = db::establish_connection();
+
+                    self.lines = purchase_order_repository::list_purchase_order_lines(&mut connection, order.id).unwrap_or_default();
+                    self.order = order;
+                }
+            }
+        }
+
+        if self.error.is_some() {
+           self.show_error_message(ui);
+        }
+
+        go_back
+    }
+
+    fn lines_table(&mut self, ui: &mut egui::Ui, editable: bool) {
+        let avaiable_height = ui.available_height();
+        let mut line_to_remove = None;
+
+        TableBuilder::new(ui)
+            .striped(true)
+            .resizable(false)
+            .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+            .column(
+                Column::remainder()
+                .at_least(160.0)
+                .clip(true)
+                .resizable(false)
+            )
+            .column(Column::exact(88.0))
+            .column(Column::exact(64.0))
+            .column(Column::exact(88.0))
+            .column(Column::exact(100.0))
+            .column(Column::exact(100.0))
+            .column(Column::exact(120.0))
+            .min_scrolled_height(0.0)
+            .max_scroll_height(avaiable_height)
+            .header(ITEM_HEIGHT, |mut header| {
+                header.col(|ui| { ui.heading("Product"); });
+                header.col(|ui| { ui.heading("Quantity"); });
+                header.col(|ui| { ui.heading("Unit"); });
+                header.col(|ui| { ui.heading("Received"); });
+                header.col(|ui| { ui.heading("Unit Cost"); });
+                header.col(|ui| { ui.heading("Total"); });
+                header.col(|ui| {
+                    ui.with_layout(Layout::centered_and_justified(Direction::LeftToRight), |ui| {
+                        ui.heading("Actions");
+                    });
+                });
+            })
+            .body(|body| {
+                body.rows(
+                    ITEM_HEIGHT,
+                    self.lines.len(),
+                    |mut row| {
+                        let line = &self.lines[row.index()];
+
+                        let product = self.products.iter().find(|product| product.id == line.product_id);
+
+                        let product_name = product
+                            .map(|product| product.name.clone())
+                            .unwrap_or_else(|| format!("#{}", line.product_id));
+
+                        let unit_symbol = line.unit_id
+                            .and_then(|unit_id| self.units.iter().find(|unit| unit.id == unit_id))
+                            .map(|unit| unit.symbol.clone())
+                            .or_else(|| product.and_then(|product| product.unity.clone()))
+                            .unwrap_or_default();
+
+                        row.col(|ui| { ui.label(product_name); });
+                        row.col(|ui| { ui.label(line.quantity.to_string()); });
+                        row.col(|ui| { ui.label(unit_symbol); });
+                        row.col(|ui| { ui.label(line.received_quantity.to_string()); });
+                        row.col(|ui| { ui.label(line.unit_cost.map(|cost| format!("{:.2}", cost)).unwrap_or_default()); });
+                        row.col(|ui| { ui.label(line.total_cost().map(|total| format!("{:.2}", total)).unwrap_or_default()); });
+                        row.col(|ui| {
+                            if !editable {
+                                return;
+                            }
+
+                            let remove_button = egui::Button::new(
+                                egui::RichText::new("Remove").color(egui::Color32::WHITE)
+                            ).fill(ui.visuals().error_fg_color);
+
+                            if ui.add(remove_button).clicked() {
+                                line_to_remove = Some(line.id);
+                            }
+
+                            if ui.add(egui::Button::new("Edit")).clicked() {
+                                self.line_form_modal = Some(PurchaseOrderLineFormModal::new(&self.order, Some(line), &self.products));
+                            }
+                        });
+                    }
+                );
+            });
+
+        if let Some(line_id) = line_to_remove {
+            let mut connection = db::establish_connection();
+
+            match purchase_order_repository::delete_purchase_order_line(&mut connection, line_id) {
+                Ok(_) => self.lines.retain(|line| line.id != line_id),
+                Err(error) => self.error = Some(error),
+            }
+        }
+    }
+
+    fn change_status(&mut self, status: PurchaseOrderStatus) {
+        let mut connection = db::establish_connection();
+
+        match purchase_order_repository::update_purchase_order_status(&mut connection, self.order.id, status) {
+            Ok(order) => self.order = order,
+            Err(error) => self.error = Some(error),
+        }
+    }
+
+    fn show_error_message(&mut self, ui: &mut egui::Ui){
+        if let Some(error) = &self.error {
+            let error_message = error.to_string();
+
+            let error_alert = egui::Modal::new(egui::Id::new("Error Message"))
+                .show(ui.ctx(), |ui| {
+                    ui.heading("Error");
+
+                    ui.separator();
+                    ui.add_space(DEFAULT_SPACING / 2.0);
+
+                    ui.label(error_message);
+
+                    ui.add_space(DEFAULT_SPACING);
+                    ui.separator();
+
+
+                    Sides::new().show(
+                        ui,
+                        |_ui| {},
+                        |ui| {
+                            if ui.button("OK").clicked() {
+                                self.error = None;
+                            }
+                        }
+                    );
+                });
+
+            if error_alert.should_close() {
+                self.error = None;
+            }
+        }
+    }
+}
@@ -0,0 +1,297 @@
+use eframe::egui;
+use egui::{ComboBox, Direction, Label, Layout, Sides};
+use egui_extras::{Column, Size, StripBuilder, TableBuilder};
+use std::error::Error;
+
+use crate::infra::db;
+use crate::infra::repositories::{purchase_order_repository, supplier_repository};
+use crate::infra::repositories::purchase_order_repository::PurchaseOrderFilter;
+use crate::domain::audit::AuditEntity;
+use crate::domain::purchase_order::{PurchaseOrder, PurchaseOrderStatus};
+use crate::domain::supplier::Supplier;
+use crate::egui::components::modals::audit_history_modal::AuditHistoryModal;
+use crate::egui::components::modals::purchase_order_form_modal::PurchaseOrderFormModal;
+use crate::egui::screens::purchase_order_detail_screen::PurchaseOrderDetailScreen;
+
+const DEFAULT_SPACING: f32 = 16.0;
+const ITEM_HEIGHT: f32 = 24.0;
+
+pub struct PurchaseOrdersScreen {
+    pub purchase_orders: Vec<PurchaseOrder>,
+    pub suppliers: Vec<Supplier>,
+    pub purchase_order_form_modal: Option<PurchaseOrderFormModal>,
+    pub audit_history_modal: Option<AuditHistoryModal>,
+    pub purchase_order_to_delete: Option<PurchaseOrder>,
+    pub detail_screen: Option<PurchaseOrderDetailScreen>,
+    pub error: Option<Box<dyn Error>>,
+    pub filter: PurchaseOrderFilter,
+}
+
+impl PurchaseOrdersScreen {
+    pub fn new() -> Self {
+        let filter = PurchaseOrderFilter::default();
+        let mut connection = db::establish_connection();
+
+        Self {
+            purchase_orders: PurchaseOrdersScreen::get_purchase_orders_list(&filter),
+            suppliers: supplier_repository::list_suppliers(&mut connection, "").unwrap_or_default(),
+            purchase_order_form_modal: None,
+            audit_history_modal: None,
+            purchase_order_to_delete: None,
+            detail_screen: None,
+            error: None,
+            filter,
+        }
+    }
+
+    pub fn ui(&mut self, ui: &mut egui::Ui) {
+        if let Some(detail_screen) = self.detail_screen.as_mut() {
+            if detail_screen.ui(ui) {
+                self.detail_screen = None;
+                self.purchase_orders = PurchaseOrdersScreen::get_purchase_orders_list(&self.filter);
+            }
+
+            return;
+        }
+
+        let add_order_btn = egui::Button::new(
+            egui::RichText::new("New Purchase Order").color(egui::Color32::WHITE)
+        ).fill(ui.visuals().selection.bg_fill);
+
+        ui.horizontal(|ui| {
+            ui.heading("Purchase Orders");
+
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if ui.add(add_order_btn).clicked() {
+                    self.purchase_order_form_modal = Some(PurchaseOrderFormModal::new(None, &self.suppliers));
+                }
+
+                let mut filter_changed = false;
+
+                ComboBox::from_id_salt("purchase_order_status_filter")
+                    .selected_text(self.filter.status.map(|status| status.label()).unwrap_or("All statuses"))
+                    .show_ui(ui, |ui| {
+                        filter_changed |= ui.selectable_value(&mut self.filter.status, None, "All statuses").changed();
+                        for status in PurchaseOrderStatus::ALL {
+                            filter_changed |= ui.selectable_value(&mut self.filter.status, Some(status), status.label()).changed();
+                        }
+                    });
+
+                let supplier_name = self.suppliers.iter()
+                    .find(|supplier| Some(supplier.id) == self.filter.supplier_id)
+                    .map(|supplier| supplier.name.clone())
+                    .unwrap_or_else(|| "All suppliers".to_owned());
+
+                ComboBox::from_id_salt("purchase_order_supplier_filter")
+                    .selected_text(supplier_name)
+                    .show_ui(ui, |ui| {
+                        filter_changed |= ui.selectable_value(&mut self.filter.supplier_id, None, "All suppliers").changed();
+                        for supplier in &self.suppliers {
+                            filter_changed |= ui.selectable_value(&mut self.filter.supplier_id, Some(supplier.id), &supplier.name).changed();
+                        }
+                    });
+
+                if filter_changed {
+                    self.purchase_orders = PurchaseOrdersScreen::get_purchase_orders_list(&self.filter);
+                }
+            });
+        });
+
+        ui.add_space(DEFAULT_SPACING);
+
+        StripBuilder::new(ui)
+            .size(Size::remainder().at_least(100.0))
+            .vertical(|mut strip| {
+                strip.cell(|ui| {
+                    egui::ScrollArea::horizontal().show(ui, |ui| {
+                        self.purchase_orders_table(ui);
+                    });
+                });
+            });
+
+        if let Some(modal) = self.purchase_order_form_modal.as_mut() {
+            let (should_close, created_order) = modal.show(ui);
+
+            if should_close {
+                self.purchase_order_form_modal = None;
+
+                if let Some(order) = created_order {
+                    self.detail_screen = Some(PurchaseOrderDetailScreen::new(order));
+                }
+            }
+        }
+
+        if let Some(modal) = self.audit_history_modal.as_mut()
+            && modal.show(ui) {
+            self.audit_history_modal = None;
+        }
+
+        if self.purchase_order_to_delete.is_some() {
+            self.show_confirm_delete_alert(ui);
+        }
+
+        if self.error.is_some() {
+           self.show_error_message(ui);
+        }
+    }
+
+    fn purchase_orders_table(&mut self, ui: &mut egui::Ui) {
+        let avaiable_height = ui.available_height();
+
+        TableBuilder::new(ui)
+            .striped(true)
+            .resizable(false)
+            .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+            .column(Column::exact(64.0))
+            .column(Column::exact(100.0))
+            .column(
+                Column::remainder()
+                .at_least(120.0)
+                .clip(true)
+                .resizable(false)
+            )
+            .column(Column::exact(140.0))
+            .column(Column::exact(100.0))
+            .column(Column::exact(190.0))
+            .min_scrolled_height(0.0)
+            .max_scroll_height(avaiable_height)
+            .header(ITEM_HEIGHT, |mut header| {
+                header.col(|ui| { ui.heading("ID"); });
+                header.col(|ui| { ui.heading("Date"); });
+                header.col(|ui| { ui.heading("Supplier"); });
+                header.col(|ui| { ui.heading("Status"); });
+                header.col(|ui| { ui.heading("Expected"); });
+                header.col(|ui| {
+                    ui.with_layout(Layout::centered_and_justified(Direction::LeftToRight), |ui| {
+                        ui.heading("Actions");
+                    });
+                });
+            })
+            .body(|body| {
+                body.rows(
+                    ITEM_HEIGHT,
+                    self.purchase_orders.len(),
+                    |mut row| {
+                        let order = &self.purchase_orders[row.index()];
+
+                        let supplier_name = self.suppliers.iter()
+                            .find(|supplier| supplier.id == order.supplier_id)
+                            .map(|supplier| supplier.name.clone())
+                            .unwrap_or_else(|| format!("#{}", order.supplier_id));
+
+                        row.col(|ui| { ui.label(order.id.to_string()); });
+                        row.col(|ui| { ui.label(order.created_at.format("%Y-%m-%d").to_string()); });
+                        row.col(|ui| { ui.label(supplier_name); });
+                        row.col(|ui| { ui.label(order.status.label()); });
+                        row.col(|ui| { ui.label(order.expected_date.map(|date| date.to_string()).unwrap_or_default()); });
+                        row.col(|ui| {
+                            if order.status.is_editable() {
+                                let delete_button = egui::Button::new(
+                                    egui::RichText::new("Delete").color(egui::Color32::WHITE)
+                                ).fill(ui.visuals().error_fg_color);
+
+                                if ui.add(delete_button).clicked() {
+                                    self.purchase_order_to_delete = Some(order.clone());
+                                }
+                            }
+
+                            if ui.add(egui::Button::new("Open")).clicked() {
+                                self.detail_screen = Some(PurchaseOrderDetailScreen::new(order.clone()));
+                            }
+
+                            if ui.add(egui::Button::new("History")).clicked() {
+                                self.audit_history_modal = Some(AuditHistoryModal::new(AuditEntity::PurchaseOrder, order.id, &format!("#{}", order.id)));
+                            }
+                        });
+                    }
+                );
+            });
+    }
+
+    fn show_confirm_delete_alert(&mut self, ui: &mut egui::Ui) {
+        let alert = egui::Modal::new(egui::Id::new("Delete Purchase Order"))
+            .show(ui.ctx(), |ui| {
+                ui.heading("Delete Purchase Order");
+
+                ui.separator();
+                ui.add_space(DEFAULT_SPACING / 2.0);
+
+                ui.add(
+                Label::new(
+                    format!("Are you sure you want to delete purchase order #{}?",
+                            self.purchase_order_to_delete.as_ref().unwrap().id)
+                        )
+                );
+
+                ui.add_space(DEFAULT_SPACING * 2.0);
+                ui.separator();
+
+                Sides::new().show(
+                    ui,
+                    |_ui| {},
+                    |ui| {
+                        if ui.add(egui::Button::new("Confirm")).clicked() {
+                            let mut connection = db::establish_connection();
+                            let order_id = self.purchase_order_to_delete.as_ref().unwrap().id;
+
+                            match purchase_order_repository::delete_purchase_order(&mut connection, order_id) {
+                                Ok(_) => self.purchase_orders.retain(|order| order.id != order_id),
+                                Err(error) => self.error = Some(error),
+                            }
+
+                            self.purchase_order_to_delete = None;
+                        }
+
+                        if ui.add(egui::Button::new("Cancel")).clicked() {
+                            self.purchase_order_to_delete = None;
+                        }
+                    }
+                );
+            });
+
+        if alert.should_close() {
+            self.purchase_order_to_delete = None;
+        }
+    }
+
+    fn show_error_message(&mut self, ui: &mut egui::Ui){
+        if let Some(error) = &self.error {
+            let error_message = error.to_string();
+
+            let error_alert = egui::Modal::new(egui::Id::new("Error Message"))
+                .show(ui.ctx(), |ui| {
+                    ui.heading("Error");
+
+                    ui.separator();
+                    ui.add_space(DEFAULT_SPACING / 2.0);
+
+                    ui.label(error_message);
+
+                    ui.add_space(DEFAULT_SPACING);
+                    ui.separator();
+
+
+                    Sides::new().show(
+                        ui,
+                        |_ui| {},
+                        |ui| {
+                            if ui.button("OK").clicked() {
+                                self.error = None;
+                            }
+                        }
+                    );
+                });
+
+            if error_alert.should_close() {
+                self.error = None;
+            }
+        }
+    }
+
+    fn get_purchase_orders_list(filter: &PurchaseOrderFilter) -> Vec<PurchaseOrder> {
+        let mut connection = db::establish_connection();
+
+        purchase_order_repository::list_purchase_orders(&mut connection, filter)
+            .unwrap_or_default()
+    }
+}
@@ -0,0 +1,183 @@
+use eframe::egui;
+use egui::{CollapsingHeader, Grid, RichText, Sides};
+use rfd::FileDialog;
+use std::error::Error;
+
+use crate::infra::db;
+use crate::infra::repositories::reorder_repository;
+use crate::domain::reorder_suggestion::ReorderSuggestion;
+use crate::services::export::export_reorder_suggestions::export_reorder_suggestions;
+
+const DEFAULT_SPACING: f32 = 16.0;
+
+pub struct ReorderSuggestionsScreen {
+    pub suggestions: Vec<ReorderSuggestion>,
+    pub message: Option<String>,
+    pub error: Option<Box<dyn Error>>,
+}
+
+impl ReorderSuggestionsScreen {
+    pub fn new() -> Self {
+        let mut connection = db::establish_connection();
+
+        let (suggestions, error) = match reorder_repository::list_reorder_suggestions(&mut connection) {
+            Ok(suggestions) => (suggestions, None),
+            Err(error) => (Vec::new(), Some(error)),
+        };
+
+        Self {
+            suggestions,
+            message: None,
+            error,
+        }
+    }
+
+    pub fn ui(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.heading("Reorder Suggestions");
+
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if ui.add(egui::Button::new("Export")).clicked()
+                    && let Some(path) = FileDialog::new().set_file_name("reorder_suggestions.xlsx").save_file() {
+                    let _ = export_reorder_suggestions(&self.suggestions, path);
+                }
+
+                if ui.add(egui::Button::new("Refresh")).clicked() {
+                    *self = ReorderSuggestionsScreen::new();
+                }
+            });
+        });
+
+        ui.label(RichText::new("Suggested quantity = max stock − on hand − on order, for products at or below their reorder point.").weak());
+
+        if let Some(message) = &self.message {
+            ui.label(RichText::new(message).strong());
+        }
+
+        ui.add_space(DEFAULT_SPACING);
+
+        if self.suggestions.is_empty() {
+            ui.label("Nothing to reorder.");
+        }
+
+        let mut order_for_supplier = None;
+
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            for group in self.suggestions.chunk_by(|a, b| a.supplier.as_ref().map(|s| s.id) == b.supplier.as_ref().map(|s| s.id)) {
+                let supplier_name = group[0].supplier_name();
+                let group_total: f64 = group.iter().filter_map(|suggestion| suggestion.total_cost()).sum();
+
+                CollapsingHeader::new(RichText::new(format!("{} ({} products)", supplier_name, group.len())).strong())
+                    .id_salt(("reorder_group", supplier_name))
+                    .default_open(true)
+                    .show(ui, |ui| {
+                        Self::group_grid(ui, group, supplier_name);
+
+                        ui.horizontal(|ui| {
+                            ui.label(RichText::new(format!("Total: {:.2}", group_total)).strong());
+
+                            if let Some(supplier) = &group[0].supplier
+                                && ui.button("Create Draft Order").clicked() {
+                                order_for_supplier = Some(supplier.id);
+                            }
+                        });
+                    });
+
+                ui.add_space(DEFAULT_SPACING / 2.0);
+            }
+        });
+
+        if let Some(supplier_id) = order_for_supplier {
+            self.create_draft_order(supplier_id);
+        }
+
+        if self.error.is_some() {
+           self.show_error_message(ui);
+        }
+    }
+
+    fn group_grid(ui: &mut egui::Ui, group: &[ReorderSuggestion], supplier_name: &str) {
+        Grid::new(("reorder_grid", supplier_name))
+            .striped(true)
+            .num_columns(8)
+            .min_col_width(72.0)
+            .show(ui, |ui| {
+                ui.strong("Product");
+                ui.strong("SKU");
+                ui.strong("On Hand");
+                ui.strong("On Order");
+                ui.strong("Reorder Point");
+                ui.strong("Max Stock");
+                ui.strong("Suggested");
+                ui.strong("Total");
+                ui.end_row();
+
+                for suggestion in group {
+                    ui.label(&suggestion.product.name);
+                    ui.label(suggestion.supplier_sku.clone().unwrap_or_default());
+                    ui.label(suggestion.on_hand.to_string());
+                    ui.label(suggestion.on_order.to_string());
+                    ui.label(suggestion.product.reorder_point.to_string());
+                    ui.label(suggestion.product.max_stock.to_string());
+                    ui.label(RichText::new(suggestion.suggested_quantity.to_string()).strong());
+                    ui.label(suggestion.total_cost().map(|total| format!("{:.2}", total)).unwrap_or_default());
+                    ui.end_row();
+                }
+            });
+    }
+
+    /// Turns a supplier's suggestions into a draft purchase order; the suggestions
+    /// then drop out of the report because their quantities count as on order.
+    fn create_draft_order(&mut self, supplier_id: i32) {
+        let mut connection = db::establish_connection();
+
+        let lines: Vec<ReorderSuggestion> = self.suggestions.iter()
+            .filter(|suggestion| suggestion.supplier.as_ref().map(|supplier| supplier.id) == Some(supplier_id))
+            .cloned()
+            .collect();
+
+        let result = reorder_repository::create_draft_purchase_order(&mut connection, supplier_id, &lines);
+
+        match result {
+            Ok(order) => {
+                *self = ReorderSuggestionsScreen::new();
+                self.message = Some(format!("Draft purchase order #{} created", order.id));
+            }
+            Err(error) => self.error = Some(error),
+        }
+    }
+
+    fn show_error_message(&mut self, ui: &mut egui::Ui){
+        if let Some(error) = &self.error {
+            let error_message = error.to_string();
+
+            let error_alert = egui::Modal::new(egui::Id::new("Error Message"))
+                .show(ui.ctx(), |ui| {
+                    ui.heading("Error");
+
+                    ui.separator();
+                    ui.add_space(DEFAULT_SPACING / 2.0);
+
+                    ui.label(error_message);
+
+                    ui.add_space(DEFAULT_SPACING);
+                    ui.separator();
+
+
+                    Sides::new().show(
+                        ui,
+                        |_ui| {},
+                        |ui| {
+                            if ui.button("OK").clicked() {
+                                self.error = None;
+                            }
+                        }
+                    );
+                });
+
+            if error_alert.should_close() {
+                self.error = None;
+            }
+        }
+    }
+}
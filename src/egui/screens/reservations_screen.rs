@@ -0,0 +1,313 @@
+use eframe::egui;
+use egui::{ComboBox, Direction, Layout, Sides};
+use egui_extras::{Column, Size, StripBuilder, TableBuilder};
+use std::error::Error;
+
+use crate::infra::db;
+use crate::infra::repositories::{place_repository, product_repository, reservation_repository};
+use crate::infra::repositories::reservation_repository::ReservationFilter;
+use crate::domain::audit::AuditEntity;
+use crate::domain::place::{Place, place_path};
+use crate::domain::product::Product;
+use crate::domain::reservation::{NAIVE_DATE_PATTERN, Reservation, ReservationStatus};
+use crate::egui::components::modals::audit_history_modal::AuditHistoryModal;
+use crate::egui::components::modals::reservation_form_modal::ReservationFormModal;
+
+const DEFAULT_SPACING: f32 = 16.0;
+const ITEM_HEIGHT: f32 = 24.0;
+const DATE_TIME_DISPLAY_PATTERN: &str = "%Y-%m-%d %H:%M";
+
+pub struct ReservationsScreen {
+    pub reservations: Vec<Reservation>,
+    pub products: Vec<Product>,
+    pub places: Vec<Place>,
+    pub filter: ReservationFilter,
+    pub reservation_form_modal: Option<ReservationFormModal>,
+    pub audit_history_modal: Option<AuditHistoryModal>,
+    pub error: Option<Box<dyn Error>>,
+}
+
+impl ReservationsScreen {
+    pub fn new() -> Self {
+        let mut connection = db::establish_connection();
+
+        let filter = ReservationFilter {
+            status: Some(ReservationStatus::Active),
+            ..Default::default()
+        };
+
+        let mut screen = Self {
+            reservations: Vec::new(),
+            products: product_repository::list_products(&mut connection, "").unwrap_or_default(),
+            places: place_repository::list_places(&mut connection, "").unwrap_or_default(),
+            filter,
+            reservation_form_modal: None,
+            audit_history_modal: None,
+            error: None,
+        };
+
+        screen.refresh_reservations();
+
+        screen
+    }
+
+    pub fn ui(&mut self, ui: &mut egui::Ui) {
+        let new_reservation_btn = egui::Button::new(
+            egui::RichText::new("New Reservation").color(egui::Color32::WHITE)
+        ).fill(ui.visuals().selection.bg_fill);
+
+        ui.horizontal(|ui| {
+            ui.heading("Reservations");
+
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if ui.add(new_reservation_btn).clicked() {
+                    self.reservation_form_modal = Some(ReservationFormModal::new(&self.products, &self.places));
+                }
+
+                if ui.add(egui::TextEdit::singleline(&mut self.filter.search).hint_text("Search by job or customer...")).changed() {
+                    self.refresh_reservations();
+                }
+            });
+        });
+
+        ui.add_space(DEFAULT_SPACING / 2.0);
+
+        if self.filters_bar(ui) {
+            self.refresh_reservations();
+        }
+
+        ui.add_space(DEFAULT_SPACING);
+
+        StripBuilder::new(ui)
+            .size(Size::remainder().at_least(100.0))
+            .vertical(|mut strip| {
+                strip.cell(|ui| {
+                    egui::ScrollArea::horizontal().show(ui, |ui| {
+                        self.reservations_table(ui);
+                    });
+                });
+            });
+
+        if let Some(modal) = self.reservation_form_modal.as_mut() {
+            let (should_close, created_reservation) = modal.show(ui);
+
+            if should_close {
+                self.reservation_form_modal = None;
+
+                if created_reservation.is_some() {
+                    self.refresh_reservations();
+                }
+            }
+        }
+
+        if let Some(modal) = self.audit_history_modal.as_mut()
+            && modal.show(ui) {
+            self.audit_history_modal = None;
+        }
+
+        if self.error.is_some() {
+           self.show_error_message(ui);
+        }
+    }
+
+    fn filters_bar(&mut self, ui: &mut egui::Ui) -> bool {
+        let mut changed = false;
+
+        ui.horizontal(|ui| {
+            ui.label("Product");
+            let product_name = self.products.iter()
+                .find(|product| Some(product.id) == self.filter.product_id)
+                .map(|product| product.name.clone())
+                .unwrap_or_else(|| "All".to_owned());
+
+            ComboBox::from_id_salt("reservations_filter_product")
+                .selected_text(product_name)
+                .show_ui(ui, |ui| {
+                    changed |= ui.selectable_value(&mut self.filter.product_id, None, "All").changed();
+                    for product in &self.products {
+                        changed |= ui.selectable_value(&mut self.filter.product_id, Some(product.id), &product.name).changed();
+                    }
+                });
+
+            ui.add_space(DEFAULT_SPACING);
+
+            ui.label("Status");
+            ComboBox::from_id_salt("reservations_filter_status")
+                .selected_text(self.filter.status.map(|status| status.label()).unwrap_or("All"))
+                .show_ui(ui, |ui| {
+                    changed |= ui.selectable_value(&mut self.filter.status, None, "All").changed();
+                    for status in ReservationStatus::ALL {
+                        changed |= ui.selectable_value(&mut self.filter.status, Some(status), status.label()).changed();
+                    }
+                });
+        });
+
+        changed
+    }
+
+    fn reservations_table(&mut self, ui: &mut egui::Ui) {
+        let avaiable_height = ui.available_height();
+        let mut reservation_to_release = None;
+
+        TableBuilder::new(ui)
+            .striped(true)
+            .resizable(false)
+            .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+            .column(Column::exact(64.0))
+            .column(Column::exact(130.0))
+            .column(
+                Column::remainder()
+                .at_least(120.0)
+                .clip(true)
+                .resizable(false)
+            )
+            .column(
+                Column::auto()
+                .at_least(80.0)
+                .at_most(200.0)
+                .clip(true)
+            )
+            .column(
+                Column::auto()
+                .at_least(100.0)
+                .at_most(240.0)
+                .clip(true)
+            )
+            .column(Column::exact(80.0))
+            .column(Column::exact(80.0))
+            .column(Column::exact(100.0))
+            .column(Column::exact(88.0))
+            .column(Column::exact(160.0))
+            .min_scrolled_height(0.0)
+            .max_scroll_height(avaiable_height)
+            .header(ITEM_HEIGHT, |mut header| {
+                header.col(|ui| { ui.heading("ID"); });
+                header.col(|ui| { ui.heading("Created"); });
+                header.col(|ui| { ui.heading("Product"); });
+                header.col(|ui| { ui.heading("Place"); });
+                header.col(|ui| { ui.heading("Reserved For"); });
+                header.col(|ui| {
+                    ui.with_layout(Layout::centered_and_justified(Direction::LeftToRight), |ui| {
+                        ui.heading("Reserved");
+                    });
+                });
+                header.col(|ui| {
+                    ui.with_layout(Layout::centered_and_justified(Direction::LeftToRight), |ui| {
+                        ui.heading("Issued");
+                    });
+                });
+                header.col(|ui| { ui.heading("Expires"); });
+                header.col(|ui| { ui.heading("Status"); });
+                header.col(|ui| {
+                    ui.with_layout(Layout::centered_and_justified(Direction::LeftToRight), |ui| {
+                        ui.heading("Actions");
+                    });
+                });
+            })
+            .body(|body| {
+                body.rows(
+                    ITEM_HEIGHT,
+                    self.reservations.len(),
+                    |mut row| {
+                        let reservation = &self.reservations[row.index()];
+
+                        let product_name = self.products.iter()
+                            .find(|product| product.id == reservation.product_id)
+                            .map(|product| product.name.clone())
+                            .unwrap_or_else(|| format!("#{}", reservation.product_id));
+
+                        let place_name = match reservation.place_id {
+                            Some(place_id) => self.places.iter()
+                                .find(|place| place.id == place_id)
+                                .map(|place| place_path(&self.places, place.id))
+                                .unwrap_or_else(|| format!("#{}", place_id)),
+                            None => "Any place".to_owned(),
+                        };
+
+                        row.col(|ui| { ui.label(reservation.id.to_string()); });
+                        row.col(|ui| { ui.label(reservation.created_at.format(DATE_TIME_DISPLAY_PATTERN).to_string()); });
+                        row.col(|ui| { ui.label(product_name); });
+                        row.col(|ui| { ui.label(place_name); });
+                        row.col(|ui| {
+                            ui.label(&reservation.reference)
+                                .on_hover_text(reservation.notes.clone().unwrap_or_default());
+                        });
+                        row.col(|ui| {
+                            ui.with_layout(Layout::centered_and_justified(Direction::LeftToRight), |ui| {
+                                ui.label(reservation.quantity.to_string());
+                            });
+                        });
+                        row.col(|ui| {
+                            ui.with_layout(Layout::centered_and_justified(Direction::LeftToRight), |ui| {
+                                ui.label(reservation.consumed_quantity.to_string());
+                            });
+                        });
+                        row.col(|ui| { ui.label(reservation.expires_on.map(|date| date.format(NAIVE_DATE_PATTERN).to_string()).unwrap_or_default()); });
+                        row.col(|ui| { ui.label(reservation.status.label()); });
+                        row.col(|ui| {
+                            if reservation.status == ReservationStatus::Active
+                                && ui.add(egui::Button::new("Release")).on_hover_text("Give the units still held back to the available stock").clicked() {
+                                reservation_to_release = Some(reservation.id);
+                            }
+
+                            if ui.add(egui::Button::new("History")).clicked() {
+                                self.audit_history_modal = Some(AuditHistoryModal::new(AuditEntity::Reservation, reservation.id, &format!("#{}", reservation.id)));
+                            }
+                        });
+                    }
+                );
+            });
+
+        if let Some(reservation_id) = reservation_to_release {
+            let mut connection = db::establish_connection();
+
+            match reservation_repository::release_reservation(&mut connection, reservation_id) {
+                Ok(_) => self.refresh_reservations(),
+                Err(error) => self.error = Some(error),
+            }
+        }
+    }
+
+    fn refresh_reservations(&mut self) {
+        let mut connection = db::establish_connection();
+
+        match reservation_repository::list_reservations(&mut connection, &self.filter) {
+            Ok(reservations) => self.reservations = reservations,
+            Err(error) => self.error = Some(error),
+        }
+    }
+
+    fn show_error_message(&mut self, ui: &mut egui::Ui){
+        if let Some(error) = &self.error {
+            let error_message = error.to_string();
+
+            let error_alert = egui::Modal::new(egui::Id::new("Error Message"))
+                .show(ui.ctx(), |ui| {
+                    ui.heading("Error");
+
+                    ui.separator();
+                    ui.add_space(DEFAULT_SPACING / 2.0);
+
+                    ui.label(error_message);
+
+                    ui.add_space(DEFAULT_SPACING);
+                    ui.separator();
+
+
+                    Sides::new().show(
+                        ui,
+                        |_ui| {},
+                        |ui| {
+                            if ui.button("OK").clicked() {
+                                self.error = None;
+                            }
+                        }
+                    );
+                });
+
+            if error_alert.should_close() {
+                self.error = None;
+            }
+        }
+    }
+}
@@ -0,0 +1,644 @@
+use chrono::NaiveDate;
+use eframe::egui;
+use egui::{Color32, ComboBox, RichText, Sides};
+use egui::text::CCursorRange;
+use egui_extras::{Column, Size, StripBuilder, TableBuilder};
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::sync::OnceLock;
+use std::sync::mpsc::{self, Receiver, SyncSender};
+use std::time::{Duration, Instant};
+
+use crate::infra::db;
+use crate::infra::repositories::{inventory_transaction_repository, place_repository, product_repository, stock_repository};
+use crate::infra::repositories::stock_repository::LotBalanceFilter;
+use crate::infra::models::NewInventoryTransactionRow;
+use crate::domain::inventory_transaction::{InventoryTransaction, TransactionType, NAIVE_DATE_PATTERN};
+use crate::domain::place::{Place, place_path};
+use crate::domain::product::Product;
+use crate::domain::serial_number::parse_serial_numbers;
+use crate::domain::stock_balance::LotBalance;
+use crate::egui::widgets::lot_input::lot_input;
+
+const DEFAULT_SPACING: f32 = 16.0;
+const ITEM_HEIGHT: f32 = 24.0;
+const TIME_DISPLAY_PATTERN: &str = "%H:%M:%S";
+const SCAN_REASON: &str = "Scanned";
+
+/// Programs that play the desktop's error sound, tried in order.
+#[cfg(target_os = "linux")]
+const ERROR_SOUND_COMMANDS: &[(&str, &[&str])] = &[
+    ("canberra-gtk-play", &["--id", "dialog-error"]),
+    ("paplay", &["/usr/share/sounds/freedesktop/stereo/dialog-error.oga"]),
+];
+#[cfg(target_os = "macos")]
+const ERROR_SOUND_COMMANDS: &[(&str, &[&str])] = &[
+    ("afplay", &["/System/Library/Sounds/Basso.aiff"]),
+];
+#[cfg(target_os = "windows")]
+const ERROR_SOUND_COMMANDS: &[(&str, &[&str])] = &[
+    ("powershell", &["-NoProfile", "-Command", "[System.Media.SystemSounds]::Hand.Play(); Start-Sleep -Milliseconds 500"]),
+];
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+const ERROR_SOUND_COMMANDS: &[(&str, &[&str])] = &[];
+
+/// Shortest gap between two error sounds, so a burst of bad scans beeps once.
+const BEEP_INTERVAL: Duration = Duration::from_millis(300);
+
+static BEEP_SENDER: OnceLock<SyncSender<()>> = OnceLock::new();
+
+/// Product resolved by the last scan, waiting for its quantity (and lot or
+/// serial numbers when the product tracks them) before the movement is posted.
+pub struct PendingScan {
+    pub code: String,
+    pub product: Product,
+    pub on_hand: i64,
+    pub quantity: String,
+    pub lot_number: String,
+    pub expiry_date: String,
+    pub lots: Vec<LotBalance>,
+    pub serial_numbers: String,
+}
+
+pub struct ScanFeedback {
+    pub message: String,
+    pub is_error: bool,
+}
+
+pub struct ScannedMovement {
+    pub code: String,
+    pub product_name: String,
+    pub place_name: String,
+    pub transaction: InventoryTransaction,
+}
+
+pub struct ScanScreen {
+    pub places: Vec<Place>,
+    pub place_id: Option<i32>,
+    pub transaction_type: TransactionType,
+    pub ask_quantity: bool,
+    pub code: String,
+    pub pending: Option<PendingScan>,
+    pub feedback: Option<ScanFeedback>,
+    pub movements: Vec<ScannedMovement>,
+    pub focus_code: bool,
+    pub focus_quantity: bool,
+}
+
+impl ScanScreen {
+    pub fn new() -> Self {
+        let mut connection = db::establish_connection();
+
+        Self {
+            places: place_repository::list_places(&mut connection, "").unwrap_or_default(),
+            place_id: None,
+            transaction_type: TransactionType::Entry,
+            ask_quantity: true,
+            code: String::new(),
+            pending: None,
+            feedback: None,
+            movements: Vec::new(),
+            focus_code: true,
+            focus_quantity: false,
+        }
+    }
+
+    pub fn ui(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.heading("Scan");
+        });
+
+        ui.add_space(DEFAULT_SPACING / 2.0);
+
+        self.settings_bar(ui);
+
+        ui.add_space(DEFAULT_SPACING);
+
+        self.scan_input(ui);
+
+        ui.add_space(DEFAULT_SPACING / 2.0);
+
+        self.feedback_banner(ui);
+
+        if self.pending.is_some() {
+            ui.add_space(DEFAULT_SPACING / 2.0);
+            self.pending_panel(ui);
+        }
+
+        ui.add_space(DEFAULT_SPACING);
+
+        StripBuilder::new(ui)
+            .size(Size::remainder().at_least(100.0))
+            .vertical(|mut strip| {
+                strip.cell(|ui| {
+                    egui::ScrollArea::horizontal().show(ui, |ui| {
+                        self.movements_table(ui);
+                    });
+                });
+            });
+    }
+
+    fn settings_bar(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            let mut refresh_stock = false;
+
+            ui.label("Place");
+            let place_name = self.place_name(self.place_id).unwrap_or_else(|| "Choose or scan a place".to_owned());
+
+            ComboBox::from_id_salt("scan_place")
+                .selected_text(place_name)
+                .show_ui(ui, |ui| {
+                    for place in &self.places {
+                        if ui.selectable_value(&mut self.place_id, Some(place.id), place_path(&self.places, place.id)).clicked() {
+                            refresh_stock = true;
+                        }
+                    }
+                });
+
+            if refresh_stock {
+                self.refresh_pending_stock();
+            }
+
+            ui.add_space(DEFAULT_SPACING);
+
+            ui.label("Movement");
+            for transaction_type in [TransactionType::Entry, TransactionType::Exit] {
+                if ui.selectable_value(&mut self.transaction_type, transaction_type, transaction_type.label()).clicked() {
+                    self.refresh_pending_stock();
+                    self.focus_code = self.pending.is_none();
+                }
+            }
+
+            ui.add_space(DEFAULT_SPACING);
+
+            if ui.checkbox(&mut self.ask_quantity, "Ask quantity after each scan").changed() {
+                self.focus_code = self.pending.is_none();
+            }
+        });
+    }
+
+    fn scan_input(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            let response = ui.add(
+                egui::TextEdit::singleline(&mut self.code)
+                    .font(egui::TextStyle::Heading)
+                    .desired_width(ui.available_width())
+                    .hint_text("Scan a barcode, SKU or place label...")
+            );
+
+            if self.focus_code {
+                response.request_focus();
+                self.focus_code = false;
+            }
+
+            if response.lost_focus() && ui.input(|input| input.key_pressed(egui::Key::Enter)) {
+                let code = self.code.trim().to_owned();
+                self.code.clear();
+
+                if !code.is_empty() {
+                    self.handle_scan(code);
+                }
+
+                if self.pending.is_none() {
+                    self.focus_code = true;
+                }
+            }
+        });
+    }
+
+    fn feedback_banner(&mut self, ui: &mut egui::Ui) {
+        let Some(feedback) = &self.feedback else {
+            return;
+        };
+
+        let fill = if feedback.is_error { Color32::from_rgb(170, 30, 30) } else { Color32::from_rgb(30, 120, 50) };
+
+        egui::Frame::new()
+            .fill(fill)
+            .corner_radius(4.0)
+            .inner_margin(DEFAULT_SPACING / 2.0)
+            .show(ui, |ui| {
+                ui.set_width(ui.available_width());
+                ui.label(RichText::new(&feedback.message).color(Color32::WHITE).heading());
+            });
+    }
+
+    fn pending_panel(&mut self, ui: &mut egui::Ui) {
+        let mut post = false;
+        let mut cancel = false;
+        let transaction_type = self.transaction_type;
+        let focus_quantity = std::mem::take(&mut self.focus_quantity);
+
+        let Some(pending) = self.pending.as_mut() else {
+            return;
+        };
+
+        egui::Frame::group(ui.style()).show(ui, |ui| {
+            ui.set_width(ui.available_width());
+
+            ui.horizontal(|ui| {
+                ui.heading(&pending.product.name);
+                if let Some(sku) = &pending.product.sku {
+                    ui.label(RichText::new(format!("SKU {}", sku)).weak());
+                }
+                ui.label(RichText::new(format!("{} in stock here", pending.on_hand)).weak());
+            });
+
+            ui.add_space(DEFAULT_SPACING / 2.0);
+
+            if pending.product.serialized {
+                ui.label("Serial Numbers");
+                let response = ui.add(
+                    egui::TextEdit::multiline(&mut pending.serial_numbers)
+                        .desired_rows(4)
+                        .desired_width(240.0)
+                        .hint_text("Scan one serial number per line")
+                );
+                ui.label(RichText::new(format!(
+                    "{} entered, press Ctrl+Enter to post",
+                    parse_serial_numbers(&pending.serial_numbers).len()
+                )).weak());
+
+                if focus_quantity {
+                    response.request_focus();
+                }
+
+                if ui.input(|input| input.modifiers.command && input.key_pressed(egui::Key::Enter)) {
+                    post = true;
+                }
+            } else {
+                ui.horizontal(|ui| {
+                    ui.label("Quantity");
+
+                    let output = egui::TextEdit::singleline(&mut pending.quantity)
+                        .font(egui::TextStyle::Heading)
+                        .desired_width(100.0)
+                        .show(ui);
+
+                    if focus_quantity {
+                        let mut state = output.state.clone();
+                        state.cursor.set_char_range(Some(CCursorRange::select_all(&output.galley)));
+                        state.store(ui.ctx(), output.response.id);
+                        output.response.request_focus();
+                    }
+
+                    if output.response.lost_focus() && ui.input(|input| input.key_pressed(egui::Key::Enter)) {
+                        post = true;
+                    }
+
+                    if let Some(unity) = &pending.product.unity {
+                        ui.label(unity);
+                    }
+                });
+            }
+
+            if pending.product.tracks_lots {
+                ui.add_space(DEFAULT_SPACING / 2.0);
+
+                ui.horizontal(|ui| {
+                    ui.vertical(|ui| {
+                        ui.label("Lot");
+                        lot_input(ui, "scan_lot", &mut pending.lot_number, &pending.lots);
+                    });
+
+                    // Exits take the expiry date already recorded for the lot.
+                    if transaction_type != TransactionType::Exit {
+                        ui.vertical(|ui| {
+                            ui.label("Expiry Date");
+                            ui.add(egui::TextEdit::singleline(&mut pending.expiry_date).desired_width(100.0).hint_text("YYYY-MM-DD"));
+                        });
+                    }
+                });
+            }
+
+            ui.add_space(DEFAULT_SPACING / 2.0);
+
+            Sides::new().show(
+                ui,
+                |_ui| {},
+                |ui| {
+                    if ui.button(format!("Post {}", transaction_type.label())).clicked() {
+                        post = true;
+                    }
+
+                    if ui.button("Cancel").clicked() {
+                        cancel = true;
+                    }
+                }
+            );
+        });
+
+        if ui.input(|input| input.key_pressed(egui::Key::Escape)) {
+            cancel = true;
+        }
+
+        if cancel {
+            self.pending = None;
+            self.feedback = None;
+            self.focus_code = true;
+        } else if post {
+            self.post_pending();
+        }
+    }
+
+    fn movements_table(&mut self, ui: &mut egui::Ui) {
+        let avaiable_height = ui.available_height();
+
+        TableBuilder::new(ui)
+            .striped(true)
+            .resizable(false)
+            .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+            .column(Column::exact(80.0))
+            .column(
+                Column::auto()
+                .at_least(120.0)
+                .at_most(200.0)
+                .clip(true)
+            )
+            .column(
+                Column::remainder()
+                .at_least(120.0)
+                .clip(true)
+                .resizable(false)
+            )
+            .column(Column::exact(80.0))
+            .column(Column::exact(80.0))
+            .column(
+                Column::auto()
+                .at_least(100.0)
+                .at_most(200.0)
+                .clip(true)
+            )
+            .min_scrolled_height(0.0)
+            .max_scroll_height(avaiable_height)
+            .header(ITEM_HEIGHT, |mut header| {
+                header.col(|ui| { ui.heading("Time"); });
+                header.col(|ui| { ui.heading("Code"); });
+                header.col(|ui| { ui.heading("Product"); });
+                header.col(|ui| { ui.heading("Type"); });
+                header.col(|ui| { ui.heading("Quantity"); });
+                header.col(|ui| { ui.heading("Place"); });
+            })
+            .body(|body| {
+                body.rows(
+                    ITEM_HEIGHT,
+                    self.movements.len(),
+                    |mut row| {
+                        let movement = &self.movements[self.movements.len() - 1 - row.index()];
+
+                        row.col(|ui| { ui.label(movement.transaction.created_at.format(TIME_DISPLAY_PATTERN).to_string()); });
+                        row.col(|ui| { ui.label(&movement.code); });
+                        row.col(|ui| { ui.label(&movement.product_name); });
+                        row.col(|ui| { ui.label(movement.transaction.transaction_type.label()); });
+                        row.col(|ui| { ui.label(movement.transaction.quantity.to_string()); });
+                        row.col(|ui| { ui.label(&movement.place_name); });
+                    }
+                );
+            });
+    }
+
+    /// Resolves a scanned code: place labels switch the current place, product
+    /// codes start a pending movement (or post it right away when quantities are
+    /// not asked and nothing else is needed).
+    fn handle_scan(&mut self, code: String) {
+        if let Some(place) = self.places.iter().find(|place| place.label_code() == code) {
+            self.place_id = Some(place.id);
+            self.pending = None;
+            self.success(format!("Place: {}", place_path(&self.places, place.id)));
+            return;
+        }
+
+        let mut connection = db::establish_connection();
+
+        let product = match product_repository::find_product_by_code(&mut connection, &code) {
+            Ok(Some(product)) => product,
+            Ok(None) => {
+                self.failure(format!("Unknown code '{}'", code));
+                return;
+            }
+            Err(error) => {
+                self.failure(error.to_string());
+                return;
+            }
+        };
+
+        if self.place_id.is_none() {
+            self.failure("Choose or scan a place before scanning products".to_owned());
+            return;
+        }
+
+        self.feedback = Some(ScanFeedback {
+            message: product.name.clone(),
+            is_error: false,
+        });
+
+        let needs_details = product.tracks_lots || product.serialized;
+
+        self.pending = Some(PendingScan {
+            code,
+            product,
+            on_hand: 0,
+            quantity: "1".to_owned(),
+            lot_number: String::new(),
+            expiry_date: String::new(),
+            lots: Vec::new(),
+            serial_numbers: String::new(),
+        });
+        self.refresh_pending_stock();
+
+        if self.ask_quantity || needs_details {
+            self.focus_quantity = true;
+        } else {
+            self.post_pending();
+        }
+    }
+
+    fn post_pending(&mut self) {
+        let (new_transaction, serial_numbers) = match self.pending_transaction() {
+            Ok(transaction) => transaction,
+            Err(error) => {
+                self.failure(error);
+                self.focus_quantity = true;
+                return;
+            }
+        };
+
+        let Some(pending) = self.pending.take() else {
+            return;
+        };
+
+        let mut connection = db::establish_connection();
+
+        match inventory_transaction_repository::create_inventory_transaction(&mut connection, new_transaction, &serial_numbers) {
+            Ok(transaction) => {
+                let place_name = self.place_name(Some(transaction.place_id)).unwrap_or_default();
+                let message = format!(
+                    "{}: {} × {} at {}",
+                    transaction.transaction_type.label(), transaction.quantity.abs(), pending.product.name, place_name
+                );
+
+                self.movements.push(ScannedMovement {
+                    code: pending.code,
+                    product_name: pending.product.name,
+                    place_name,
+                    transaction,
+                });
+                self.focus_code = true;
+                self.success(message);
+            }
+            Err(error) => {
+                self.pending = Some(pending);
+                self.refresh_pending_stock();
+                self.failure(error.to_string());
+            }
+        }
+    }
+
+    fn pending_transaction(&self) -> Result<(NewInventoryTransactionRow, Vec<String>), String> {
+        let pending = self.pending.as_ref().ok_or("Nothing was scanned")?;
+        let place_id = self.place_id.ok_or("Choose or scan a place before posting")?;
+
+        let serial_numbers = if pending.product.serialized {
+            parse_serial_numbers(&pending.serial_numbers)
+        } else {
+            Vec::new()
+        };
+
+        let quantity = if pending.product.serialized {
+            match serial_numbers.len() {
+                0 => return Err("Scan at least one serial number".to_owned()),
+                count => count as i32,
+            }
+        } else {
+            match pending.quantity.trim().parse::<i32>() {
+                Ok(value) if value > 0 => value,
+                _ => return Err("Quantity should be a positive integer".to_owned()),
+            }
+        };
+
+        let expiry_date = match pending.expiry_date.trim() {
+            value if value.is_empty() || self.transaction_type == TransactionType::Exit => None,
+            value => match NaiveDate::parse_from_str(value, NAIVE_DATE_PATTERN) {
+                Ok(date) => Some(date.format(NAIVE_DATE_PATTERN).to_string()),
+                Err(_) => return Err("Use the YYYY-MM-DD format for the expiry date".to_owned()),
+            },
+        };
+
+        let new_transaction = NewInventoryTransactionRow {
+            product_id: pending.product.id,
+            place_id,
+            supplier_id: None,
+            quantity: self.transaction_type.signed_quantity(quantity),
+            transaction_type: self.transaction_type.as_str().to_owned(),
+            reason: Some(SCAN_REASON.to_owned()),
+            lot_number: Some(pending.lot_number.clone()),
+            expiry_date,
+            unit_cost: None,
+            reservation_id: None,
+        };
+
+        Ok((new_transaction, serial_numbers))
+    }
+
+    /// Re-reads the stock shown for the pending product, since the place, the
+    /// movement type or the stock itself may have changed since it was scanned.
+    fn refresh_pending_stock(&mut self) {
+        let Some(pending) = self.pending.as_mut() else {
+            return;
+        };
+
+        let mut connection = db::establish_connection();
+
+        pending.on_hand = match self.place_id {
+            Some(place_id) => stock_repository::get_stock_balance(&mut connection, pending.product.id, place_id).unwrap_or_default(),
+            None => 0,
+        };
+
+        pending.lots = match self.place_id {
+            Some(place_id) if pending.product.tracks_lots && self.transaction_type == TransactionType::Exit => {
+                let filter = LotBalanceFilter {
+                    product_id: Some(pending.product.id),
+                    place_id: Some(place_id),
+                    ..Default::default()
+                };
+
+                stock_repository::list_lot_balances(&mut connection, &filter).unwrap_or_default()
+            }
+            _ => Vec::new(),
+        };
+    }
+
+    fn place_name(&self, place_id: Option<i32>) -> Option<String> {
+        self.places.iter()
+            .find(|place| Some(place.id) == place_id)
+            .map(|place| place_path(&self.places, place.id))
+    }
+
+    fn success(&mut self, message: String) {
+        self.feedback = Some(ScanFeedback { message, is_error: false });
+    }
+
+    /// Shows the error in red and plays the system error sound, so the operator
+    /// notices a bad scan without looking at the screen.
+    fn failure(&mut self, message: String) {
+        self.feedback = Some(ScanFeedback { message, is_error: true });
+        beep();
+    }
+}
+
+/// Plays the system error sound; egui has no audio output of its own. A single
+/// thread plays the sounds, one player at a time, so scanning never waits for
+/// it. Bad scans made while a sound is still playing, or right after one, stay
+/// silent instead of starting another player: the highlighted message already
+/// flags each failure.
+fn beep() {
+    let sender = BEEP_SENDER.get_or_init(|| {
+        let (sender, receiver) = mpsc::sync_channel(1);
+        std::thread::spawn(move || play_error_sounds(receiver));
+        sender
+    });
+
+    // A full channel means a sound is already on its way.
+    let _ = sender.try_send(());
+}
+
+fn play_error_sounds(receiver: Receiver<()>) {
+    let mut last_played: Option<Instant> = None;
+
+    while receiver.recv().is_ok() {
+        if last_played.is_some_and(|played_at| played_at.elapsed() < BEEP_INTERVAL) {
+            continue;
+        }
+
+        play_error_sound();
+        last_played = Some(Instant::now());
+
+        // Drops the requests made while the sound was playing.
+        while receiver.try_recv().is_ok() {}
+    }
+}
+
+/// Waits for the player to finish; falls back to the terminal bell when no
+/// sound player is available.
+fn play_error_sound() {
+    for (program, args) in ERROR_SOUND_COMMANDS {
+        let mut command = Command::new(program);
+        command.args(*args).stdout(Stdio::null()).stderr(Stdio::null());
+
+        #[cfg(target_os = "windows")]
+        {
+            use std::os::windows::process::CommandExt;
+
+            // CREATE_NO_WINDOW: no console flashes up behind the app.
+            command.creation_flags(0x0800_0000);
+        }
+
+        if command.status().is_ok_and(|status| status.success()) {
+            return;
+        }
+    }
+
+    let mut stderr = std::io::stderr();
+    let _ = stderr.write_all(b"\x07");
+    let _ = stderr.flush();
+}
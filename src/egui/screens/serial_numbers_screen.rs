@@ -0,0 +1,248 @@
+use eframe::egui;
+use egui::{ComboBox, Direction, Layout, RichText, Sides};
+use egui_extras::{Column, Size, StripBuilder, TableBuilder};
+use std::error::Error;
+
+use crate::infra::db;
+use crate::infra::repositories::{place_repository, product_repository, serial_number_repository};
+use crate::infra::repositories::serial_number_repository::SerialNumberFilter;
+use crate::domain::place::{Place, place_path};
+use crate::domain::product::Product;
+use crate::domain::serial_number::SerialNumber;
+use crate::egui::components::modals::serial_number_history_modal::SerialNumberHistoryModal;
+
+const DEFAULT_SPACING: f32 = 16.0;
+const ITEM_HEIGHT: f32 = 24.0;
+const DATE_TIME_DISPLAY_PATTERN: &str = "%Y-%m-%d %H:%M";
+
+pub struct SerialNumbersScreen {
+    pub serial_numbers: Vec<SerialNumber>,
+    pub products: Vec<Product>,
+    pub places: Vec<Place>,
+    pub history_modal: Option<SerialNumberHistoryModal>,
+    pub error: Option<Box<dyn Error>>,
+    pub filter: SerialNumberFilter,
+}
+
+impl SerialNumbersScreen {
+    pub fn new() -> Self {
+        let mut connection = db::establish_connection();
+
+        let mut screen = Self {
+            serial_numbers: Vec::new(),
+            products: product_repository::list_products(&mut connection, "").unwrap_or_default(),
+            places: place_repository::list_places(&mut connection, "").unwrap_or_default(),
+            history_modal: None,
+            error: None,
+            filter: SerialNumberFilter::default(),
+        };
+
+        screen.refresh_serial_numbers();
+
+        screen
+    }
+
+    pub fn ui(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.heading("Serial Numbers");
+
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if ui.add(egui::TextEdit::singleline(&mut self.filter.search).hint_text("Search by serial number...")).changed() {
+                    self.refresh_serial_numbers();
+                };
+            });
+        });
+
+        ui.add_space(DEFAULT_SPACING / 2.0);
+
+        if self.filters_bar(ui) {
+            self.refresh_serial_numbers();
+        }
+
+        ui.add_space(DEFAULT_SPACING);
+
+        StripBuilder::new(ui)
+            .size(Size::remainder().at_least(100.0))
+            .vertical(|mut strip| {
+                strip.cell(|ui| {
+                    egui::ScrollArea::horizontal().show(ui, |ui| {
+                        self.serial_numbers_table(ui);
+                    });
+                });
+            });
+
+        if let Some(modal) = self.history_modal.as_mut()
+            && modal.show(ui) {
+            self.history_modal = None;
+        }
+
+        if self.error.is_some() {
+           self.show_error_message(ui);
+        }
+    }
+
+    fn filters_bar(&mut self, ui: &mut egui::Ui) -> bool {
+        let mut changed = false;
+
+        ui.horizontal(|ui| {
+            ui.label("Product");
+            let product_name = self.products.iter()
+                .find(|product| Some(product.id) == self.filter.product_id)
+                .map(|product| product.name.clone())
+                .unwrap_or_else(|| "All".to_owned());
+
+            ComboBox::from_id_salt("serial_numbers_filter_product")
+                .selected_text(product_name)
+                .show_ui(ui, |ui| {
+                    changed |= ui.selectable_value(&mut self.filter.product_id, None, "All").changed();
+                    for product in self.products.iter().filter(|product| product.serialized) {
+                        changed |= ui.selectable_value(&mut self.filter.product_id, Some(product.id), &product.name).changed();
+                    }
+                });
+
+            ui.add_space(DEFAULT_SPACING);
+
+            ui.label("Place");
+            let place_name = self.places.iter()
+                .find(|place| Some(place.id) == self.filter.place_id)
+                .map(|place| place_path(&self.places, place.id))
+                .unwrap_or_else(|| "All".to_owned());
+
+            ComboBox::from_id_salt("serial_numbers_filter_place")
+                .selected_text(place_name)
+                .show_ui(ui, |ui| {
+                    changed |= ui.selectable_value(&mut self.filter.place_id, None, "All").changed();
+                    for place in &self.places {
+                        changed |= ui.selectable_value(&mut self.filter.place_id, Some(place.id), place_path(&self.places, place.id)).changed();
+                    }
+                });
+        });
+
+        changed
+    }
+
+    fn serial_numbers_table(&mut self, ui: &mut egui::Ui) {
+        let avaiable_height = ui.available_height();
+
+        TableBuilder::new(ui)
+            .striped(true)
+            .resizable(false)
+            .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+            .column(
+                Column::auto()
+                .at_least(120.0)
+                .at_most(240.0)
+                .clip(true)
+            )
+            .column(
+                Column::remainder()
+                .at_least(120.0)
+                .clip(true)
+                .resizable(false)
+            )
+            .column(
+                Column::auto()
+                .at_least(100.0)
+                .at_most(200.0)
+                .clip(true)
+            )
+            .column(Column::exact(130.0))
+            .column(Column::exact(130.0))
+            .column(Column::exact(88.0))
+            .min_scrolled_height(0.0)
+            .max_scroll_height(avaiable_height)
+            .header(ITEM_HEIGHT, |mut header| {
+                header.col(|ui| { ui.heading("Serial Number"); });
+                header.col(|ui| { ui.heading("Product"); });
+                header.col(|ui| { ui.heading("Location"); });
+                header.col(|ui| { ui.heading("Registered"); });
+                header.col(|ui| { ui.heading("Last Movement"); });
+                header.col(|ui| {
+                    ui.with_layout(Layout::centered_and_justified(Direction::LeftToRight), |ui| {
+                        ui.heading("Actions");
+                    });
+                });
+            })
+            .body(|body| {
+                body.rows(
+                    ITEM_HEIGHT,
+                    self.serial_numbers.len(),
+                    |mut row| {
+                        let serial_number = &self.serial_numbers[row.index()];
+
+                        let product_name = self.products.iter()
+                            .find(|product| product.id == serial_number.product_id)
+                            .map(|product| product.name.clone())
+                            .unwrap_or_else(|| format!("#{}", serial_number.product_id));
+
+                        let place_name = serial_number.place_id
+                            .map(|place_id| {
+                                self.places.iter()
+                                    .find(|place| place.id == place_id)
+                                    .map(|place| place_path(&self.places, place.id))
+                                    .unwrap_or_else(|| format!("#{}", place_id))
+                            });
+
+                        row.col(|ui| { ui.label(&serial_number.serial_number); });
+                        row.col(|ui| { ui.label(&product_name); });
+                        row.col(|ui| {
+                            match place_name {
+                                Some(place_name) => ui.label(place_name),
+                                None => ui.label(RichText::new("Issued").weak()),
+                            };
+                        });
+                        row.col(|ui| { ui.label(serial_number.created_at.format(DATE_TIME_DISPLAY_PATTERN).to_string()); });
+                        row.col(|ui| { ui.label(serial_number.updated_at.format(DATE_TIME_DISPLAY_PATTERN).to_string()); });
+                        row.col(|ui| {
+                            if ui.add(egui::Button::new("History")).clicked() {
+                                self.history_modal = Some(SerialNumberHistoryModal::new(serial_number, &product_name));
+                            }
+                        });
+                    }
+                );
+            });
+    }
+
+    fn refresh_serial_numbers(&mut self) {
+        let mut connection = db::establish_connection();
+
+        match serial_number_repository::list_serial_numbers(&mut connection, &self.filter) {
+            Ok(serial_numbers) => self.serial_numbers = serial_numbers,
+            Err(error) => self.error = Some(error),
+        }
+    }
+
+    fn show_error_message(&mut self, ui: &mut egui::Ui){
+        if let Some(error) = &self.error {
+            let error_message = error.to_string();
+
+            let error_alert = egui::Modal::new(egui::Id::new("Error Message"))
+                .show(ui.ctx(), |ui| {
+                    ui.heading("Error");
+
+                    ui.separator();
+                    ui.add_space(DEFAULT_SPACING / 2.0);
+
+                    ui.label(error_message);
+
+                    ui.add_space(DEFAULT_SPACING);
+                    ui.separator();
+
+
+                    Sides::new().show(
+                        ui,
+                        |_ui| {},
+                        |ui| {
+                            if ui.button("OK").clicked() {
+                                self.error = None;
+                            }
+                        }
+                    );
+                });
+
+            if error_alert.should_close() {
+                self.error = None;
+            }
+        }
+    }
+}
@@ -0,0 +1,465 @@
+use eframe::egui;
+use egui::{ComboBox, RichText, Sides};
+use egui_extras::{Column, Size, StripBuilder, TableBuilder};
+use rfd::FileDialog;
+use std::collections::HashMap;
+use std::error::Error;
+
+use crate::infra::db;
+use crate::infra::repositories::{place_repository, product_repository, stocktake_repository};
+use crate::domain::place::{Place, place_path};
+use crate::domain::product::Product;
+use crate::domain::stocktake::{Stocktake, StocktakeLine};
+use crate::egui::components::modals::approve_stocktake_modal::ApproveStocktakeModal;
+use crate::egui::components::modals::stocktake_line_form_modal::StocktakeLineFormModal;
+use crate::services::export::export_stocktake::export_stocktake;
+use crate::services::import::import_stocktake_counts::import_stocktake_counts;
+
+const DEFAULT_SPACING: f32 = 16.0;
+const ITEM_HEIGHT: f32 = 24.0;
+const DATE_TIME_DISPLAY_PATTERN: &str = "%Y-%m-%d %H:%M";
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum LineFilter {
+    #[default]
+    All,
+    Uncounted,
+    Variances,
+}
+
+impl LineFilter {
+    pub const ALL: [LineFilter; 3] = [LineFilter::All, LineFilter::Uncounted, LineFilter::Variances];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            LineFilter::All => "All lines",
+            LineFilter::Uncounted => "Uncounted",
+            LineFilter::Variances => "With variance",
+        }
+    }
+
+    fn matches(&self, line: &StocktakeLine) -> bool {
+        match self {
+            LineFilter::All => true,
+            LineFilter::Uncounted => line.counted_quantity.is_none(),
+            LineFilter::Variances => line.variance().is_some_and(|variance| variance != 0),
+        }
+    }
+}
+
+pub struct StocktakeDetailScreen {
+    pub stocktake: Stocktake,
+    pub lines: Vec<StocktakeLine>,
+    pub products: Vec<Product>,
+    pub places: Vec<Place>,
+    /// Text typed in the "Counted" cells, keyed by line id.
+    pub count_inputs: HashMap<i32, String>,
+    pub search: String,
+    pub line_filter: LineFilter,
+    pub line_form_modal: Option<StocktakeLineFormModal>,
+    pub approve_modal: Option<ApproveStocktakeModal>,
+    pub stocktake_to_cancel: bool,
+    pub error: Option<Box<dyn Error>>,
+}
+
+impl StocktakeDetailScreen {
+    pub fn new(stocktake: Stocktake) -> Self {
+        let mut connection = db::establish_connection();
+
+        let mut screen = Self {
+            lines: Vec::new(),
+            products: product_repository::list_products(&mut connection, "").unwrap_or_default(),
+            places: place_repository::list_places(&mut connection, "").unwrap_or_default(),
+            count_inputs: HashMap::new(),
+            search: String::new(),
+            line_filter: LineFilter::default(),
+            line_form_modal: None,
+            approve_modal: None,
+            stocktake_to_cancel: false,
+            error: None,
+            stocktake,
+        };
+
+        screen.reload_lines();
+
+        screen
+    }
+
+    /// Renders the stocktake and returns `true` when the user asks to go back to the list.
+    pub fn ui(&mut self, ui: &mut egui::Ui) -> bool {
+        let mut go_back = false;
+        let editable = self.stocktake.status.is_editable();
+
+        ui.horizontal(|ui| {
+            if ui.button("Back").clicked() {
+                go_back = true;
+            }
+
+            ui.heading(format!("Stocktake #{}", self.stocktake.id));
+            ui.label(RichText::new(self.stocktake.status.label()).strong());
+
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if editable {
+                    let approve_btn = egui::Button::new(
+                        egui::RichText::new("Approve").color(egui::Color32::WHITE)
+                    ).fill(ui.visuals().selection.bg_fill);
+
+                    if ui.add(approve_btn).clicked() {
+                        self.approve_modal = Some(ApproveStocktakeModal::new(&self.stocktake, &self.lines));
+                    }
+
+                    if ui.add(egui::Button::new("Cancel Stocktake")).clicked() {
+                        self.stocktake_to_cancel = true;
+                    }
+
+                    if ui.add(egui::Button::new("Import Counts")).clicked()
+                        && let Some(path) = FileDialog::new().pick_file() {
+                        self.import_counts(path);
+                    }
+                }
+
+                if ui.add(egui::Button::new("Export")).clicked()
+                    && let Some(path) = FileDialog::new().set_file_name(format!("stocktake_{}.xlsx", self.stocktake.id)).save_file() {
+                    let _ = export_stocktake(&self.lines, &self.products, &self.places, path);
+                }
+
+                if editable && ui.add(egui::Button::new("Add Product")).clicked() {
+                    self.line_form_modal = Some(StocktakeLineFormModal::new(&self.stocktake, &self.products, &self.places));
+                }
+            });
+        });
+
+        ui.add_space(DEFAULT_SPACING / 2.0);
+
+        egui::Grid::new("stocktake_header").num_columns(2).show(ui, |ui| {
+            ui.label("Place");
+            ui.label(self.stocktake.place_id.and_then(|place_id| self.place_name(place_id)).unwrap_or_else(|| "All places".to_owned()));
+            ui.end_row();
+
+            ui.label("Frozen at");
+            ui.label(self.stocktake.created_at.format(DATE_TIME_DISPLAY_PATTERN).to_string());
+            ui.end_row();
+
+            if let Some(approved_at) = self.stocktake.approved_at {
+                ui.label("Approved at");
+                ui.label(approved_at.format(DATE_TIME_DISPLAY_PATTERN).to_string());
+                ui.end_row();
+            }
+
+            ui.label("Notes");
+            ui.label(self.stocktake.notes.clone().unwrap_or_default());
+            ui.end_row();
+
+            ui.label("Counted");
+            let counted = self.lines.iter().filter(|line| line.counted_quantity.is_some()).count();
+            ui.label(RichText::new(format!("{} of {} lines", counted, self.lines.len())).strong());
+            ui.end_row();
+        });
+
+        ui.add_space(DEFAULT_SPACING / 2.0);
+
+        ui.horizontal(|ui| {
+            ui.add(egui::TextEdit::singleline(&mut self.search).hint_text("Search by product or SKU..."));
+
+            ComboBox::from_id_salt("stocktake_line_filter")
+                .selected_text(self.line_filter.label())
+                .show_ui(ui, |ui| {
+                    for line_filter in LineFilter::ALL {
+                        ui.selectable_value(&mut self.line_filter, line_filter, line_filter.label());
+                    }
+                });
+        });
+
+        ui.add_space(DEFAULT_SPACING);
+
+        StripBuilder::new(ui)
+            .size(Size::remainder().at_least(100.0))
+            .vertical(|mut strip| {
+                strip.cell(|ui| {
+                    egui::ScrollArea::horizontal().show(ui, |ui| {
+                        self.lines_table(ui, editable);
+                    });
+                });
+            });
+
+        if let Some(modal) = self.line_form_modal.as_mut() {
+            let (should_close, created_line) = modal.show(ui);
+
+            if should_close {
+                self.line_form_modal = None;
+
+                if let Some(line) = created_line {
+                    self.count_inputs.insert(line.id, line.counted_quantity.map(|counted| counted.to_string()).unwrap_or_default());
+                    self.lines.push(line);
+                }
+            }
+        }
+
+        if let Some(modal) = self.approve_modal.as_mut() {
+            let (should_close, approved_stocktake) = modal.show(ui);
+
+            if should_close {
+                self.approve_modal = None;
+
+                if let Some(stocktake) = approved_stocktake {
+                    self.stocktake = stocktake;
+                }
+            }
+        }
+
+        if self.stocktake_to_cancel {
+            self.show_confirm_cancel_alert(ui);
+        }
+
+        if self.error.is_some() {
+           self.show_error_message(ui);
+        }
+
+        go_back
+    }
+
+    fn lines_table(&mut self, ui: &mut egui::Ui, editable: bool) {
+        let avaiable_height = ui.available_height();
+        let mut counts_to_save = Vec::new();
+
+        let search = self.search.trim().to_lowercase();
+
+        let visible_lines: Vec<usize> = self.lines.iter()
+            .enumerate()
+            .filter(|(_, line)| self.line_filter.matches(line))
+            .filter(|(_, line)| {
+                search.is_empty() || self.products.iter()
+                    .find(|product| product.id == line.product_id)
+                    .is_some_and(|product| {
+                        product.name.to_lowercase().contains(&search)
+                            || product.sku.as_ref().is_some_and(|sku| sku.to_lowercase().contains(&search))
+                    })
+            })
+            .map(|(index, _)| index)
+            .collect();
+
+        TableBuilder::new(ui)
+            .striped(true)
+            .resizable(false)
+            .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+            .column(
+                Column::remainder()
+                .at_least(160.0)
+                .clip(true)
+                .resizable(false)
+            )
+            .column(Column::exact(100.0))
+            .column(
+                Column::auto()
+                .at_least(100.0)
+                .at_most(200.0)
+                .clip(true)
+            )
+            .column(Column::exact(100.0))
+            .column(Column::exact(64.0))
+            .column(Column::exact(88.0))
+            .column(Column::exact(88.0))
+            .column(Column::exact(88.0))
+            .min_scrolled_height(0.0)
+            .max_scroll_height(avaiable_height)
+            .header(ITEM_HEIGHT, |mut header| {
+                header.col(|ui| { ui.heading("Product"); });
+                header.col(|ui| { ui.heading("SKU"); });
+                header.col(|ui| { ui.heading("Place"); });
+                header.col(|ui| { ui.heading("Lot"); });
+                header.col(|ui| { ui.heading("Unit"); });
+                header.col(|ui| { ui.heading("Expected"); });
+                header.col(|ui| { ui.heading("Counted"); });
+                header.col(|ui| { ui.heading("Variance"); });
+            })
+            .body(|body| {
+                body.rows(
+                    ITEM_HEIGHT,
+                    visible_lines.len(),
+                    |mut row| {
+                        let line = &self.lines[visible_lines[row.index()]];
+
+                        let product = self.products.iter().find(|product| product.id == line.product_id);
+
+                        let place_name = self.places.iter()
+                            .find(|place| place.id == line.place_id)
+                            .map(|place| place_path(&self.places, place.id))
+                            .unwrap_or_else(|| format!("#{}", line.place_id));
+
+                        row.col(|ui| { ui.label(product.map(|product| product.name.clone()).unwrap_or_else(|| format!("#{}", line.product_id))); });
+                        row.col(|ui| { ui.label(product.and_then(|product| product.sku.clone()).unwrap_or_default()); });
+                        row.col(|ui| { ui.label(place_name); });
+                        row.col(|ui| { ui.label(line.lot_number.clone().unwrap_or_default()); });
+                        row.col(|ui| { ui.label(product.and_then(|product| product.unity.clone()).unwrap_or_default()); });
+                        row.col(|ui| { ui.label(line.expected_quantity.to_string()); });
+                        row.col(|ui| {
+                            if !editable {
+                                ui.label(line.counted_quantity.map(|counted| counted.to_string()).unwrap_or_default());
+                                return;
+                            }
+
+                            let input = self.count_inputs.entry(line.id).or_default();
+                            let response = ui.add(egui::TextEdit::singleline(input).desired_width(72.0));
+
+                            if response.lost_focus() {
+                                counts_to_save.push(line.id);
+                            }
+                        });
+                        row.col(|ui| {
+                            match line.variance() {
+                                Some(0) => ui.label("0"),
+                                Some(variance) => ui.label(RichText::new(format!("{:+}", variance)).color(ui.visuals().warn_fg_color)),
+                                None => ui.label(RichText::new("Not counted").weak()),
+                            };
+                        });
+                    }
+                );
+            });
+
+        for line_id in counts_to_save {
+            self.save_count(line_id);
+        }
+    }
+
+    /// Saves the count typed for a line, when it changed; a blank cell clears it.
+    fn save_count(&mut self, line_id: i32) {
+        let Some(line) = self.lines.iter_mut().find(|line| line.id == line_id) else {
+            return;
+        };
+
+        let input = self.count_inputs.get(&line_id).map(|input| input.trim()).unwrap_or_default();
+
+        let counted_quantity = match input {
+            "" => None,
+            value => match value.parse::<i32>() {
+                Ok(counted) => Some(counted),
+                Err(_) => {
+                    self.error = Some(format!("'{}' is not a valid count, enter a whole number", value).into());
+                    return;
+                }
+            },
+        };
+
+        if counted_quantity == line.counted_quantity {
+            return;
+        }
+
+        let mut connection = db::establish_connection();
+
+        match stocktake_repository::record_stocktake_count(&mut connection, line_id, counted_quantity) {
+            Ok(updated_line) => *line = updated_line,
+            Err(error) => self.error = Some(error),
+        }
+    }
+
+    fn import_counts(&mut self, path: std::path::PathBuf) {
+        let counts = match import_stocktake_counts(path) {
+            Ok(counts) => counts,
+            Err(error) => {
+                self.error = Some(error);
+                return;
+            }
+        };
+
+        let mut connection = db::establish_connection();
+
+        match stocktake_repository::import_stocktake_counts(&mut connection, self.stocktake.id, &counts) {
+            Ok(_) => self.reload_lines(),
+            Err(error) => self.error = Some(error),
+        }
+    }
+
+    fn reload_lines(&mut self) {
+        let mut connection = db::establish_connection();
+
+        match stocktake_repository::list_stocktake_lines(&mut connection, self.stocktake.id) {
+            Ok(lines) => {
+                self.count_inputs = lines.iter()
+                    .map(|line| (line.id, line.counted_quantity.map(|counted| counted.to_string()).unwrap_or_default()))
+                    .collect();
+                self.lines = lines;
+            }
+            Err(error) => self.error = Some(error),
+        }
+    }
+
+    fn place_name(&self, place_id: i32) -> Option<String> {
+        self.places.iter()
+            .find(|place| place.id == place_id)
+            .map(|place| place_path(&self.places, place.id))
+    }
+
+    fn show_confirm_cancel_alert(&mut self, ui: &mut egui::Ui) {
+        let alert = egui::Modal::new(egui::Id::new("Cancel Stocktake"))
+            .show(ui.ctx(), |ui| {
+                ui.heading("Cancel Stocktake");
+
+                ui.separator();
+                ui.add_space(DEFAULT_SPACING / 2.0);
+
+                ui.label(format!("Cancel stocktake #{}? Its counts will not be posted.", self.stocktake.id));
+
+                ui.add_space(DEFAULT_SPACING * 2.0);
+                ui.separator();
+
+                Sides::new().show(
+                    ui,
+                    |_ui| {},
+                    |ui| {
+                        if ui.add(egui::Button::new("Confirm")).clicked() {
+                            let mut connection = db::establish_connection();
+
+                            match stocktake_repository::cancel_stocktake(&mut connection, self.stocktake.id) {
+                                Ok(stocktake) => self.stocktake = stocktake,
+                                Err(error) => self.error = Some(error),
+                            }
+
+                            self.stocktake_to_cancel = false;
+                        }
+
+                        if ui.add(egui::Button::new("Back")).clicked() {
+                            self.stocktake_to_cancel = false;
+                        }
+                    }
+                );
+            });
+
+        if alert.should_close() {
+            self.stocktake_to_cancel = false;
+        }
+    }
+
+    fn show_error_message(&mut self, ui: &mut egui::Ui){
+        if let Some(error) = &self.error {
+            let error_message = error.to_string();
+
+            let error_alert = egui::Modal::new(egui::Id::new("Error Message"))
+                .show(ui.ctx(), |ui| {
+                    ui.heading("Error");
+
+                    ui.separator();
+                    ui.add_space(DEFAULT_SPACING / 2.0);
+
+                    ui.label(error_message);
+
+                    ui.add_space(DEFAULT_SPACING);
+                    ui.separator();
+
+
+                    Sides::new().show(
+                        ui,
+                        |_ui| {},
+                        |ui| {
+                            if ui.button("OK").clicked() {
+                                self.error = None;
+                            }
+                        }
+                    );
+                });
+
+            if error_alert.should_close() {
+                self.error = None;
+            }
+        }
+    }
+}
@@ -0,0 +1,173 @@
+use eframe::egui;
+use egui::{Direction, Layout};
+use egui_extras::{Column, Size, StripBuilder, TableBuilder};
+
+use crate::infra::db;
+use crate::infra::repositories::{place_repository, stocktake_repository};
+use crate::domain::audit::AuditEntity;
+use crate::domain::place::{Place, place_path};
+use crate::domain::stocktake::Stocktake;
+use crate::egui::components::modals::audit_history_modal::AuditHistoryModal;
+use crate::egui::components::modals::stocktake_form_modal::StocktakeFormModal;
+use crate::egui::screens::stocktake_detail_screen::StocktakeDetailScreen;
+
+const DEFAULT_SPACING: f32 = 16.0;
+const ITEM_HEIGHT: f32 = 24.0;
+const DATE_TIME_DISPLAY_PATTERN: &str = "%Y-%m-%d %H:%M";
+
+pub struct StocktakesScreen {
+    pub stocktakes: Vec<Stocktake>,
+    pub places: Vec<Place>,
+    pub stocktake_form_modal: Option<StocktakeFormModal>,
+    pub audit_history_modal: Option<AuditHistoryModal>,
+    pub detail_screen: Option<StocktakeDetailScreen>,
+}
+
+impl StocktakesScreen {
+    pub fn new() -> Self {
+        let mut connection = db::establish_connection();
+
+        Self {
+            stocktakes: StocktakesScreen::get_stocktakes_list(),
+            places: place_repository::list_places(&mut connection, "").unwrap_or_default(),
+            stocktake_form_modal: None,
+            audit_history_modal: None,
+            detail_screen: None,
+        }
+    }
+
+    pub fn ui(&mut self, ui: &mut egui::Ui) {
+        if let Some(detail_screen) = self.detail_screen.as_mut() {
+            if detail_screen.ui(ui) {
+                self.detail_screen = None;
+                self.stocktakes = StocktakesScreen::get_stocktakes_list();
+            }
+
+            return;
+        }
+
+        let new_stocktake_btn = egui::Button::new(
+            egui::RichText::new("New Stocktake").color(egui::Color32::WHITE)
+        ).fill(ui.visuals().selection.bg_fill);
+
+        ui.horizontal(|ui| {
+            ui.heading("Stocktakes");
+
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if ui.add(new_stocktake_btn).clicked() {
+                    self.stocktake_form_modal = Some(StocktakeFormModal::new(&self.places));
+                }
+            });
+        });
+
+        ui.add_space(DEFAULT_SPACING);
+
+        StripBuilder::new(ui)
+            .size(Size::remainder().at_least(100.0))
+            .vertical(|mut strip| {
+                strip.cell(|ui| {
+                    egui::ScrollArea::horizontal().show(ui, |ui| {
+                        self.stocktakes_table(ui);
+                    });
+                });
+            });
+
+        if let Some(modal) = self.stocktake_form_modal.as_mut() {
+            let (should_close, created_stocktake) = modal.show(ui);
+
+            if should_close {
+                self.stocktake_form_modal = None;
+
+                if let Some(stocktake) = created_stocktake {
+                    self.detail_screen = Some(StocktakeDetailScreen::new(stocktake));
+                }
+            }
+        }
+
+        if let Some(modal) = self.audit_history_modal.as_mut()
+            && modal.show(ui) {
+            self.audit_history_modal = None;
+        }
+    }
+
+    fn stocktakes_table(&mut self, ui: &mut egui::Ui) {
+        let avaiable_height = ui.available_height();
+
+        TableBuilder::new(ui)
+            .striped(true)
+            .resizable(false)
+            .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+            .column(Column::exact(64.0))
+            .column(Column::exact(130.0))
+            .column(
+                Column::auto()
+                .at_least(120.0)
+                .at_most(240.0)
+                .clip(true)
+            )
+            .column(Column::exact(100.0))
+            .column(Column::exact(130.0))
+            .column(
+                Column::remainder()
+                .at_least(120.0)
+                .clip(true)
+                .resizable(false)
+            )
+            .column(Column::exact(150.0))
+            .min_scrolled_height(0.0)
+            .max_scroll_height(avaiable_height)
+            .header(ITEM_HEIGHT, |mut header| {
+                header.col(|ui| { ui.heading("ID"); });
+                header.col(|ui| { ui.heading("Started"); });
+                header.col(|ui| { ui.heading("Place"); });
+                header.col(|ui| { ui.heading("Status"); });
+                header.col(|ui| { ui.heading("Approved"); });
+                header.col(|ui| { ui.heading("Notes"); });
+                header.col(|ui| {
+                    ui.with_layout(Layout::centered_and_justified(Direction::LeftToRight), |ui| {
+                        ui.heading("Actions");
+                    });
+                });
+            })
+            .body(|body| {
+                body.rows(
+                    ITEM_HEIGHT,
+                    self.stocktakes.len(),
+                    |mut row| {
+                        let stocktake = &self.stocktakes[row.index()];
+
+                        let place_name = match stocktake.place_id {
+                            Some(place_id) => self.places.iter()
+                                .find(|place| place.id == place_id)
+                                .map(|place| place_path(&self.places, place.id))
+                                .unwrap_or_else(|| format!("#{}", place_id)),
+                            None => "All places".to_owned(),
+                        };
+
+                        row.col(|ui| { ui.label(stocktake.id.to_string()); });
+                        row.col(|ui| { ui.label(stocktake.created_at.format(DATE_TIME_DISPLAY_PATTERN).to_string()); });
+                        row.col(|ui| { ui.label(place_name); });
+                        row.col(|ui| { ui.label(stocktake.status.label()); });
+                        row.col(|ui| { ui.label(stocktake.approved_at.map(|date| date.format(DATE_TIME_DISPLAY_PATTERN).to_string()).unwrap_or_default()); });
+                        row.col(|ui| { ui.label(stocktake.notes.clone().unwrap_or_default()); });
+                        row.col(|ui| {
+                            if ui.add(egui::Button::new("Open")).clicked() {
+                                self.detail_screen = Some(StocktakeDetailScreen::new(stocktake.clone()));
+                            }
+
+                            if ui.add(egui::Button::new("History")).clicked() {
+                                self.audit_history_modal = Some(AuditHistoryModal::new(AuditEntity::Stocktake, stocktake.id, &format!("#{}", stocktake.id)));
+                            }
+                        });
+                    }
+                );
+            });
+    }
+
+    fn get_stocktakes_list() -> Vec<Stocktake> {
+        let mut connection = db::establish_connection();
+
+        stocktake_repository::list_stocktakes(&mut connection)
+            .unwrap_or_default()
+    }
+}
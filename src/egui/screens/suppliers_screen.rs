@@ -6,18 +6,28 @@ use std::error::Error;
 
 use crate::infra::db;
 use crate::infra::repositories::supplier_repository;
+use crate::domain::audit::AuditEntity;
 use crate::domain::supplier::Supplier;
+use crate::egui::components::modals::audit_history_modal::AuditHistoryModal;
+use crate::egui::components::modals::supplier_detail_modal::SupplierDetailModal;
 use crate::egui::components::modals::supplier_form_modal::SupplierFormModal;
+use crate::egui::widgets::trash_retention::TrashRetention;
 use crate::services::export::export_suppliers::export_suppliers;
 use crate::services::import::import_suppliers::import_suppliers;
 
 const DEFAULT_SPACING: f32 = 16.0;
 const ITEM_HEIGHT: f32 = 24.0;
+const DATE_TIME_DISPLAY_PATTERN: &str = "%Y-%m-%d %H:%M";
 
 pub struct SuppliersScreen {
     pub suppliers: Vec<Supplier>,
     pub supplier_form_modal: Option<SupplierFormModal>,
+    pub supplier_detail_modal: Option<SupplierDetailModal>,
+    pub audit_history_modal: Option<AuditHistoryModal>,
     pub supplier_to_delete: Option<Supplier>,
+    pub show_trash: bool,
+    pub deleted_suppliers: Vec<Supplier>,
+    pub supplier_to_purge: Option<Supplier>,
     pub error: Option<Box<dyn Error>>,
     pub search: String,
 }
@@ -29,13 +39,18 @@ impl SuppliersScreen {
         Self {
             suppliers,
             supplier_form_modal: None,
+            supplier_detail_modal: None,
+            audit_history_modal: None,
             supplier_to_delete: None,
+            show_trash: false,
+            deleted_suppliers: Vec::new(),
+            supplier_to_purge: None,
             error: None,
             search: String::new(),
         }
     }
 
-    pub fn ui(&mut self, ui: &mut egui::Ui) {
+    pub fn ui(&mut self, ui: &mut egui::Ui, trash_retention: &mut TrashRetention) {
         let add_supplier_btn = egui::Button::new(
             egui::RichText::new("Add Supplier").color(egui::Color32::WHITE)
         ).fill(ui.visuals().selection.bg_fill);
@@ -48,6 +63,10 @@ impl SuppliersScreen {
                     self.supplier_form_modal = Some(SupplierFormModal::new(None));
                 }
 
+                if ui.toggle_value(&mut self.show_trash, "Trash").on_hover_text("Deleted suppliers").changed() && self.show_trash {
+                    self.reload_deleted_suppliers();
+                }
+
                 if ui.add(egui::Button::new("Export")).clicked() {
                     match FileDialog::new().set_file_name("suppliers.xlsx").save_file() {
                         Some(path) => {
@@ -68,8 +87,9 @@ impl SuppliersScreen {
                                     &new_suppliers_row
                                 );
 
-                                if let Ok(mut created_suppliers) = creation_result {
-                                    self.suppliers.append(&mut created_suppliers);
+                                match creation_result {
+                                    Ok(mut created_suppliers) => self.suppliers.append(&mut created_suppliers),
+                                    Err(error) => self.error = Some(error),
                                 }
                             },
                             Err(error) => {
@@ -82,18 +102,34 @@ impl SuppliersScreen {
                 if ui.add(egui::TextEdit::singleline(&mut self.search).hint_text("Search for supplier...")).changed() {
                     let filtered_suppliers = SuppliersScreen::get_suppliers_list(&self.search);
                     self.suppliers = filtered_suppliers;
+
+                    if self.show_trash {
+                        self.reload_deleted_suppliers();
+                    }
                 };
             });
         });
 
         ui.add_space(DEFAULT_SPACING);
 
+        if self.show_trash {
+            if trash_retention.ui(ui) {
+                self.reload_deleted_suppliers();
+            }
+
+            ui.add_space(DEFAULT_SPACING / 2.0);
+        }
+
         StripBuilder::new(ui)
             .size(Size::remainder().at_least(100.0))
             .vertical(|mut strip| {
                 strip.cell(|ui| {
                     egui::ScrollArea::horizontal().show(ui, |ui| {
-                        self.suppliers_table(ui);
+                        if self.show_trash {
+                            self.trash_table(ui);
+                        } else {
+                            self.suppliers_table(ui);
+                        }
                     });
                 });
             });
@@ -110,14 +146,33 @@ impl SuppliersScreen {
                     } else {
                         self.suppliers.push(supplier);
                     }
+
+                    // The supplier may have been restored from the trash instead of created.
+                    if self.show_trash {
+                        self.reload_deleted_suppliers();
+                    }
                 }
             }
         }
 
+        if let Some(modal) = self.supplier_detail_modal.as_mut()
+            && modal.show(ui) {
+            self.supplier_detail_modal = None;
+        }
+
+        if let Some(modal) = self.audit_history_modal.as_mut()
+            && modal.show(ui) {
+            self.audit_history_modal = None;
+        }
+
         if self.supplier_to_delete.is_some() {
             self.show_confirm_delete_alert(ui);
         }
 
+        if self.supplier_to_purge.is_some() {
+            self.show_confirm_purge_alert(ui);
+        }
+
         if self.error.is_some() {
            self.show_error_message(ui);
         }
@@ -137,12 +192,32 @@ impl SuppliersScreen {
                 .clip(true)
                 .resizable(false)
             )
-            .column(Column::exact(100.0))     
+            .column(Column::exact(150.0))
+            .column(
+                Column::auto()
+                .at_least(100.0)
+                .at_most(180.0)
+                .clip(true)
+            )
+            .column(Column::exact(120.0))
+            .column(
+                Column::auto()
+                .at_least(120.0)
+                .at_most(220.0)
+                .clip(true)
+            )
+            .column(Column::exact(72.0))
+            .column(Column::exact(240.0))
             .min_scrolled_height(0.0)
             .max_scroll_height(avaiable_height)
             .header(ITEM_HEIGHT,|mut header| {
                 header.col(|ui| { ui.heading("ID"); });
                 header.col(|ui| { ui.heading("Name"); });
+                header.col(|ui| { ui.heading("Tax ID"); });
+                header.col(|ui| { ui.heading("Contact"); });
+                header.col(|ui| { ui.heading("Phone"); });
+                header.col(|ui| { ui.heading("Email"); });
+                header.col(|ui| { ui.heading("Status"); });
                 header.col(|ui| { 
                     ui.with_layout(Layout::centered_and_justified(Direction::LeftToRight), |ui| {
                         ui.heading("Actions");
@@ -160,6 +235,17 @@ impl SuppliersScreen {
 
                         row.col(|ui| { ui.label(supplier.id.to_string()); });
                         row.col(|ui| { ui.label(supplier.name.clone()); });
+                        row.col(|ui| { ui.label(supplier.formatted_tax_id().unwrap_or_default()); });
+                        row.col(|ui| { ui.label(supplier.contact_name.clone().unwrap_or_default()); });
+                        row.col(|ui| { ui.label(supplier.phone.clone().or_else(|| supplier.mobile_phone.clone()).unwrap_or_default()); });
+                        row.col(|ui| { ui.label(supplier.email.clone().unwrap_or_default()); });
+                        row.col(|ui| {
+                            if supplier.active {
+                                ui.label("Active");
+                            } else {
+                                ui.label(egui::RichText::new("Inactive").weak());
+                            }
+                        });
                         row.col(|ui| {
                             let delete_button = egui::Button::new(
                             egui::RichText::new("Delete").color(egui::Color32::WHITE)
@@ -172,6 +258,14 @@ impl SuppliersScreen {
                             if ui.add(egui::Button::new("Edit")).clicked() {
                                 self.supplier_form_modal = Some(SupplierFormModal::new(Some(&supplier)));
                             }
+
+                            if ui.add(egui::Button::new("Details")).clicked() {
+                                self.supplier_detail_modal = Some(SupplierDetailModal::new(supplier));
+                            }
+
+                            if ui.add(egui::Button::new("History")).clicked() {
+                                self.audit_history_modal = Some(AuditHistoryModal::new(AuditEntity::Supplier, supplier.id, &supplier.name));
+                            }
                         });
                     }
                 );
@@ -225,6 +319,132 @@ impl SuppliersScreen {
         }
     }
 
+    fn trash_table(&mut self, ui: &mut egui::Ui) {
+        let avaiable_height = ui.available_height();
+        let mut supplier_to_restore = None;
+
+        TableBuilder::new(ui)
+            .striped(true)
+            .resizable(false)
+            .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+            .column(Column::exact(64.0))
+            .column(
+                Column::remainder()
+                .at_least(120.0)
+                .clip(true)
+                .resizable(false)
+            )
+            .column(Column::exact(130.0))
+            .column(Column::exact(160.0))
+            .min_scrolled_height(0.0)
+            .max_scroll_height(avaiable_height)
+            .header(ITEM_HEIGHT, |mut header| {
+                header.col(|ui| { ui.heading("ID"); });
+                header.col(|ui| { ui.heading("Name"); });
+                header.col(|ui| { ui.heading("Deleted"); });
+                header.col(|ui| {
+                    ui.with_layout(Layout::centered_and_justified(Direction::LeftToRight), |ui| {
+                        ui.heading("Actions");
+                    });
+                });
+            })
+            .body(|body| {
+                body.rows(
+                    ITEM_HEIGHT,
+                    self.deleted_suppliers.len(),
+                    |mut row| {
+                        let supplier = &self.deleted_suppliers[row.index()];
+
+                        row.col(|ui| { ui.label(supplier.id.to_string()); });
+                        row.col(|ui| { ui.label(&supplier.name); });
+                        row.col(|ui| {
+                            ui.label(supplier.deleted_at.map(|date| date.format(DATE_TIME_DISPLAY_PATTERN).to_string()).unwrap_or_default());
+                        });
+                        row.col(|ui| {
+                            let purge_button = egui::Button::new(
+                                egui::RichText::new("Purge").color(egui::Color32::WHITE)
+                            ).fill(ui.visuals().error_fg_color);
+
+                            if ui.add(purge_button).on_hover_text("Delete permanently").clicked() {
+                                self.supplier_to_purge = Some(supplier.clone());
+                            }
+
+                            if ui.add(egui::Button::new("Restore")).clicked() {
+                                supplier_to_restore = Some(supplier.id);
+                            }
+                        });
+                    }
+                );
+            });
+
+        if let Some(supplier_id) = supplier_to_restore {
+            let mut connection = db::establish_connection();
+
+            match supplier_repository::restore_supplier(&mut connection, supplier_id) {
+                Ok(_) => {
+                    self.deleted_suppliers.retain(|supplier| supplier.id != supplier_id);
+                    self.suppliers = SuppliersScreen::get_suppliers_list(&self.search);
+                }
+                Err(error) => self.error = Some(error),
+            }
+        }
+    }
+
+    fn show_confirm_purge_alert(&mut self, ui: &mut egui::Ui) {
+        let alert = egui::Modal::new(egui::Id::new("Purge Supplier"))
+            .show(ui.ctx(), |ui| {
+                ui.heading("Purge Supplier");
+
+                ui.separator();
+                ui.add_space(DEFAULT_SPACING / 2.0);
+
+                ui.add(
+                Label::new(
+                    format!("Permanently delete supplier '{}'? It cannot be restored afterwards.",
+                            self.supplier_to_purge.as_ref().unwrap().name)
+                        )
+                );
+
+                ui.add_space(DEFAULT_SPACING * 2.0);
+                ui.separator();
+
+                Sides::new().show(
+                    ui,
+                    |_ui| {},
+                    |ui| {
+                        if ui.add(egui::Button::new("Confirm")).clicked() {
+                            let mut connection = db::establish_connection();
+                            let supplier_id = self.supplier_to_purge.as_ref().unwrap().id;
+
+                            match supplier_repository::purge_supplier(&mut connection, supplier_id) {
+                                Ok(_) => self.deleted_suppliers.retain(|supplier| supplier.id != supplier_id),
+                                Err(error) => self.error = Some(error),
+                            }
+
+                            self.supplier_to_purge = None;
+                        }
+
+                        if ui.add(egui::Button::new("Cancel")).clicked() {
+                            self.supplier_to_purge = None;
+                        }
+                    }
+                );
+            });
+
+        if alert.should_close() {
+            self.supplier_to_purge = None;
+        }
+    }
+
+    fn reload_deleted_suppliers(&mut self) {
+        let mut connection = db::establish_connection();
+
+        match supplier_repository::list_deleted_suppliers(&mut connection, &self.search) {
+            Ok(suppliers) => self.deleted_suppliers = suppliers,
+            Err(error) => self.error = Some(error),
+        }
+    }
+
     fn show_error_message(&mut self, ui: &mut egui::Ui){
         if let Some(error) = &self.error {
             let error_message = error.to_string();
@@ -0,0 +1,248 @@
+use eframe::egui;
+use egui::{Direction, Label, Layout, RichText, Sides};
+use egui_extras::{Column, Size, StripBuilder, TableBuilder};
+use std::error::Error;
+
+use crate::infra::db;
+use crate::infra::repositories::unit_repository;
+use crate::domain::audit::AuditEntity;
+use crate::domain::unit::Unit;
+use crate::egui::components::modals::audit_history_modal::AuditHistoryModal;
+use crate::egui::components::modals::unit_form_modal::UnitFormModal;
+
+const DEFAULT_SPACING: f32 = 16.0;
+const ITEM_HEIGHT: f32 = 24.0;
+
+pub struct UnitsScreen {
+    pub units: Vec<Unit>,
+    pub unit_form_modal: Option<UnitFormModal>,
+    pub audit_history_modal: Option<AuditHistoryModal>,
+    pub unit_to_delete: Option<Unit>,
+    pub error: Option<Box<dyn Error>>,
+    pub search: String,
+}
+
+impl UnitsScreen {
+    pub fn new() -> Self {
+        Self {
+            units: UnitsScreen::get_units_list(""),
+            unit_form_modal: None,
+            audit_history_modal: None,
+            unit_to_delete: None,
+            error: None,
+            search: String::new(),
+        }
+    }
+
+    pub fn ui(&mut self, ui: &mut egui::Ui) {
+        let add_unit_btn = egui::Button::new(
+            egui::RichText::new("Add Unit").color(egui::Color32::WHITE)
+        ).fill(ui.visuals().selection.bg_fill);
+
+        ui.horizontal(|ui| {
+            ui.heading("Units of Measure");
+
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if ui.add(add_unit_btn).clicked() {
+                    self.unit_form_modal = Some(UnitFormModal::new(None, &UnitsScreen::get_units_list("")));
+                }
+
+                if ui.add(egui::TextEdit::singleline(&mut self.search).hint_text("Search for unit...")).changed() {
+                    self.units = UnitsScreen::get_units_list(&self.search);
+                };
+            });
+        });
+
+        ui.add_space(DEFAULT_SPACING);
+
+        StripBuilder::new(ui)
+            .size(Size::remainder().at_least(100.0))
+            .vertical(|mut strip| {
+                strip.cell(|ui| {
+                    egui::ScrollArea::horizontal().show(ui, |ui| {
+                        self.units_table(ui);
+                    });
+                });
+            });
+
+        if let Some(modal) = self.unit_form_modal.as_mut() {
+            let (should_close, saved_unit) = modal.show(ui);
+
+            if should_close {
+                self.unit_form_modal = None;
+
+                if saved_unit.is_some() {
+                    self.units = UnitsScreen::get_units_list(&self.search);
+                }
+            }
+        }
+
+        if let Some(modal) = self.audit_history_modal.as_mut()
+            && modal.show(ui) {
+            self.audit_history_modal = None;
+        }
+
+        if self.unit_to_delete.is_some() {
+            self.show_confirm_delete_alert(ui);
+        }
+
+        if self.error.is_some() {
+           self.show_error_message(ui);
+        }
+    }
+
+    fn units_table(&mut self, ui: &mut egui::Ui) {
+        let avaiable_height = ui.available_height();
+
+        TableBuilder::new(ui)
+            .striped(true)
+            .resizable(false)
+            .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+            .column(
+                Column::remainder()
+                .at_least(120.0)
+                .clip(true)
+                .resizable(false)
+            )
+            .column(Column::exact(88.0))
+            .column(Column::exact(220.0))
+            .column(Column::exact(190.0))
+            .min_scrolled_height(0.0)
+            .max_scroll_height(avaiable_height)
+            .header(ITEM_HEIGHT, |mut header| {
+                header.col(|ui| { ui.heading("Name"); });
+                header.col(|ui| { ui.heading("Symbol"); });
+                header.col(|ui| { ui.heading("Conversion"); });
+                header.col(|ui| {
+                    ui.with_layout(Layout::centered_and_justified(Direction::LeftToRight), |ui| {
+                        ui.heading("Actions");
+                    });
+                });
+            })
+            .body(|body| {
+                body.rows(
+                    ITEM_HEIGHT,
+                    self.units.len(),
+                    |mut row| {
+                        let unit = &self.units[row.index()];
+
+                        let base_unit = unit.base_unit_id
+                            .and_then(|base_unit_id| self.units.iter().find(|candidate| candidate.id == base_unit_id));
+
+                        row.col(|ui| { ui.label(&unit.name); });
+                        row.col(|ui| { ui.label(&unit.symbol); });
+                        row.col(|ui| {
+                            match base_unit {
+                                Some(base_unit) => ui.label(format!("1 {} = {} {}", unit.symbol, unit.factor, base_unit.symbol)),
+                                None => ui.label(RichText::new("Base unit").weak()),
+                            };
+                        });
+                        row.col(|ui| {
+                            let delete_button = egui::Button::new(
+                                egui::RichText::new("Delete").color(egui::Color32::WHITE)
+                            ).fill(ui.visuals().error_fg_color);
+
+                            if ui.add(delete_button).clicked() {
+                                self.unit_to_delete = Some(unit.clone());
+                            }
+
+                            if ui.add(egui::Button::new("Edit")).clicked() {
+                                self.unit_form_modal = Some(UnitFormModal::new(Some(unit), &self.units));
+                            }
+
+                            if ui.add(egui::Button::new("History")).clicked() {
+                                self.audit_history_modal = Some(AuditHistoryModal::new(AuditEntity::Unit, unit.id, &unit.label()));
+                            }
+                        });
+                    }
+                );
+            });
+    }
+
+    fn show_confirm_delete_alert(&mut self, ui: &mut egui::Ui) {
+        let alert = egui::Modal::new(egui::Id::new("Delete Unit"))
+            .show(ui.ctx(), |ui| {
+                ui.heading("Delete Unit");
+
+                ui.separator();
+                ui.add_space(DEFAULT_SPACING / 2.0);
+
+                ui.add(
+                Label::new(
+                    format!("Are you sure you want to delete unit '{}'?",
+                            self.unit_to_delete.as_ref().unwrap().label())
+                        )
+                );
+
+                ui.add_space(DEFAULT_SPACING * 2.0);
+                ui.separator();
+
+                Sides::new().show(
+                    ui,
+                    |_ui| {},
+                    |ui| {
+                        if ui.add(egui::Button::new("Confirm")).clicked() {
+                            let mut connection = db::establish_connection();
+                            let unit_id = self.unit_to_delete.as_ref().unwrap().id;
+
+                            match unit_repository::delete_unit(&mut connection, unit_id) {
+                                Ok(_) => self.units.retain(|unit| unit.id != unit_id),
+                                Err(error) => self.error = Some(error),
+                            }
+
+                            self.unit_to_delete = None;
+                        }
+
+                        if ui.add(egui::Button::new("Cancel")).clicked() {
+                            self.unit_to_delete = None;
+                        }
+                    }
+                );
+
+            });
+
+        if alert.should_close() {
+            self.unit_to_delete = None;
+        }
+    }
+
+    fn show_error_message(&mut self, ui: &mut egui::Ui){
+        if let Some(error) = &self.error {
+            let error_message = error.to_string();
+
+            let error_alert = egui::Modal::new(egui::Id::new("Error Message"))
+                .show(ui.ctx(), |ui| {
+                    ui.heading("Error");
+
+                    ui.separator();
+                    ui.add_space(DEFAULT_SPACING / 2.0);
+
+                    ui.label(error_message);
+
+                    ui.add_space(DEFAULT_SPACING);
+                    ui.separator();
+
+
+                    Sides::new().show(
+                        ui,
+                        |_ui| {},
+                        |ui| {
+                            if ui.button("OK").clicked() {
+                                self.error = None;
+                            }
+                        }
+                    );
+                });
+
+            if error_alert.should_close() {
+                self.error = None;
+            }
+        }
+    }
+
+    fn get_units_list(search: &str) -> Vec<Unit> {
+        let mut connection = db::establish_connection();
+
+        unit_repository::list_units(&mut connection, search).unwrap_or_default()
+    }
+}
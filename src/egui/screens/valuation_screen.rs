@@ -0,0 +1,392 @@
+use chrono::{Local, NaiveDate};
+use eframe::egui;
+use egui::{ComboBox, Direction, Layout, RichText, Sides};
+use egui_extras::{Column, Size, StripBuilder, TableBuilder};
+use rfd::FileDialog;
+use std::error::Error;
+
+use crate::infra::db;
+use crate::infra::repositories::{place_repository, product_repository, setting_repository, valuation_repository};
+use crate::domain::place::{Place, descendant_place_ids, place_path};
+use crate::domain::product::Product;
+use crate::domain::valuation::{CostingMethod, StockValuation};
+use crate::services::export::export_stock_valuation::export_stock_valuation;
+
+const DEFAULT_SPACING: f32 = 16.0;
+const ITEM_HEIGHT: f32 = 24.0;
+const NAIVE_DATE_PATTERN: &str = "%Y-%m-%d";
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ValuationGrouping {
+    #[default]
+    ProductAndPlace,
+    Product,
+    Place,
+}
+
+impl ValuationGrouping {
+    pub const ALL: [ValuationGrouping; 3] = [
+        ValuationGrouping::ProductAndPlace,
+        ValuationGrouping::Product,
+        ValuationGrouping::Place,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ValuationGrouping::ProductAndPlace => "Product & place",
+            ValuationGrouping::Product => "Product",
+            ValuationGrouping::Place => "Place",
+        }
+    }
+}
+
+/// One line of the report. Places mix units, so their lines carry no quantity.
+struct ValuationLine {
+    product: String,
+    place: String,
+    quantity: Option<i64>,
+    unit: String,
+    value: f64,
+}
+
+impl ValuationLine {
+    fn unit_cost(&self) -> Option<f64> {
+        self.quantity
+            .filter(|quantity| *quantity != 0)
+            .map(|quantity| self.value / quantity as f64)
+    }
+}
+
+pub struct ValuationScreen {
+    pub valuations: Vec<StockValuation>,
+    pub products: Vec<Product>,
+    pub places: Vec<Place>,
+    /// Only values stock at this place and the places inside it.
+    pub place_id: Option<i32>,
+    pub method: CostingMethod,
+    pub grouping: ValuationGrouping,
+    pub as_of: NaiveDate,
+    pub as_of_input: String,
+    pub as_of_error: Option<String>,
+    pub error: Option<Box<dyn Error>>,
+}
+
+impl ValuationScreen {
+    pub fn new() -> Self {
+        let mut connection = db::establish_connection();
+
+        let (method, error) = match setting_repository::get_costing_method(&mut connection) {
+            Ok(method) => (method, None),
+            Err(error) => (CostingMethod::default(), Some(error)),
+        };
+
+        let today = Local::now().date_naive();
+
+        let mut screen = Self {
+            valuations: Vec::new(),
+            products: product_repository::list_products(&mut connection, "").unwrap_or_default(),
+            places: place_repository::list_places(&mut connection, "").unwrap_or_default(),
+            place_id: None,
+            method,
+            grouping: ValuationGrouping::default(),
+            as_of: today,
+            as_of_input: today.format(NAIVE_DATE_PATTERN).to_string(),
+            as_of_error: None,
+            error,
+        };
+
+        screen.refresh_valuations();
+
+        screen
+    }
+
+    pub fn ui(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.heading("Stock Valuation");
+
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if ui.add(egui::Button::new("Export")).clicked()
+                    && let Some(path) = FileDialog::new().set_file_name(format!("stock_valuation_{}.xlsx", self.as_of)).save_file() {
+                    let _ = export_stock_valuation(&self.valuations, &self.products, &self.places, self.method, self.as_of, path);
+                }
+            });
+        });
+
+        ui.add_space(DEFAULT_SPACING / 2.0);
+
+        self.filters_bar(ui);
+
+        ui.add_space(DEFAULT_SPACING / 2.0);
+
+        let total: f64 = self.valuations.iter().map(|valuation| valuation.value).sum();
+        ui.label(RichText::new(format!("Total value: {:.2}", total)).strong());
+
+        ui.add_space(DEFAULT_SPACING);
+
+        StripBuilder::new(ui)
+            .size(Size::remainder().at_least(100.0))
+            .vertical(|mut strip| {
+                strip.cell(|ui| {
+                    egui::ScrollArea::horizontal().show(ui, |ui| {
+                        self.valuation_table(ui);
+                    });
+                });
+            });
+
+        if self.error.is_some() {
+           self.show_error_message(ui);
+        }
+    }
+
+    fn filters_bar(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label("As of");
+            let as_of = ui.add(egui::TextEdit::singleline(&mut self.as_of_input).desired_width(100.0).hint_text("YYYY-MM-DD"));
+
+            if as_of.lost_focus() {
+                match NaiveDate::parse_from_str(self.as_of_input.trim(), NAIVE_DATE_PATTERN) {
+                    Ok(date) => {
+                        self.as_of_error = None;
+
+                        if date != self.as_of {
+                            self.as_of = date;
+                            self.refresh_valuations();
+                        }
+                    }
+                    Err(_) => self.as_of_error = Some("Use the format YYYY-MM-DD".into()),
+                }
+            }
+
+            if let Some(error) = &self.as_of_error {
+                ui.label(RichText::new(error).color(ui.visuals().error_fg_color));
+            }
+
+            ui.add_space(DEFAULT_SPACING);
+
+            ui.label("Place");
+            let place_name = self.places.iter()
+                .find(|place| Some(place.id) == self.place_id)
+                .map(|place| place_path(&self.places, place.id))
+                .unwrap_or_else(|| "All".to_owned());
+
+            let mut place_changed = false;
+
+            ComboBox::from_id_salt("valuation_place")
+                .selected_text(place_name)
+                .show_ui(ui, |ui| {
+                    place_changed |= ui.selectable_value(&mut self.place_id, None, "All").changed();
+                    for place in &self.places {
+                        place_changed |= ui.selectable_value(&mut self.place_id, Some(place.id), place_path(&self.places, place.id)).changed();
+                    }
+                });
+
+            if place_changed {
+                self.refresh_valuations();
+            }
+
+            ui.add_space(DEFAULT_SPACING);
+
+            ui.label("Costing method");
+            let mut method = self.method;
+
+            ComboBox::from_id_salt("valuation_costing_method")
+                .selected_text(method.label())
+                .show_ui(ui, |ui| {
+                    for option in CostingMethod::ALL {
+                        ui.selectable_value(&mut method, option, option.label());
+                    }
+                });
+
+            if method != self.method {
+                self.change_costing_method(method);
+            }
+
+            ui.add_space(DEFAULT_SPACING);
+
+            ui.label("Group by");
+            ComboBox::from_id_salt("valuation_grouping")
+                .selected_text(self.grouping.label())
+                .show_ui(ui, |ui| {
+                    for option in ValuationGrouping::ALL {
+                        ui.selectable_value(&mut self.grouping, option, option.label());
+                    }
+                });
+        });
+    }
+
+    fn valuation_table(&mut self, ui: &mut egui::Ui) {
+        let avaiable_height = ui.available_height();
+        let lines = self.valuation_lines();
+
+        TableBuilder::new(ui)
+            .striped(true)
+            .resizable(false)
+            .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+            .column(
+                Column::remainder()
+                .at_least(120.0)
+                .clip(true)
+                .resizable(false)
+            )
+            .column(
+                Column::auto()
+                .at_least(80.0)
+                .at_most(200.0)
+                .clip(true)
+            )
+            .column(Column::exact(100.0))
+            .column(Column::exact(64.0))
+            .column(Column::exact(100.0))
+            .column(Column::exact(120.0))
+            .min_scrolled_height(0.0)
+            .max_scroll_height(avaiable_height)
+            .header(ITEM_HEIGHT, |mut header| {
+                header.col(|ui| { ui.heading("Product"); });
+                header.col(|ui| { ui.heading("Place"); });
+                header.col(|ui| {
+                    ui.with_layout(Layout::centered_and_justified(Direction::LeftToRight), |ui| {
+                        ui.heading("Quantity");
+                    });
+                });
+                header.col(|ui| { ui.heading("Unit"); });
+                header.col(|ui| { ui.heading("Unit Cost"); });
+                header.col(|ui| { ui.heading("Value"); });
+            })
+            .body(|body| {
+                body.rows(
+                    ITEM_HEIGHT,
+                    lines.len(),
+                    |mut row| {
+                        let line = &lines[row.index()];
+
+                        row.col(|ui| { ui.label(&line.product); });
+                        row.col(|ui| { ui.label(&line.place); });
+                        row.col(|ui| {
+                            ui.with_layout(Layout::centered_and_justified(Direction::LeftToRight), |ui| {
+                                ui.label(line.quantity.map(|quantity| quantity.to_string()).unwrap_or_default());
+                            });
+                        });
+                        row.col(|ui| { ui.label(&line.unit); });
+                        row.col(|ui| { ui.label(line.unit_cost().map(|unit_cost| format!("{:.4}", unit_cost)).unwrap_or_default()); });
+                        row.col(|ui| { ui.label(format!("{:.2}", line.value)); });
+                    }
+                );
+            });
+    }
+
+    fn valuation_lines(&self) -> Vec<ValuationLine> {
+        let mut lines: Vec<ValuationLine> = Vec::new();
+
+        for valuation in &self.valuations {
+            let product = self.products.iter().find(|product| product.id == valuation.product_id);
+
+            let product_name = product
+                .map(|product| product.name.clone())
+                .unwrap_or_else(|| format!("#{}", valuation.product_id));
+
+            let place_name = self.places.iter()
+                .find(|place| place.id == valuation.place_id)
+                .map(|place| place_path(&self.places, place.id))
+                .unwrap_or_else(|| format!("#{}", valuation.place_id));
+
+            let line = match self.grouping {
+                ValuationGrouping::ProductAndPlace => ValuationLine {
+                    product: product_name,
+                    place: place_name,
+                    quantity: Some(valuation.quantity),
+                    unit: product.and_then(|product| product.unity.clone()).unwrap_or_default(),
+                    value: valuation.value,
+                },
+                ValuationGrouping::Product => ValuationLine {
+                    product: product_name,
+                    place: String::new(),
+                    quantity: Some(valuation.quantity),
+                    unit: product.and_then(|product| product.unity.clone()).unwrap_or_default(),
+                    value: valuation.value,
+                },
+                ValuationGrouping::Place => ValuationLine {
+                    product: String::new(),
+                    place: place_name,
+                    quantity: None,
+                    unit: String::new(),
+                    value: valuation.value,
+                },
+            };
+
+            match lines.iter_mut().find(|existing| existing.product == line.product && existing.place == line.place) {
+                Some(existing) => {
+                    existing.quantity = existing.quantity.zip(line.quantity).map(|(a, b)| a + b);
+                    existing.value += line.value;
+                }
+                None => lines.push(line),
+            }
+        }
+
+        if self.grouping == ValuationGrouping::Place {
+            lines.sort_by(|a, b| a.place.cmp(&b.place));
+        }
+
+        lines
+    }
+
+    fn change_costing_method(&mut self, method: CostingMethod) {
+        let mut connection = db::establish_connection();
+
+        match setting_repository::set_costing_method(&mut connection, method) {
+            Ok(()) => {
+                self.method = method;
+                self.refresh_valuations();
+            }
+            Err(error) => self.error = Some(error),
+        }
+    }
+
+    fn refresh_valuations(&mut self) {
+        let mut connection = db::establish_connection();
+
+        let place_ids = self.place_id.map(|place_id| descendant_place_ids(&self.places, place_id));
+
+        match valuation_repository::list_stock_valuation(&mut connection, self.method, self.as_of) {
+            Ok(valuations) => {
+                self.valuations = valuations.into_iter()
+                    .filter(|valuation| place_ids.as_ref().is_none_or(|place_ids| place_ids.contains(&valuation.place_id)))
+                    .collect();
+            }
+            Err(error) => self.error = Some(error),
+        }
+    }
+
+    fn show_error_message(&mut self, ui: &mut egui::Ui){
+        if let Some(error) = &self.error {
+            let error_message = error.to_string();
+
+            let error_alert = egui::Modal::new(egui::Id::new("Error Message"))
+                .show(ui.ctx(), |ui| {
+                    ui.heading("Error");
+
+                    ui.separator();
+                    ui.add_space(DEFAULT_SPACING / 2.0);
+
+                    ui.label(error_message);
+
+                    ui.add_space(DEFAULT_SPACING);
+                    ui.separator();
+
+
+                    Sides::new().show(
+                        ui,
+                        |_ui| {},
+                        |ui| {
+                            if ui.button("OK").clicked() {
+                                self.error = None;
+                            }
+                        }
+                    );
+                });
+
+            if error_alert.should_close() {
+                self.error = None;
+            }
+        }
+    }
+}
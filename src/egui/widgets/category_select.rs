@@ -0,0 +1,38 @@
+use eframe::egui;
+use egui::ComboBox;
+
+use crate::domain::category::{Category, category_path, descendant_ids};
+
+/// Category picker listing every category by its full path. `excluded_id` hides
+/// a category and everything below it, so a category cannot become its own
+/// parent. Returns `true` when the selection changed.
+pub fn category_select(ui: &mut egui::Ui, id_salt: &str, selected: &mut Option<i32>, categories: &[Category], excluded_id: Option<i32>) -> bool {
+    let mut changed = false;
+
+    let excluded_ids = excluded_id
+        .map(|excluded_id| descendant_ids(categories, excluded_id))
+        .unwrap_or_default();
+
+    let mut options: Vec<(i32, String)> = categories.iter()
+        .filter(|category| !excluded_ids.contains(&category.id))
+        .map(|category| (category.id, category_path(categories, category.id)))
+        .collect();
+
+    options.sort_by(|a, b| a.1.cmp(&b.1));
+
+    let selected_label = selected
+        .map(|category_id| category_path(categories, category_id))
+        .unwrap_or_else(|| "None".to_owned());
+
+    ComboBox::from_id_salt(id_salt)
+        .selected_text(selected_label)
+        .show_ui(ui, |ui| {
+            changed |= ui.selectable_value(selected, None, "None").changed();
+
+            for (category_id, path) in options {
+                changed |= ui.selectable_value(selected, Some(category_id), path).changed();
+            }
+        });
+
+    changed
+}
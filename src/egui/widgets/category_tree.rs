@@ -0,0 +1,190 @@
+use eframe::egui;
+use egui::collapsing_header::CollapsingState;
+use egui::RichText;
+
+use crate::infra::db;
+use crate::infra::repositories::category_repository;
+use crate::domain::audit::AuditEntity;
+use crate::domain::category::{Category, category_path, descendant_ids};
+use crate::egui::components::modals::audit_history_modal::AuditHistoryModal;
+use crate::egui::components::modals::category_form_modal::CategoryFormModal;
+
+enum TreeAction {
+    Add(Option<i32>),
+    Edit(Category),
+    Delete(i32),
+    History(Category),
+}
+
+/// Category tree used to filter the products list. Selecting a category shows
+/// its products and those of every subcategory; right-click a category to add a
+/// subcategory, rename, move, delete it or see its history.
+pub struct CategoryTree {
+    categories: Vec<Category>,
+    selected: Option<i32>,
+    category_form_modal: Option<CategoryFormModal>,
+    audit_history_modal: Option<AuditHistoryModal>,
+    error: Option<String>,
+}
+
+impl CategoryTree {
+    pub fn new() -> Self {
+        Self {
+            categories: Self::get_categories_list(),
+            selected: None,
+            category_form_modal: None,
+            audit_history_modal: None,
+            error: None,
+        }
+    }
+
+    pub fn categories(&self) -> &[Category] {
+        &self.categories
+    }
+
+    /// Ids to filter the products by, `None` when showing every product.
+    pub fn selected_category_ids(&self) -> Option<Vec<i32>> {
+        self.selected.map(|category_id| descendant_ids(&self.categories, category_id))
+    }
+
+    pub fn reload(&mut self) {
+        self.categories = Self::get_categories_list();
+
+        if self.selected.is_some_and(|selected| !self.categories.iter().any(|category| category.id == selected)) {
+            self.selected = None;
+        }
+    }
+
+    /// Returns `true` when the products shown should be reloaded.
+    pub fn ui(&mut self, ui: &mut egui::Ui) -> bool {
+        let previous_selection = self.selected;
+        let mut action = None;
+
+        ui.horizontal(|ui| {
+            ui.strong("Categories");
+
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if ui.small_button("Add").on_hover_text("Add a category under the selected one").clicked() {
+                    action = Some(TreeAction::Add(self.selected));
+                }
+            });
+        });
+
+        ui.separator();
+
+        egui::ScrollArea::vertical().id_salt("category_tree").show(ui, |ui| {
+            if ui.selectable_label(self.selected.is_none(), "All Products").clicked() {
+                self.selected = None;
+            }
+
+            for category in self.categories.iter().filter(|category| category.parent_id.is_none()) {
+                Self::category_node(ui, &self.categories, category, &mut self.selected, &mut action);
+            }
+        });
+
+        if let Some(error) = &self.error {
+            ui.label(RichText::new(error).color(ui.visuals().error_fg_color));
+        }
+
+        let mut categories_changed = false;
+
+        match action {
+            Some(TreeAction::Add(parent_id)) => {
+                self.category_form_modal = Some(CategoryFormModal::new(None, parent_id, &self.categories));
+            }
+            Some(TreeAction::Edit(category)) => {
+                self.category_form_modal = Some(CategoryFormModal::new(Some(&category), None, &self.categories));
+            }
+            Some(TreeAction::Delete(category_id)) => {
+                let mut connection = db::establish_connection();
+
+                match category_repository::delete_category(&mut connection, category_id) {
+                    Ok(_) => {
+                        self.error = None;
+                        self.reload();
+                        categories_changed = true;
+                    }
+                    Err(error) => self.error = Some(error.to_string()),
+                }
+            }
+            Some(TreeAction::History(category)) => {
+                let path = category_path(&self.categories, category.id);
+                self.audit_history_modal = Some(AuditHistoryModal::new(AuditEntity::Category, category.id, &path));
+            }
+            None => {}
+        }
+
+        if let Some(modal) = self.category_form_modal.as_mut() {
+            let (should_close, saved_category) = modal.show(ui);
+
+            if should_close {
+                self.category_form_modal = None;
+
+                if saved_category.is_some() {
+                    self.error = None;
+                    self.reload();
+                    categories_changed = true;
+                }
+            }
+        }
+
+        if let Some(modal) = self.audit_history_modal.as_mut()
+            && modal.show(ui) {
+            self.audit_history_modal = None;
+        }
+
+        categories_changed || self.selected != previous_selection
+    }
+
+    fn category_node(ui: &mut egui::Ui, categories: &[Category], category: &Category, selected: &mut Option<i32>, action: &mut Option<TreeAction>) {
+        let children: Vec<&Category> = categories.iter()
+            .filter(|child| child.parent_id == Some(category.id))
+            .collect();
+
+        let is_selected = *selected == Some(category.id);
+
+        let response = if children.is_empty() {
+            ui.selectable_label(is_selected, &category.name)
+        } else {
+            let id = ui.make_persistent_id(("category_node", category.id));
+
+            let (_, header, _) = CollapsingState::load_with_default_open(ui.ctx(), id, false)
+                .show_header(ui, |ui| ui.selectable_label(is_selected, &category.name))
+                .body(|ui| {
+                    for child in children {
+                        Self::category_node(ui, categories, child, selected, action);
+                    }
+                });
+
+            header.inner
+        };
+
+        if response.clicked() {
+            *selected = Some(category.id);
+        }
+
+        response.context_menu(|ui| {
+            if ui.button("Add Subcategory").clicked() {
+                *action = Some(TreeAction::Add(Some(category.id)));
+            }
+
+            if ui.button("Edit").clicked() {
+                *action = Some(TreeAction::Edit(category.clone()));
+            }
+
+            if ui.button("History").clicked() {
+                *action = Some(TreeAction::History(category.clone()));
+            }
+
+            if ui.button(RichText::new("Delete").color(ui.visuals().error_fg_color)).clicked() {
+                *action = Some(TreeAction::Delete(category.id));
+            }
+        });
+    }
+
+    fn get_categories_list() -> Vec<Category> {
+        let mut connection = db::establish_connection();
+
+        category_repository::list_categories(&mut connection).unwrap_or_default()
+    }
+}
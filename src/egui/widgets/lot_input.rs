@@ -0,0 +1,33 @@
+use eframe::egui;
+use egui::ComboBox;
+
+use crate::domain::stock_balance::LotBalance;
+
+/// Lot number text field with a picker of the lots already in stock, so exits and
+/// transfers can pick an existing lot while entries can type a new one. Returns
+/// `true` when the lot number changed.
+pub fn lot_input(ui: &mut egui::Ui, id_salt: &str, lot_number: &mut String, lots: &[LotBalance]) -> bool {
+    let mut changed = false;
+
+    ui.horizontal(|ui| {
+        changed |= ui.add(egui::TextEdit::singleline(lot_number).desired_width(140.0).hint_text("Lot number")).changed();
+
+        if !lots.is_empty() {
+            ComboBox::from_id_salt(id_salt)
+                .selected_text("In stock")
+                .show_ui(ui, |ui| {
+                    for lot in lots {
+                        let expiry = lot.expiry_date.map(|date| format!(", exp. {}", date)).unwrap_or_default();
+                        let label = format!("{} ({} units{})", lot.lot_number, lot.quantity, expiry);
+
+                        if ui.selectable_label(*lot_number == lot.lot_number, label).clicked() {
+                            *lot_number = lot.lot_number.clone();
+                            changed = true;
+                        }
+                    }
+                });
+        }
+    });
+
+    changed
+}
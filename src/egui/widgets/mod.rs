@@ -1 +1,8 @@
-pub mod sidebar;
\ No newline at end of file
+pub mod category_select;
+pub mod category_tree;
+pub mod lot_input;
+pub mod product_supplier_links;
+pub mod serial_numbers_input;
+pub mod sidebar;
+pub mod trash_retention;
+pub mod unit_select;
\ No newline at end of file
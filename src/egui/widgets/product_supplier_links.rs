@@ -0,0 +1,162 @@
+use eframe::egui;
+use egui::{Grid, RichText};
+
+use crate::infra::db;
+use crate::infra::repositories::{product_repository, product_supplier_repository, supplier_repository};
+use crate::infra::repositories::product_supplier_repository::ProductSupplierFilter;
+use crate::domain::product::Product;
+use crate::domain::product_supplier::ProductSupplier;
+use crate::domain::supplier::Supplier;
+use crate::egui::components::modals::product_supplier_form_modal::{LinkOwner, ProductSupplierFormModal};
+
+/// Lists the product/supplier links of one product or one supplier and lets the
+/// user add, edit and remove them. Used by the product form and the supplier view.
+pub struct ProductSupplierLinks {
+    owner: LinkOwner,
+    links: Vec<ProductSupplier>,
+    products: Vec<Product>,
+    suppliers: Vec<Supplier>,
+    link_form_modal: Option<ProductSupplierFormModal>,
+    error: Option<String>,
+}
+
+impl ProductSupplierLinks {
+    pub fn new(owner: LinkOwner) -> Self {
+        let mut connection = db::establish_connection();
+
+        Self {
+            owner,
+            links: product_supplier_repository::list_product_suppliers(&mut connection, &Self::owner_filter(owner)).unwrap_or_default(),
+            products: product_repository::list_products(&mut connection, "").unwrap_or_default(),
+            suppliers: supplier_repository::list_suppliers(&mut connection, "").unwrap_or_default(),
+            link_form_modal: None,
+            error: None,
+        }
+    }
+
+    pub fn ui(&mut self, ui: &mut egui::Ui) {
+        let counterpart_heading = match self.owner {
+            LinkOwner::Product(_) => "Supplier",
+            LinkOwner::Supplier(_) => "Product",
+        };
+
+        if self.links.is_empty() {
+            ui.label(match self.owner {
+                LinkOwner::Product(_) => "No suppliers linked to this product yet.",
+                LinkOwner::Supplier(_) => "No products linked to this supplier yet.",
+            });
+        } else {
+            let mut link_to_edit = None;
+            let mut link_to_remove = None;
+
+            Grid::new(("product_supplier_links", self.owner_id()))
+                .striped(true)
+                .num_columns(7)
+                .show(ui, |ui| {
+                    ui.strong(counterpart_heading);
+                    ui.strong("SKU");
+                    ui.strong("Unit Cost");
+                    ui.strong("Min Order");
+                    ui.strong("Lead Time");
+                    ui.label("");
+                    ui.label("");
+                    ui.end_row();
+
+                    for link in &self.links {
+                        if link.preferred {
+                            ui.label(RichText::new(format!("★ {}", self.counterpart_name(link))).strong())
+                                .on_hover_text("Preferred supplier");
+                        } else {
+                            ui.label(self.counterpart_name(link));
+                        }
+                        ui.label(link.supplier_sku.clone().unwrap_or_default());
+                        ui.label(
+                            link.last_unit_cost
+                                .map(|cost| format!("{} {:.2}", link.currency, cost))
+                                .unwrap_or_default()
+                        );
+                        ui.label(link.min_order_quantity.to_string());
+                        ui.label(link.lead_time_days.map(|days| format!("{} days", days)).unwrap_or_default());
+
+                        if ui.button("Edit").clicked() {
+                            link_to_edit = Some(link.clone());
+                        }
+
+                        if ui.button(RichText::new("Remove").color(ui.visuals().error_fg_color)).clicked() {
+                            link_to_remove = Some(link.id);
+                        }
+
+                        ui.end_row();
+                    }
+                });
+
+            if let Some(link) = link_to_edit {
+                self.link_form_modal = Some(ProductSupplierFormModal::new(Some(&link), self.owner, &self.products, &self.suppliers));
+            }
+
+            if let Some(link_id) = link_to_remove {
+                let mut connection = db::establish_connection();
+
+                match product_supplier_repository::delete_product_supplier(&mut connection, link_id) {
+                    Ok(_) => self.links.retain(|link| link.id != link_id),
+                    Err(error) => self.error = Some(error.to_string()),
+                }
+            }
+        }
+
+        if let Some(error) = &self.error {
+            ui.label(RichText::new(error).color(ui.visuals().error_fg_color));
+        }
+
+        let add_label = match self.owner {
+            LinkOwner::Product(_) => "Link Supplier",
+            LinkOwner::Supplier(_) => "Link Product",
+        };
+
+        if ui.button(add_label).clicked() {
+            self.link_form_modal = Some(ProductSupplierFormModal::new(None, self.owner, &self.products, &self.suppliers));
+        }
+
+        if let Some(modal) = self.link_form_modal.as_mut() {
+            let (should_close, saved_link) = modal.show(ui);
+
+            if should_close {
+                self.link_form_modal = None;
+
+                // Saving a preferred link may unmark the product's other links.
+                if saved_link.is_some() {
+                    let mut connection = db::establish_connection();
+
+                    self.links = product_supplier_repository::list_product_suppliers(&mut connection, &Self::owner_filter(self.owner))
+                        .unwrap_or_default();
+                }
+            }
+        }
+    }
+
+    fn owner_filter(owner: LinkOwner) -> ProductSupplierFilter {
+        match owner {
+            LinkOwner::Product(product_id) => ProductSupplierFilter { product_id: Some(product_id), ..Default::default() },
+            LinkOwner::Supplier(supplier_id) => ProductSupplierFilter { supplier_id: Some(supplier_id), ..Default::default() },
+        }
+    }
+
+    fn owner_id(&self) -> i32 {
+        match self.owner {
+            LinkOwner::Product(id) | LinkOwner::Supplier(id) => id,
+        }
+    }
+
+    fn counterpart_name(&self, link: &ProductSupplier) -> String {
+        match self.owner {
+            LinkOwner::Product(_) => self.suppliers.iter()
+                .find(|supplier| supplier.id == link.supplier_id)
+                .map(|supplier| supplier.name.clone())
+                .unwrap_or_else(|| format!("#{}", link.supplier_id)),
+            LinkOwner::Supplier(_) => self.products.iter()
+                .find(|product| product.id == link.product_id)
+                .map(|product| product.name.clone())
+                .unwrap_or_else(|| format!("#{}", link.product_id)),
+        }
+    }
+}
@@ -0,0 +1,40 @@
+use eframe::egui;
+use egui::ComboBox;
+
+use crate::domain::serial_number::{parse_serial_numbers, SerialNumber};
+
+/// Serial numbers typed one per line, with a picker of the units in stock that
+/// appends the chosen serial. Returns `true` when the text changed.
+pub fn serial_numbers_input(ui: &mut egui::Ui, id_salt: &str, text: &mut String, in_stock: &[SerialNumber]) -> bool {
+    let mut changed = false;
+
+    ui.horizontal(|ui| {
+        changed |= ui.add(egui::TextEdit::multiline(text).desired_rows(3).desired_width(200.0).hint_text("One serial number per line")).changed();
+
+        ui.vertical(|ui| {
+            let entered = parse_serial_numbers(text);
+
+            if !in_stock.is_empty() {
+                ComboBox::from_id_salt(id_salt)
+                    .selected_text("In stock")
+                    .show_ui(ui, |ui| {
+                        for serial_number in in_stock {
+                            let already_entered = entered.contains(&serial_number.serial_number);
+
+                            if ui.selectable_label(already_entered, &serial_number.serial_number).clicked() && !already_entered {
+                                if !text.trim_end().is_empty() {
+                                    *text = format!("{}\n", text.trim_end());
+                                }
+                                text.push_str(&serial_number.serial_number);
+                                changed = true;
+                            }
+                        }
+                    });
+            }
+
+            ui.label(format!("{} entered", entered.len()));
+        });
+    });
+
+    changed
+}
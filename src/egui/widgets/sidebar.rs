@@ -2,7 +2,7 @@ use eframe::egui;
 use crate::egui::ScreenId;
 
 pub struct SideBar {
-    menus: [(&'static str, ScreenId); 5],
+    menus: [(&'static str, ScreenId); 15],
 }
 
 impl SideBar {
@@ -13,7 +13,17 @@ impl SideBar {
                 ("Products", ScreenId::Products),
                 ("Suppliers", ScreenId::Suppliers),
                 ("Places", ScreenId::Places),
+                ("Units", ScreenId::Units),
                 ("Inventory Transactions", ScreenId::InventoryTransactions),
+                ("Scan", ScreenId::Scan),
+                ("Stocktakes", ScreenId::Stocktakes),
+                ("Reservations", ScreenId::Reservations),
+                ("Issues", ScreenId::Issues),
+                ("Lots & Expiry", ScreenId::Lots),
+                ("Serial Numbers", ScreenId::SerialNumbers),
+                ("Purchase Orders", ScreenId::PurchaseOrders),
+                ("Reorder Suggestions", ScreenId::ReorderSuggestions),
+                ("Stock Valuation", ScreenId::Valuation),
             ],
         }
     }
@@ -0,0 +1,91 @@
+use eframe::egui;
+use egui::RichText;
+
+use crate::infra::db;
+use crate::infra::repositories::{setting_repository, trash_repository};
+
+/// Retention setting shown above the trash lists: deleted records older than
+/// this many days are purged when the app starts and when the setting is saved.
+/// Blank keeps them until they are purged by hand.
+pub struct TrashRetention {
+    days: String,
+    saved_days: Option<u32>,
+    error: Option<String>,
+}
+
+impl TrashRetention {
+    pub fn new() -> Self {
+        let mut connection = db::establish_connection();
+
+        let (saved_days, error) = match setting_repository::get_trash_retention_days(&mut connection) {
+            Ok(days) => (days, None),
+            Err(error) => (None, Some(error.to_string())),
+        };
+
+        Self {
+            days: saved_days.map(|days| days.to_string()).unwrap_or_default(),
+            saved_days,
+            error,
+        }
+    }
+
+    /// Returns `true` when saving the setting purged records, so the trash list
+    /// should be reloaded.
+    pub fn ui(&mut self, ui: &mut egui::Ui) -> bool {
+        let mut purged = false;
+
+        ui.horizontal(|ui| {
+            ui.label("Purge deleted records after");
+            ui.add(egui::TextEdit::singleline(&mut self.days).desired_width(48.0).hint_text("Never"));
+            ui.label("days");
+
+            let changed = self.days.trim() != self.saved_days.map(|days| days.to_string()).unwrap_or_default();
+
+            if ui.add_enabled(changed, egui::Button::new("Save")).clicked() {
+                purged = self.save();
+            }
+
+            if let Some(error) = &self.error {
+                ui.label(RichText::new(error).color(ui.visuals().error_fg_color));
+            }
+        });
+
+        purged
+    }
+
+    fn save(&mut self) -> bool {
+        let days = match self.days.trim() {
+            "" => None,
+            days => match days.parse::<u32>() {
+                Ok(days) if days > setting_repository::MAX_TRASH_RETENTION_DAYS => {
+                    self.error = Some(format!("Days should be at most {}", setting_repository::MAX_TRASH_RETENTION_DAYS));
+                    return false;
+                }
+                Ok(days) => Some(days).filter(|days| *days > 0),
+                Err(_) => {
+                    self.error = Some("Days should be a whole number".into());
+                    return false;
+                }
+            },
+        };
+
+        let mut connection = db::establish_connection();
+
+        if let Err(error) = setting_repository::set_trash_retention_days(&mut connection, days) {
+            self.error = Some(error.to_string());
+            return false;
+        }
+
+        self.saved_days = days;
+        self.days = days.map(|days| days.to_string()).unwrap_or_default();
+        self.error = None;
+
+        match trash_repository::purge_expired_trash(&mut connection) {
+            Ok(purged) => purged > 0,
+            Err(error) => {
+                self.error = Some(error.to_string());
+                false
+            }
+        }
+    }
+}
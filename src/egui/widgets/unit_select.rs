@@ -0,0 +1,28 @@
+use eframe::egui;
+use egui::ComboBox;
+
+use crate::domain::unit::Unit;
+
+/// Unit picker limited to the units a product's quantities can be converted to,
+/// e.g. "un" and "Box of 12" for a product counted in units. Returns `true` when
+/// the selection changed.
+pub fn unit_select(ui: &mut egui::Ui, id_salt: &str, selected: &mut Option<i32>, units: &[Unit], product_unit_id: Option<i32>) -> bool {
+    let mut changed = false;
+
+    let product_unit = units.iter().find(|unit| Some(unit.id) == product_unit_id);
+
+    let selected_label = units.iter()
+        .find(|unit| Some(unit.id) == *selected)
+        .map(|unit| unit.symbol.clone())
+        .unwrap_or_default();
+
+    ComboBox::from_id_salt(id_salt)
+        .selected_text(selected_label)
+        .show_ui(ui, |ui| {
+            for unit in units.iter().filter(|unit| product_unit.is_none_or(|product_unit| unit.is_convertible_to(product_unit))) {
+                changed |= ui.selectable_value(selected, Some(unit.id), unit.label()).changed();
+            }
+        });
+
+    changed
+}
@@ -36,10 +36,18 @@ impl ProductsScreen {
 					brand: Some("Brand X".into()),
 					min_stock: None,
 					observation: None,
+					reorder_point: None,
+					safety_stock: None,
+					max_stock: None,
+					tracks_lots: None,
+					serialized: None,
+					unit_id: None,
+					category_id: None,
+					sku: None,
 				};
 
 				let mut connection = db::establish_connection();
-				let product_created = product_repository::create_product(&mut connection, new_product);
+				let product_created = product_repository::create_product(&mut connection, new_product, &[]);
 				self.products.push(product_created.unwrap());
 			},
 			ProductsScreenMessage::Delete(product_id) => {
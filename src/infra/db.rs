@@ -11,4 +11,26 @@ pub fn establish_connection() -> SqliteConnection {
 
 	SqliteConnection::establish(&db_url)
 		.unwrap_or_else(|_| panic!("Error connecting to {}", db_url))
-}
\ No newline at end of file
+}
+
+/// Fresh in-memory database with every migration applied, for repository tests.
+#[cfg(test)]
+pub fn establish_test_connection() -> SqliteConnection {
+	let migrations_dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("database/migrations");
+
+	let mut migrations: Vec<_> = std::fs::read_dir(&migrations_dir)
+		.expect("Failed to read the migrations directory")
+		.map(|entry| entry.expect("Failed to read a migration").path())
+		.filter(|path| path.is_dir())
+		.collect();
+	migrations.sort();
+
+	let mut conn = SqliteConnection::establish(":memory:").expect("Failed to open an in-memory database");
+
+	for migration in migrations {
+		let up_sql = std::fs::read_to_string(migration.join("up.sql")).expect("Failed to read a migration");
+		diesel::connection::SimpleConnection::batch_execute(&mut conn, &up_sql).expect("Failed to run a migration");
+	}
+
+	conn
+}
@@ -1,5 +1,29 @@
 use diesel::prelude::*;
-use crate::infra::schema::{places, products, suppliers};
+use crate::infra::schema::{
+	audit_log,
+	categories,
+	goods_receipt_lines,
+	goods_receipts,
+	inventory_transactions,
+	issue_lines,
+	issues,
+	kit_components,
+	places,
+	product_barcodes,
+	product_suppliers,
+	products,
+	purchase_order_lines,
+	purchase_orders,
+	reservations,
+	serial_number_movements,
+	serial_numbers,
+	settings,
+	stock_transfers,
+	stocktake_lines,
+	stocktakes,
+	suppliers,
+	units,
+};
 
 
 #[derive(Queryable, Selectable, Identifiable)]
@@ -14,6 +38,14 @@ pub struct ProductRow {
 	pub created_at: String,
 	pub updated_at: String,
 	pub deleted_at: Option<String>,
+	pub reorder_point: i32,
+	pub safety_stock: i32,
+	pub max_stock: i32,
+	pub tracks_lots: bool,
+	pub serialized: bool,
+	pub unit_id: Option<i32>,
+	pub category_id: Option<i32>,
+	pub sku: Option<String>,
 }
 
 #[derive(Insertable)]
@@ -24,6 +56,14 @@ pub struct NewProductRow {
 	pub brand: Option<String>,
 	pub min_stock: Option<i32>,
 	pub observation: Option<String>,
+	pub reorder_point: Option<i32>,
+	pub safety_stock: Option<i32>,
+	pub max_stock: Option<i32>,
+	pub tracks_lots: Option<bool>,
+	pub serialized: Option<bool>,
+	pub unit_id: Option<i32>,
+	pub category_id: Option<i32>,
+	pub sku: Option<String>,
 }
 
 #[derive(AsChangeset)]
@@ -35,6 +75,16 @@ pub struct EditProductRow {
 	pub brand: Option<String>,
 	pub min_stock: Option<i32>,
 	pub observation: Option<String>,
+	pub reorder_point: Option<i32>,
+	pub safety_stock: Option<i32>,
+	pub max_stock: Option<i32>,
+	pub tracks_lots: Option<bool>,
+	pub serialized: Option<bool>,
+	pub unit_id: Option<i32>,
+	/// `Some(None)` clears the category; `None` leaves it unchanged.
+	pub category_id: Option<Option<i32>>,
+	/// `Some(None)` clears the SKU; `None` leaves it unchanged.
+	pub sku: Option<Option<String>>,
 }
 
 
@@ -46,19 +96,128 @@ pub struct SupplierRow {
 	pub created_at: String,
 	pub updated_at: String,
 	pub deleted_at: Option<String>,
+	pub tax_id: Option<String>,
+	pub contact_name: Option<String>,
+	pub phone: Option<String>,
+	pub mobile_phone: Option<String>,
+	pub email: Option<String>,
+	pub address: Option<String>,
+	pub payment_terms: Option<String>,
+	pub notes: Option<String>,
+	pub active: bool,
 }
 
 #[derive(Insertable)]
 #[diesel(table_name=suppliers)]
 pub struct NewSupplierRow {
 	pub name: String,
+	/// CNPJ or CPF, stored without punctuation.
+	pub tax_id: Option<String>,
+	pub contact_name: Option<String>,
+	pub phone: Option<String>,
+	pub mobile_phone: Option<String>,
+	pub email: Option<String>,
+	pub address: Option<String>,
+	pub payment_terms: Option<String>,
+	pub notes: Option<String>,
+	pub active: Option<bool>,
 }
 
+/// Every detail is written as given, so `None` clears it.
 #[derive(AsChangeset)]
-#[diesel(table_name=suppliers)]
+#[diesel(table_name=suppliers, treat_none_as_null = true)]
 pub struct EditSupplierRow {
 	pub id: i32,
 	pub name: String,
+	pub tax_id: Option<String>,
+	pub contact_name: Option<String>,
+	pub phone: Option<String>,
+	pub mobile_phone: Option<String>,
+	pub email: Option<String>,
+	pub address: Option<String>,
+	pub payment_terms: Option<String>,
+	pub notes: Option<String>,
+	pub active: bool,
+}
+
+
+#[derive(Queryable, Selectable, Identifiable)]
+#[diesel(table_name=product_barcodes)]
+pub struct ProductBarcodeRow {
+	pub id: i32,
+	pub product_id: i32,
+	pub barcode: String,
+	pub symbology: String,
+	pub created_at: String,
+	pub deleted_at: Option<String>,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name=product_barcodes)]
+pub struct NewProductBarcodeRow {
+	pub product_id: i32,
+	pub barcode: String,
+	pub symbology: String,
+}
+
+
+#[derive(Queryable, Selectable, Identifiable)]
+#[diesel(table_name=categories)]
+pub struct CategoryRow {
+	pub id: i32,
+	pub name: String,
+	pub parent_id: Option<i32>,
+	pub created_at: String,
+	pub updated_at: String,
+	pub deleted_at: Option<String>,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name=categories)]
+pub struct NewCategoryRow {
+	pub name: String,
+	pub parent_id: Option<i32>,
+}
+
+#[derive(AsChangeset)]
+#[diesel(table_name=categories, treat_none_as_null = true)]
+pub struct EditCategoryRow {
+	pub id: i32,
+	pub name: String,
+	pub parent_id: Option<i32>,
+}
+
+
+#[derive(Queryable, Selectable, Identifiable)]
+#[diesel(table_name=units)]
+pub struct UnitRow {
+	pub id: i32,
+	pub name: String,
+	pub symbol: String,
+	pub base_unit_id: Option<i32>,
+	pub factor: f64,
+	pub created_at: String,
+	pub updated_at: String,
+	pub deleted_at: Option<String>,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name=units)]
+pub struct NewUnitRow {
+	pub name: String,
+	pub symbol: String,
+	pub base_unit_id: Option<i32>,
+	pub factor: f64,
+}
+
+#[derive(AsChangeset)]
+#[diesel(table_name=units, treat_none_as_null = true)]
+pub struct EditUnitRow {
+	pub id: i32,
+	pub name: String,
+	pub symbol: String,
+	pub base_unit_id: Option<i32>,
+	pub factor: f64,
 }
 
 
@@ -70,17 +229,431 @@ pub struct PlaceRow {
 	pub created_at: String,
 	pub updated_at: String,
 	pub deleted_at: Option<String>,
+	pub parent_id: Option<i32>,
+	pub place_type: String,
 }
 
 #[derive(Insertable)]
 #[diesel(table_name=places)]
 pub struct NewPlaceRow {
 	pub name: String,
+	pub parent_id: Option<i32>,
+	pub place_type: String,
 }
 
 #[derive(AsChangeset)]
-#[diesel(table_name=places)]
+#[diesel(table_name=places, treat_none_as_null = true)]
 pub struct EditPlaceRow {
 	pub id: i32,
 	pub name: String,
-}
\ No newline at end of file
+	/// `None` moves the place to the top level.
+	pub parent_id: Option<i32>,
+	pub place_type: String,
+}
+
+
+#[derive(Queryable, Selectable, Identifiable)]
+#[diesel(table_name=inventory_transactions)]
+pub struct InventoryTransactionRow {
+	pub id: i32,
+	pub product_id: i32,
+	pub place_id: i32,
+	pub supplier_id: Option<i32>,
+	pub quantity: i32,
+	pub transaction_type: String,
+	pub reason: Option<String>,
+	pub created_at: String,
+	pub lot_number: Option<String>,
+	pub expiry_date: Option<String>,
+	pub unit_cost: Option<f64>,
+	pub reservation_id: Option<i32>,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name=inventory_transactions)]
+pub struct NewInventoryTransactionRow {
+	pub product_id: i32,
+	pub place_id: i32,
+	pub supplier_id: Option<i32>,
+	pub quantity: i32,
+	pub transaction_type: String,
+	pub reason: Option<String>,
+	pub lot_number: Option<String>,
+	pub expiry_date: Option<String>,
+	/// Cost per unit of the product; entries without one are valued at the current cost.
+	pub unit_cost: Option<f64>,
+	/// Reservation the exit is consumed from, if any.
+	pub reservation_id: Option<i32>,
+}
+
+#[derive(Queryable)]
+pub struct LotBalanceRow {
+	pub product_id: i32,
+	pub place_id: i32,
+	pub lot_number: Option<String>,
+	pub expiry_date: Option<String>,
+	pub quantity: Option<i64>,
+}
+
+#[derive(Queryable)]
+pub struct StockBalanceRow {
+	pub product_id: i32,
+	pub place_id: i32,
+	pub quantity: Option<i64>,
+}
+
+#[derive(Queryable)]
+pub struct ProductStockRow {
+	pub product_id: i32,
+	pub quantity: Option<i64>,
+}
+
+#[derive(Queryable, Selectable, Identifiable)]
+#[diesel(table_name=serial_numbers)]
+pub struct SerialNumberRow {
+	pub id: i32,
+	pub product_id: i32,
+	pub serial_number: String,
+	pub place_id: Option<i32>,
+	pub created_at: String,
+	pub updated_at: String,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name=serial_numbers)]
+pub struct NewSerialNumberRow {
+	pub product_id: i32,
+	pub serial_number: String,
+	pub place_id: Option<i32>,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name=serial_number_movements)]
+pub struct NewSerialNumberMovementRow {
+	pub serial_number_id: i32,
+	pub inventory_transaction_id: i32,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name=stock_transfers)]
+pub struct NewStockTransferRow {
+	pub product_id: i32,
+	pub from_place_id: i32,
+	pub to_place_id: i32,
+	pub quantity: i32,
+	pub outbound_transaction_id: i32,
+	pub inbound_transaction_id: i32,
+	pub reason: Option<String>,
+}
+
+
+#[derive(Queryable, Selectable, Identifiable)]
+#[diesel(table_name=product_suppliers)]
+pub struct ProductSupplierRow {
+	pub id: i32,
+	pub product_id: i32,
+	pub supplier_id: i32,
+	pub supplier_sku: Option<String>,
+	pub last_unit_cost: Option<f64>,
+	pub currency: String,
+	pub min_order_quantity: i32,
+	pub lead_time_days: Option<i32>,
+	pub created_at: String,
+	pub updated_at: String,
+	pub preferred: bool,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name=product_suppliers)]
+pub struct NewProductSupplierRow {
+	pub product_id: i32,
+	pub supplier_id: i32,
+	pub supplier_sku: Option<String>,
+	pub last_unit_cost: Option<f64>,
+	pub currency: String,
+	pub min_order_quantity: i32,
+	pub lead_time_days: Option<i32>,
+	pub preferred: bool,
+}
+
+#[derive(AsChangeset)]
+#[diesel(table_name=product_suppliers, treat_none_as_null = true)]
+pub struct EditProductSupplierRow {
+	pub id: i32,
+	pub supplier_sku: Option<String>,
+	pub last_unit_cost: Option<f64>,
+	pub currency: String,
+	pub min_order_quantity: i32,
+	pub lead_time_days: Option<i32>,
+	pub preferred: bool,
+}
+
+
+#[derive(Queryable, Selectable, Identifiable)]
+#[diesel(table_name=purchase_orders)]
+pub struct PurchaseOrderRow {
+	pub id: i32,
+	pub supplier_id: i32,
+	pub status: String,
+	pub expected_date: Option<String>,
+	pub notes: Option<String>,
+	pub created_at: String,
+	pub deleted_at: Option<String>,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name=purchase_orders)]
+pub struct NewPurchaseOrderRow {
+	pub supplier_id: i32,
+	pub expected_date: Option<String>,
+	pub notes: Option<String>,
+}
+
+#[derive(AsChangeset)]
+#[diesel(table_name=purchase_orders, treat_none_as_null = true)]
+pub struct EditPurchaseOrderRow {
+	pub id: i32,
+	pub supplier_id: i32,
+	pub expected_date: Option<String>,
+	pub notes: Option<String>,
+}
+
+
+#[derive(Queryable, Selectable, Identifiable)]
+#[diesel(table_name=purchase_order_lines)]
+pub struct PurchaseOrderLineRow {
+	pub id: i32,
+	pub purchase_order_id: i32,
+	pub product_id: i32,
+	pub quantity: i32,
+	pub received_quantity: i32,
+	pub unit_cost: Option<f64>,
+	pub unit_id: Option<i32>,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name=purchase_order_lines)]
+pub struct NewPurchaseOrderLineRow {
+	pub purchase_order_id: i32,
+	pub product_id: i32,
+	pub quantity: i32,
+	pub unit_cost: Option<f64>,
+	pub unit_id: Option<i32>,
+}
+
+#[derive(AsChangeset)]
+#[diesel(table_name=purchase_order_lines, treat_none_as_null = true)]
+pub struct EditPurchaseOrderLineRow {
+	pub id: i32,
+	pub product_id: i32,
+	pub quantity: i32,
+	pub unit_cost: Option<f64>,
+	pub unit_id: Option<i32>,
+}
+
+
+#[derive(Insertable)]
+#[diesel(table_name=goods_receipts)]
+pub struct NewGoodsReceiptRow {
+	pub purchase_order_id: i32,
+	pub place_id: i32,
+	pub notes: Option<String>,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name=goods_receipt_lines)]
+pub struct NewGoodsReceiptLineRow {
+	pub goods_receipt_id: i32,
+	pub purchase_order_line_id: i32,
+	pub quantity: i32,
+	pub inventory_transaction_id: i32,
+}
+
+
+#[derive(Queryable, Selectable, Identifiable)]
+#[diesel(table_name=stocktakes)]
+pub struct StocktakeRow {
+	pub id: i32,
+	pub place_id: Option<i32>,
+	pub status: String,
+	pub notes: Option<String>,
+	pub approved_at: Option<String>,
+	pub created_at: String,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name=stocktakes)]
+pub struct NewStocktakeRow {
+	pub place_id: Option<i32>,
+	pub notes: Option<String>,
+}
+
+#[derive(Queryable, Selectable, Identifiable)]
+#[diesel(table_name=stocktake_lines)]
+pub struct StocktakeLineRow {
+	pub id: i32,
+	pub stocktake_id: i32,
+	pub product_id: i32,
+	pub place_id: i32,
+	pub lot_number: Option<String>,
+	pub expected_quantity: i32,
+	pub counted_quantity: Option<i32>,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name=stocktake_lines)]
+pub struct NewStocktakeLineRow {
+	pub stocktake_id: i32,
+	pub product_id: i32,
+	pub place_id: i32,
+	pub lot_number: Option<String>,
+	pub expected_quantity: i32,
+	pub counted_quantity: Option<i32>,
+}
+
+
+#[derive(Insertable, AsChangeset)]
+#[diesel(table_name=settings)]
+pub struct SettingRow {
+	pub key: String,
+	pub value: String,
+}
+
+
+#[derive(Queryable, Selectable, Identifiable)]
+#[diesel(table_name=reservations)]
+pub struct ReservationRow {
+	pub id: i32,
+	pub product_id: i32,
+	pub place_id: Option<i32>,
+	pub quantity: i32,
+	pub consumed_quantity: i32,
+	pub reference: String,
+	pub notes: Option<String>,
+	pub expires_on: Option<String>,
+	pub status: String,
+	pub created_at: String,
+	pub updated_at: String,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name=reservations)]
+pub struct NewReservationRow {
+	pub product_id: i32,
+	pub place_id: Option<i32>,
+	pub quantity: i32,
+	pub reference: String,
+	pub notes: Option<String>,
+	pub expires_on: Option<String>,
+}
+
+
+#[derive(Queryable, Selectable, Identifiable)]
+#[diesel(table_name=issues)]
+pub struct IssueRow {
+	pub id: i32,
+	pub place_id: i32,
+	pub requester: String,
+	pub department: String,
+	pub destination: Option<String>,
+	pub status: String,
+	pub notes: Option<String>,
+	pub posted_at: Option<String>,
+	pub created_at: String,
+	pub deleted_at: Option<String>,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name=issues)]
+pub struct NewIssueRow {
+	pub place_id: i32,
+	pub requester: String,
+	pub department: String,
+	pub destination: Option<String>,
+	pub notes: Option<String>,
+}
+
+#[derive(AsChangeset)]
+#[diesel(table_name=issues, treat_none_as_null = true)]
+pub struct EditIssueRow {
+	pub id: i32,
+	pub place_id: i32,
+	pub requester: String,
+	pub department: String,
+	pub destination: Option<String>,
+	pub notes: Option<String>,
+}
+
+
+#[derive(Queryable, Selectable, Identifiable)]
+#[diesel(table_name=issue_lines)]
+pub struct IssueLineRow {
+	pub id: i32,
+	pub issue_id: i32,
+	pub product_id: i32,
+	pub quantity: i32,
+	pub lot_number: Option<String>,
+	pub serial_numbers: Option<String>,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name=issue_lines)]
+pub struct NewIssueLineRow {
+	pub issue_id: i32,
+	pub product_id: i32,
+	pub quantity: i32,
+	pub lot_number: Option<String>,
+	/// Serial numbers handed out, one per line; only for serialized products.
+	pub serial_numbers: Option<String>,
+}
+
+#[derive(AsChangeset)]
+#[diesel(table_name=issue_lines, treat_none_as_null = true)]
+pub struct EditIssueLineRow {
+	pub id: i32,
+	pub product_id: i32,
+	pub quantity: i32,
+	pub lot_number: Option<String>,
+	pub serial_numbers: Option<String>,
+}
+
+
+#[derive(Queryable, Selectable, Identifiable)]
+#[diesel(table_name=kit_components)]
+pub struct KitComponentRow {
+	pub id: i32,
+	pub kit_product_id: i32,
+	pub component_product_id: i32,
+	pub quantity: i32,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name=kit_components)]
+pub struct NewKitComponentRow {
+	pub kit_product_id: i32,
+	pub component_product_id: i32,
+	/// Units of the component that go into one kit.
+	pub quantity: i32,
+}
+
+
+#[derive(Queryable, Selectable, Identifiable)]
+#[diesel(table_name=audit_log)]
+pub struct AuditLogRow {
+	pub id: i32,
+	pub entity: String,
+	pub entity_id: i32,
+	pub action: String,
+	pub changes: String,
+	pub created_at: String,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name=audit_log)]
+pub struct NewAuditLogRow {
+	pub entity: String,
+	pub entity_id: i32,
+	pub action: String,
+	/// JSON array of the fields that changed with their old and new values.
+	pub changes: String,
+}
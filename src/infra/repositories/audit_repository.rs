@@ -0,0 +1,41 @@
+use diesel::prelude::*;
+use std::error::Error;
+
+use crate::infra::models::{AuditLogRow, NewAuditLogRow};
+use crate::infra::schema::audit_log;
+use crate::domain::audit::{AuditAction, AuditEntity, AuditEntry, AuditField, diff_fields};
+
+/// Changes made to one record, newest first.
+pub fn list_audit_entries(conn: &mut SqliteConnection, entity: AuditEntity, entity_id: i32) -> Result<Vec<AuditEntry>, Box<dyn Error>> {
+    let entry_list: Vec<AuditLogRow> = audit_log::table
+        .filter(audit_log::entity.eq(entity.as_str()))
+        .filter(audit_log::entity_id.eq(entity_id))
+        .order(audit_log::id.desc())
+        .select(AuditLogRow::as_select())
+        .load(conn)?;
+
+    entry_list.into_iter()
+        .map(AuditEntry::try_from)
+        .collect()
+}
+
+/// Records the fields that differ between two snapshots of a record. Edits that
+/// change nothing leave no entry.
+pub fn record_audit(conn: &mut SqliteConnection, entity: AuditEntity, entity_id: i32, action: AuditAction, before: &[AuditField], after: &[AuditField]) -> Result<(), Box<dyn Error>> {
+    let changes = diff_fields(before, after);
+
+    if changes.is_empty() && action == AuditAction::Edit {
+        return Ok(());
+    }
+
+    diesel::insert_into(audit_log::table)
+        .values(NewAuditLogRow {
+            entity: entity.as_str().to_owned(),
+            entity_id,
+            action: action.as_str().to_owned(),
+            changes: serde_json::to_string(&changes)?,
+        })
+        .execute(conn)?;
+
+    Ok(())
+}
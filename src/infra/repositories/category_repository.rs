@@ -0,0 +1,188 @@
+use diesel::prelude::*;
+use std::error::Error;
+
+use crate::infra::models::{CategoryRow, NewCategoryRow, EditCategoryRow};
+use crate::infra::repositories::audit_repository;
+use crate::infra::schema::{categories, products};
+use crate::domain::audit::{AuditAction, AuditEntity};
+use crate::domain::category::{Category, descendant_ids, parse_category_path};
+
+use chrono::Utc;
+
+const NAIVE_DATE_TIME_PATTERN: &str =  "%Y-%m-%d %H:%M:%S";
+
+pub fn list_categories(conn: &mut SqliteConnection) -> Result<Vec<Category>, Box<dyn Error>> {
+    let category_list: Vec<CategoryRow> = categories::table
+        .filter(categories::deleted_at.is_null())
+        .order(categories::name.asc())
+        .load(conn)?;
+
+    let categories = category_list.into_iter()
+        .map(Category::from)
+        .collect();
+
+    Ok(categories)
+}
+
+pub fn get_category_by_id(conn: &mut SqliteConnection, category_id: i32) -> Result<Category, Box<dyn Error>> {
+    let category = categories::table
+        .find(category_id)
+        .first::<CategoryRow>(conn)?;
+
+    let category_item = category.into();
+
+    Ok(category_item)
+}
+
+pub fn create_category(conn: &mut SqliteConnection, mut new_category: NewCategoryRow) -> Result<Category, Box<dyn Error>> {
+    new_category.name = new_category.name.trim().to_owned();
+
+    check_category(conn, None, &new_category.name, new_category.parent_id)?;
+
+    conn.transaction::<_, Box<dyn Error>, _>(|conn| {
+        diesel::insert_into(categories::table)
+            .values(&new_category)
+            .execute(conn)?;
+
+        let created_category = categories::table
+            .order(categories::id.desc())
+            .first::<CategoryRow>(conn)?;
+
+        let category_item: Category = created_category.into();
+
+        audit_repository::record_audit(conn, AuditEntity::Category, category_item.id, AuditAction::Create, &[], &category_item.audit_fields())?;
+
+        Ok(category_item)
+    })
+}
+
+pub fn edit_category(conn: &mut SqliteConnection, mut category: EditCategoryRow) -> Result<Category, Box<dyn Error>> {
+    let category_id = category.id;
+    category.name = category.name.trim().to_owned();
+
+    check_category(conn, Some(category_id), &category.name, category.parent_id)?;
+
+    conn.transaction::<_, Box<dyn Error>, _>(|conn| {
+        let before = get_category_by_id(conn, category_id)?.audit_fields();
+
+        diesel::update(categories::table.find(category_id))
+            .set((
+                &category,
+                categories::updated_at.eq(Utc::now().format(NAIVE_DATE_TIME_PATTERN).to_string())
+            ))
+            .execute(conn)?;
+
+        let category_item = get_category_by_id(conn, category_id)?;
+
+        audit_repository::record_audit(conn, AuditEntity::Category, category_id, AuditAction::Edit, &before, &category_item.audit_fields())?;
+
+        Ok(category_item)
+    })
+}
+
+/// Soft-deletes a category that has no subcategories and no products.
+pub fn delete_category(conn: &mut SqliteConnection, category_id: i32) -> Result<bool, Box<dyn Error>> {
+    let subcategories: i64 = categories::table
+        .filter(categories::parent_id.eq(category_id))
+        .filter(categories::deleted_at.is_null())
+        .count()
+        .get_result(conn)?;
+
+    if subcategories > 0 {
+        return Err("Delete or move the subcategories of this category first".into());
+    }
+
+    let products_in: i64 = products::table
+        .filter(products::category_id.eq(category_id))
+        .filter(products::deleted_at.is_null())
+        .count()
+        .get_result(conn)?;
+
+    if products_in > 0 {
+        return Err(format!("The category has {} product(s)", products_in).into());
+    }
+
+    conn.transaction::<_, Box<dyn Error>, _>(|conn| {
+        let before = get_category_by_id(conn, category_id)?.audit_fields();
+
+        let deleted = diesel::update(categories::table.find(category_id))
+            .set(categories::deleted_at.eq(Some(Utc::now().naive_utc().format(NAIVE_DATE_TIME_PATTERN).to_string())))
+            .execute(conn)?;
+
+        audit_repository::record_audit(conn, AuditEntity::Category, category_id, AuditAction::Delete, &before, &[])?;
+
+        Ok(deleted > 0)
+    })
+}
+
+/// Resolves a "Parent > Child" path to the id of its last category, creating the
+/// categories that do not exist yet. Blank paths resolve to no category.
+pub fn find_or_create_category_path(conn: &mut SqliteConnection, path: &str) -> Result<Option<i32>, Box<dyn Error>> {
+    conn.transaction::<_, Box<dyn Error>, _>(|conn| {
+        let mut parent_id = None;
+
+        for name in parse_category_path(path) {
+            let existing = find_child_category(conn, parent_id, &name)?;
+
+            let category_id = match existing {
+                Some(category) => category.id,
+                None => create_category(conn, NewCategoryRow { name, parent_id })?.id,
+            };
+
+            parent_id = Some(category_id);
+        }
+
+        Ok(parent_id)
+    })
+}
+
+fn find_child_category(conn: &mut SqliteConnection, parent_id: Option<i32>, name: &str) -> Result<Option<CategoryRow>, Box<dyn Error>> {
+    let mut query = categories::table
+        .filter(categories::deleted_at.is_null())
+        .filter(categories::name.eq(name))
+        .into_boxed();
+
+    query = match parent_id {
+        Some(parent_id) => query.filter(categories::parent_id.eq(parent_id)),
+        None => query.filter(categories::parent_id.is_null()),
+    };
+
+    Ok(query.first::<CategoryRow>(conn).optional()?)
+}
+
+/// Sibling names must be unique so paths stay unambiguous, and a category
+/// cannot be moved below itself or into a deleted category.
+fn check_category(conn: &mut SqliteConnection, category_id: Option<i32>, name: &str, parent_id: Option<i32>) -> Result<(), Box<dyn Error>> {
+    if name.is_empty() {
+        return Err("Enter a category name".into());
+    }
+
+    if name.contains('>') {
+        return Err("Category names cannot contain '>'".into());
+    }
+
+    if let Some(existing) = find_child_category(conn, parent_id, name)?
+        && Some(existing.id) != category_id {
+        return Err(format!("There is already a category named '{}' here", name).into());
+    }
+
+    if let Some(parent_id) = parent_id {
+        let parent = categories::table
+            .find(parent_id)
+            .first::<CategoryRow>(conn)?;
+
+        if parent.deleted_at.is_some() {
+            return Err(format!("Category '{}' was deleted", parent.name).into());
+        }
+    }
+
+    if let (Some(category_id), Some(parent_id)) = (category_id, parent_id) {
+        let categories = list_categories(conn)?;
+
+        if descendant_ids(&categories, category_id).contains(&parent_id) {
+            return Err("A category cannot be moved below itself".into());
+        }
+    }
+
+    Ok(())
+}
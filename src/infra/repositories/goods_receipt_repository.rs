@@ -0,0 +1,128 @@
+use diesel::prelude::*;
+use std::error::Error;
+
+use crate::infra::models::{NewGoodsReceiptLineRow, NewGoodsReceiptRow, NewInventoryTransactionRow};
+use crate::infra::repositories::{inventory_transaction_repository, product_repository, purchase_order_repository, unit_repository};
+use crate::infra::schema::{goods_receipt_lines, goods_receipts, purchase_order_lines};
+use crate::domain::inventory_transaction::TransactionType;
+use crate::domain::purchase_order::{PurchaseOrder, PurchaseOrderStatus};
+
+use chrono::Utc;
+
+const NAIVE_DATE_TIME_PATTERN: &str =  "%Y-%m-%d %H:%M:%S";
+
+pub struct NewGoodsReceipt {
+    pub purchase_order_id: i32,
+    pub place_id: i32,
+    pub notes: Option<String>,
+    /// Lines with a zero quantity are skipped.
+    pub lines: Vec<NewGoodsReceiptLine>,
+}
+
+pub struct NewGoodsReceiptLine {
+    pub purchase_order_line_id: i32,
+    pub quantity: i32,
+    pub lot_number: Option<String>,
+    pub expiry_date: Option<String>,
+    pub serial_numbers: Vec<String>,
+}
+
+/// Receives a delivery against an open purchase order: posts one inbound movement
+/// per line into the chosen place, adds to each line's received quantity and moves
+/// the order to partially received or received. Over-deliveries are accepted.
+pub fn receive_purchase_order(conn: &mut SqliteConnection, receipt: NewGoodsReceipt) -> Result<PurchaseOrder, Box<dyn Error>> {
+    conn.transaction::<_, Box<dyn Error>, _>(|conn| {
+        let order = purchase_order_repository::get_purchase_order(conn, receipt.purchase_order_id)?;
+
+        if !order.status.is_receivable() {
+            return Err(format!("Purchase order #{} is {} and cannot receive goods", order.id, order.status.label().to_lowercase()).into());
+        }
+
+        let order_lines = purchase_order_repository::list_purchase_order_lines(conn, order.id)?;
+        let lines_before = purchase_order_repository::audit_lines(conn, order.id)?;
+
+        let received_lines: Vec<NewGoodsReceiptLine> = receipt.lines.into_iter()
+            .filter(|line| line.quantity != 0)
+            .collect();
+
+        if received_lines.is_empty() {
+            return Err("Enter the received quantity of at least one line".into());
+        }
+
+        diesel::insert_into(goods_receipts::table)
+            .values(&NewGoodsReceiptRow {
+                purchase_order_id: order.id,
+                place_id: receipt.place_id,
+                notes: receipt.notes.clone(),
+            })
+            .execute(conn)?;
+
+        let receipt_id: i32 = goods_receipts::table
+            .order(goods_receipts::id.desc())
+            .select(goods_receipts::id)
+            .first(conn)?;
+
+        for received_line in received_lines {
+            let (line_id, quantity) = (received_line.purchase_order_line_id, received_line.quantity);
+
+            if quantity < 0 {
+                return Err("Received quantities cannot be negative".into());
+            }
+
+            let order_line = order_lines.iter()
+                .find(|line| line.id == line_id)
+                .ok_or_else(|| format!("Line #{} does not belong to purchase order #{}", line_id, order.id))?;
+
+            // Stock is kept in the product's unit, whatever unit the line was ordered in.
+            let product = product_repository::get_product_by_id(conn, order_line.product_id)?;
+            let stock_quantity = unit_repository::convert_quantity(conn, quantity, order_line.unit_id, product.unit_id)?;
+
+            let transaction = inventory_transaction_repository::create_inventory_transaction(conn, NewInventoryTransactionRow {
+                product_id: order_line.product_id,
+                place_id: receipt.place_id,
+                supplier_id: Some(order.supplier_id),
+                quantity: TransactionType::Entry.signed_quantity(stock_quantity),
+                transaction_type: TransactionType::Entry.as_str().to_owned(),
+                reason: Some(format!("Purchase order #{}", order.id)),
+                lot_number: received_line.lot_number,
+                expiry_date: received_line.expiry_date,
+                unit_cost: order_line.unit_cost.map(|unit_cost| unit_cost * f64::from(quantity) / f64::from(stock_quantity)),
+                reservation_id: None,
+            }, &received_line.serial_numbers)?;
+
+            diesel::insert_into(goods_receipt_lines::table)
+                .values(&NewGoodsReceiptLineRow {
+                    goods_receipt_id: receipt_id,
+                    purchase_order_line_id: order_line.id,
+                    quantity,
+                    inventory_transaction_id: transaction.id,
+                })
+                .execute(conn)?;
+
+            diesel::update(purchase_order_lines::table.find(order_line.id))
+                .set((
+                    purchase_order_lines::received_quantity.eq(purchase_order_lines::received_quantity + quantity),
+                    purchase_order_lines::updated_at.eq(Utc::now().format(NAIVE_DATE_TIME_PATTERN).to_string())
+                ))
+                .execute(conn)?;
+        }
+
+        purchase_order_repository::record_lines_audit(conn, order.id, &lines_before)?;
+
+        let fully_received = purchase_order_repository::list_purchase_order_lines(conn, order.id)?
+            .iter()
+            .all(|line| line.remaining_quantity() == 0);
+
+        let next_status = if fully_received {
+            PurchaseOrderStatus::Received
+        } else {
+            PurchaseOrderStatus::PartiallyReceived
+        };
+
+        if next_status == order.status {
+            return Ok(order);
+        }
+
+        purchase_order_repository::update_purchase_order_status(conn, order.id, next_status)
+    })
+}
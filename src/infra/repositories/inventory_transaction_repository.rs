@@ -0,0 +1,206 @@
+use diesel::prelude::*;
+use std::collections::HashSet;
+use std::error::Error;
+
+use crate::infra::models::{InventoryTransactionRow, NewInventoryTransactionRow};
+use crate::infra::repositories::{place_repository, product_repository, reservation_repository, serial_number_repository, stock_repository};
+use crate::infra::schema::inventory_transactions;
+use crate::domain::inventory_transaction::{InventoryTransaction, TransactionType};
+use crate::domain::place::place_path;
+use crate::domain::product::Product;
+
+#[derive(Debug, Default, Clone)]
+pub struct InventoryTransactionFilter {
+    pub search: String,
+    pub product_id: Option<i32>,
+    /// Any of these places, e.g. a warehouse and everything inside it.
+    pub place_ids: Option<Vec<i32>>,
+    pub transaction_type: Option<TransactionType>,
+}
+
+pub fn list_inventory_transactions(conn: &mut SqliteConnection, filter: &InventoryTransactionFilter) -> Result<Vec<InventoryTransaction>, Box<dyn Error>> {
+    let mut transactions_query = inventory_transactions::table
+        .order((inventory_transactions::created_at.desc(), inventory_transactions::id.desc()))
+        .into_boxed();
+
+    if let Some(product_id) = filter.product_id {
+        transactions_query = transactions_query.filter(inventory_transactions::product_id.eq(product_id));
+    }
+
+    if let Some(place_ids) = &filter.place_ids {
+        transactions_query = transactions_query.filter(inventory_transactions::place_id.eq_any(place_ids.clone()));
+    }
+
+    if let Some(transaction_type) = filter.transaction_type {
+        transactions_query = transactions_query.filter(inventory_transactions::transaction_type.eq(transaction_type.as_str()));
+    }
+
+    if !filter.search.is_empty() {
+        let search_like = format!("%{}%", filter.search);
+
+        transactions_query = transactions_query.filter(
+            inventory_transactions::reason.like(search_like.clone())
+                .or(inventory_transactions::lot_number.like(search_like))
+        );
+    }
+
+    let transaction_list: Vec<InventoryTransactionRow> = transactions_query.load(conn)?;
+
+    let transactions = transaction_list.into_iter()
+        .map(|transaction| transaction.try_into())
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(transactions)
+}
+
+/// Records a movement. Stock at a place cannot go below zero, and exits and
+/// transfers out cannot take units reserved for another job. Products that
+/// track lots must name the lot, except for adjustments that write down stock
+/// recorded before tracking began; a lot keeps the expiry date of its first
+/// movement, and lot stock cannot go below zero either. Serialized products take
+/// one serial number per unit moved. Exits made against a reservation are
+/// booked as consumed from it.
+pub fn create_inventory_transaction(conn: &mut SqliteConnection, mut new_transaction: NewInventoryTransactionRow, serial_numbers: &[String]) -> Result<InventoryTransaction, Box<dyn Error>> {
+    conn.transaction::<_, Box<dyn Error>, _>(|conn| {
+        let product = product_repository::get_product_by_id(conn, new_transaction.product_id)?;
+
+        check_lot(conn, &product, &mut new_transaction)?;
+        check_stock(conn, &product, &new_transaction)?;
+        let serial_numbers = check_serial_numbers(&product, &new_transaction, serial_numbers)?;
+
+        if let Some(reservation_id) = new_transaction.reservation_id {
+            if new_transaction.quantity >= 0 {
+                return Err("Only exits can be made against a reservation".into());
+            }
+
+            reservation_repository::consume_reservation(conn, reservation_id, new_transaction.product_id, new_transaction.place_id, -new_transaction.quantity)?;
+        }
+
+        diesel::insert_into(inventory_transactions::table)
+            .values(&new_transaction)
+            .execute(conn)?;
+
+        let created_transaction = inventory_transactions::table
+            .order(inventory_transactions::id.desc())
+            .first::<InventoryTransactionRow>(conn)?;
+
+        let transaction_item = created_transaction.try_into()?;
+
+        if product.serialized {
+            serial_number_repository::record_serial_number_movements(conn, &transaction_item, &serial_numbers)?;
+        }
+
+        Ok(transaction_item)
+    })
+}
+
+/// Stock at a place cannot go below zero. Exits and transfers out also leave
+/// the units reserved for other jobs in place, unless they are made against a
+/// reservation, which already holds the units being taken.
+fn check_stock(conn: &mut SqliteConnection, product: &Product, new_transaction: &NewInventoryTransactionRow) -> Result<(), Box<dyn Error>> {
+    if new_transaction.quantity >= 0 {
+        return Ok(());
+    }
+
+    let requested = -i64::from(new_transaction.quantity);
+    let on_hand = stock_repository::get_stock_balance(conn, product.id, new_transaction.place_id)?;
+
+    let transaction_type = TransactionType::try_from(new_transaction.transaction_type.as_str())?;
+    let keeps_reservations = new_transaction.reservation_id.is_none()
+        && matches!(transaction_type, TransactionType::Exit | TransactionType::TransferOut);
+
+    let (available, available_text) = if keeps_reservations {
+        let unreserved = reservation_repository::get_available_quantity(conn, product.id, Some(new_transaction.place_id))?;
+        (on_hand.min(unreserved), "available after reservations")
+    } else {
+        (on_hand, "on hand")
+    };
+
+    if available < requested {
+        let places = place_repository::list_places(conn, "")?;
+
+        return Err(format!(
+            "Not enough '{}' at {}: {} {}, {} requested",
+            product.name, place_path(&places, new_transaction.place_id), available.max(0), available_text, requested
+        ).into());
+    }
+
+    Ok(())
+}
+
+fn check_serial_numbers(product: &Product, new_transaction: &NewInventoryTransactionRow, serial_numbers: &[String]) -> Result<Vec<String>, Box<dyn Error>> {
+    let serial_numbers: Vec<String> = serial_numbers.iter()
+        .map(|serial_number| serial_number.trim().to_owned())
+        .filter(|serial_number| !serial_number.is_empty())
+        .collect();
+
+    if !product.serialized {
+        if !serial_numbers.is_empty() {
+            return Err(format!("'{}' does not track serial numbers", product.name).into());
+        }
+
+        return Ok(serial_numbers);
+    }
+
+    let units = new_transaction.quantity.unsigned_abs() as usize;
+
+    if serial_numbers.len() != units {
+        return Err(format!(
+            "'{}' is serialized, enter one serial number per unit: {} expected, {} entered",
+            product.name, units, serial_numbers.len()
+        ).into());
+    }
+
+    let mut seen = HashSet::new();
+
+    if let Some(duplicate) = serial_numbers.iter().find(|serial_number| !seen.insert(*serial_number)) {
+        return Err(format!("Serial number {} is entered more than once", duplicate).into());
+    }
+
+    Ok(serial_numbers)
+}
+
+fn check_lot(conn: &mut SqliteConnection, product: &Product, new_transaction: &mut NewInventoryTransactionRow) -> Result<(), Box<dyn Error>> {
+    new_transaction.lot_number = new_transaction.lot_number.take()
+        .map(|lot_number| lot_number.trim().to_owned())
+        .filter(|lot_number| !lot_number.is_empty());
+
+    let Some(lot_number) = new_transaction.lot_number.clone() else {
+        // Stock recorded before lot tracking was turned on has no lot, and an
+        // adjustment (e.g. from a stocktake) can still write it down.
+        let writes_down_untracked = new_transaction.transaction_type == TransactionType::Adjustment.as_str()
+            && new_transaction.quantity < 0
+            && stock_repository::get_untracked_balance(conn, new_transaction.product_id, new_transaction.place_id)? + i64::from(new_transaction.quantity) >= 0;
+
+        if product.tracks_lots && !writes_down_untracked {
+            return Err(format!("'{}' is tracked by lot, enter a lot number", product.name).into());
+        }
+
+        new_transaction.expiry_date = None;
+
+        return Ok(());
+    };
+
+    match stock_repository::get_lot_expiry_date(conn, new_transaction.product_id, &lot_number)? {
+        Some(known_expiry_date) if new_transaction.expiry_date.is_none() => {
+            new_transaction.expiry_date = Some(known_expiry_date);
+        }
+        Some(known_expiry_date) if new_transaction.expiry_date.as_ref() != Some(&known_expiry_date) => {
+            return Err(format!("Lot {} already expires on {}", lot_number, known_expiry_date).into());
+        }
+        _ => {}
+    }
+
+    if new_transaction.quantity < 0 {
+        let available = stock_repository::get_lot_balance(conn, new_transaction.product_id, new_transaction.place_id, &lot_number)?;
+
+        if available + i64::from(new_transaction.quantity) < 0 {
+            return Err(format!(
+                "Not enough stock in lot {}: {} available, {} requested",
+                lot_number, available, -new_transaction.quantity
+            ).into());
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,317 @@
+use diesel::prelude::*;
+use std::error::Error;
+
+use crate::infra::models::{
+    IssueRow, NewIssueRow, EditIssueRow,
+    IssueLineRow, NewIssueLineRow, EditIssueLineRow,
+    NewInventoryTransactionRow,
+};
+use crate::infra::repositories::{audit_repository, inventory_transaction_repository, product_repository};
+use crate::infra::schema::{issue_lines, issues};
+use crate::domain::audit::{AuditAction, AuditEntity, AuditField};
+use crate::domain::inventory_transaction::TransactionType;
+use crate::domain::issue::{Issue, IssueLine, IssueStatus};
+
+use chrono::Utc;
+
+const NAIVE_DATE_TIME_PATTERN: &str =  "%Y-%m-%d %H:%M:%S";
+
+#[derive(Debug, Default, Clone)]
+pub struct IssueFilter {
+    /// Matches the requester, department or destination.
+    pub search: String,
+    pub place_id: Option<i32>,
+    pub status: Option<IssueStatus>,
+}
+
+pub fn list_issues(conn: &mut SqliteConnection, filter: &IssueFilter) -> Result<Vec<Issue>, Box<dyn Error>> {
+    let mut issues_query = issues::table
+        .filter(issues::deleted_at.is_null())
+        .order(issues::id.desc())
+        .select(IssueRow::as_select())
+        .into_boxed();
+
+    if let Some(place_id) = filter.place_id {
+        issues_query = issues_query.filter(issues::place_id.eq(place_id));
+    }
+
+    if let Some(status) = filter.status {
+        issues_query = issues_query.filter(issues::status.eq(status.as_str()));
+    }
+
+    if !filter.search.is_empty() {
+        let search_like = format!("%{}%", filter.search);
+
+        issues_query = issues_query.filter(
+            issues::requester.like(search_like.clone())
+                .or(issues::department.like(search_like.clone()))
+                .or(issues::destination.like(search_like))
+        );
+    }
+
+    let issue_list: Vec<IssueRow> = issues_query.load(conn)?;
+
+    let issue_items = issue_list.into_iter()
+        .map(|issue| issue.try_into())
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(issue_items)
+}
+
+pub fn get_issue(conn: &mut SqliteConnection, issue_id: i32) -> Result<Issue, Box<dyn Error>> {
+    let issue = issues::table
+        .find(issue_id)
+        .select(IssueRow::as_select())
+        .first(conn)?;
+
+    issue.try_into()
+}
+
+pub fn create_issue(conn: &mut SqliteConnection, new_issue: NewIssueRow) -> Result<Issue, Box<dyn Error>> {
+    conn.transaction::<_, Box<dyn Error>, _>(|conn| {
+        diesel::insert_into(issues::table)
+            .values(&new_issue)
+            .execute(conn)?;
+
+        let created_issue: Issue = issues::table
+            .order(issues::id.desc())
+            .select(IssueRow::as_select())
+            .first(conn)?
+            .try_into()?;
+
+        audit_repository::record_audit(conn, AuditEntity::Issue, created_issue.id, AuditAction::Create, &[], &created_issue.audit_fields())?;
+
+        Ok(created_issue)
+    })
+}
+
+pub fn edit_issue(conn: &mut SqliteConnection, issue: EditIssueRow) -> Result<Issue, Box<dyn Error>> {
+    conn.transaction::<_, Box<dyn Error>, _>(|conn| {
+        let before = ensure_editable(conn, issue.id)?.audit_fields();
+
+        diesel::update(issues::table.find(issue.id))
+            .set((
+                &issue,
+                issues::updated_at.eq(Utc::now().format(NAIVE_DATE_TIME_PATTERN).to_string())
+            ))
+            .execute(conn)?;
+
+        let issue_item = get_issue(conn, issue.id)?;
+
+        audit_repository::record_audit(conn, AuditEntity::Issue, issue_item.id, AuditAction::Edit, &before, &issue_item.audit_fields())?;
+
+        Ok(issue_item)
+    })
+}
+
+pub fn delete_issue(conn: &mut SqliteConnection, issue_id: i32) -> Result<bool, Box<dyn Error>> {
+    conn.transaction::<_, Box<dyn Error>, _>(|conn| {
+        let mut before = ensure_editable(conn, issue_id)?.audit_fields();
+        before.extend(audit_lines(conn, issue_id)?);
+
+        let deleted = diesel::update(issues::table.find(issue_id))
+            .set(issues::deleted_at.eq(Some(Utc::now().naive_utc().format(NAIVE_DATE_TIME_PATTERN).to_string())))
+            .execute(conn)?;
+
+        audit_repository::record_audit(conn, AuditEntity::Issue, issue_id, AuditAction::Delete, &before, &[])?;
+
+        Ok(deleted > 0)
+    })
+}
+
+/// Closes a draft without moving any stock.
+pub fn cancel_issue(conn: &mut SqliteConnection, issue_id: i32) -> Result<Issue, Box<dyn Error>> {
+    conn.transaction::<_, Box<dyn Error>, _>(|conn| {
+        let before = ensure_editable(conn, issue_id)?.audit_fields();
+
+        diesel::update(issues::table.find(issue_id))
+            .set((
+                issues::status.eq(IssueStatus::Cancelled.as_str()),
+                issues::updated_at.eq(Utc::now().format(NAIVE_DATE_TIME_PATTERN).to_string())
+            ))
+            .execute(conn)?;
+
+        let issue_item = get_issue(conn, issue_id)?;
+
+        audit_repository::record_audit(conn, AuditEntity::Issue, issue_id, AuditAction::Edit, &before, &issue_item.audit_fields())?;
+
+        Ok(issue_item)
+    })
+}
+
+/// Hands the material out: posts one exit per line from the issue's place and
+/// marks the issue as posted. Nothing is written if any product lacks stock
+/// that is not reserved, which `create_inventory_transaction` checks per exit.
+pub fn post_issue(conn: &mut SqliteConnection, issue_id: i32) -> Result<Issue, Box<dyn Error>> {
+    conn.transaction::<_, Box<dyn Error>, _>(|conn| {
+        let issue = ensure_editable(conn, issue_id)?;
+        let lines = list_issue_lines(conn, issue.id)?;
+
+        if lines.is_empty() {
+            return Err("Add at least one line before posting the issue".into());
+        }
+
+        let now = Utc::now().format(NAIVE_DATE_TIME_PATTERN).to_string();
+
+        for line in lines {
+            let transaction = inventory_transaction_repository::create_inventory_transaction(conn, NewInventoryTransactionRow {
+                product_id: line.product_id,
+                place_id: issue.place_id,
+                supplier_id: None,
+                quantity: TransactionType::Exit.signed_quantity(line.quantity),
+                transaction_type: TransactionType::Exit.as_str().to_owned(),
+                reason: Some(format!("Issue #{} to {}", issue.id, issue.department)),
+                lot_number: line.lot_number,
+                expiry_date: None,
+                unit_cost: None,
+                reservation_id: None,
+            }, &line.serial_numbers)?;
+
+            diesel::update(issue_lines::table.find(line.id))
+                .set((
+                    issue_lines::inventory_transaction_id.eq(Some(transaction.id)),
+                    issue_lines::updated_at.eq(&now)
+                ))
+                .execute(conn)?;
+        }
+
+        diesel::update(issues::table.find(issue.id))
+            .set((
+                issues::status.eq(IssueStatus::Posted.as_str()),
+                issues::posted_at.eq(Some(&now)),
+                issues::updated_at.eq(&now)
+            ))
+            .execute(conn)?;
+
+        let issue_item = get_issue(conn, issue.id)?;
+
+        audit_repository::record_audit(conn, AuditEntity::Issue, issue.id, AuditAction::Edit, &issue.audit_fields(), &issue_item.audit_fields())?;
+
+        Ok(issue_item)
+    })
+}
+
+pub fn list_issue_lines(conn: &mut SqliteConnection, issue_id: i32) -> Result<Vec<IssueLine>, Box<dyn Error>> {
+    let line_list: Vec<IssueLineRow> = issue_lines::table
+        .filter(issue_lines::issue_id.eq(issue_id))
+        .order(issue_lines::id)
+        .select(IssueLineRow::as_select())
+        .load(conn)?;
+
+    Ok(line_list.into_iter().map(IssueLine::from).collect())
+}
+
+pub fn create_issue_line(conn: &mut SqliteConnection, new_line: NewIssueLineRow) -> Result<IssueLine, Box<dyn Error>> {
+    conn.transaction::<_, Box<dyn Error>, _>(|conn| {
+        ensure_editable(conn, new_line.issue_id)?;
+
+        let before = audit_lines(conn, new_line.issue_id)?;
+
+        diesel::insert_into(issue_lines::table)
+            .values(&new_line)
+            .execute(conn)?;
+
+        let created_line = issue_lines::table
+            .order(issue_lines::id.desc())
+            .select(IssueLineRow::as_select())
+            .first(conn)?;
+
+        record_lines_audit(conn, new_line.issue_id, &before)?;
+
+        Ok(IssueLine::from(created_line))
+    })
+}
+
+pub fn edit_issue_line(conn: &mut SqliteConnection, line: EditIssueLineRow) -> Result<IssueLine, Box<dyn Error>> {
+    conn.transaction::<_, Box<dyn Error>, _>(|conn| {
+        let issue_id: i32 = issue_lines::table
+            .find(line.id)
+            .select(issue_lines::issue_id)
+            .first(conn)?;
+
+        ensure_editable(conn, issue_id)?;
+
+        let before = audit_lines(conn, issue_id)?;
+
+        diesel::update(issue_lines::table.find(line.id))
+            .set((
+                &line,
+                issue_lines::updated_at.eq(Utc::now().format(NAIVE_DATE_TIME_PATTERN).to_string())
+            ))
+            .execute(conn)?;
+
+        let updated_line = issue_lines::table
+            .find(line.id)
+            .select(IssueLineRow::as_select())
+            .first(conn)?;
+
+        record_lines_audit(conn, issue_id, &before)?;
+
+        Ok(IssueLine::from(updated_line))
+    })
+}
+
+pub fn delete_issue_line(conn: &mut SqliteConnection, line_id: i32) -> Result<bool, Box<dyn Error>> {
+    conn.transaction::<_, Box<dyn Error>, _>(|conn| {
+        let issue_id: i32 = issue_lines::table
+            .find(line_id)
+            .select(issue_lines::issue_id)
+            .first(conn)?;
+
+        ensure_editable(conn, issue_id)?;
+
+        let before = audit_lines(conn, issue_id)?;
+
+        let deleted = diesel::delete(issue_lines::table.find(line_id))
+            .execute(conn)?;
+
+        record_lines_audit(conn, issue_id, &before)?;
+
+        Ok(deleted > 0)
+    })
+}
+
+/// Only drafts that were not deleted can be changed or posted.
+fn ensure_editable(conn: &mut SqliteConnection, issue_id: i32) -> Result<Issue, Box<dyn Error>> {
+    let issue = get_issue(conn, issue_id)?;
+
+    if issue.deleted_at.is_some() {
+        return Err(format!("Issue #{} was deleted and can no longer be changed", issue.id).into());
+    }
+
+    if !issue.status.is_editable() {
+        return Err(format!("Issue #{} is {} and can no longer be changed", issue.id, issue.status.label().to_lowercase()).into());
+    }
+
+    Ok(issue)
+}
+
+/// An issue's lines as one audited field, e.g. "2 × Drill (lot L1), 1 × Saw",
+/// so changes to them show in the issue's history.
+fn audit_lines(conn: &mut SqliteConnection, issue_id: i32) -> Result<Vec<AuditField>, Box<dyn Error>> {
+    let mut summaries = Vec::new();
+
+    for line in list_issue_lines(conn, issue_id)? {
+        let product = product_repository::get_product_by_id(conn, line.product_id)?;
+
+        let mut summary = format!("{} × {}", line.quantity, product.name);
+
+        if let Some(lot_number) = &line.lot_number {
+            summary.push_str(&format!(" (lot {})", lot_number));
+        }
+
+        if !line.serial_numbers.is_empty() {
+            summary.push_str(&format!(" (serials {})", line.serial_numbers.join(" ")));
+        }
+
+        summaries.push(summary);
+    }
+
+    Ok(vec![("lines", Some(summaries.join(", ")).filter(|summary| !summary.is_empty()))])
+}
+
+fn record_lines_audit(conn: &mut SqliteConnection, issue_id: i32, before: &[AuditField]) -> Result<(), Box<dyn Error>> {
+    let after = audit_lines(conn, issue_id)?;
+
+    audit_repository::record_audit(conn, AuditEntity::Issue, issue_id, AuditAction::Edit, before, &after)
+}
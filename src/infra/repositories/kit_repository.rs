@@ -0,0 +1,376 @@
+use diesel::prelude::*;
+use std::collections::HashMap;
+use std::error::Error;
+
+use crate::infra::models::{KitComponentRow, NewInventoryTransactionRow, NewKitComponentRow};
+use crate::infra::repositories::{
+    audit_repository, inventory_transaction_repository, product_repository, setting_repository,
+    valuation_repository,
+};
+use crate::infra::schema::kit_components;
+use crate::domain::audit::{AuditAction, AuditEntity, AuditField};
+use crate::domain::inventory_transaction::{InventoryTransaction, TransactionType};
+use crate::domain::kit::{KitComponent, kit_contains};
+use crate::domain::product::Product;
+use crate::domain::valuation::StockValuation;
+
+use chrono::{Local, Utc};
+
+const NAIVE_DATE_TIME_PATTERN: &str =  "%Y-%m-%d %H:%M:%S";
+
+pub struct KitAssembly {
+    pub kit_product_id: i32,
+    /// Place the components are taken from and the kits are put in (or the
+    /// reverse when disassembling).
+    pub place_id: i32,
+    pub quantity: i32,
+}
+
+pub fn list_kit_components(conn: &mut SqliteConnection, kit_product_id: i32) -> Result<Vec<KitComponent>, Box<dyn Error>> {
+    let component_list: Vec<KitComponentRow> = kit_components::table
+        .filter(kit_components::kit_product_id.eq(kit_product_id))
+        .order(kit_components::id)
+        .select(KitComponentRow::as_select())
+        .load(conn)?;
+
+    Ok(component_list.into_iter().map(KitComponent::from).collect())
+}
+
+/// Bills of materials of every kit, keyed by the kit's product id.
+pub fn list_kits(conn: &mut SqliteConnection) -> Result<HashMap<i32, Vec<KitComponent>>, Box<dyn Error>> {
+    let component_list: Vec<KitComponentRow> = kit_components::table
+        .order(kit_components::id)
+        .select(KitComponentRow::as_select())
+        .load(conn)?;
+
+    let mut kits: HashMap<i32, Vec<KitComponent>> = HashMap::new();
+
+    for component in component_list.into_iter().map(KitComponent::from) {
+        kits.entry(component.kit_product_id).or_default().push(component);
+    }
+
+    Ok(kits)
+}
+
+/// Adds a component to a kit, or changes its quantity if it is already there.
+/// A kit cannot contain itself, not even through one of its sub-assemblies.
+pub fn set_kit_component(conn: &mut SqliteConnection, component: NewKitComponentRow) -> Result<KitComponent, Box<dyn Error>> {
+    if component.quantity <= 0 {
+        return Err("Quantity per kit must be greater than zero".into());
+    }
+
+    let kits = list_kits(conn)?;
+
+    if kit_contains(&kits, component.component_product_id, component.kit_product_id) {
+        return Err("A kit cannot contain itself, directly or through its components".into());
+    }
+
+    conn.transaction::<_, Box<dyn Error>, _>(|conn| {
+        let before = audit_components(conn, component.kit_product_id)?;
+
+        let existing_id: Option<i32> = kit_components::table
+            .filter(kit_components::kit_product_id.eq(component.kit_product_id))
+            .filter(kit_components::component_product_id.eq(component.component_product_id))
+            .select(kit_components::id)
+            .first(conn)
+            .optional()?;
+
+        let component_id = match existing_id {
+            Some(component_id) => {
+                diesel::update(kit_components::table.find(component_id))
+                    .set((
+                        kit_components::quantity.eq(component.quantity),
+                        kit_components::updated_at.eq(Utc::now().format(NAIVE_DATE_TIME_PATTERN).to_string())
+                    ))
+                    .execute(conn)?;
+
+                component_id
+            }
+            None => {
+                diesel::insert_into(kit_components::table)
+                    .values(&component)
+                    .execute(conn)?;
+
+                kit_components::table
+                    .order(kit_components::id.desc())
+                    .select(kit_components::id)
+                    .first(conn)?
+            }
+        };
+
+        let saved_component = kit_components::table
+            .find(component_id)
+            .select(KitComponentRow::as_select())
+            .first(conn)?;
+
+        record_components_audit(conn, component.kit_product_id, &before)?;
+
+        Ok(KitComponent::from(saved_component))
+    })
+}
+
+pub fn delete_kit_component(conn: &mut SqliteConnection, component_id: i32) -> Result<bool, Box<dyn Error>> {
+    conn.transaction::<_, Box<dyn Error>, _>(|conn| {
+        let kit_product_id: i32 = kit_components::table
+            .find(component_id)
+            .select(kit_components::kit_product_id)
+            .first(conn)?;
+
+        let before = audit_components(conn, kit_product_id)?;
+
+        let deleted = diesel::delete(kit_components::table.find(component_id))
+            .execute(conn)?;
+
+        record_components_audit(conn, kit_product_id, &before)?;
+
+        Ok(deleted > 0)
+    })
+}
+
+/// Builds kits at a place: takes the components out and puts the kits in, in a
+/// single transaction. Kits come in at the current cost of their components.
+/// Returns the movement that added the kits.
+pub fn assemble_kits(conn: &mut SqliteConnection, assembly: KitAssembly) -> Result<InventoryTransaction, Box<dyn Error>> {
+    if assembly.quantity <= 0 {
+        return Err("Enter how many kits to assemble".into());
+    }
+
+    conn.transaction::<_, Box<dyn Error>, _>(|conn| {
+        let (kit, components) = get_kit(conn, assembly.kit_product_id)?;
+
+        let mut component_products = Vec::with_capacity(components.len());
+
+        for component in &components {
+            let product = product_repository::get_product_by_id(conn, component.component_product_id)?;
+            ensure_untracked(&product)?;
+
+            let needed = component_quantity(component, &product, assembly.quantity)?;
+
+            component_products.push((product, needed));
+        }
+
+        let method = setting_repository::get_costing_method(conn)?;
+        let valuations = valuation_repository::list_stock_valuation(conn, method, Local::now().date_naive())?;
+
+        // Unknown if any component has never had a cost.
+        let kit_cost = components.iter()
+            .map(|component| {
+                current_unit_cost(&valuations, component.component_product_id, assembly.place_id)
+                    .map(|unit_cost| unit_cost * f64::from(component.quantity))
+            })
+            .sum::<Option<f64>>();
+
+        let reason = format!("Assembly of {} × {}", assembly.quantity, kit.name);
+
+        for (product, needed) in &component_products {
+            inventory_transaction_repository::create_inventory_transaction(conn, NewInventoryTransactionRow {
+                product_id: product.id,
+                place_id: assembly.place_id,
+                supplier_id: None,
+                quantity: TransactionType::Exit.signed_quantity(*needed),
+                transaction_type: TransactionType::Exit.as_str().to_owned(),
+                reason: Some(reason.clone()),
+                lot_number: None,
+                expiry_date: None,
+                unit_cost: None,
+                reservation_id: None,
+            }, &[])?;
+        }
+
+        inventory_transaction_repository::create_inventory_transaction(conn, NewInventoryTransactionRow {
+            product_id: kit.id,
+            place_id: assembly.place_id,
+            supplier_id: None,
+            quantity: TransactionType::Entry.signed_quantity(assembly.quantity),
+            transaction_type: TransactionType::Entry.as_str().to_owned(),
+            reason: Some(reason),
+            lot_number: None,
+            expiry_date: None,
+            unit_cost: kit_cost,
+            reservation_id: None,
+        }, &[])
+    })
+}
+
+/// Breaks kits at a place back into their components, in a single transaction.
+/// Components come back at their current cost. Returns the movement that took
+/// the kits out.
+pub fn disassemble_kits(conn: &mut SqliteConnection, assembly: KitAssembly) -> Result<InventoryTransaction, Box<dyn Error>> {
+    if assembly.quantity <= 0 {
+        return Err("Enter how many kits to disassemble".into());
+    }
+
+    conn.transaction::<_, Box<dyn Error>, _>(|conn| {
+        let (kit, components) = get_kit(conn, assembly.kit_product_id)?;
+
+        let reason = format!("Disassembly of {} × {}", assembly.quantity, kit.name);
+
+        let kit_exit = inventory_transaction_repository::create_inventory_transaction(conn, NewInventoryTransactionRow {
+            product_id: kit.id,
+            place_id: assembly.place_id,
+            supplier_id: None,
+            quantity: TransactionType::Exit.signed_quantity(assembly.quantity),
+            transaction_type: TransactionType::Exit.as_str().to_owned(),
+            reason: Some(reason.clone()),
+            lot_number: None,
+            expiry_date: None,
+            unit_cost: None,
+            reservation_id: None,
+        }, &[])?;
+
+        for component in &components {
+            let product = product_repository::get_product_by_id(conn, component.component_product_id)?;
+            ensure_untracked(&product)?;
+
+            let returned = component_quantity(component, &product, assembly.quantity)?;
+
+            inventory_transaction_repository::create_inventory_transaction(conn, NewInventoryTransactionRow {
+                product_id: product.id,
+                place_id: assembly.place_id,
+                supplier_id: None,
+                quantity: TransactionType::Entry.signed_quantity(returned),
+                transaction_type: TransactionType::Entry.as_str().to_owned(),
+                reason: Some(reason.clone()),
+                lot_number: None,
+                expiry_date: None,
+                unit_cost: None,
+                reservation_id: None,
+            }, &[])?;
+        }
+
+        Ok(kit_exit)
+    })
+}
+
+fn get_kit(conn: &mut SqliteConnection, kit_product_id: i32) -> Result<(Product, Vec<KitComponent>), Box<dyn Error>> {
+    let kit = product_repository::get_product_by_id(conn, kit_product_id)?;
+    ensure_untracked(&kit)?;
+
+    let components = list_kit_components(conn, kit.id)?;
+
+    if components.is_empty() {
+        return Err(format!("'{}' has no components", kit.name).into());
+    }
+
+    Ok((kit, components))
+}
+
+/// A kit's bill of materials as one audited field, e.g. "2 × Screw, 1 × Panel",
+/// so changes to it show in the kit's history.
+fn audit_components(conn: &mut SqliteConnection, kit_product_id: i32) -> Result<Vec<AuditField>, Box<dyn Error>> {
+    let mut summaries = Vec::new();
+
+    for component in list_kit_components(conn, kit_product_id)? {
+        let product = product_repository::get_product_by_id(conn, component.component_product_id)?;
+        summaries.push(format!("{} × {}", component.quantity, product.name));
+    }
+
+    Ok(vec![("components", Some(summaries.join(", ")).filter(|summary| !summary.is_empty()))])
+}
+
+fn record_components_audit(conn: &mut SqliteConnection, kit_product_id: i32, before: &[AuditField]) -> Result<(), Box<dyn Error>> {
+    let after = audit_components(conn, kit_product_id)?;
+
+    audit_repository::record_audit(conn, AuditEntity::Product, kit_product_id, AuditAction::Edit, before, &after)
+}
+
+/// Units of a component that go into `kits` kits, refused when they would not
+/// fit in a single movement.
+fn component_quantity(component: &KitComponent, product: &Product, kits: i32) -> Result<i32, Box<dyn Error>> {
+    component.quantity
+        .checked_mul(kits)
+        .ok_or_else(|| format!("{} kits need more '{}' than can be moved at once", kits, product.name).into())
+}
+
+/// Assembly moves whole units without choosing lots or serial numbers.
+fn ensure_untracked(product: &Product) -> Result<(), Box<dyn Error>> {
+    if product.tracks_lots || product.serialized {
+        return Err(format!("'{}' is tracked by lot or serial number and cannot be assembled or disassembled", product.name).into());
+    }
+
+    Ok(())
+}
+
+/// Unit cost of the product at the place, or across all places when the place
+/// holds none of it.
+fn current_unit_cost(valuations: &[StockValuation], product_id: i32, place_id: i32) -> Option<f64> {
+    let at_place = valuations.iter()
+        .find(|valuation| valuation.product_id == product_id && valuation.place_id == place_id)
+        .and_then(StockValuation::unit_cost);
+
+    at_place.or_else(|| {
+        let (quantity, value) = valuations.iter()
+            .filter(|valuation| valuation.product_id == product_id)
+            .fold((0, 0.0), |(quantity, value), valuation| (quantity + valuation.quantity, value + valuation.value));
+
+        (quantity != 0).then(|| value / quantity as f64)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infra::db;
+    use crate::infra::models::NewProductRow;
+
+    fn product(conn: &mut SqliteConnection, name: &str) -> i32 {
+        product_repository::create_product(conn, NewProductRow {
+            name: name.to_owned(),
+            unity: Some("un".to_owned()),
+            brand: None,
+            min_stock: None,
+            observation: None,
+            reorder_point: None,
+            safety_stock: None,
+            max_stock: None,
+            tracks_lots: Some(false),
+            serialized: None,
+            unit_id: None,
+            category_id: None,
+            sku: None,
+        }, &[]).unwrap().id
+    }
+
+    fn set_component(conn: &mut SqliteConnection, kit_product_id: i32, component_product_id: i32) -> Result<KitComponent, Box<dyn Error>> {
+        set_kit_component(conn, NewKitComponentRow { kit_product_id, component_product_id, quantity: 1 })
+    }
+
+    #[test]
+    fn refuses_a_kit_that_contains_itself() {
+        let mut conn = db::establish_test_connection();
+        let kit = product(&mut conn, "Kit");
+
+        assert!(set_component(&mut conn, kit, kit).is_err());
+        assert!(list_kit_components(&mut conn, kit).unwrap().is_empty());
+    }
+
+    #[test]
+    fn refuses_a_kit_that_contains_itself_through_a_sub_assembly() {
+        let mut conn = db::establish_test_connection();
+        let kit = product(&mut conn, "Kit");
+        let sub_assembly = product(&mut conn, "Sub-assembly");
+        let part = product(&mut conn, "Part");
+
+        set_component(&mut conn, kit, sub_assembly).unwrap();
+        set_component(&mut conn, sub_assembly, part).unwrap();
+
+        assert!(set_component(&mut conn, sub_assembly, kit).is_err());
+        assert!(set_component(&mut conn, part, kit).is_err());
+        assert_eq!(list_kit_components(&mut conn, sub_assembly).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn allows_a_component_shared_by_several_kits() {
+        let mut conn = db::establish_test_connection();
+        let kit = product(&mut conn, "Kit");
+        let sub_assembly = product(&mut conn, "Sub-assembly");
+        let screw = product(&mut conn, "Screw");
+
+        set_component(&mut conn, sub_assembly, screw).unwrap();
+        set_component(&mut conn, kit, sub_assembly).unwrap();
+        set_component(&mut conn, kit, screw).unwrap();
+
+        assert_eq!(list_kit_components(&mut conn, kit).unwrap().len(), 2);
+        assert_eq!(list_kit_components(&mut conn, sub_assembly).unwrap().len(), 1);
+    }
+}
@@ -1,3 +1,22 @@
+pub mod audit_repository;
+pub mod category_repository;
+pub mod goods_receipt_repository;
+pub mod inventory_transaction_repository;
+pub mod issue_repository;
+pub mod kit_repository;
 pub mod place_repository;
+pub mod product_barcode_repository;
 pub mod product_repository;
-pub mod supplier_repository;
\ No newline at end of file
+pub mod product_supplier_repository;
+pub mod purchase_order_repository;
+pub mod reorder_repository;
+pub mod reservation_repository;
+pub mod serial_number_repository;
+pub mod setting_repository;
+pub mod stock_repository;
+pub mod stock_transfer_repository;
+pub mod stocktake_repository;
+pub mod supplier_repository;
+pub mod trash_repository;
+pub mod unit_repository;
+pub mod valuation_repository;
\ No newline at end of file
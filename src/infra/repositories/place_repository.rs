@@ -2,8 +2,13 @@ use diesel::prelude::*;
 use std::error::Error;
 
 use crate::infra::models::{PlaceRow, NewPlaceRow, EditPlaceRow};
-use crate::infra::schema::places;
-use crate::domain::place::Place;
+use crate::infra::repositories::audit_repository;
+use crate::infra::schema::{
+    goods_receipts, inventory_transactions, issues, places, reservations, serial_numbers, stock_transfers,
+    stocktake_lines, stocktakes,
+};
+use crate::domain::audit::{AuditAction, AuditEntity};
+use crate::domain::place::{Place, PlaceType, parse_place_path, place_path};
 
 use chrono::Utc;
 
@@ -27,75 +32,303 @@ pub fn list_places(conn: &mut SqliteConnection, search: &str) -> Result<Vec<Plac
         
     let place_list: Vec<PlaceRow>  = places_query.load(conn).expect("Error loading places");
 
-  	let prods = place_list.into_iter()
+  	let mut prods = place_list.into_iter()
     	.map(|place| place.try_into())
-		.collect::<Result<Vec<_>, _>>()?;
+		.collect::<Result<Vec<Place>, _>>()?;
+
+    // Listed in tree order; matches of a search still need all places for their paths.
+    let all_places = if search.is_empty() { prods.clone() } else { list_places(conn, "")? };
+    prods.sort_by_cached_key(|place| parse_place_path(&place_path(&all_places, place.id)));
 
     Ok(prods)
 }
 
-pub fn create_place(conn: &mut SqliteConnection, new_place: NewPlaceRow) -> Result<Place, Box<dyn Error>> {
-    diesel::insert_into(places::table)
-        .values(&new_place)
-        .execute(conn)
-        .expect("Failed to insert place");
-
-    let created_place = places::table
-        .order(places::id.desc())
-        .first::<PlaceRow>(conn)
-        .expect("Failed to retrieve created place");
+pub fn get_place_by_id(conn: &mut SqliteConnection, place_id: i32) -> Result<Place, Box<dyn Error>> {
+    let place = places::table
+        .find(place_id)
+        .first::<PlaceRow>(conn)?;
 
-    let place_item = created_place.try_into()?;
+    let place_item = place.try_into()?;
 
     Ok(place_item)
 }
 
-pub fn create_places(conn: &mut SqliteConnection, new_places: &[NewPlaceRow]) -> Result<Vec<Place>, Box<dyn Error>> {
-    diesel::insert_into(places::table)
-        .values(new_places)
-        .execute(conn)
-        .expect("Failed to insert places");
-
-    let created_places: Vec<PlaceRow> = places::table
-        .order(places::id.desc())
-        .limit(new_places.len() as i64)
-        .load(conn)
-        .expect("Failed to retrieve created places");
-
-    let new_places = created_places.into_iter()
-        .rev()
-        .map(|p| p.try_into())
-        .collect::<Result<Vec<_>, _>>()?;
+pub fn create_place(conn: &mut SqliteConnection, new_place: NewPlaceRow) -> Result<Place, Box<dyn Error>> {
+    let place_type = PlaceType::try_from(new_place.place_type.as_str())?;
+
+    check_place(conn, None, &new_place.name, new_place.parent_id, place_type)?;
+
+    conn.transaction::<_, Box<dyn Error>, _>(|conn| {
+        diesel::insert_into(places::table)
+            .values(&new_place)
+            .execute(conn)?;
+
+        let created_place = places::table
+            .order(places::id.desc())
+            .first::<PlaceRow>(conn)?;
+
+        let place_item: Place = created_place.try_into()?;
+
+        audit_repository::record_audit(conn, AuditEntity::Place, place_item.id, AuditAction::Create, &[], &place_item.audit_fields())?;
 
-    Ok(new_places)
+        Ok(place_item)
+    })
 }
 
 pub fn edit_place(conn: &mut SqliteConnection, place: EditPlaceRow) -> Result<Place, Box<dyn Error>> {
     let place_id = place.id;
+    let place_type = PlaceType::try_from(place.place_type.as_str())?;
 
-    diesel::update(places::table.find(place_id))
-        .set((
-            &place,
-            places::updated_at.eq(Utc::now().format(NAIVE_DATE_TIME_PATTERN).to_string())
-        ))
-        .execute(conn)
-        .expect("Failed to update place");
+    check_place(conn, Some(place_id), &place.name, place.parent_id, place_type)?;
 
-    
-    let updated_place = places::table
-        .filter(places::id.eq(place_id))
-        .first::<PlaceRow>(conn)
-        .expect("Failed to retrieve updated place");
+    conn.transaction::<_, Box<dyn Error>, _>(|conn| {
+        let before = get_place_by_id(conn, place_id)?.audit_fields();
 
-    let place_item = updated_place.try_into()?;
+        diesel::update(places::table.find(place_id))
+            .set((
+                &place,
+                places::updated_at.eq(Utc::now().format(NAIVE_DATE_TIME_PATTERN).to_string())
+            ))
+            .execute(conn)?;
 
-    Ok(place_item)
+        let place_item = get_place_by_id(conn, place_id)?;
+
+        audit_repository::record_audit(conn, AuditEntity::Place, place_id, AuditAction::Edit, &before, &place_item.audit_fields())?;
+
+        Ok(place_item)
+    })
 }
 
+/// Soft-deletes a place that has no places inside it.
 pub fn delete_place(conn: &mut SqliteConnection, place_id: i32) -> Result<bool, Box<dyn Error>> {
-    let deleted = diesel::update(places::table.find(place_id))
-        .set(places::deleted_at.eq(Some(Utc::now().naive_utc().format(NAIVE_DATE_TIME_PATTERN).to_string())))
-        .execute(conn)?;
+    let children: i64 = places::table
+        .filter(places::parent_id.eq(place_id))
+        .filter(places::deleted_at.is_null())
+        .count()
+        .get_result(conn)?;
+
+    if children > 0 {
+        return Err("Delete or move the places inside this place first".into());
+    }
+
+    conn.transaction::<_, Box<dyn Error>, _>(|conn| {
+        let before = get_place_by_id(conn, place_id)?.audit_fields();
+
+        let deleted = diesel::update(places::table.find(place_id))
+            .set(places::deleted_at.eq(Some(Utc::now().naive_utc().format(NAIVE_DATE_TIME_PATTERN).to_string())))
+            .execute(conn)?;
+
+        audit_repository::record_audit(conn, AuditEntity::Place, place_id, AuditAction::Delete, &before, &[])?;
+
+        Ok(deleted > 0)
+    })
+}
+
+/// Deleted places, most recently deleted first.
+pub fn list_deleted_places(conn: &mut SqliteConnection, search: &str) -> Result<Vec<Place>, Box<dyn Error>> {
+    let search_like = format!("%{}%", search);
+
+    let place_list: Vec<PlaceRow> = places::table
+        .filter(places::deleted_at.is_not_null())
+        .filter(places::name.like(&search_like))
+        .order(places::deleted_at.desc())
+        .load(conn)?;
+
+    let places = place_list.into_iter()
+        .map(|place| place.try_into())
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(places)
+}
+
+/// Brings a deleted place back inside its old parent, which must not be
+/// deleted, unless a sibling has taken its name in the meantime.
+pub fn restore_place(conn: &mut SqliteConnection, place_id: i32) -> Result<Place, Box<dyn Error>> {
+    conn.transaction::<_, Box<dyn Error>, _>(|conn| {
+        let place = get_place_by_id(conn, place_id)?;
+
+        if place.deleted_at.is_none() {
+            return Err(format!("'{}' is not deleted", place.name).into());
+        }
+
+        if let Some(parent_id) = place.parent_id {
+            let parent = get_place_by_id(conn, parent_id)?;
+
+            if parent.deleted_at.is_some() {
+                return Err(format!("Restore '{}', the place it was inside, first", parent.name).into());
+            }
+        }
+
+        check_place(conn, Some(place_id), &place.name, place.parent_id, place.place_type)?;
+
+        diesel::update(places::table.find(place_id))
+            .set((
+                places::deleted_at.eq(None::<String>),
+                places::updated_at.eq(Utc::now().format(NAIVE_DATE_TIME_PATTERN).to_string())
+            ))
+            .execute(conn)?;
+
+        audit_repository::record_audit(conn, AuditEntity::Place, place_id, AuditAction::Restore, &[], &[])?;
+
+        get_place_by_id(conn, place_id)
+    })
+}
+
+/// Permanently removes a deleted place. Places that held stock, appear on a
+/// document or still have places inside them, deleted or not, are kept.
+pub fn purge_place(conn: &mut SqliteConnection, place_id: i32) -> Result<bool, Box<dyn Error>> {
+    conn.transaction::<_, Box<dyn Error>, _>(|conn| {
+        let place = get_place_by_id(conn, place_id)?;
+
+        if place.deleted_at.is_none() {
+            return Err(format!("Delete '{}' before purging it", place.name).into());
+        }
 
-    Ok(deleted > 0)
-}
\ No newline at end of file
+        let children: i64 = places::table
+            .filter(places::parent_id.eq(place_id))
+            .count()
+            .get_result(conn)?;
+
+        if children > 0 {
+            return Err(format!("Purge the places inside '{}' first", place.name).into());
+        }
+
+        let references: i64 = [
+            inventory_transactions::table.filter(inventory_transactions::place_id.eq(place_id)).count().get_result::<i64>(conn)?,
+            stock_transfers::table
+                .filter(stock_transfers::from_place_id.eq(place_id).or(stock_transfers::to_place_id.eq(place_id)))
+                .count()
+                .get_result::<i64>(conn)?,
+            goods_receipts::table.filter(goods_receipts::place_id.eq(place_id)).count().get_result::<i64>(conn)?,
+            issues::table.filter(issues::place_id.eq(place_id)).count().get_result::<i64>(conn)?,
+            reservations::table.filter(reservations::place_id.eq(place_id)).count().get_result::<i64>(conn)?,
+            serial_numbers::table.filter(serial_numbers::place_id.eq(place_id)).count().get_result::<i64>(conn)?,
+            stocktakes::table.filter(stocktakes::place_id.eq(place_id)).count().get_result::<i64>(conn)?,
+            stocktake_lines::table.filter(stocktake_lines::place_id.eq(place_id)).count().get_result::<i64>(conn)?,
+        ].iter().sum();
+
+        if references > 0 {
+            return Err(format!("'{}' has stock movements or documents and cannot be purged", place.name).into());
+        }
+
+        let purged = diesel::delete(places::table.find(place_id)).execute(conn)?;
+
+        audit_repository::record_audit(conn, AuditEntity::Place, place_id, AuditAction::Purge, &[], &[])?;
+
+        Ok(purged > 0)
+    })
+}
+
+/// Resolves a "WH1/A/03" path to the id of its last place, creating the places
+/// that do not exist yet. Missing places take `place_type` for the last one and
+/// the level below their parent otherwise. Blank paths resolve to no place.
+pub fn find_or_create_place_path(conn: &mut SqliteConnection, path: &str, place_type: Option<PlaceType>) -> Result<Option<i32>, Box<dyn Error>> {
+    conn.transaction::<_, Box<dyn Error>, _>(|conn| {
+        let names = parse_place_path(path);
+        let mut parent: Option<Place> = None;
+
+        for (index, name) in names.iter().enumerate() {
+            let parent_id = parent.as_ref().map(|parent| parent.id);
+
+            let place = match find_child_place(conn, parent_id, name)? {
+                Some(existing) => existing.try_into()?,
+                None => {
+                    let inferred_type = parent.as_ref()
+                        .map(|parent| parent.place_type.child_type())
+                        .unwrap_or_default();
+
+                    let new_place = NewPlaceRow {
+                        name: name.clone(),
+                        parent_id,
+                        place_type: place_type
+                            .filter(|_| index == names.len() - 1)
+                            .unwrap_or(inferred_type)
+                            .as_str()
+                            .to_owned(),
+                    };
+
+                    create_place(conn, new_place)?
+                }
+            };
+
+            parent = Some(place);
+        }
+
+        Ok(parent.map(|place| place.id))
+    })
+}
+
+/// The most recently deleted place with this name inside `parent_id`, which can
+/// be restored instead of creating a new one.
+pub fn find_deleted_child_place(conn: &mut SqliteConnection, parent_id: Option<i32>, name: &str) -> Result<Option<Place>, Box<dyn Error>> {
+    let mut query = places::table
+        .filter(places::deleted_at.is_not_null())
+        .filter(places::name.eq(name))
+        .order(places::deleted_at.desc())
+        .into_boxed();
+
+    query = match parent_id {
+        Some(parent_id) => query.filter(places::parent_id.eq(parent_id)),
+        None => query.filter(places::parent_id.is_null()),
+    };
+
+    let place = query.first::<PlaceRow>(conn).optional()?;
+
+    place.map(Place::try_from).transpose()
+}
+
+fn find_child_place(conn: &mut SqliteConnection, parent_id: Option<i32>, name: &str) -> Result<Option<PlaceRow>, Box<dyn Error>> {
+    let mut query = places::table
+        .filter(places::deleted_at.is_null())
+        .filter(places::name.eq(name))
+        .into_boxed();
+
+    query = match parent_id {
+        Some(parent_id) => query.filter(places::parent_id.eq(parent_id)),
+        None => query.filter(places::parent_id.is_null()),
+    };
+
+    Ok(query.first::<PlaceRow>(conn).optional()?)
+}
+
+/// Sibling names must be unique so paths stay unambiguous, and a place can only
+/// hold places of a deeper type (a warehouse holds zones, a zone aisles...).
+fn check_place(conn: &mut SqliteConnection, place_id: Option<i32>, name: &str, parent_id: Option<i32>, place_type: PlaceType) -> Result<(), Box<dyn Error>> {
+    if name.contains('/') {
+        return Err("Place names cannot contain '/'".into());
+    }
+
+    if let Some(existing) = find_child_place(conn, parent_id, name)?
+        && Some(existing.id) != place_id {
+        return Err(format!("There is already a place named '{}' here", name).into());
+    }
+
+    if let Some(parent_id) = parent_id {
+        let parent = get_place_by_id(conn, parent_id)?;
+
+        if parent.deleted_at.is_some() {
+            return Err(format!("Place '{}' was deleted", parent.name).into());
+        }
+
+        if !parent.place_type.can_contain(place_type) {
+            return Err(format!("{} '{}' cannot contain a {}", parent.place_type.label(), parent.name, place_type.label().to_lowercase()).into());
+        }
+    }
+
+    if let Some(place_id) = place_id {
+        let child_rows: Vec<PlaceRow> = places::table
+            .filter(places::parent_id.eq(place_id))
+            .filter(places::deleted_at.is_null())
+            .load(conn)?;
+
+        for child_row in child_rows {
+            let child: Place = child_row.try_into()?;
+
+            if !place_type.can_contain(child.place_type) {
+                return Err(format!("A {} cannot contain {} '{}', which is inside this place", place_type.label().to_lowercase(), child.place_type.label().to_lowercase(), child.name).into());
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,73 @@
+use diesel::prelude::*;
+use std::collections::HashSet;
+use std::error::Error;
+
+use crate::infra::models::{NewProductBarcodeRow, ProductBarcodeRow};
+use crate::infra::schema::{product_barcodes, products};
+use crate::domain::barcode::{ProductBarcode, Symbology};
+
+pub fn list_product_barcodes(conn: &mut SqliteConnection, product_id: i32) -> Result<Vec<ProductBarcode>, Box<dyn Error>> {
+    let barcode_list: Vec<ProductBarcodeRow> = product_barcodes::table
+        .filter(product_barcodes::product_id.eq(product_id))
+        .order(product_barcodes::id.asc())
+        .load(conn)?;
+
+    let barcodes = barcode_list.into_iter()
+        .map(|barcode| barcode.try_into())
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(barcodes)
+}
+
+/// Replaces the barcodes of a product. Every code must have a recognizable
+/// symbology and must not belong to another product that is not deleted.
+pub fn replace_product_barcodes(conn: &mut SqliteConnection, product_id: i32, barcodes: &[String]) -> Result<Vec<ProductBarcode>, Box<dyn Error>> {
+    let mut seen = HashSet::new();
+    let mut new_barcodes = Vec::with_capacity(barcodes.len());
+
+    for barcode in barcodes {
+        let barcode = barcode.trim();
+
+        if !seen.insert(barcode) {
+            return Err(format!("Barcode {} was entered more than once", barcode).into());
+        }
+
+        let symbology = Symbology::detect(barcode)?;
+
+        if let Some(owner_name) = find_barcode_owner(conn, barcode, product_id)? {
+            return Err(format!("Barcode {} already belongs to '{}'", barcode, owner_name).into());
+        }
+
+        new_barcodes.push(NewProductBarcodeRow {
+            product_id,
+            barcode: barcode.to_owned(),
+            symbology: symbology.as_str().to_owned(),
+        });
+    }
+
+    conn.transaction::<_, Box<dyn Error>, _>(|conn| {
+        diesel::delete(product_barcodes::table.filter(product_barcodes::product_id.eq(product_id)))
+            .execute(conn)?;
+
+        diesel::insert_into(product_barcodes::table)
+            .values(&new_barcodes)
+            .execute(conn)?;
+
+        list_product_barcodes(conn, product_id)
+    })
+}
+
+/// Name of the product, other than `except_product_id`, that has this barcode.
+/// Barcodes of deleted products are free to be reused.
+pub fn find_barcode_owner(conn: &mut SqliteConnection, barcode: &str, except_product_id: i32) -> Result<Option<String>, Box<dyn Error>> {
+    let owner_name = product_barcodes::table
+        .inner_join(products::table)
+        .filter(product_barcodes::barcode.eq(barcode))
+        .filter(products::deleted_at.is_null())
+        .filter(products::id.ne(except_product_id))
+        .select(products::name)
+        .first::<String>(conn)
+        .optional()?;
+
+    Ok(owner_name)
+}
@@ -1,8 +1,14 @@
 use diesel::prelude::*;
+use std::collections::HashSet;
 use std::error::Error;
 
 use crate::infra::models::{ProductRow, NewProductRow, EditProductRow};
-use crate::infra::schema::products;
+use crate::infra::repositories::{audit_repository, product_barcode_repository, unit_repository};
+use crate::infra::schema::{
+    inventory_transactions, issue_lines, kit_components, product_barcodes, product_suppliers, products,
+    purchase_order_lines, reservations, serial_numbers, stock_transfers, stocktake_lines,
+};
+use crate::domain::audit::{AuditAction, AuditEntity, AuditField};
 use crate::domain::product::Product;
 
 use chrono::Utc;
@@ -10,14 +16,29 @@ use chrono::Utc;
 const NAIVE_DATE_TIME_PATTERN: &str =  "%Y-%m-%d %H:%M:%S";
 
 pub fn list_products(conn: &mut SqliteConnection, search: &str) -> Result<Vec<Product>, Box<dyn Error>> {
+    list_products_in_categories(conn, search, None)
+}
+
+/// Same search as `list_products`, limited to the given categories when some.
+pub fn list_products_in_categories(conn: &mut SqliteConnection, search: &str, category_ids: Option<&[i32]>) -> Result<Vec<Product>, Box<dyn Error>> {
     let search_like = format!("%{}%", search);
 
   	let mut products_query= products::table.filter(products::deleted_at.is_null()).into_boxed();
 
+    if let Some(category_ids) = category_ids {
+        products_query = products_query.filter(products::category_id.eq_any(category_ids));
+    }
+
     let filter_expression =  products::name.like(&search_like)
         .or(products::brand.like(&search_like))
         .or(products::observation.like(&search_like))
-        .or(products::unity.like(&search_like));
+        .or(products::unity.like(&search_like))
+        .or(products::sku.like(&search_like))
+        .or(products::id.eq_any(
+            product_barcodes::table
+                .filter(product_barcodes::barcode.eq(search.trim().to_owned()))
+                .select(product_barcodes::product_id)
+        ));
 
     if let Ok(search_number) = search.parse::<i32>(){
         products_query = products_query.filter(
@@ -41,76 +62,341 @@ pub fn list_products(conn: &mut SqliteConnection, search: &str) -> Result<Vec<Pr
 pub fn get_product_by_id(conn: &mut SqliteConnection, product_id: i32) -> Result<Product, Box<dyn Error>> {
     let product = products::table
         .find(product_id)
-        .first::<ProductRow>(conn)
-        .expect("Failed to retrieve product");
+        .first::<ProductRow>(conn)?;
 
     let product_item = product.try_into()?;
 
     Ok(product_item)
 }
 
-pub fn create_product(conn: &mut SqliteConnection, new_product: NewProductRow) -> Result<Product, Box<dyn Error>> {
-    diesel::insert_into(products::table)
-        .values(&new_product)
-        .execute(conn)
-        .expect("Failed to insert product");
+/// Resolves a scanned or typed code to a product by exact match on its
+/// barcodes first and its SKU second. Deleted products are never returned.
+pub fn find_product_by_code(conn: &mut SqliteConnection, code: &str) -> Result<Option<Product>, Box<dyn Error>> {
+    let code = code.trim();
+
+    if code.is_empty() {
+        return Ok(None);
+    }
 
-    let created_product = products::table
-        .order(products::id.desc())
+    let by_barcode = products::table
+        .inner_join(product_barcodes::table)
+        .filter(product_barcodes::barcode.eq(code))
+        .filter(products::deleted_at.is_null())
+        .select(ProductRow::as_select())
         .first::<ProductRow>(conn)
-        .expect("Failed to retrieve created product");
+        .optional()?;
 
-    let product_item = created_product.try_into()?;
+    let product = match by_barcode {
+        Some(product) => Some(product),
+        None => products::table
+            .filter(products::sku.eq(code))
+            .filter(products::deleted_at.is_null())
+            .first::<ProductRow>(conn)
+            .optional()?,
+    };
 
-    Ok(product_item)
+    Ok(product.map(|product| product.try_into()).transpose()?)
 }
 
-pub fn create_products(conn: &mut SqliteConnection, new_products: &[NewProductRow]) -> Result<Vec<Product>, Box<dyn Error>> {
-    diesel::insert_into(products::table)
-        .values(new_products)
-        .execute(conn)
-        .expect("Failed to insert products");
-
-    let created_products: Vec<ProductRow> = products::table
-        .order(products::id.desc())
-        .limit(new_products.len() as i64)
-        .load(conn)
-        .expect("Failed to retrieve created products");
-
-    let new_products = created_products.into_iter()
-        .rev()
-        .map(|p| p.try_into())
-        .collect::<Result<Vec<_>, _>>()?;
+pub fn create_product(conn: &mut SqliteConnection, mut new_product: NewProductRow, barcodes: &[String]) -> Result<Product, Box<dyn Error>> {
+    apply_unit_symbol(conn, new_product.unit_id, &mut new_product.unity)?;
+
+    new_product.sku = normalize_sku(new_product.sku);
+    check_sku(conn, new_product.sku.as_deref(), None)?;
+
+    conn.transaction::<_, Box<dyn Error>, _>(|conn| {
+        diesel::insert_into(products::table)
+            .values(&new_product)
+            .execute(conn)?;
+
+        let created_product = products::table
+            .order(products::id.desc())
+            .first::<ProductRow>(conn)?;
 
-    Ok(new_products)
+        product_barcode_repository::replace_product_barcodes(conn, created_product.id, barcodes)?;
+
+        let product_item: Product = created_product.try_into()?;
+
+        let after = audit_snapshot(conn, &product_item)?;
+        audit_repository::record_audit(conn, AuditEntity::Product, product_item.id, AuditAction::Create, &[], &after)?;
+
+        Ok(product_item)
+    })
 }
 
-pub fn edit_product(conn: &mut SqliteConnection, product: EditProductRow) -> Result<Product, Box<dyn Error>> {
+pub fn create_products(conn: &mut SqliteConnection, mut new_products: Vec<NewProductRow>) -> Result<Vec<Product>, Box<dyn Error>> {
+    let units = unit_repository::list_units(conn, "")?;
+
+    for product in &mut new_products {
+        product.sku = normalize_sku(product.sku.take());
+
+        // Imported products only carry a unit symbol; link them to the matching unit.
+        if product.unit_id.is_none() {
+            product.unit_id = units.iter()
+                .find(|unit| product.unity.as_deref() == Some(unit.symbol.as_str()))
+                .map(|unit| unit.id);
+        }
+    }
+
+    let mut skus = HashSet::new();
+
+    for sku in new_products.iter().filter_map(|product| product.sku.as_deref()) {
+        if !skus.insert(sku) {
+            return Err(format!("SKU {} appears more than once", sku).into());
+        }
+
+        check_sku(conn, Some(sku), None)?;
+    }
+
+    conn.transaction::<_, Box<dyn Error>, _>(|conn| {
+        diesel::insert_into(products::table)
+            .values(&new_products)
+            .execute(conn)?;
+
+        let created_products: Vec<ProductRow> = products::table
+            .order(products::id.desc())
+            .limit(new_products.len() as i64)
+            .load(conn)?;
+
+        let new_products: Vec<Product> = created_products.into_iter()
+            .rev()
+            .map(|p| p.try_into())
+            .collect::<Result<Vec<_>, _>>()?;
+
+        for product in &new_products {
+            let after = audit_snapshot(conn, product)?;
+            audit_repository::record_audit(conn, AuditEntity::Product, product.id, AuditAction::Create, &[], &after)?;
+        }
+
+        Ok(new_products)
+    })
+}
+
+pub fn edit_product(conn: &mut SqliteConnection, mut product: EditProductRow, barcodes: &[String]) -> Result<Product, Box<dyn Error>> {
     let product_id = product.id;
 
-    diesel::update(products::table.find(product_id))
-        .set((
-            &product,
-            products::updated_at.eq(Utc::now().format(NAIVE_DATE_TIME_PATTERN).to_string())
-        ))
-        .execute(conn)
-        .expect("Failed to update product");
-
-    
-    let updated_product = products::table
-        .filter(products::id.eq(product_id))
-        .first::<ProductRow>(conn)
-        .expect("Failed to retrieve updated product");
+    apply_unit_symbol(conn, product.unit_id, &mut product.unity)?;
 
-    let product_item = updated_product.try_into()?;
+    product.sku = product.sku.map(normalize_sku);
 
-    Ok(product_item)
+    if let Some(sku) = &product.sku {
+        check_sku(conn, sku.as_deref(), Some(product_id))?;
+    }
+
+    conn.transaction::<_, Box<dyn Error>, _>(|conn| {
+        let product_before = get_product_by_id(conn, product_id)?;
+        let before = audit_snapshot(conn, &product_before)?;
+
+        // Movements are recorded in the product's unit, so switching units would
+        // silently reinterpret every quantity already on the ledger. Linking an
+        // unlinked product to the unit of its current symbol changes nothing.
+        let unit_changed = product.unit_id.is_some()
+            && product.unit_id != product_before.unit_id
+            && (product_before.unit_id.is_some() || product.unity != product_before.unity);
+
+        if unit_changed {
+            let movements: i64 = inventory_transactions::table
+                .filter(inventory_transactions::product_id.eq(product_id))
+                .count()
+                .get_result(conn)?;
+
+            if movements > 0 {
+                return Err(format!("'{}' already has stock movements, so its unit cannot be changed", product_before.name).into());
+            }
+        }
+
+        diesel::update(products::table.find(product_id))
+            .set((
+                &product,
+                products::updated_at.eq(Utc::now().format(NAIVE_DATE_TIME_PATTERN).to_string())
+            ))
+            .execute(conn)?;
+
+        product_barcode_repository::replace_product_barcodes(conn, product_id, barcodes)?;
+
+        let updated_product = products::table
+            .filter(products::id.eq(product_id))
+            .first::<ProductRow>(conn)?;
+
+        let product_item: Product = updated_product.try_into()?;
+
+        let after = audit_snapshot(conn, &product_item)?;
+        audit_repository::record_audit(conn, AuditEntity::Product, product_id, AuditAction::Edit, &before, &after)?;
+
+        Ok(product_item)
+    })
 }
 
 pub fn delete_product(conn: &mut SqliteConnection, product_id: i32) -> Result<bool, Box<dyn Error>> {
-    let deleted = diesel::update(products::table.find(product_id))
-        .set(products::deleted_at.eq(Some(Utc::now().naive_utc().format(NAIVE_DATE_TIME_PATTERN).to_string())))
-        .execute(conn)?;
+    conn.transaction::<_, Box<dyn Error>, _>(|conn| {
+        let product_before = get_product_by_id(conn, product_id)?;
+        let before = audit_snapshot(conn, &product_before)?;
 
-    Ok(deleted > 0)
-}
\ No newline at end of file
+        let deleted_at = Utc::now().naive_utc().format(NAIVE_DATE_TIME_PATTERN).to_string();
+
+        let deleted = diesel::update(products::table.find(product_id))
+            .set(products::deleted_at.eq(Some(&deleted_at)))
+            .execute(conn)?;
+
+        // Frees the barcodes for other products.
+        diesel::update(product_barcodes::table.filter(product_barcodes::product_id.eq(product_id)))
+            .set(product_barcodes::deleted_at.eq(Some(&deleted_at)))
+            .execute(conn)?;
+
+        audit_repository::record_audit(conn, AuditEntity::Product, product_id, AuditAction::Delete, &before, &[])?;
+
+        Ok(deleted > 0)
+    })
+}
+
+/// Deleted products, most recently deleted first.
+pub fn list_deleted_products(conn: &mut SqliteConnection, search: &str) -> Result<Vec<Product>, Box<dyn Error>> {
+    let search_like = format!("%{}%", search);
+
+    let product_list: Vec<ProductRow> = products::table
+        .filter(products::deleted_at.is_not_null())
+        .filter(products::name.like(&search_like).or(products::sku.like(&search_like)))
+        .order(products::deleted_at.desc())
+        .load(conn)?;
+
+    let prods = product_list.into_iter()
+        .map(|product| product.try_into())
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(prods)
+}
+
+/// Brings a deleted product back, unless its SKU or one of its barcodes has
+/// been given to another product in the meantime.
+pub fn restore_product(conn: &mut SqliteConnection, product_id: i32) -> Result<Product, Box<dyn Error>> {
+    conn.transaction::<_, Box<dyn Error>, _>(|conn| {
+        let product = get_product_by_id(conn, product_id)?;
+
+        if product.deleted_at.is_none() {
+            return Err(format!("'{}' is not deleted", product.name).into());
+        }
+
+        check_sku(conn, product.sku.as_deref(), Some(product_id))?;
+
+        for barcode in product_barcode_repository::list_product_barcodes(conn, product_id)? {
+            if let Some(owner_name) = product_barcode_repository::find_barcode_owner(conn, &barcode.barcode, product_id)? {
+                return Err(format!("Barcode {} now belongs to '{}'", barcode.barcode, owner_name).into());
+            }
+        }
+
+        diesel::update(products::table.find(product_id))
+            .set((
+                products::deleted_at.eq(None::<String>),
+                products::updated_at.eq(Utc::now().format(NAIVE_DATE_TIME_PATTERN).to_string())
+            ))
+            .execute(conn)?;
+
+        diesel::update(product_barcodes::table.filter(product_barcodes::product_id.eq(product_id)))
+            .set(product_barcodes::deleted_at.eq(None::<String>))
+            .execute(conn)?;
+
+        audit_repository::record_audit(conn, AuditEntity::Product, product_id, AuditAction::Restore, &[], &[])?;
+
+        get_product_by_id(conn, product_id)
+    })
+}
+
+/// Permanently removes a deleted product with its barcodes, supplier links and
+/// its own bill of materials. Products with stock movements, on any document or
+/// used as a kit component are kept, since those still point at them.
+pub fn purge_product(conn: &mut SqliteConnection, product_id: i32) -> Result<bool, Box<dyn Error>> {
+    conn.transaction::<_, Box<dyn Error>, _>(|conn| {
+        let product = get_product_by_id(conn, product_id)?;
+
+        if product.deleted_at.is_none() {
+            return Err(format!("Delete '{}' before purging it", product.name).into());
+        }
+
+        let references: i64 = [
+            inventory_transactions::table.filter(inventory_transactions::product_id.eq(product_id)).count().get_result::<i64>(conn)?,
+            stock_transfers::table.filter(stock_transfers::product_id.eq(product_id)).count().get_result::<i64>(conn)?,
+            purchase_order_lines::table.filter(purchase_order_lines::product_id.eq(product_id)).count().get_result::<i64>(conn)?,
+            issue_lines::table.filter(issue_lines::product_id.eq(product_id)).count().get_result::<i64>(conn)?,
+            stocktake_lines::table.filter(stocktake_lines::product_id.eq(product_id)).count().get_result::<i64>(conn)?,
+            reservations::table.filter(reservations::product_id.eq(product_id)).count().get_result::<i64>(conn)?,
+            serial_numbers::table.filter(serial_numbers::product_id.eq(product_id)).count().get_result::<i64>(conn)?,
+        ].iter().sum();
+
+        if references > 0 {
+            return Err(format!("'{}' has stock movements or documents and cannot be purged", product.name).into());
+        }
+
+        // Purging a component would silently change the bill of materials of
+        // every kit that uses it, so it has to be taken out of them first.
+        let used_in_kit: Option<String> = kit_components::table
+            .inner_join(products::table.on(products::id.eq(kit_components::kit_product_id)))
+            .filter(kit_components::component_product_id.eq(product_id))
+            .select(products::name)
+            .first(conn)
+            .optional()?;
+
+        if let Some(kit_name) = used_in_kit {
+            return Err(format!("'{}' is a component of kit '{}' and cannot be purged", product.name, kit_name).into());
+        }
+
+        diesel::delete(product_barcodes::table.filter(product_barcodes::product_id.eq(product_id))).execute(conn)?;
+        diesel::delete(product_suppliers::table.filter(product_suppliers::product_id.eq(product_id))).execute(conn)?;
+        diesel::delete(kit_components::table.filter(kit_components::kit_product_id.eq(product_id))).execute(conn)?;
+
+        let purged = diesel::delete(products::table.find(product_id)).execute(conn)?;
+
+        audit_repository::record_audit(conn, AuditEntity::Product, product_id, AuditAction::Purge, &[], &[])?;
+
+        Ok(purged > 0)
+    })
+}
+
+/// Audited fields of a product, with its barcodes.
+fn audit_snapshot(conn: &mut SqliteConnection, product: &Product) -> Result<Vec<AuditField>, Box<dyn Error>> {
+    let barcodes = product_barcode_repository::list_product_barcodes(conn, product.id)?
+        .into_iter()
+        .map(|barcode| barcode.barcode)
+        .collect::<Vec<_>>();
+
+    let mut fields = product.audit_fields();
+    fields.push(("barcodes", Some(barcodes.join(", ")).filter(|barcodes| !barcodes.is_empty())));
+
+    Ok(fields)
+}
+
+/// Blank SKUs are stored as no SKU.
+fn normalize_sku(sku: Option<String>) -> Option<String> {
+    sku.map(|sku| sku.trim().to_owned()).filter(|sku| !sku.is_empty())
+}
+
+/// SKUs are unique among products that are not deleted.
+fn check_sku(conn: &mut SqliteConnection, sku: Option<&str>, except_product_id: Option<i32>) -> Result<(), Box<dyn Error>> {
+    let Some(sku) = sku else {
+        return Ok(());
+    };
+
+    let mut query = products::table
+        .filter(products::sku.eq(sku))
+        .filter(products::deleted_at.is_null())
+        .select(products::name)
+        .into_boxed();
+
+    if let Some(product_id) = except_product_id {
+        query = query.filter(products::id.ne(product_id));
+    }
+
+    if let Some(owner_name) = query.first::<String>(conn).optional()? {
+        return Err(format!("SKU {} already belongs to '{}'", sku, owner_name).into());
+    }
+
+    Ok(())
+}
+
+/// `unity` caches the symbol of the product's unit for lists and exports.
+fn apply_unit_symbol(conn: &mut SqliteConnection, unit_id: Option<i32>, unity: &mut Option<String>) -> Result<(), Box<dyn Error>> {
+    if let Some(unit_id) = unit_id {
+        *unity = Some(unit_repository::get_unit_by_id(conn, unit_id)?.symbol);
+    }
+
+    Ok(())
+}
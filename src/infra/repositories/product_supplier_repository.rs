@@ -0,0 +1,187 @@
+use diesel::prelude::*;
+use std::error::Error;
+
+use crate::infra::models::{ProductSupplierRow, NewProductSupplierRow, EditProductSupplierRow};
+use crate::infra::repositories::audit_repository;
+use crate::infra::schema::{product_suppliers, suppliers};
+use crate::domain::audit::{AuditAction, AuditEntity, AuditField};
+use crate::domain::product_supplier::ProductSupplier;
+
+use chrono::Utc;
+
+const NAIVE_DATE_TIME_PATTERN: &str =  "%Y-%m-%d %H:%M:%S";
+
+#[derive(Debug, Default, Clone)]
+pub struct ProductSupplierFilter {
+    pub product_id: Option<i32>,
+    pub supplier_id: Option<i32>,
+}
+
+pub fn list_product_suppliers(conn: &mut SqliteConnection, filter: &ProductSupplierFilter) -> Result<Vec<ProductSupplier>, Box<dyn Error>> {
+    let mut links_query = product_suppliers::table
+        .order(product_suppliers::id)
+        .into_boxed();
+
+    if let Some(product_id) = filter.product_id {
+        links_query = links_query.filter(product_suppliers::product_id.eq(product_id));
+    }
+
+    if let Some(supplier_id) = filter.supplier_id {
+        links_query = links_query.filter(product_suppliers::supplier_id.eq(supplier_id));
+    }
+
+    let link_list: Vec<ProductSupplierRow> = links_query.load(conn)?;
+
+    let links = link_list.into_iter()
+        .map(|link| link.try_into())
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(links)
+}
+
+pub fn create_product_supplier(conn: &mut SqliteConnection, new_link: NewProductSupplierRow) -> Result<ProductSupplier, Box<dyn Error>> {
+    conn.transaction::<_, Box<dyn Error>, _>(|conn| {
+        let before = audit_links(conn, new_link.product_id)?;
+
+        diesel::insert_into(product_suppliers::table)
+            .values(&new_link)
+            .execute(conn)?;
+
+        let created_link = product_suppliers::table
+            .order(product_suppliers::id.desc())
+            .first::<ProductSupplierRow>(conn)?;
+
+        if created_link.preferred {
+            clear_other_preferred(conn, created_link.product_id, created_link.id)?;
+        }
+
+        let link_item: ProductSupplier = created_link.try_into()?;
+
+        record_links_audit(conn, link_item.product_id, &before)?;
+
+        Ok(link_item)
+    })
+}
+
+pub fn edit_product_supplier(conn: &mut SqliteConnection, link: EditProductSupplierRow) -> Result<ProductSupplier, Box<dyn Error>> {
+    let link_id = link.id;
+
+    conn.transaction::<_, Box<dyn Error>, _>(|conn| {
+        let product_id = get_product_id(conn, link_id)?;
+        let before = audit_links(conn, product_id)?;
+
+        diesel::update(product_suppliers::table.find(link_id))
+            .set((
+                &link,
+                product_suppliers::updated_at.eq(Utc::now().format(NAIVE_DATE_TIME_PATTERN).to_string())
+            ))
+            .execute(conn)?;
+
+        let updated_link = product_suppliers::table
+            .find(link_id)
+            .first::<ProductSupplierRow>(conn)?;
+
+        if updated_link.preferred {
+            clear_other_preferred(conn, updated_link.product_id, updated_link.id)?;
+        }
+
+        let link_item = updated_link.try_into()?;
+
+        record_links_audit(conn, product_id, &before)?;
+
+        Ok(link_item)
+    })
+}
+
+/// Links carry no history of their own, so removing one deletes the row; the
+/// product's audit log keeps what it was.
+pub fn delete_product_supplier(conn: &mut SqliteConnection, link_id: i32) -> Result<bool, Box<dyn Error>> {
+    conn.transaction::<_, Box<dyn Error>, _>(|conn| {
+        let product_id = get_product_id(conn, link_id)?;
+        let before = audit_links(conn, product_id)?;
+
+        let deleted = diesel::delete(product_suppliers::table.find(link_id))
+            .execute(conn)?;
+
+        record_links_audit(conn, product_id, &before)?;
+
+        Ok(deleted > 0)
+    })
+}
+
+/// Removes every link of a supplier that is being purged, recording the change
+/// on each product that loses it.
+pub fn delete_supplier_links(conn: &mut SqliteConnection, supplier_id: i32) -> Result<(), Box<dyn Error>> {
+    let product_ids: Vec<i32> = product_suppliers::table
+        .filter(product_suppliers::supplier_id.eq(supplier_id))
+        .select(product_suppliers::product_id)
+        .load(conn)?;
+
+    for product_id in product_ids {
+        let before = audit_links(conn, product_id)?;
+
+        diesel::delete(
+            product_suppliers::table
+                .filter(product_suppliers::product_id.eq(product_id))
+                .filter(product_suppliers::supplier_id.eq(supplier_id))
+        )
+            .execute(conn)?;
+
+        record_links_audit(conn, product_id, &before)?;
+    }
+
+    Ok(())
+}
+
+/// A product has at most one preferred supplier, so marking a link as preferred
+/// unmarks the product's other links.
+fn clear_other_preferred(conn: &mut SqliteConnection, product_id: i32, preferred_link_id: i32) -> Result<(), Box<dyn Error>> {
+    diesel::update(
+        product_suppliers::table
+            .filter(product_suppliers::product_id.eq(product_id))
+            .filter(product_suppliers::id.ne(preferred_link_id))
+            .filter(product_suppliers::preferred.eq(true))
+    )
+        .set((
+            product_suppliers::preferred.eq(false),
+            product_suppliers::updated_at.eq(Utc::now().format(NAIVE_DATE_TIME_PATTERN).to_string())
+        ))
+        .execute(conn)?;
+
+    Ok(())
+}
+
+fn get_product_id(conn: &mut SqliteConnection, link_id: i32) -> Result<i32, Box<dyn Error>> {
+    Ok(product_suppliers::table
+        .find(link_id)
+        .select(product_suppliers::product_id)
+        .first(conn)?)
+}
+
+/// A product's supplier links as one audited field, so adding, changing or
+/// removing any of them shows in the product's history.
+fn audit_links(conn: &mut SqliteConnection, product_id: i32) -> Result<Vec<AuditField>, Box<dyn Error>> {
+    let links = list_product_suppliers(conn, &ProductSupplierFilter {
+        product_id: Some(product_id),
+        ..Default::default()
+    })?;
+
+    let mut summaries = Vec::with_capacity(links.len());
+
+    for link in links {
+        let supplier_name: String = suppliers::table
+            .find(link.supplier_id)
+            .select(suppliers::name)
+            .first(conn)?;
+
+        summaries.push(link.audit_summary(&supplier_name));
+    }
+
+    Ok(vec![("suppliers", Some(summaries.join("; ")).filter(|summary| !summary.is_empty()))])
+}
+
+fn record_links_audit(conn: &mut SqliteConnection, product_id: i32, before: &[AuditField]) -> Result<(), Box<dyn Error>> {
+    let after = audit_links(conn, product_id)?;
+
+    audit_repository::record_audit(conn, AuditEntity::Product, product_id, AuditAction::Edit, before, &after)
+}
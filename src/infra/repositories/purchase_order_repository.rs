@@ -0,0 +1,414 @@
+use diesel::prelude::*;
+use std::collections::HashMap;
+use std::error::Error;
+
+use crate::infra::models::{
+    PurchaseOrderRow, NewPurchaseOrderRow, EditPurchaseOrderRow,
+    PurchaseOrderLineRow, NewPurchaseOrderLineRow, EditPurchaseOrderLineRow,
+};
+use crate::infra::repositories::{audit_repository, product_repository, unit_repository};
+use crate::infra::schema::{products, purchase_order_lines, purchase_orders};
+use crate::domain::audit::{AuditAction, AuditEntity, AuditField};
+use crate::domain::purchase_order::{PurchaseOrder, PurchaseOrderLine, PurchaseOrderStatus};
+use crate::domain::unit::Unit;
+
+use chrono::Utc;
+
+const NAIVE_DATE_TIME_PATTERN: &str =  "%Y-%m-%d %H:%M:%S";
+
+#[derive(Debug, Default, Clone)]
+pub struct PurchaseOrderFilter {
+    pub supplier_id: Option<i32>,
+    pub status: Option<PurchaseOrderStatus>,
+}
+
+pub fn list_purchase_orders(conn: &mut SqliteConnection, filter: &PurchaseOrderFilter) -> Result<Vec<PurchaseOrder>, Box<dyn Error>> {
+    let mut orders_query = purchase_orders::table
+        .filter(purchase_orders::deleted_at.is_null())
+        .order(purchase_orders::id.desc())
+        .select(PurchaseOrderRow::as_select())
+        .into_boxed();
+
+    if let Some(supplier_id) = filter.supplier_id {
+        orders_query = orders_query.filter(purchase_orders::supplier_id.eq(supplier_id));
+    }
+
+    if let Some(status) = filter.status {
+        orders_query = orders_query.filter(purchase_orders::status.eq(status.as_str()));
+    }
+
+    let order_list: Vec<PurchaseOrderRow> = orders_query.load(conn)?;
+
+    let orders = order_list.into_iter()
+        .map(|order| order.try_into())
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(orders)
+}
+
+pub fn get_purchase_order(conn: &mut SqliteConnection, order_id: i32) -> Result<PurchaseOrder, Box<dyn Error>> {
+    let order = purchase_orders::table
+        .find(order_id)
+        .select(PurchaseOrderRow::as_select())
+        .first(conn)?;
+
+    order.try_into()
+}
+
+pub fn create_purchase_order(conn: &mut SqliteConnection, new_order: NewPurchaseOrderRow) -> Result<PurchaseOrder, Box<dyn Error>> {
+    conn.transaction::<_, Box<dyn Error>, _>(|conn| {
+        diesel::insert_into(purchase_orders::table)
+            .values(&new_order)
+            .execute(conn)?;
+
+        let created_order: PurchaseOrder = purchase_orders::table
+            .order(purchase_orders::id.desc())
+            .select(PurchaseOrderRow::as_select())
+            .first(conn)?
+            .try_into()?;
+
+        audit_repository::record_audit(conn, AuditEntity::PurchaseOrder, created_order.id, AuditAction::Create, &[], &created_order.audit_fields())?;
+
+        Ok(created_order)
+    })
+}
+
+pub fn edit_purchase_order(conn: &mut SqliteConnection, order: EditPurchaseOrderRow) -> Result<PurchaseOrder, Box<dyn Error>> {
+    conn.transaction::<_, Box<dyn Error>, _>(|conn| {
+        let before = ensure_editable(conn, order.id)?.audit_fields();
+
+        diesel::update(purchase_orders::table.find(order.id))
+            .set((
+                &order,
+                purchase_orders::updated_at.eq(Utc::now().format(NAIVE_DATE_TIME_PATTERN).to_string())
+            ))
+            .execute(conn)?;
+
+        let order_item = get_purchase_order(conn, order.id)?;
+
+        audit_repository::record_audit(conn, AuditEntity::PurchaseOrder, order_item.id, AuditAction::Edit, &before, &order_item.audit_fields())?;
+
+        Ok(order_item)
+    })
+}
+
+/// Moves the order to `status`, refusing transitions the lifecycle does not allow.
+pub fn update_purchase_order_status(conn: &mut SqliteConnection, order_id: i32, status: PurchaseOrderStatus) -> Result<PurchaseOrder, Box<dyn Error>> {
+    conn.transaction::<_, Box<dyn Error>, _>(|conn| {
+        let order = get_purchase_order(conn, order_id)?;
+
+        if order.deleted_at.is_some() {
+            return Err(format!("Purchase order #{} was deleted and can no longer be changed", order.id).into());
+        }
+
+        if !order.status.can_transition_to(status) {
+            return Err(format!(
+                "A purchase order cannot go from '{}' to '{}'",
+                order.status.label(), status.label()
+            ).into());
+        }
+
+        if status == PurchaseOrderStatus::Sent {
+            let line_count: i64 = purchase_order_lines::table
+                .filter(purchase_order_lines::purchase_order_id.eq(order_id))
+                .count()
+                .get_result(conn)?;
+
+            if line_count == 0 {
+                return Err("Add at least one line before sending the order".into());
+            }
+        }
+
+        diesel::update(purchase_orders::table.find(order_id))
+            .set((
+                purchase_orders::status.eq(status.as_str()),
+                purchase_orders::updated_at.eq(Utc::now().format(NAIVE_DATE_TIME_PATTERN).to_string())
+            ))
+            .execute(conn)?;
+
+        let order_item = get_purchase_order(conn, order_id)?;
+
+        audit_repository::record_audit(conn, AuditEntity::PurchaseOrder, order_id, AuditAction::Edit, &order.audit_fields(), &order_item.audit_fields())?;
+
+        Ok(order_item)
+    })
+}
+
+pub fn delete_purchase_order(conn: &mut SqliteConnection, order_id: i32) -> Result<bool, Box<dyn Error>> {
+    conn.transaction::<_, Box<dyn Error>, _>(|conn| {
+        let mut before = ensure_editable(conn, order_id)?.audit_fields();
+        before.extend(audit_lines(conn, order_id)?);
+
+        let deleted = diesel::update(purchase_orders::table.find(order_id))
+            .set(purchase_orders::deleted_at.eq(Some(Utc::now().naive_utc().format(NAIVE_DATE_TIME_PATTERN).to_string())))
+            .execute(conn)?;
+
+        audit_repository::record_audit(conn, AuditEntity::PurchaseOrder, order_id, AuditAction::Delete, &before, &[])?;
+
+        Ok(deleted > 0)
+    })
+}
+
+pub fn list_purchase_order_lines(conn: &mut SqliteConnection, order_id: i32) -> Result<Vec<PurchaseOrderLine>, Box<dyn Error>> {
+    let line_list: Vec<PurchaseOrderLineRow> = purchase_order_lines::table
+        .filter(purchase_order_lines::purchase_order_id.eq(order_id))
+        .order(purchase_order_lines::id)
+        .select(PurchaseOrderLineRow::as_select())
+        .load(conn)?;
+
+    Ok(line_list.into_iter().map(PurchaseOrderLine::from).collect())
+}
+
+/// Outstanding quantity per product across every open, non-deleted order.
+pub fn list_on_order_quantities(conn: &mut SqliteConnection) -> Result<HashMap<i32, i64>, Box<dyn Error>> {
+    let open_statuses: Vec<&str> = PurchaseOrderStatus::ALL.iter()
+        .filter(|status| status.is_open())
+        .map(|status| status.as_str())
+        .collect();
+
+    let line_list: Vec<PurchaseOrderLineRow> = purchase_order_lines::table
+        .inner_join(purchase_orders::table)
+        .filter(purchase_orders::deleted_at.is_null())
+        .filter(purchase_orders::status.eq_any(open_statuses))
+        .select(PurchaseOrderLineRow::as_select())
+        .load(conn)?;
+
+    let product_units: HashMap<i32, Option<i32>> = products::table
+        .select((products::id, products::unit_id))
+        .load(conn)?
+        .into_iter()
+        .collect();
+
+    let units: HashMap<i32, Unit> = unit_repository::list_units(conn, "")?
+        .into_iter()
+        .map(|unit| (unit.id, unit))
+        .collect();
+
+    let mut on_order = HashMap::new();
+
+    for line in line_list.into_iter().map(PurchaseOrderLine::from) {
+        let product_unit_id = product_units.get(&line.product_id).copied().flatten();
+
+        // Lines ordered in another unit (e.g. boxes) count in the product's unit.
+        let ratio = match (line.unit_id, product_unit_id) {
+            (Some(line_unit_id), Some(product_unit_id)) => units.get(&line_unit_id)
+                .zip(units.get(&product_unit_id))
+                .and_then(|(line_unit, product_unit)| line_unit.ratio_to(product_unit))
+                .unwrap_or(1.0),
+            _ => 1.0,
+        };
+
+        *on_order.entry(line.product_id).or_insert(0) += (f64::from(line.remaining_quantity()) * ratio).ceil() as i64;
+    }
+
+    Ok(on_order)
+}
+
+pub fn create_purchase_order_line(conn: &mut SqliteConnection, new_line: NewPurchaseOrderLineRow) -> Result<PurchaseOrderLine, Box<dyn Error>> {
+    conn.transaction::<_, Box<dyn Error>, _>(|conn| {
+        ensure_editable(conn, new_line.purchase_order_id)?;
+
+        let before = audit_lines(conn, new_line.purchase_order_id)?;
+
+        diesel::insert_into(purchase_order_lines::table)
+            .values(&new_line)
+            .execute(conn)?;
+
+        let created_line = purchase_order_lines::table
+            .order(purchase_order_lines::id.desc())
+            .select(PurchaseOrderLineRow::as_select())
+            .first(conn)?;
+
+        record_lines_audit(conn, new_line.purchase_order_id, &before)?;
+
+        Ok(PurchaseOrderLine::from(created_line))
+    })
+}
+
+pub fn edit_purchase_order_line(conn: &mut SqliteConnection, line: EditPurchaseOrderLineRow) -> Result<PurchaseOrderLine, Box<dyn Error>> {
+    conn.transaction::<_, Box<dyn Error>, _>(|conn| {
+        let order_id: i32 = purchase_order_lines::table
+            .find(line.id)
+            .select(purchase_order_lines::purchase_order_id)
+            .first(conn)?;
+
+        ensure_editable(conn, order_id)?;
+
+        let before = audit_lines(conn, order_id)?;
+
+        diesel::update(purchase_order_lines::table.find(line.id))
+            .set((
+                &line,
+                purchase_order_lines::updated_at.eq(Utc::now().format(NAIVE_DATE_TIME_PATTERN).to_string())
+            ))
+            .execute(conn)?;
+
+        let updated_line = purchase_order_lines::table
+            .find(line.id)
+            .select(PurchaseOrderLineRow::as_select())
+            .first(conn)?;
+
+        record_lines_audit(conn, order_id, &before)?;
+
+        Ok(PurchaseOrderLine::from(updated_line))
+    })
+}
+
+pub fn delete_purchase_order_line(conn: &mut SqliteConnection, line_id: i32) -> Result<bool, Box<dyn Error>> {
+    conn.transaction::<_, Box<dyn Error>, _>(|conn| {
+        let order_id: i32 = purchase_order_lines::table
+            .find(line_id)
+            .select(purchase_order_lines::purchase_order_id)
+            .first(conn)?;
+
+        ensure_editable(conn, order_id)?;
+
+        let before = audit_lines(conn, order_id)?;
+
+        let deleted = diesel::delete(purchase_order_lines::table.find(line_id))
+            .execute(conn)?;
+
+        record_lines_audit(conn, order_id, &before)?;
+
+        Ok(deleted > 0)
+    })
+}
+
+/// Only drafts that were not deleted can be changed.
+fn ensure_editable(conn: &mut SqliteConnection, order_id: i32) -> Result<PurchaseOrder, Box<dyn Error>> {
+    let order = get_purchase_order(conn, order_id)?;
+
+    if order.deleted_at.is_some() {
+        return Err(format!("Purchase order #{} was deleted and can no longer be changed", order.id).into());
+    }
+
+    if !order.status.is_editable() {
+        return Err(format!("Purchase order #{} is {} and can no longer be changed", order.id, order.status.label().to_lowercase()).into());
+    }
+
+    Ok(order)
+}
+
+/// An order's lines as one audited field, e.g. "10 × Screw at 0.50 (4 received),
+/// 2 × Panel", so changes to them and the goods received show in the order's
+/// history.
+pub fn audit_lines(conn: &mut SqliteConnection, order_id: i32) -> Result<Vec<AuditField>, Box<dyn Error>> {
+    let mut summaries = Vec::new();
+
+    for line in list_purchase_order_lines(conn, order_id)? {
+        let product = product_repository::get_product_by_id(conn, line.product_id)?;
+
+        let mut summary = match line.unit_cost {
+            Some(unit_cost) => format!("{} × {} at {:.2}", line.quantity, product.name, unit_cost),
+            None => format!("{} × {}", line.quantity, product.name),
+        };
+
+        if line.received_quantity > 0 {
+            summary.push_str(&format!(" ({} received)", line.received_quantity));
+        }
+
+        summaries.push(summary);
+    }
+
+    Ok(vec![("lines", Some(summaries.join(", ")).filter(|summary| !summary.is_empty()))])
+}
+
+pub fn record_lines_audit(conn: &mut SqliteConnection, order_id: i32, before: &[AuditField]) -> Result<(), Box<dyn Error>> {
+    let after = audit_lines(conn, order_id)?;
+
+    audit_repository::record_audit(conn, AuditEntity::PurchaseOrder, order_id, AuditAction::Edit, before, &after)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infra::db;
+    use crate::infra::models::{NewProductRow, NewSupplierRow};
+    use crate::infra::repositories::supplier_repository;
+
+    fn draft_order_with_line(conn: &mut SqliteConnection) -> (PurchaseOrder, PurchaseOrderLine) {
+        let supplier = supplier_repository::create_supplier(conn, NewSupplierRow {
+            name: "Acme".to_owned(),
+            tax_id: None,
+            contact_name: None,
+            phone: None,
+            mobile_phone: None,
+            email: None,
+            address: None,
+            payment_terms: None,
+            notes: None,
+            active: None,
+        }).unwrap();
+
+        let product = product_repository::create_product(conn, NewProductRow {
+            name: "Screw".to_owned(),
+            unity: Some("un".to_owned()),
+            brand: None,
+            min_stock: None,
+            observation: None,
+            reorder_point: None,
+            safety_stock: None,
+            max_stock: None,
+            tracks_lots: Some(false),
+            serialized: None,
+            unit_id: None,
+            category_id: None,
+            sku: None,
+        }, &[]).unwrap();
+
+        let order = create_purchase_order(conn, NewPurchaseOrderRow {
+            supplier_id: supplier.id,
+            expected_date: None,
+            notes: None,
+        }).unwrap();
+
+        let line = create_purchase_order_line(conn, NewPurchaseOrderLineRow {
+            purchase_order_id: order.id,
+            product_id: product.id,
+            quantity: 10,
+            unit_cost: None,
+            unit_id: None,
+        }).unwrap();
+
+        (order, line)
+    }
+
+    #[test]
+    fn refuses_to_change_a_deleted_order() {
+        let mut conn = db::establish_test_connection();
+        let (order, line) = draft_order_with_line(&mut conn);
+
+        assert!(delete_purchase_order(&mut conn, order.id).unwrap());
+
+        let edited = edit_purchase_order(&mut conn, EditPurchaseOrderRow {
+            id: order.id,
+            supplier_id: order.supplier_id,
+            expected_date: None,
+            notes: Some("Changed".to_owned()),
+        });
+
+        let added_line = create_purchase_order_line(&mut conn, NewPurchaseOrderLineRow {
+            purchase_order_id: order.id,
+            product_id: line.product_id,
+            quantity: 5,
+            unit_cost: None,
+            unit_id: None,
+        });
+
+        let edited_line = edit_purchase_order_line(&mut conn, EditPurchaseOrderLineRow {
+            id: line.id,
+            product_id: line.product_id,
+            quantity: 20,
+            unit_cost: None,
+            unit_id: None,
+        });
+
+        assert!(edited.is_err());
+        assert!(added_line.is_err());
+        assert!(edited_line.is_err());
+        assert!(delete_purchase_order_line(&mut conn, line.id).is_err());
+        assert!(update_purchase_order_status(&mut conn, order.id, PurchaseOrderStatus::Sent).is_err());
+        assert_eq!(get_purchase_order(&mut conn, order.id).unwrap().status, PurchaseOrderStatus::Draft);
+        assert_eq!(list_purchase_order_lines(&mut conn, order.id).unwrap(), vec![line]);
+    }
+}
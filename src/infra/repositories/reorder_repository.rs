@@ -0,0 +1,105 @@
+use diesel::prelude::*;
+use std::collections::HashMap;
+use std::error::Error;
+
+use crate::infra::models::{NewPurchaseOrderLineRow, NewPurchaseOrderRow};
+use crate::infra::repositories::{product_repository, product_supplier_repository, purchase_order_repository, stock_repository, supplier_repository};
+use crate::infra::repositories::product_supplier_repository::ProductSupplierFilter;
+use crate::domain::product_supplier::ProductSupplier;
+use crate::domain::purchase_order::PurchaseOrder;
+use crate::domain::reorder_suggestion::ReorderSuggestion;
+use crate::domain::supplier::Supplier;
+
+/// Products that have fallen to their reorder point, with the quantity needed to
+/// reach max stock. Each product is attributed to its preferred supplier, or to its
+/// only supplier when it has a single link; inactive suppliers are skipped.
+/// Sorted by supplier, then product.
+pub fn list_reorder_suggestions(conn: &mut SqliteConnection) -> Result<Vec<ReorderSuggestion>, Box<dyn Error>> {
+    let on_hand: HashMap<i32, i64> = stock_repository::list_product_stock(conn)?
+        .into_iter()
+        .map(|stock| (stock.product_id, stock.quantity))
+        .collect();
+
+    let on_order = purchase_order_repository::list_on_order_quantities(conn)?;
+    // Inactive suppliers are not ordered from, so their links are ignored.
+    let suppliers: Vec<Supplier> = supplier_repository::list_suppliers(conn, "")?
+        .into_iter()
+        .filter(|supplier| supplier.active)
+        .collect();
+
+    let mut links_by_product: HashMap<i32, Vec<ProductSupplier>> = HashMap::new();
+
+    for link in product_supplier_repository::list_product_suppliers(conn, &ProductSupplierFilter::default())? {
+        if !suppliers.iter().any(|supplier| supplier.id == link.supplier_id) {
+            continue;
+        }
+
+        links_by_product.entry(link.product_id).or_default().push(link);
+    }
+
+    let mut suggestions = Vec::new();
+
+    for product in product_repository::list_products(conn, "")? {
+        let product_on_hand = on_hand.get(&product.id).copied().unwrap_or_default();
+        let product_on_order = on_order.get(&product.id).copied().unwrap_or_default();
+
+        let Some(mut suggested_quantity) = product.reorder_quantity(product_on_hand, product_on_order) else {
+            continue;
+        };
+
+        let link = links_by_product.get(&product.id).and_then(|links| {
+            links.iter()
+                .find(|link| link.preferred)
+                .or_else(|| (links.len() == 1).then(|| &links[0]))
+        });
+
+        if let Some(link) = link {
+            suggested_quantity = suggested_quantity.max(i64::from(link.min_order_quantity));
+        }
+
+        suggestions.push(ReorderSuggestion {
+            supplier: link.and_then(|link| suppliers.iter().find(|supplier| supplier.id == link.supplier_id).cloned()),
+            supplier_sku: link.and_then(|link| link.supplier_sku.clone()),
+            unit_cost: link.and_then(|link| link.last_unit_cost),
+            on_hand: product_on_hand,
+            on_order: product_on_order,
+            suggested_quantity,
+            product,
+        });
+    }
+
+    suggestions.sort_by(|a, b| {
+        a.supplier.is_none().cmp(&b.supplier.is_none())
+            .then_with(|| a.supplier_name().cmp(b.supplier_name()))
+            .then_with(|| a.product.name.cmp(&b.product.name))
+    });
+
+    Ok(suggestions)
+}
+
+/// Creates a draft purchase order for `supplier_id` with one line per suggestion.
+pub fn create_draft_purchase_order(conn: &mut SqliteConnection, supplier_id: i32, suggestions: &[ReorderSuggestion]) -> Result<PurchaseOrder, Box<dyn Error>> {
+    if suggestions.is_empty() {
+        return Err("There is nothing to order from this supplier".into());
+    }
+
+    conn.transaction::<_, Box<dyn Error>, _>(|conn| {
+        let order = purchase_order_repository::create_purchase_order(conn, NewPurchaseOrderRow {
+            supplier_id,
+            expected_date: None,
+            notes: Some("Created from reorder suggestions".to_owned()),
+        })?;
+
+        for suggestion in suggestions {
+            purchase_order_repository::create_purchase_order_line(conn, NewPurchaseOrderLineRow {
+                purchase_order_id: order.id,
+                product_id: suggestion.product.id,
+                quantity: i32::try_from(suggestion.suggested_quantity)?,
+                unit_cost: suggestion.unit_cost,
+                unit_id: suggestion.product.unit_id,
+            })?;
+        }
+
+        Ok(order)
+    })
+}
@@ -0,0 +1,417 @@
+use diesel::prelude::*;
+use std::error::Error;
+
+use crate::infra::models::{NewReservationRow, ReservationRow};
+use crate::infra::repositories::{audit_repository, place_repository, product_repository, stock_repository};
+use crate::infra::repositories::stock_repository::StockBalanceFilter;
+use crate::infra::schema::reservations;
+use crate::domain::audit::{AuditAction, AuditEntity};
+use crate::domain::place::{descendant_place_ids, place_path};
+use crate::domain::reservation::{NAIVE_DATE_PATTERN, Reservation, ReservationStatus};
+use crate::domain::stock_balance::ProductAvailability;
+
+use chrono::{Local, NaiveDate, Utc};
+
+const NAIVE_DATE_TIME_PATTERN: &str =  "%Y-%m-%d %H:%M:%S";
+
+#[derive(Debug, Default, Clone)]
+pub struct ReservationFilter {
+    /// Matches the reference or the notes.
+    pub search: String,
+    pub product_id: Option<i32>,
+    pub status: Option<ReservationStatus>,
+}
+
+pub fn list_reservations(conn: &mut SqliteConnection, filter: &ReservationFilter) -> Result<Vec<Reservation>, Box<dyn Error>> {
+    let mut reservations_query = reservations::table
+        .order(reservations::id.desc())
+        .select(ReservationRow::as_select())
+        .into_boxed();
+
+    if let Some(product_id) = filter.product_id {
+        reservations_query = reservations_query.filter(reservations::product_id.eq(product_id));
+    }
+
+    if let Some(status) = filter.status {
+        reservations_query = reservations_query.filter(reservations::status.eq(status.as_str()));
+    }
+
+    if !filter.search.is_empty() {
+        let search_like = format!("%{}%", filter.search);
+
+        reservations_query = reservations_query.filter(
+            reservations::reference.like(search_like.clone())
+                .or(reservations::notes.like(search_like))
+        );
+    }
+
+    let reservation_list: Vec<ReservationRow> = reservations_query.load(conn)?;
+
+    let reservation_items = reservation_list.into_iter()
+        .map(|reservation| reservation.try_into())
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(reservation_items)
+}
+
+pub fn get_reservation(conn: &mut SqliteConnection, reservation_id: i32) -> Result<Reservation, Box<dyn Error>> {
+    let reservation = reservations::table
+        .find(reservation_id)
+        .select(ReservationRow::as_select())
+        .first(conn)?;
+
+    reservation.try_into()
+}
+
+/// Holds units of a product for a job or customer. Only the quantity still
+/// available to promise can be reserved, so the same units are never promised
+/// twice.
+pub fn create_reservation(conn: &mut SqliteConnection, mut new_reservation: NewReservationRow) -> Result<Reservation, Box<dyn Error>> {
+    conn.transaction::<_, Box<dyn Error>, _>(|conn| {
+        expire_reservations(conn)?;
+
+        new_reservation.reference = new_reservation.reference.trim().to_owned();
+
+        if new_reservation.reference.is_empty() {
+            return Err("Enter the job or customer the units are reserved for".into());
+        }
+
+        if new_reservation.quantity <= 0 {
+            return Err("The reserved quantity must be greater than zero".into());
+        }
+
+        if let Some(expires_on) = &new_reservation.expires_on
+            && NaiveDate::parse_from_str(expires_on, NAIVE_DATE_PATTERN)? < Local::now().date_naive() {
+            return Err("The expiry date is already in the past".into());
+        }
+
+        let product = product_repository::get_product_by_id(conn, new_reservation.product_id)?;
+        let available = get_available_quantity(conn, new_reservation.product_id, new_reservation.place_id)?;
+
+        if i64::from(new_reservation.quantity) > available {
+            let place_text = match new_reservation.place_id {
+                Some(place_id) => {
+                    let places = place_repository::list_places(conn, "")?;
+                    format!(" at {}", place_path(&places, place_id))
+                }
+                None => String::new(),
+            };
+
+            return Err(format!(
+                "Only {} of '{}' available to promise{}, {} requested",
+                available.max(0), product.name, place_text, new_reservation.quantity
+            ).into());
+        }
+
+        diesel::insert_into(reservations::table)
+            .values(&new_reservation)
+            .execute(conn)?;
+
+        let created_reservation: Reservation = reservations::table
+            .order(reservations::id.desc())
+            .select(ReservationRow::as_select())
+            .first(conn)?
+            .try_into()?;
+
+        audit_repository::record_audit(conn, AuditEntity::Reservation, created_reservation.id, AuditAction::Create, &[], &created_reservation.audit_fields())?;
+
+        Ok(created_reservation)
+    })
+}
+
+/// Gives the units still held back to the available quantity.
+pub fn release_reservation(conn: &mut SqliteConnection, reservation_id: i32) -> Result<Reservation, Box<dyn Error>> {
+    conn.transaction::<_, Box<dyn Error>, _>(|conn| {
+        let reservation = ensure_active(conn, reservation_id)?;
+
+        set_status(conn, &reservation, ReservationStatus::Released)
+    })
+}
+
+/// Books `quantity` units issued from `place_id` against the reservation, which
+/// becomes consumed once every reserved unit is issued.
+pub fn consume_reservation(conn: &mut SqliteConnection, reservation_id: i32, product_id: i32, place_id: i32, quantity: i32) -> Result<Reservation, Box<dyn Error>> {
+    conn.transaction::<_, Box<dyn Error>, _>(|conn| {
+        let reservation = ensure_active(conn, reservation_id)?;
+
+        if reservation.product_id != product_id {
+            return Err(format!("Reservation #{} is for another product", reservation.id).into());
+        }
+
+        if let Some(reserved_place_id) = reservation.place_id {
+            let places = place_repository::list_places(conn, "")?;
+
+            if !descendant_place_ids(&places, reserved_place_id).contains(&place_id) {
+                return Err(format!("Reservation #{} holds the units at {}", reservation.id, place_path(&places, reserved_place_id)).into());
+            }
+        }
+
+        if quantity > reservation.remaining() {
+            return Err(format!("Reservation #{} only has {} units left, {} issued", reservation.id, reservation.remaining(), quantity).into());
+        }
+
+        let consumed_quantity = reservation.consumed_quantity + quantity;
+        let status = if consumed_quantity >= reservation.quantity {
+            ReservationStatus::Consumed
+        } else {
+            ReservationStatus::Active
+        };
+
+        diesel::update(reservations::table.find(reservation.id))
+            .set((
+                reservations::consumed_quantity.eq(consumed_quantity),
+                reservations::status.eq(status.as_str()),
+                reservations::updated_at.eq(Utc::now().format(NAIVE_DATE_TIME_PATTERN).to_string())
+            ))
+            .execute(conn)?;
+
+        let reservation_item = get_reservation(conn, reservation_id)?;
+
+        audit_repository::record_audit(conn, AuditEntity::Reservation, reservation_id, AuditAction::Edit, &reservation.audit_fields(), &reservation_item.audit_fields())?;
+
+        Ok(reservation_item)
+    })
+}
+
+/// On hand, reserved and available quantity of every product with stock or
+/// open reservations.
+pub fn list_product_availability(conn: &mut SqliteConnection) -> Result<Vec<ProductAvailability>, Box<dyn Error>> {
+    let mut availability: Vec<ProductAvailability> = stock_repository::list_product_stock(conn)?
+        .into_iter()
+        .map(|stock| ProductAvailability { product_id: stock.product_id, on_hand: stock.quantity, reserved: 0 })
+        .collect();
+
+    for reservation in list_open_reservations(conn, None)? {
+        match availability.iter_mut().find(|item| item.product_id == reservation.product_id) {
+            Some(item) => item.reserved += i64::from(reservation.remaining()),
+            None => availability.push(ProductAvailability {
+                product_id: reservation.product_id,
+                on_hand: 0,
+                reserved: i64::from(reservation.remaining()),
+            }),
+        }
+    }
+
+    Ok(availability)
+}
+
+/// Quantity of a product that can still be promised, at a place and the places
+/// inside it when given. Units reserved without a place count against every
+/// place, so a place never offers more than the product as a whole. Units
+/// reserved at a place that contains this one count against it as well: a bin
+/// never offers the units its warehouse still has to hold back.
+pub fn get_available_quantity(conn: &mut SqliteConnection, product_id: i32, place_id: Option<i32>) -> Result<i64, Box<dyn Error>> {
+    let open_reservations = list_open_reservations(conn, Some(product_id))?;
+
+    let on_hand: i64 = stock_repository::list_stock_balances(conn, &StockBalanceFilter {
+        product_id: Some(product_id),
+        ..Default::default()
+    })?.iter().map(|balance| balance.quantity).sum();
+
+    let reserved: i64 = open_reservations.iter().map(|reservation| i64::from(reservation.remaining())).sum();
+
+    let Some(place_id) = place_id else {
+        return Ok(on_hand - reserved);
+    };
+
+    let places = place_repository::list_places(conn, "")?;
+    let mut available = on_hand - reserved;
+    let mut current_id = Some(place_id);
+    let mut checked_ids = Vec::new();
+
+    // Walks up to the warehouse; the check on visited places guards against a
+    // tree corrupted into a cycle.
+    while let Some(checked_id) = current_id
+        && !checked_ids.contains(&checked_id) {
+        let place_ids = descendant_place_ids(&places, checked_id);
+
+        let on_hand_at_place: i64 = stock_repository::list_stock_balances(conn, &StockBalanceFilter {
+            product_id: Some(product_id),
+            place_ids: Some(place_ids.clone()),
+            ..Default::default()
+        })?.iter().map(|balance| balance.quantity).sum();
+
+        let reserved_at_place: i64 = open_reservations.iter()
+            .filter(|reservation| reservation.place_id.is_some_and(|reserved_place_id| place_ids.contains(&reserved_place_id)))
+            .map(|reservation| i64::from(reservation.remaining()))
+            .sum();
+
+        available = available.min(on_hand_at_place - reserved_at_place);
+
+        checked_ids.push(checked_id);
+        current_id = places.iter()
+            .find(|place| place.id == checked_id)
+            .and_then(|place| place.parent_id);
+    }
+
+    Ok(available)
+}
+
+/// Active reservations that have not passed their expiry date, whether or not
+/// `expire_reservations` has run since.
+fn list_open_reservations(conn: &mut SqliteConnection, product_id: Option<i32>) -> Result<Vec<Reservation>, Box<dyn Error>> {
+    let today = Local::now().date_naive();
+
+    let open_reservations = list_reservations(conn, &ReservationFilter {
+        product_id,
+        status: Some(ReservationStatus::Active),
+        ..Default::default()
+    })?
+        .into_iter()
+        .filter(|reservation| reservation.expires_on.is_none_or(|expires_on| expires_on >= today))
+        .collect();
+
+    Ok(open_reservations)
+}
+
+/// Reservations stop holding stock the day after their expiry date. Marks the
+/// ones past it as expired and records the change in their audit log; listing
+/// never does this on its own.
+pub fn expire_reservations(conn: &mut SqliteConnection) -> Result<(), Box<dyn Error>> {
+    let today = Local::now().date_naive().format(NAIVE_DATE_PATTERN).to_string();
+
+    conn.transaction::<_, Box<dyn Error>, _>(|conn| {
+        let expired_list: Vec<ReservationRow> = reservations::table
+            .filter(reservations::status.eq(ReservationStatus::Active.as_str()))
+            .filter(reservations::expires_on.lt(today))
+            .select(ReservationRow::as_select())
+            .load(conn)?;
+
+        for reservation in expired_list {
+            let reservation: Reservation = reservation.try_into()?;
+            set_status(conn, &reservation, ReservationStatus::Expired)?;
+        }
+
+        Ok(())
+    })
+}
+
+/// Moves the reservation to `status` and records the change in its audit log.
+fn set_status(conn: &mut SqliteConnection, reservation: &Reservation, status: ReservationStatus) -> Result<Reservation, Box<dyn Error>> {
+    diesel::update(reservations::table.find(reservation.id))
+        .set((
+            reservations::status.eq(status.as_str()),
+            reservations::updated_at.eq(Utc::now().format(NAIVE_DATE_TIME_PATTERN).to_string())
+        ))
+        .execute(conn)?;
+
+    let reservation_item = get_reservation(conn, reservation.id)?;
+
+    audit_repository::record_audit(conn, AuditEntity::Reservation, reservation.id, AuditAction::Edit, &reservation.audit_fields(), &reservation_item.audit_fields())?;
+
+    Ok(reservation_item)
+}
+
+fn ensure_active(conn: &mut SqliteConnection, reservation_id: i32) -> Result<Reservation, Box<dyn Error>> {
+    expire_reservations(conn)?;
+
+    let reservation = get_reservation(conn, reservation_id)?;
+
+    if reservation.status != ReservationStatus::Active {
+        return Err(format!("Reservation #{} is {} and no longer holds stock", reservation.id, reservation.status.label().to_lowercase()).into());
+    }
+
+    Ok(reservation)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infra::db;
+    use crate::infra::models::{NewInventoryTransactionRow, NewProductRow};
+    use crate::infra::repositories::inventory_transaction_repository;
+    use crate::domain::inventory_transaction::TransactionType;
+
+    fn product(conn: &mut SqliteConnection) -> i32 {
+        product_repository::create_product(conn, NewProductRow {
+            name: "Cable".to_owned(),
+            unity: Some("un".to_owned()),
+            brand: None,
+            min_stock: None,
+            observation: None,
+            reorder_point: None,
+            safety_stock: None,
+            max_stock: None,
+            tracks_lots: Some(false),
+            serialized: None,
+            unit_id: None,
+            category_id: None,
+            sku: None,
+        }, &[]).unwrap().id
+    }
+
+    fn post(conn: &mut SqliteConnection, product_id: i32, place_id: i32, transaction_type: TransactionType, quantity: i32) -> Result<(), Box<dyn Error>> {
+        inventory_transaction_repository::create_inventory_transaction(conn, NewInventoryTransactionRow {
+            product_id,
+            place_id,
+            supplier_id: None,
+            quantity: transaction_type.signed_quantity(quantity),
+            transaction_type: transaction_type.as_str().to_owned(),
+            reason: None,
+            lot_number: None,
+            expiry_date: None,
+            unit_cost: None,
+            reservation_id: None,
+        }, &[]).map(|_| ())
+    }
+
+    #[test]
+    fn keeps_units_reserved_at_a_warehouse_out_of_its_bins() {
+        let mut conn = db::establish_test_connection();
+        let product_id = product(&mut conn);
+        let warehouse_id = place_repository::find_or_create_place_path(&mut conn, "WH1", None).unwrap().unwrap();
+        let bin_id = place_repository::find_or_create_place_path(&mut conn, "WH1/A/01", None).unwrap().unwrap();
+        let other_id = place_repository::find_or_create_place_path(&mut conn, "WH2", None).unwrap().unwrap();
+
+        post(&mut conn, product_id, bin_id, TransactionType::Entry, 10).unwrap();
+        post(&mut conn, product_id, other_id, TransactionType::Entry, 10).unwrap();
+
+        create_reservation(&mut conn, NewReservationRow {
+            product_id,
+            place_id: Some(warehouse_id),
+            quantity: 10,
+            reference: "Job 1".to_owned(),
+            notes: None,
+            expires_on: None,
+        }).unwrap();
+
+        assert_eq!(get_available_quantity(&mut conn, product_id, Some(bin_id)).unwrap(), 0);
+        assert_eq!(get_available_quantity(&mut conn, product_id, Some(other_id)).unwrap(), 10);
+        assert!(post(&mut conn, product_id, bin_id, TransactionType::Exit, 10).is_err());
+        assert_eq!(stock_repository::get_stock_balance(&mut conn, product_id, bin_id).unwrap(), 10);
+    }
+
+    #[test]
+    fn lists_without_expiring_and_ignores_past_reservations_for_availability() {
+        let mut conn = db::establish_test_connection();
+        let product_id = product(&mut conn);
+        let place_id = place_repository::find_or_create_place_path(&mut conn, "WH1", None).unwrap().unwrap();
+        let yesterday = Local::now().date_naive().pred_opt().unwrap();
+
+        post(&mut conn, product_id, place_id, TransactionType::Entry, 10).unwrap();
+
+        diesel::insert_into(reservations::table)
+            .values(&NewReservationRow {
+                product_id,
+                place_id: None,
+                quantity: 10,
+                reference: "Job 1".to_owned(),
+                notes: None,
+                expires_on: Some(yesterday.format(NAIVE_DATE_PATTERN).to_string()),
+            })
+            .execute(&mut conn)
+            .unwrap();
+
+        let active_filter = ReservationFilter {
+            status: Some(ReservationStatus::Active),
+            ..Default::default()
+        };
+
+        assert_eq!(get_available_quantity(&mut conn, product_id, None).unwrap(), 10);
+        assert_eq!(list_reservations(&mut conn, &active_filter).unwrap().len(), 1);
+
+        expire_reservations(&mut conn).unwrap();
+
+        assert!(list_reservations(&mut conn, &active_filter).unwrap().is_empty());
+    }
+}
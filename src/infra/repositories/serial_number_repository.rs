@@ -0,0 +1,125 @@
+use diesel::prelude::*;
+use std::error::Error;
+
+use crate::infra::models::{InventoryTransactionRow, NewSerialNumberMovementRow, NewSerialNumberRow, SerialNumberRow};
+use crate::infra::schema::{inventory_transactions, serial_number_movements, serial_numbers};
+use crate::domain::inventory_transaction::InventoryTransaction;
+use crate::domain::serial_number::SerialNumber;
+
+use chrono::Utc;
+
+const NAIVE_DATE_TIME_PATTERN: &str =  "%Y-%m-%d %H:%M:%S";
+
+#[derive(Debug, Default, Clone)]
+pub struct SerialNumberFilter {
+    pub search: String,
+    pub product_id: Option<i32>,
+    /// Only units currently stored at this place.
+    pub place_id: Option<i32>,
+}
+
+pub fn list_serial_numbers(conn: &mut SqliteConnection, filter: &SerialNumberFilter) -> Result<Vec<SerialNumber>, Box<dyn Error>> {
+    let mut serial_numbers_query = serial_numbers::table
+        .order(serial_numbers::serial_number.asc())
+        .into_boxed();
+
+    if let Some(product_id) = filter.product_id {
+        serial_numbers_query = serial_numbers_query.filter(serial_numbers::product_id.eq(product_id));
+    }
+
+    if let Some(place_id) = filter.place_id {
+        serial_numbers_query = serial_numbers_query.filter(serial_numbers::place_id.eq(place_id));
+    }
+
+    if !filter.search.is_empty() {
+        serial_numbers_query = serial_numbers_query.filter(serial_numbers::serial_number.like(format!("%{}%", filter.search)));
+    }
+
+    let serial_number_list: Vec<SerialNumberRow> = serial_numbers_query.load(conn)?;
+
+    let serial_numbers = serial_number_list.into_iter()
+        .map(SerialNumber::try_from)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(serial_numbers)
+}
+
+/// Every movement of a unit, oldest first: where it was received from, the places
+/// it went through and when it was issued.
+pub fn list_serial_number_history(conn: &mut SqliteConnection, serial_number_id: i32) -> Result<Vec<InventoryTransaction>, Box<dyn Error>> {
+    let transaction_list: Vec<InventoryTransactionRow> = serial_number_movements::table
+        .inner_join(inventory_transactions::table)
+        .filter(serial_number_movements::serial_number_id.eq(serial_number_id))
+        .order((inventory_transactions::created_at.asc(), inventory_transactions::id.asc()))
+        .select(InventoryTransactionRow::as_select())
+        .load(conn)?;
+
+    let transactions = transaction_list.into_iter()
+        .map(|transaction| transaction.try_into())
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(transactions)
+}
+
+/// Moves the given units in or out of stock following the sign of `transaction`
+/// and links them to it. Incoming units must not be in stock already (new serials
+/// are registered); outgoing units must be stored at the transaction's place.
+pub fn record_serial_number_movements(conn: &mut SqliteConnection, transaction: &InventoryTransaction, serial_number_list: &[String]) -> Result<(), Box<dyn Error>> {
+    let incoming = transaction.quantity > 0;
+    let updated_at = Utc::now().format(NAIVE_DATE_TIME_PATTERN).to_string();
+
+    for serial_number in serial_number_list {
+        let existing = serial_numbers::table
+            .filter(serial_numbers::product_id.eq(transaction.product_id))
+            .filter(serial_numbers::serial_number.eq(serial_number))
+            .first::<SerialNumberRow>(conn)
+            .optional()?;
+
+        let serial_number_id = match existing {
+            Some(row) if incoming && row.place_id.is_some() => {
+                return Err(format!("Serial number {} is already in stock", serial_number).into());
+            }
+            Some(row) if !incoming && row.place_id != Some(transaction.place_id) => {
+                return Err(format!("Serial number {} is not in stock at this place", serial_number).into());
+            }
+            Some(row) => {
+                let place_id = if incoming { Some(transaction.place_id) } else { None };
+
+                diesel::update(serial_numbers::table.find(row.id))
+                    .set((
+                        serial_numbers::place_id.eq(place_id),
+                        serial_numbers::updated_at.eq(&updated_at),
+                    ))
+                    .execute(conn)?;
+
+                row.id
+            }
+            None if incoming => {
+                diesel::insert_into(serial_numbers::table)
+                    .values(&NewSerialNumberRow {
+                        product_id: transaction.product_id,
+                        serial_number: serial_number.clone(),
+                        place_id: Some(transaction.place_id),
+                    })
+                    .execute(conn)?;
+
+                serial_numbers::table
+                    .order(serial_numbers::id.desc())
+                    .select(serial_numbers::id)
+                    .first(conn)?
+            }
+            None => {
+                return Err(format!("Serial number {} is not in stock at this place", serial_number).into());
+            }
+        };
+
+        diesel::insert_into(serial_number_movements::table)
+            .values(&NewSerialNumberMovementRow {
+                serial_number_id,
+                inventory_transaction_id: transaction.id,
+            })
+            .execute(conn)?;
+    }
+
+    Ok(())
+}
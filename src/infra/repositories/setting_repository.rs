@@ -0,0 +1,79 @@
+use diesel::prelude::*;
+use std::error::Error;
+
+use crate::infra::models::SettingRow;
+use crate::infra::repositories::audit_repository;
+use crate::infra::schema::settings;
+use crate::domain::audit::{AuditAction, AuditEntity};
+use crate::domain::valuation::CostingMethod;
+
+use chrono::Utc;
+
+const NAIVE_DATE_TIME_PATTERN: &str =  "%Y-%m-%d %H:%M:%S";
+
+pub const COSTING_METHOD: &str = "costing_method";
+pub const TRASH_RETENTION_DAYS: &str = "trash_retention_days";
+
+/// Longest retention accepted, about a hundred years.
+pub const MAX_TRASH_RETENTION_DAYS: u32 = 36_500;
+
+pub fn get_setting(conn: &mut SqliteConnection, key: &str) -> Result<Option<String>, Box<dyn Error>> {
+    let value = settings::table
+        .find(key)
+        .select(settings::value)
+        .first::<String>(conn)
+        .optional()?;
+
+    Ok(value)
+}
+
+pub fn set_setting(conn: &mut SqliteConnection, key: &str, value: &str) -> Result<(), Box<dyn Error>> {
+    diesel::replace_into(settings::table)
+        .values((
+            &SettingRow { key: key.to_owned(), value: value.to_owned() },
+            settings::updated_at.eq(Utc::now().format(NAIVE_DATE_TIME_PATTERN).to_string()),
+        ))
+        .execute(conn)?;
+
+    Ok(())
+}
+
+/// Sets a setting and records the change in the settings' audit log.
+fn set_audited_setting(conn: &mut SqliteConnection, key: &'static str, value: &str) -> Result<(), Box<dyn Error>> {
+    conn.transaction::<_, Box<dyn Error>, _>(|conn| {
+        let before = get_setting(conn, key)?;
+
+        set_setting(conn, key, value)?;
+
+        audit_repository::record_audit(conn, AuditEntity::Setting, 0, AuditAction::Edit, &[(key, before)], &[(key, Some(value.to_owned()))])
+    })
+}
+
+/// Costing method of this installation; weighted average until one is chosen.
+pub fn get_costing_method(conn: &mut SqliteConnection) -> Result<CostingMethod, Box<dyn Error>> {
+    match get_setting(conn, COSTING_METHOD)? {
+        Some(value) => CostingMethod::try_from(value.as_str()),
+        None => Ok(CostingMethod::default()),
+    }
+}
+
+pub fn set_costing_method(conn: &mut SqliteConnection, method: CostingMethod) -> Result<(), Box<dyn Error>> {
+    set_audited_setting(conn, COSTING_METHOD, method.as_str())
+}
+
+/// Days deleted records stay in the trash before they are purged; `None` keeps
+/// them until they are purged by hand.
+pub fn get_trash_retention_days(conn: &mut SqliteConnection) -> Result<Option<u32>, Box<dyn Error>> {
+    match get_setting(conn, TRASH_RETENTION_DAYS)? {
+        Some(value) => Ok(Some(value.parse::<u32>()?).filter(|days| *days > 0)),
+        None => Ok(None),
+    }
+}
+
+pub fn set_trash_retention_days(conn: &mut SqliteConnection, days: Option<u32>) -> Result<(), Box<dyn Error>> {
+    if days.is_some_and(|days| days > MAX_TRASH_RETENTION_DAYS) {
+        return Err(format!("Deleted records can be kept for at most {} days", MAX_TRASH_RETENTION_DAYS).into());
+    }
+
+    set_audited_setting(conn, TRASH_RETENTION_DAYS, &days.unwrap_or(0).to_string())
+}
@@ -0,0 +1,202 @@
+use diesel::dsl;
+use diesel::prelude::*;
+use std::collections::HashMap;
+use std::error::Error;
+
+use crate::infra::models::{LotBalanceRow, ProductStockRow, StockBalanceRow};
+use crate::infra::repositories::product_repository;
+use crate::infra::schema::inventory_transactions;
+use crate::domain::stock_balance::{LotBalance, LowStockAlert, ProductStock, StockBalance};
+
+use chrono::NaiveDate;
+
+#[derive(Debug, Default, Clone)]
+pub struct StockBalanceFilter {
+    pub product_id: Option<i32>,
+    pub place_id: Option<i32>,
+    /// Any of these places, e.g. a warehouse and everything inside it.
+    pub place_ids: Option<Vec<i32>>,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct LotBalanceFilter {
+    pub product_id: Option<i32>,
+    pub place_id: Option<i32>,
+    /// Any of these places, e.g. a warehouse and everything inside it.
+    pub place_ids: Option<Vec<i32>>,
+    /// Only lots expiring on or before this date.
+    pub expires_until: Option<NaiveDate>,
+}
+
+/// Quantity on hand per (product, place), summed from the ledger. Pairs whose
+/// movements cancel out are left out.
+pub fn list_stock_balances(conn: &mut SqliteConnection, filter: &StockBalanceFilter) -> Result<Vec<StockBalance>, Box<dyn Error>> {
+    let mut balances_query = inventory_transactions::table
+        .group_by((inventory_transactions::product_id, inventory_transactions::place_id))
+        .select((
+            inventory_transactions::product_id,
+            inventory_transactions::place_id,
+            dsl::sum(inventory_transactions::quantity),
+        ))
+        .order((inventory_transactions::product_id, inventory_transactions::place_id))
+        .into_boxed();
+
+    if let Some(product_id) = filter.product_id {
+        balances_query = balances_query.filter(inventory_transactions::product_id.eq(product_id));
+    }
+
+    if let Some(place_id) = filter.place_id {
+        balances_query = balances_query.filter(inventory_transactions::place_id.eq(place_id));
+    }
+
+    if let Some(place_ids) = &filter.place_ids {
+        balances_query = balances_query.filter(inventory_transactions::place_id.eq_any(place_ids.clone()));
+    }
+
+    let balance_list: Vec<StockBalanceRow> = balances_query.load(conn)?;
+
+    let balances = balance_list.into_iter()
+        .map(StockBalance::from)
+        .filter(|balance| balance.quantity != 0)
+        .collect();
+
+    Ok(balances)
+}
+
+/// Quantity on hand per product across every place.
+pub fn list_product_stock(conn: &mut SqliteConnection) -> Result<Vec<ProductStock>, Box<dyn Error>> {
+    let stock_list: Vec<ProductStockRow> = inventory_transactions::table
+        .group_by(inventory_transactions::product_id)
+        .select((
+            inventory_transactions::product_id,
+            dsl::sum(inventory_transactions::quantity),
+        ))
+        .load(conn)?;
+
+    let stock = stock_list.into_iter()
+        .map(ProductStock::from)
+        .collect();
+
+    Ok(stock)
+}
+
+/// Quantity on hand of a single product at a single place.
+pub fn get_stock_balance(conn: &mut SqliteConnection, product_id: i32, place_id: i32) -> Result<i64, Box<dyn Error>> {
+    let quantity: Option<i64> = inventory_transactions::table
+        .filter(inventory_transactions::product_id.eq(product_id))
+        .filter(inventory_transactions::place_id.eq(place_id))
+        .select(dsl::sum(inventory_transactions::quantity))
+        .first(conn)?;
+
+    Ok(quantity.unwrap_or_default())
+}
+
+/// Products whose total quantity on hand is at or below their `min_stock`,
+/// largest shortfall first.
+pub fn list_low_stock_alerts(conn: &mut SqliteConnection) -> Result<Vec<LowStockAlert>, Box<dyn Error>> {
+    let stock: HashMap<i32, i64> = list_product_stock(conn)?
+        .into_iter()
+        .map(|stock| (stock.product_id, stock.quantity))
+        .collect();
+
+    let mut alerts: Vec<LowStockAlert> = product_repository::list_products(conn, "")?
+        .into_iter()
+        .filter_map(|product| {
+            let on_hand = stock.get(&product.id).copied().unwrap_or_default();
+
+            product.is_low_stock(on_hand).then_some(LowStockAlert { product, on_hand })
+        })
+        .collect();
+
+    alerts.sort_by(|a, b| b.shortfall().cmp(&a.shortfall()).then_with(|| a.product.name.cmp(&b.product.name)));
+
+    Ok(alerts)
+}
+
+/// Quantity on hand per (product, place, lot) for movements that carry a lot,
+/// soonest expiry first. Lots that are used up are left out.
+pub fn list_lot_balances(conn: &mut SqliteConnection, filter: &LotBalanceFilter) -> Result<Vec<LotBalance>, Box<dyn Error>> {
+    let mut balances_query = inventory_transactions::table
+        .filter(inventory_transactions::lot_number.is_not_null())
+        .group_by((
+            inventory_transactions::product_id,
+            inventory_transactions::place_id,
+            inventory_transactions::lot_number,
+        ))
+        .select((
+            inventory_transactions::product_id,
+            inventory_transactions::place_id,
+            inventory_transactions::lot_number,
+            dsl::max(inventory_transactions::expiry_date),
+            dsl::sum(inventory_transactions::quantity),
+        ))
+        .into_boxed();
+
+    if let Some(product_id) = filter.product_id {
+        balances_query = balances_query.filter(inventory_transactions::product_id.eq(product_id));
+    }
+
+    if let Some(place_id) = filter.place_id {
+        balances_query = balances_query.filter(inventory_transactions::place_id.eq(place_id));
+    }
+
+    if let Some(place_ids) = &filter.place_ids {
+        balances_query = balances_query.filter(inventory_transactions::place_id.eq_any(place_ids.clone()));
+    }
+
+    let balance_list: Vec<LotBalanceRow> = balances_query.load(conn)?;
+
+    let mut balances = balance_list.into_iter()
+        .map(LotBalance::try_from)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    balances.retain(|balance| {
+        balance.quantity != 0
+            && filter.expires_until.is_none_or(|until| balance.expiry_date.is_some_and(|expiry_date| expiry_date <= until))
+    });
+
+    // Lots without an expiry date go last.
+    balances.sort_by(|a, b| {
+        a.expiry_date.is_none().cmp(&b.expiry_date.is_none())
+            .then_with(|| a.expiry_date.cmp(&b.expiry_date))
+            .then_with(|| a.lot_number.cmp(&b.lot_number))
+    });
+
+    Ok(balances)
+}
+
+/// Quantity on hand of one lot of a product at a single place.
+pub fn get_lot_balance(conn: &mut SqliteConnection, product_id: i32, place_id: i32, lot_number: &str) -> Result<i64, Box<dyn Error>> {
+    let quantity: Option<i64> = inventory_transactions::table
+        .filter(inventory_transactions::product_id.eq(product_id))
+        .filter(inventory_transactions::place_id.eq(place_id))
+        .filter(inventory_transactions::lot_number.eq(lot_number))
+        .select(dsl::sum(inventory_transactions::quantity))
+        .first(conn)?;
+
+    Ok(quantity.unwrap_or_default())
+}
+
+/// Quantity on hand of a product at a single place that carries no lot, i.e.
+/// stock recorded before the product started tracking lots.
+pub fn get_untracked_balance(conn: &mut SqliteConnection, product_id: i32, place_id: i32) -> Result<i64, Box<dyn Error>> {
+    let quantity: Option<i64> = inventory_transactions::table
+        .filter(inventory_transactions::product_id.eq(product_id))
+        .filter(inventory_transactions::place_id.eq(place_id))
+        .filter(inventory_transactions::lot_number.is_null())
+        .select(dsl::sum(inventory_transactions::quantity))
+        .first(conn)?;
+
+    Ok(quantity.unwrap_or_default())
+}
+
+/// Expiry date recorded for a lot of a product, if the lot is known.
+pub fn get_lot_expiry_date(conn: &mut SqliteConnection, product_id: i32, lot_number: &str) -> Result<Option<String>, Box<dyn Error>> {
+    let expiry_date: Option<String> = inventory_transactions::table
+        .filter(inventory_transactions::product_id.eq(product_id))
+        .filter(inventory_transactions::lot_number.eq(lot_number))
+        .select(dsl::max(inventory_transactions::expiry_date))
+        .first(conn)?;
+
+    Ok(expiry_date)
+}
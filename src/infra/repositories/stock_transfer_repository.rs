@@ -0,0 +1,75 @@
+use diesel::prelude::*;
+use std::error::Error;
+
+use crate::infra::models::{NewInventoryTransactionRow, NewStockTransferRow};
+use crate::infra::repositories::inventory_transaction_repository;
+use crate::infra::schema::stock_transfers;
+use crate::domain::inventory_transaction::{InventoryTransaction, TransactionType};
+
+pub struct NewStockTransfer {
+    pub product_id: i32,
+    pub from_place_id: i32,
+    pub to_place_id: i32,
+    pub quantity: i32,
+    /// Lot being moved; required for products that track lots.
+    pub lot_number: Option<String>,
+    /// Units being moved; one per unit for serialized products.
+    pub serial_numbers: Vec<String>,
+    pub reason: Option<String>,
+}
+
+/// Moves stock between two places, writing the outbound and inbound ledger rows
+/// and the `stock_transfers` record that pairs them in a single transaction.
+/// Fails without writing anything if the source would go below zero or the
+/// units are reserved for another job.
+pub fn transfer_stock(conn: &mut SqliteConnection, transfer: NewStockTransfer) -> Result<(InventoryTransaction, InventoryTransaction), Box<dyn Error>> {
+    if transfer.quantity <= 0 {
+        return Err("Transfer quantity must be greater than zero".into());
+    }
+
+    if transfer.from_place_id == transfer.to_place_id {
+        return Err("Source and destination places must be different".into());
+    }
+
+    conn.transaction::<_, Box<dyn Error>, _>(|conn| {
+        let outbound = inventory_transaction_repository::create_inventory_transaction(conn, NewInventoryTransactionRow {
+            product_id: transfer.product_id,
+            place_id: transfer.from_place_id,
+            supplier_id: None,
+            quantity: TransactionType::TransferOut.signed_quantity(transfer.quantity),
+            transaction_type: TransactionType::TransferOut.as_str().to_owned(),
+            reason: transfer.reason.clone(),
+            lot_number: transfer.lot_number.clone(),
+            expiry_date: None,
+            unit_cost: None,
+            reservation_id: None,
+        }, &transfer.serial_numbers)?;
+
+        let inbound = inventory_transaction_repository::create_inventory_transaction(conn, NewInventoryTransactionRow {
+            product_id: transfer.product_id,
+            place_id: transfer.to_place_id,
+            supplier_id: None,
+            quantity: TransactionType::TransferIn.signed_quantity(transfer.quantity),
+            transaction_type: TransactionType::TransferIn.as_str().to_owned(),
+            reason: transfer.reason.clone(),
+            lot_number: transfer.lot_number.clone(),
+            expiry_date: None,
+            unit_cost: None,
+            reservation_id: None,
+        }, &transfer.serial_numbers)?;
+
+        diesel::insert_into(stock_transfers::table)
+            .values(&NewStockTransferRow {
+                product_id: transfer.product_id,
+                from_place_id: transfer.from_place_id,
+                to_place_id: transfer.to_place_id,
+                quantity: transfer.quantity,
+                outbound_transaction_id: outbound.id,
+                inbound_transaction_id: inbound.id,
+                reason: transfer.reason,
+            })
+            .execute(conn)?;
+
+        Ok((outbound, inbound))
+    })
+}
@@ -0,0 +1,590 @@
+use diesel::prelude::*;
+use std::collections::HashMap;
+use std::error::Error;
+
+use crate::infra::models::{NewInventoryTransactionRow, NewStocktakeLineRow, NewStocktakeRow, StocktakeLineRow, StocktakeRow};
+use crate::infra::repositories::{audit_repository, inventory_transaction_repository, place_repository, product_repository, stock_repository};
+use crate::infra::repositories::stock_repository::{LotBalanceFilter, StockBalanceFilter};
+use crate::infra::schema::{stocktake_lines, stocktakes};
+use crate::domain::audit::{AuditAction, AuditEntity, AuditField};
+use crate::domain::inventory_transaction::TransactionType;
+use crate::domain::place::{descendant_place_ids, place_path};
+use crate::domain::stocktake::{Stocktake, StocktakeCount, StocktakeLine, StocktakeStatus};
+
+use chrono::Utc;
+
+const NAIVE_DATE_TIME_PATTERN: &str =  "%Y-%m-%d %H:%M:%S";
+
+pub fn list_stocktakes(conn: &mut SqliteConnection) -> Result<Vec<Stocktake>, Box<dyn Error>> {
+    let stocktake_list: Vec<StocktakeRow> = stocktakes::table
+        .order(stocktakes::id.desc())
+        .select(StocktakeRow::as_select())
+        .load(conn)?;
+
+    let stocktake_items = stocktake_list.into_iter()
+        .map(|stocktake| stocktake.try_into())
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(stocktake_items)
+}
+
+pub fn get_stocktake(conn: &mut SqliteConnection, stocktake_id: i32) -> Result<Stocktake, Box<dyn Error>> {
+    let stocktake = stocktakes::table
+        .find(stocktake_id)
+        .select(StocktakeRow::as_select())
+        .first(conn)?;
+
+    stocktake.try_into()
+}
+
+/// Opens a session and freezes the expected quantities: one line per product
+/// and place with stock (per lot for products tracked by lot), taken from the
+/// ledger at this moment. A session on a place also counts the places inside
+/// it. Later movements do not change them.
+pub fn create_stocktake(conn: &mut SqliteConnection, new_stocktake: NewStocktakeRow) -> Result<Stocktake, Box<dyn Error>> {
+    conn.transaction::<_, Box<dyn Error>, _>(|conn| {
+        diesel::insert_into(stocktakes::table)
+            .values(&new_stocktake)
+            .execute(conn)?;
+
+        let created_stocktake: Stocktake = stocktakes::table
+            .order(stocktakes::id.desc())
+            .select(StocktakeRow::as_select())
+            .first(conn)?
+            .try_into()?;
+
+        let place_ids = match created_stocktake.place_id {
+            Some(place_id) => Some(descendant_place_ids(&place_repository::list_places(conn, "")?, place_id)),
+            None => None,
+        };
+
+        let balances = stock_repository::list_stock_balances(conn, &StockBalanceFilter {
+            place_ids: place_ids.clone(),
+            ..Default::default()
+        })?;
+
+        let lot_balances = stock_repository::list_lot_balances(conn, &LotBalanceFilter {
+            place_ids,
+            ..Default::default()
+        })?;
+
+        // Deleted products are not counted.
+        let products: HashMap<i32, bool> = product_repository::list_products(conn, "")?
+            .into_iter()
+            .map(|product| (product.id, product.tracks_lots))
+            .collect();
+
+        let mut new_lines = Vec::new();
+
+        for balance in balances {
+            let Some(&tracks_lots) = products.get(&balance.product_id) else {
+                continue;
+            };
+
+            let mut remaining = balance.quantity;
+
+            if tracks_lots {
+                for lot in lot_balances.iter().filter(|lot| lot.product_id == balance.product_id && lot.place_id == balance.place_id) {
+                    remaining -= lot.quantity;
+
+                    new_lines.push(NewStocktakeLineRow {
+                        stocktake_id: created_stocktake.id,
+                        product_id: balance.product_id,
+                        place_id: balance.place_id,
+                        lot_number: Some(lot.lot_number.clone()),
+                        expected_quantity: i32::try_from(lot.quantity)?,
+                        counted_quantity: None,
+                    });
+                }
+            }
+
+            // Stock recorded before lot tracking was turned on has no lot.
+            if remaining != 0 {
+                new_lines.push(NewStocktakeLineRow {
+                    stocktake_id: created_stocktake.id,
+                    product_id: balance.product_id,
+                    place_id: balance.place_id,
+                    lot_number: None,
+                    expected_quantity: i32::try_from(remaining)?,
+                    counted_quantity: None,
+                });
+            }
+        }
+
+        diesel::insert_into(stocktake_lines::table)
+            .values(&new_lines)
+            .execute(conn)?;
+
+        let mut after = created_stocktake.audit_fields();
+        after.push(("lines", Some(new_lines.len().to_string())));
+
+        audit_repository::record_audit(conn, AuditEntity::Stocktake, created_stocktake.id, AuditAction::Create, &[], &after)?;
+
+        Ok(created_stocktake)
+    })
+}
+
+pub fn list_stocktake_lines(conn: &mut SqliteConnection, stocktake_id: i32) -> Result<Vec<StocktakeLine>, Box<dyn Error>> {
+    let line_list: Vec<StocktakeLineRow> = stocktake_lines::table
+        .filter(stocktake_lines::stocktake_id.eq(stocktake_id))
+        .order(stocktake_lines::id)
+        .select(StocktakeLineRow::as_select())
+        .load(conn)?;
+
+    Ok(line_list.into_iter().map(StocktakeLine::from).collect())
+}
+
+/// Records (or clears, with `None`) the counted quantity of a line.
+pub fn record_stocktake_count(conn: &mut SqliteConnection, line_id: i32, counted_quantity: Option<i32>) -> Result<StocktakeLine, Box<dyn Error>> {
+    conn.transaction::<_, Box<dyn Error>, _>(|conn| {
+        let line = get_stocktake_line(conn, line_id)?;
+
+        ensure_editable(conn, line.stocktake_id)?;
+
+        if counted_quantity.is_some_and(|counted| counted < 0) {
+            return Err("Counted quantities cannot be negative".into());
+        }
+
+        diesel::update(stocktake_lines::table.find(line_id))
+            .set((
+                stocktake_lines::counted_quantity.eq(counted_quantity),
+                stocktake_lines::updated_at.eq(Utc::now().format(NAIVE_DATE_TIME_PATTERN).to_string())
+            ))
+            .execute(conn)?;
+
+        let updated_line = get_stocktake_line(conn, line_id)?;
+
+        let before = audit_count(conn, &line)?;
+        let after = audit_count(conn, &updated_line)?;
+
+        audit_repository::record_audit(conn, AuditEntity::Stocktake, line.stocktake_id, AuditAction::Edit, &before, &after)?;
+
+        Ok(updated_line)
+    })
+}
+
+/// Adds a line for stock found during the count that was not expected: nothing
+/// was on hand when the quantities were frozen, so the expected quantity is zero.
+pub fn create_stocktake_line(conn: &mut SqliteConnection, new_line: NewStocktakeLineRow) -> Result<StocktakeLine, Box<dyn Error>> {
+    conn.transaction::<_, Box<dyn Error>, _>(|conn| {
+        let created_line = insert_stocktake_line(conn, new_line)?;
+
+        let after = audit_count(conn, &created_line)?;
+
+        audit_repository::record_audit(conn, AuditEntity::Stocktake, created_line.stocktake_id, AuditAction::Edit, &[], &after)?;
+
+        Ok(created_line)
+    })
+}
+
+fn insert_stocktake_line(conn: &mut SqliteConnection, mut new_line: NewStocktakeLineRow) -> Result<StocktakeLine, Box<dyn Error>> {
+    let stocktake = ensure_editable(conn, new_line.stocktake_id)?;
+    let product = product_repository::get_product_by_id(conn, new_line.product_id)?;
+
+    if let Some(place_id) = stocktake.place_id {
+        let places = place_repository::list_places(conn, "")?;
+
+        if !descendant_place_ids(&places, place_id).contains(&new_line.place_id) {
+            return Err(format!("Stocktake #{} only counts {} and the places inside it", stocktake.id, place_path(&places, place_id)).into());
+        }
+    }
+
+    new_line.lot_number = new_line.lot_number.take()
+        .map(|lot_number| lot_number.trim().to_owned())
+        .filter(|lot_number| !lot_number.is_empty());
+
+    if product.tracks_lots && new_line.lot_number.is_none() {
+        return Err(format!("'{}' is tracked by lot, enter a lot number", product.name).into());
+    }
+
+    if new_line.counted_quantity.is_some_and(|counted| counted < 0) {
+        return Err("Counted quantities cannot be negative".into());
+    }
+
+    if find_stocktake_line(conn, new_line.stocktake_id, new_line.product_id, new_line.place_id, new_line.lot_number.as_deref())?.is_some() {
+        return Err(format!("'{}' is already on this stocktake, enter the count on its line", product.name).into());
+    }
+
+    new_line.expected_quantity = 0;
+
+    diesel::insert_into(stocktake_lines::table)
+        .values(&new_line)
+        .execute(conn)?;
+
+    let created_line = stocktake_lines::table
+        .order(stocktake_lines::id.desc())
+        .select(StocktakeLineRow::as_select())
+        .first(conn)?;
+
+    Ok(StocktakeLine::from(created_line))
+}
+
+/// Applies the counts of a filled-in count sheet. Rows are matched by line id,
+/// or by product, place and lot; unknown combinations become new lines. Returns
+/// the number of counts recorded.
+pub fn import_stocktake_counts(conn: &mut SqliteConnection, stocktake_id: i32, counts: &[StocktakeCount]) -> Result<usize, Box<dyn Error>> {
+    conn.transaction::<_, Box<dyn Error>, _>(|conn| {
+        let stocktake = ensure_editable(conn, stocktake_id)?;
+        let places = place_repository::list_places(conn, "")?;
+
+        for count in counts {
+            if let Some(line_id) = count.line_id {
+                let line = get_stocktake_line(conn, line_id)?;
+
+                if line.stocktake_id != stocktake_id {
+                    return Err(format!("Line #{} does not belong to stocktake #{}", line_id, stocktake_id).into());
+                }
+
+                record_stocktake_count(conn, line_id, Some(count.counted_quantity))?;
+                continue;
+            }
+
+            let product_id = count.product_id
+                .ok_or("Rows added to the count sheet need a product_id")?;
+
+            let place_id = match count.place_name.as_deref().map(str::trim).filter(|name| !name.is_empty()) {
+                Some(place_name) => places.iter()
+                    .find(|place| place_path(&places, place.id) == place_name)
+                    .map(|place| place.id)
+                    .ok_or_else(|| format!("Unknown place '{}'", place_name))?,
+                None => stocktake.place_id
+                    .ok_or("Rows added to the count sheet need a place")?,
+            };
+
+            let lot_number = count.lot_number.as_deref().map(str::trim).filter(|lot_number| !lot_number.is_empty());
+
+            match find_stocktake_line(conn, stocktake_id, product_id, place_id, lot_number)? {
+                Some(line) => {
+                    record_stocktake_count(conn, line.id, Some(count.counted_quantity))?;
+                }
+                None => {
+                    create_stocktake_line(conn, NewStocktakeLineRow {
+                        stocktake_id,
+                        product_id,
+                        place_id,
+                        lot_number: lot_number.map(str::to_owned),
+                        expected_quantity: 0,
+                        counted_quantity: Some(count.counted_quantity),
+                    })?;
+                }
+            }
+        }
+
+        Ok(counts.len())
+    })
+}
+
+/// Closes the session and posts one adjustment per counted line whose count
+/// differs from the frozen quantity. Uncounted lines are left untouched.
+pub fn approve_stocktake(conn: &mut SqliteConnection, stocktake_id: i32, reason: &str) -> Result<Stocktake, Box<dyn Error>> {
+    conn.transaction::<_, Box<dyn Error>, _>(|conn| {
+        let stocktake = ensure_editable(conn, stocktake_id)?;
+
+        let reason = match reason.trim() {
+            "" => format!("Stocktake #{}", stocktake.id),
+            reason => format!("Stocktake #{}: {}", stocktake.id, reason),
+        };
+
+        for line in list_stocktake_lines(conn, stocktake_id)? {
+            let Some(variance) = line.variance().filter(|variance| *variance != 0) else {
+                continue;
+            };
+
+            let product = product_repository::get_product_by_id(conn, line.product_id)?;
+
+            // Serialized units can only be adjusted by naming each serial number.
+            if product.serialized {
+                return Err(format!(
+                    "'{}' is serialized: record its variance of {} from Inventory Transactions with the serial numbers, then recount it",
+                    product.name, variance
+                ).into());
+            }
+
+            inventory_transaction_repository::create_inventory_transaction(conn, NewInventoryTransactionRow {
+                product_id: line.product_id,
+                place_id: line.place_id,
+                supplier_id: None,
+                quantity: TransactionType::Adjustment.signed_quantity(variance),
+                transaction_type: TransactionType::Adjustment.as_str().to_owned(),
+                reason: Some(reason.clone()),
+                lot_number: line.lot_number.clone(),
+                expiry_date: None,
+                unit_cost: None,
+                reservation_id: None,
+            }, &[])?;
+        }
+
+        let now = Utc::now().format(NAIVE_DATE_TIME_PATTERN).to_string();
+
+        diesel::update(stocktakes::table.find(stocktake_id))
+            .set((
+                stocktakes::status.eq(StocktakeStatus::Approved.as_str()),
+                stocktakes::approved_at.eq(Some(&now)),
+                stocktakes::updated_at.eq(&now),
+            ))
+            .execute(conn)?;
+
+        let stocktake_item = get_stocktake(conn, stocktake_id)?;
+
+        audit_repository::record_audit(conn, AuditEntity::Stocktake, stocktake_id, AuditAction::Edit, &stocktake.audit_fields(), &stocktake_item.audit_fields())?;
+
+        Ok(stocktake_item)
+    })
+}
+
+pub fn cancel_stocktake(conn: &mut SqliteConnection, stocktake_id: i32) -> Result<Stocktake, Box<dyn Error>> {
+    conn.transaction::<_, Box<dyn Error>, _>(|conn| {
+        let before = ensure_editable(conn, stocktake_id)?.audit_fields();
+
+        diesel::update(stocktakes::table.find(stocktake_id))
+            .set((
+                stocktakes::status.eq(StocktakeStatus::Cancelled.as_str()),
+                stocktakes::updated_at.eq(Utc::now().format(NAIVE_DATE_TIME_PATTERN).to_string())
+            ))
+            .execute(conn)?;
+
+        let stocktake_item = get_stocktake(conn, stocktake_id)?;
+
+        audit_repository::record_audit(conn, AuditEntity::Stocktake, stocktake_id, AuditAction::Edit, &before, &stocktake_item.audit_fields())?;
+
+        Ok(stocktake_item)
+    })
+}
+
+fn get_stocktake_line(conn: &mut SqliteConnection, line_id: i32) -> Result<StocktakeLine, Box<dyn Error>> {
+    let line = stocktake_lines::table
+        .find(line_id)
+        .select(StocktakeLineRow::as_select())
+        .first(conn)?;
+
+    Ok(StocktakeLine::from(line))
+}
+
+fn find_stocktake_line(conn: &mut SqliteConnection, stocktake_id: i32, product_id: i32, place_id: i32, lot_number: Option<&str>) -> Result<Option<StocktakeLine>, Box<dyn Error>> {
+    let mut line_query = stocktake_lines::table
+        .filter(stocktake_lines::stocktake_id.eq(stocktake_id))
+        .filter(stocktake_lines::product_id.eq(product_id))
+        .filter(stocktake_lines::place_id.eq(place_id))
+        .select(StocktakeLineRow::as_select())
+        .into_boxed();
+
+    line_query = match lot_number {
+        Some(lot_number) => line_query.filter(stocktake_lines::lot_number.eq(lot_number)),
+        None => line_query.filter(stocktake_lines::lot_number.is_null()),
+    };
+
+    Ok(line_query.first(conn).optional()?.map(StocktakeLine::from))
+}
+
+fn ensure_editable(conn: &mut SqliteConnection, stocktake_id: i32) -> Result<Stocktake, Box<dyn Error>> {
+    let stocktake = get_stocktake(conn, stocktake_id)?;
+
+    if !stocktake.status.is_editable() {
+        return Err(format!("Stocktake #{} is {} and can no longer be changed", stocktake.id, stocktake.status.label().to_lowercase()).into());
+    }
+
+    Ok(stocktake)
+}
+
+/// A line's count as one audited field of its session, e.g.
+/// "Screw at WH1/A/03 (lot L1): 12", so each count shows in the session's history.
+fn audit_count(conn: &mut SqliteConnection, line: &StocktakeLine) -> Result<Vec<AuditField>, Box<dyn Error>> {
+    let product = product_repository::get_product_by_id(conn, line.product_id)?;
+    let places = place_repository::list_places(conn, "")?;
+
+    let mut summary = format!("{} at {}", product.name, place_path(&places, line.place_id));
+
+    if let Some(lot_number) = &line.lot_number {
+        summary.push_str(&format!(" (lot {})", lot_number));
+    }
+
+    let counted = match line.counted_quantity {
+        Some(counted_quantity) => counted_quantity.to_string(),
+        None => "not counted".to_owned(),
+    };
+
+    Ok(vec![("count", Some(format!("{}: {}", summary, counted)))])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infra::db;
+    use crate::infra::models::{EditProductRow, NewProductRow};
+
+    /// A product with 10 units received before lot tracking was turned on and 5
+    /// units of lot L1 received after.
+    fn tracked_product_with_untracked_stock(conn: &mut SqliteConnection) -> (i32, i32) {
+        let place_id = place_repository::find_or_create_place_path(conn, "WH1", None).unwrap().unwrap();
+
+        let product = product_repository::create_product(conn, NewProductRow {
+            name: "Resin".to_owned(),
+            unity: Some("kg".to_owned()),
+            brand: None,
+            min_stock: None,
+            observation: None,
+            reorder_point: None,
+            safety_stock: None,
+            max_stock: None,
+            tracks_lots: Some(false),
+            serialized: None,
+            unit_id: None,
+            category_id: None,
+            sku: None,
+        }, &[]).unwrap();
+
+        post(conn, product.id, place_id, 10, None);
+
+        product_repository::edit_product(conn, EditProductRow {
+            id: product.id,
+            name: product.name,
+            unity: product.unity,
+            brand: product.brand,
+            min_stock: Some(product.min_stock),
+            observation: product.observation,
+            reorder_point: Some(product.reorder_point),
+            safety_stock: Some(product.safety_stock),
+            max_stock: Some(product.max_stock),
+            tracks_lots: Some(true),
+            serialized: Some(product.serialized),
+            unit_id: product.unit_id,
+            category_id: None,
+            sku: None,
+        }, &[]).unwrap();
+
+        post(conn, product.id, place_id, 5, Some("L1"));
+
+        (product.id, place_id)
+    }
+
+    fn post(conn: &mut SqliteConnection, product_id: i32, place_id: i32, quantity: i32, lot_number: Option<&str>) {
+        inventory_transaction_repository::create_inventory_transaction(conn, NewInventoryTransactionRow {
+            product_id,
+            place_id,
+            supplier_id: None,
+            quantity,
+            transaction_type: TransactionType::Entry.as_str().to_owned(),
+            reason: None,
+            lot_number: lot_number.map(str::to_owned),
+            expiry_date: None,
+            unit_cost: None,
+            reservation_id: None,
+        }, &[]).unwrap();
+    }
+
+    fn count_lines(conn: &mut SqliteConnection, stocktake_id: i32, untracked: i32, lot: i32) {
+        for line in list_stocktake_lines(conn, stocktake_id).unwrap() {
+            let counted = if line.lot_number.is_some() { lot } else { untracked };
+            record_stocktake_count(conn, line.id, Some(counted)).unwrap();
+        }
+    }
+
+    #[test]
+    fn counts_the_places_inside_the_stocktake_place() {
+        let mut conn = db::establish_test_connection();
+        let (product_id, warehouse_id) = tracked_product_with_untracked_stock(&mut conn);
+        let bin_id = place_repository::find_or_create_place_path(&mut conn, "WH1/A/01", None).unwrap().unwrap();
+        let other_id = place_repository::find_or_create_place_path(&mut conn, "WH2", None).unwrap().unwrap();
+
+        post(&mut conn, product_id, bin_id, 3, Some("L2"));
+        post(&mut conn, product_id, other_id, 4, Some("L3"));
+
+        let stocktake = create_stocktake(&mut conn, NewStocktakeRow { place_id: Some(warehouse_id), notes: None }).unwrap();
+
+        let mut places: Vec<i32> = list_stocktake_lines(&mut conn, stocktake.id).unwrap()
+            .into_iter()
+            .map(|line| line.place_id)
+            .collect();
+        places.dedup();
+
+        assert_eq!(places, vec![warehouse_id, bin_id]);
+
+        let outside = create_stocktake_line(&mut conn, NewStocktakeLineRow {
+            stocktake_id: stocktake.id,
+            product_id,
+            place_id: other_id,
+            lot_number: Some("L4".to_owned()),
+            expected_quantity: 0,
+            counted_quantity: Some(1),
+        });
+
+        assert!(outside.is_err());
+    }
+
+    #[test]
+    fn freezes_untracked_stock_on_its_own_line() {
+        let mut conn = db::establish_test_connection();
+        let (product_id, place_id) = tracked_product_with_untracked_stock(&mut conn);
+
+        let stocktake = create_stocktake(&mut conn, NewStocktakeRow { place_id: Some(place_id), notes: None }).unwrap();
+
+        let mut lines: Vec<(Option<String>, i32)> = list_stocktake_lines(&mut conn, stocktake.id).unwrap()
+            .into_iter()
+            .filter(|line| line.product_id == product_id)
+            .map(|line| (line.lot_number, line.expected_quantity))
+            .collect();
+        lines.sort();
+
+        assert_eq!(lines, vec![(None, 10), (Some("L1".to_owned()), 5)]);
+    }
+
+    #[test]
+    fn approves_a_shortfall_in_untracked_stock() {
+        let mut conn = db::establish_test_connection();
+        let (product_id, place_id) = tracked_product_with_untracked_stock(&mut conn);
+
+        let stocktake = create_stocktake(&mut conn, NewStocktakeRow { place_id: Some(place_id), notes: None }).unwrap();
+        count_lines(&mut conn, stocktake.id, 7, 4);
+
+        let approved = approve_stocktake(&mut conn, stocktake.id, "").unwrap();
+
+        assert_eq!(approved.status, StocktakeStatus::Approved);
+        assert_eq!(stock_repository::get_untracked_balance(&mut conn, product_id, place_id).unwrap(), 7);
+        assert_eq!(stock_repository::get_lot_balance(&mut conn, product_id, place_id, "L1").unwrap(), 4);
+        assert_eq!(stock_repository::get_stock_balance(&mut conn, product_id, place_id).unwrap(), 11);
+    }
+
+    #[test]
+    fn approves_untracked_stock_counted_down_to_zero() {
+        let mut conn = db::establish_test_connection();
+        let (product_id, place_id) = tracked_product_with_untracked_stock(&mut conn);
+
+        let stocktake = create_stocktake(&mut conn, NewStocktakeRow { place_id: Some(place_id), notes: None }).unwrap();
+        count_lines(&mut conn, stocktake.id, 0, 5);
+
+        approve_stocktake(&mut conn, stocktake.id, "").unwrap();
+
+        assert_eq!(stock_repository::get_untracked_balance(&mut conn, product_id, place_id).unwrap(), 0);
+        assert_eq!(stock_repository::get_stock_balance(&mut conn, product_id, place_id).unwrap(), 5);
+    }
+
+    #[test]
+    fn records_counts_and_approval_in_the_session_history() {
+        let mut conn = db::establish_test_connection();
+        let (_, place_id) = tracked_product_with_untracked_stock(&mut conn);
+
+        let stocktake = create_stocktake(&mut conn, NewStocktakeRow { place_id: Some(place_id), notes: None }).unwrap();
+        count_lines(&mut conn, stocktake.id, 10, 5);
+        approve_stocktake(&mut conn, stocktake.id, "").unwrap();
+
+        let actions: Vec<AuditAction> = audit_repository::list_audit_entries(&mut conn, AuditEntity::Stocktake, stocktake.id).unwrap()
+            .into_iter()
+            .map(|entry| entry.action)
+            .collect();
+
+        assert_eq!(actions, vec![AuditAction::Edit, AuditAction::Edit, AuditAction::Edit, AuditAction::Create]);
+    }
+
+    #[test]
+    fn refuses_a_surplus_of_untracked_stock() {
+        let mut conn = db::establish_test_connection();
+        let (product_id, place_id) = tracked_product_with_untracked_stock(&mut conn);
+
+        let stocktake = create_stocktake(&mut conn, NewStocktakeRow { place_id: Some(place_id), notes: None }).unwrap();
+        count_lines(&mut conn, stocktake.id, 12, 5);
+
+        assert!(approve_stocktake(&mut conn, stocktake.id, "").is_err());
+        assert_eq!(get_stocktake(&mut conn, stocktake.id).unwrap().status, StocktakeStatus::Counting);
+        assert_eq!(stock_repository::get_stock_balance(&mut conn, product_id, place_id).unwrap(), 15);
+    }
+}
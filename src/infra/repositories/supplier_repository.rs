@@ -1,9 +1,13 @@
 use diesel::prelude::*;
+use std::collections::HashSet;
 use std::error::Error;
 
 use crate::infra::models::{SupplierRow, NewSupplierRow, EditSupplierRow};
-use crate::infra::schema::suppliers;
+use crate::infra::repositories::{audit_repository, product_supplier_repository};
+use crate::infra::schema::{inventory_transactions, purchase_orders, suppliers};
+use crate::domain::audit::{AuditAction, AuditEntity};
 use crate::domain::supplier::Supplier;
+use crate::domain::tax_id::{format_tax_id, TaxIdKind};
 
 use chrono::Utc;
 
@@ -14,7 +18,10 @@ pub fn list_suppliers(conn: &mut SqliteConnection, search: &str) -> Result<Vec<S
 
   	let mut suppliers_query= suppliers::table.filter(suppliers::deleted_at.is_null()).into_boxed();
 
-    let filter_expression =  suppliers::name.like(&search_like);
+    let filter_expression =  suppliers::name.like(&search_like)
+        .or(suppliers::contact_name.like(&search_like))
+        .or(suppliers::email.like(&search_like))
+        .or(suppliers::tax_id.like(&search_like));
 
     if let Ok(search_number) = search.parse::<i32>(){
         suppliers_query = suppliers_query.filter(
@@ -34,68 +41,245 @@ pub fn list_suppliers(conn: &mut SqliteConnection, search: &str) -> Result<Vec<S
     Ok(prods)
 }
 
+pub fn get_supplier_by_id(conn: &mut SqliteConnection, supplier_id: i32) -> Result<Supplier, Box<dyn Error>> {
+    let supplier = suppliers::table
+        .find(supplier_id)
+        .first::<SupplierRow>(conn)?;
+
+    let supplier_item = supplier.try_into()?;
+
+    Ok(supplier_item)
+}
+
 pub fn create_supplier(conn: &mut SqliteConnection, new_supplier: NewSupplierRow) -> Result<Supplier, Box<dyn Error>> {
-    diesel::insert_into(suppliers::table)
-        .values(&new_supplier)
-        .execute(conn)
-        .expect("Failed to insert supplier");
+    ensure_unique_name(conn, &new_supplier.name, None)?;
+    ensure_valid_tax_id(conn, new_supplier.tax_id.as_deref(), None)?;
 
-    let created_supplier = suppliers::table
-        .order(suppliers::id.desc())
-        .first::<SupplierRow>(conn)
-        .expect("Failed to retrieve created supplier");
+    conn.transaction::<_, Box<dyn Error>, _>(|conn| {
+        diesel::insert_into(suppliers::table)
+            .values(&new_supplier)
+            .execute(conn)?;
 
-    let supplier_item = created_supplier.try_into()?;
+        let created_supplier = suppliers::table
+            .order(suppliers::id.desc())
+            .first::<SupplierRow>(conn)?;
 
-    Ok(supplier_item)
+        let supplier_item: Supplier = created_supplier.try_into()?;
+
+        audit_repository::record_audit(conn, AuditEntity::Supplier, supplier_item.id, AuditAction::Create, &[], &supplier_item.audit_fields())?;
+
+        Ok(supplier_item)
+    })
 }
 
 pub fn create_suppliers(conn: &mut SqliteConnection, new_suppliers: &[NewSupplierRow]) -> Result<Vec<Supplier>, Box<dyn Error>> {
-    diesel::insert_into(suppliers::table)
-        .values(new_suppliers)
-        .execute(conn)
-        .expect("Failed to insert suppliers");
-
-    let created_suppliers: Vec<SupplierRow> = suppliers::table
-        .order(suppliers::id.desc())
-        .limit(new_suppliers.len() as i64)
-        .load(conn)
-        .expect("Failed to retrieve created suppliers");
-
-    let new_suppliers = created_suppliers.into_iter()
-        .rev()
-        .map(|p| p.try_into())
-        .collect::<Result<Vec<_>, _>>()?;
+    let mut seen_names = HashSet::new();
+    let mut seen_tax_ids = HashSet::new();
+
+    for new_supplier in new_suppliers {
+        ensure_unique_name(conn, &new_supplier.name, None)?;
+
+        if !seen_names.insert(&new_supplier.name) {
+            return Err(format!("Supplier '{}' appears more than once", new_supplier.name).into());
+        }
+
+        ensure_valid_tax_id(conn, new_supplier.tax_id.as_deref(), None)
+            .map_err(|error| format!("Supplier '{}': {}", new_supplier.name, error))?;
+
+        if let Some(tax_id) = &new_supplier.tax_id
+            && !seen_tax_ids.insert(tax_id) {
+            return Err(format!("{} appears more than once", format_tax_id(tax_id)).into());
+        }
+    }
+
+    conn.transaction::<_, Box<dyn Error>, _>(|conn| {
+        diesel::insert_into(suppliers::table)
+            .values(new_suppliers)
+            .execute(conn)?;
+
+        let created_suppliers: Vec<SupplierRow> = suppliers::table
+            .order(suppliers::id.desc())
+            .limit(new_suppliers.len() as i64)
+            .load(conn)?;
 
-    Ok(new_suppliers)
+        let new_suppliers: Vec<Supplier> = created_suppliers.into_iter()
+            .rev()
+            .map(|p| p.try_into())
+            .collect::<Result<Vec<_>, _>>()?;
+
+        for supplier in &new_suppliers {
+            audit_repository::record_audit(conn, AuditEntity::Supplier, supplier.id, AuditAction::Create, &[], &supplier.audit_fields())?;
+        }
+
+        Ok(new_suppliers)
+    })
 }
 
 pub fn edit_supplier(conn: &mut SqliteConnection, supplier: EditSupplierRow) -> Result<Supplier, Box<dyn Error>> {
     let supplier_id = supplier.id;
 
-    diesel::update(suppliers::table.find(supplier_id))
-        .set((
-            &supplier,
-            suppliers::updated_at.eq(Utc::now().format(NAIVE_DATE_TIME_PATTERN).to_string())
-        ))
-        .execute(conn)
-        .expect("Failed to update supplier");
-
-    
-    let updated_supplier = suppliers::table
-        .filter(suppliers::id.eq(supplier_id))
-        .first::<SupplierRow>(conn)
-        .expect("Failed to retrieve updated supplier");
+    ensure_unique_name(conn, &supplier.name, Some(supplier_id))?;
+    ensure_valid_tax_id(conn, supplier.tax_id.as_deref(), Some(supplier_id))?;
 
-    let supplier_item = updated_supplier.try_into()?;
+    conn.transaction::<_, Box<dyn Error>, _>(|conn| {
+        let before = get_supplier_by_id(conn, supplier_id)?.audit_fields();
 
-    Ok(supplier_item)
+        diesel::update(suppliers::table.find(supplier_id))
+            .set((
+                &supplier,
+                suppliers::updated_at.eq(Utc::now().format(NAIVE_DATE_TIME_PATTERN).to_string())
+            ))
+            .execute(conn)?;
+
+        let updated_supplier = suppliers::table
+            .filter(suppliers::id.eq(supplier_id))
+            .first::<SupplierRow>(conn)?;
+
+        let supplier_item: Supplier = updated_supplier.try_into()?;
+
+        audit_repository::record_audit(conn, AuditEntity::Supplier, supplier_id, AuditAction::Edit, &before, &supplier_item.audit_fields())?;
+
+        Ok(supplier_item)
+    })
 }
 
 pub fn delete_supplier(conn: &mut SqliteConnection, supplier_id: i32) -> Result<bool, Box<dyn Error>> {
-    let deleted = diesel::update(suppliers::table.find(supplier_id))
-        .set(suppliers::deleted_at.eq(Some(Utc::now().naive_utc().format(NAIVE_DATE_TIME_PATTERN).to_string())))
-        .execute(conn)?;
+    conn.transaction::<_, Box<dyn Error>, _>(|conn| {
+        let before = get_supplier_by_id(conn, supplier_id)?.audit_fields();
+
+        let deleted = diesel::update(suppliers::table.find(supplier_id))
+            .set(suppliers::deleted_at.eq(Some(Utc::now().naive_utc().format(NAIVE_DATE_TIME_PATTERN).to_string())))
+            .execute(conn)?;
+
+        audit_repository::record_audit(conn, AuditEntity::Supplier, supplier_id, AuditAction::Delete, &before, &[])?;
+
+        Ok(deleted > 0)
+    })
+}
+
+/// Deleted suppliers, most recently deleted first.
+pub fn list_deleted_suppliers(conn: &mut SqliteConnection, search: &str) -> Result<Vec<Supplier>, Box<dyn Error>> {
+    let search_like = format!("%{}%", search);
+
+    let supplier_list: Vec<SupplierRow> = suppliers::table
+        .filter(suppliers::deleted_at.is_not_null())
+        .filter(suppliers::name.like(&search_like).or(suppliers::tax_id.like(&search_like)))
+        .order(suppliers::deleted_at.desc())
+        .load(conn)?;
+
+    let suppliers = supplier_list.into_iter()
+        .map(|supplier| supplier.try_into())
+        .collect::<Result<Vec<_>, _>>()?;
 
-    Ok(deleted > 0)
-}
\ No newline at end of file
+    Ok(suppliers)
+}
+
+/// Brings a deleted supplier back, unless its name or tax ID has been given to
+/// another supplier in the meantime.
+pub fn restore_supplier(conn: &mut SqliteConnection, supplier_id: i32) -> Result<Supplier, Box<dyn Error>> {
+    conn.transaction::<_, Box<dyn Error>, _>(|conn| {
+        let supplier = get_supplier_by_id(conn, supplier_id)?;
+
+        if supplier.deleted_at.is_none() {
+            return Err(format!("'{}' is not deleted", supplier.name).into());
+        }
+
+        ensure_unique_name(conn, &supplier.name, Some(supplier_id))?;
+        ensure_valid_tax_id(conn, supplier.tax_id.as_deref(), Some(supplier_id))?;
+
+        diesel::update(suppliers::table.find(supplier_id))
+            .set((
+                suppliers::deleted_at.eq(None::<String>),
+                suppliers::updated_at.eq(Utc::now().format(NAIVE_DATE_TIME_PATTERN).to_string())
+            ))
+            .execute(conn)?;
+
+        audit_repository::record_audit(conn, AuditEntity::Supplier, supplier_id, AuditAction::Restore, &[], &[])?;
+
+        get_supplier_by_id(conn, supplier_id)
+    })
+}
+
+/// The most recently deleted supplier with exactly this name, which can be
+/// restored instead of creating a new one.
+pub fn find_deleted_supplier_by_name(conn: &mut SqliteConnection, name: &str) -> Result<Option<Supplier>, Box<dyn Error>> {
+    let supplier = suppliers::table
+        .filter(suppliers::deleted_at.is_not_null())
+        .filter(suppliers::name.eq(name))
+        .order(suppliers::deleted_at.desc())
+        .first::<SupplierRow>(conn)
+        .optional()?;
+
+    Ok(supplier.map(|supplier| supplier.try_into()).transpose()?)
+}
+
+/// Permanently removes a deleted supplier with its product links. Suppliers
+/// with purchase orders or stock movements are kept, since those still point
+/// at them.
+pub fn purge_supplier(conn: &mut SqliteConnection, supplier_id: i32) -> Result<bool, Box<dyn Error>> {
+    conn.transaction::<_, Box<dyn Error>, _>(|conn| {
+        let supplier = get_supplier_by_id(conn, supplier_id)?;
+
+        if supplier.deleted_at.is_none() {
+            return Err(format!("Delete '{}' before purging it", supplier.name).into());
+        }
+
+        let references: i64 = [
+            purchase_orders::table.filter(purchase_orders::supplier_id.eq(supplier_id)).count().get_result::<i64>(conn)?,
+            inventory_transactions::table.filter(inventory_transactions::supplier_id.eq(supplier_id)).count().get_result::<i64>(conn)?,
+        ].iter().sum();
+
+        if references > 0 {
+            return Err(format!("'{}' has purchase orders or stock movements and cannot be purged", supplier.name).into());
+        }
+
+        product_supplier_repository::delete_supplier_links(conn, supplier_id)?;
+
+        let purged = diesel::delete(suppliers::table.find(supplier_id)).execute(conn)?;
+
+        audit_repository::record_audit(conn, AuditEntity::Supplier, supplier_id, AuditAction::Purge, &[], &[])?;
+
+        Ok(purged > 0)
+    })
+}
+
+/// Names only have to be unique among active suppliers; deleted ones keep
+/// theirs so they can be restored. `supplier_id` is the supplier being edited, if any.
+fn ensure_unique_name(conn: &mut SqliteConnection, name: &str, supplier_id: Option<i32>) -> Result<(), Box<dyn Error>> {
+    let taken: i64 = suppliers::table
+        .filter(suppliers::name.eq(name))
+        .filter(suppliers::deleted_at.is_null())
+        .filter(suppliers::id.ne(supplier_id.unwrap_or_default()))
+        .count()
+        .get_result(conn)?;
+
+    if taken > 0 {
+        return Err(format!("There is already a supplier named '{}'", name).into());
+    }
+
+    Ok(())
+}
+
+/// Checks the check digits of a normalized tax ID and that no other supplier
+/// uses it. `supplier_id` is the supplier being edited, if any.
+fn ensure_valid_tax_id(conn: &mut SqliteConnection, tax_id: Option<&str>, supplier_id: Option<i32>) -> Result<(), Box<dyn Error>> {
+    let Some(tax_id) = tax_id else {
+        return Ok(());
+    };
+
+    let kind = TaxIdKind::detect(tax_id)?;
+
+    let owner: Option<String> = suppliers::table
+        .filter(suppliers::tax_id.eq(tax_id))
+        .filter(suppliers::deleted_at.is_null())
+        .filter(suppliers::id.ne(supplier_id.unwrap_or_default()))
+        .select(suppliers::name)
+        .first(conn)
+        .optional()?;
+
+    if let Some(owner) = owner {
+        return Err(format!("{} {} is already used by supplier '{}'", kind.label(), format_tax_id(tax_id), owner).into());
+    }
+
+    Ok(())
+}
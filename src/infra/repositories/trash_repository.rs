@@ -0,0 +1,46 @@
+use diesel::prelude::*;
+use std::error::Error;
+
+use crate::infra::repositories::{place_repository, product_repository, setting_repository, supplier_repository};
+
+use chrono::{Duration, NaiveDateTime, Utc};
+
+/// Purges the products, suppliers and places that have been in the trash for
+/// longer than the retention setting. Records that cannot be purged, because
+/// movements or documents still point at them, stay in the trash. Returns how
+/// many records were purged.
+pub fn purge_expired_trash(conn: &mut SqliteConnection) -> Result<usize, Box<dyn Error>> {
+    let Some(days) = setting_repository::get_trash_retention_days(conn)? else {
+        return Ok(0);
+    };
+
+    // A cutoff before the earliest representable date leaves nothing expired.
+    let Some(cutoff) = Utc::now().naive_utc().checked_sub_signed(Duration::days(i64::from(days))) else {
+        return Ok(0);
+    };
+
+    let expired = |deleted_at: Option<NaiveDateTime>| deleted_at.is_some_and(|deleted_at| deleted_at <= cutoff);
+
+    let mut purged = 0;
+
+    for product in product_repository::list_deleted_products(conn, "")? {
+        if expired(product.deleted_at) && product_repository::purge_product(conn, product.id).unwrap_or(false) {
+            purged += 1;
+        }
+    }
+
+    for supplier in supplier_repository::list_deleted_suppliers(conn, "")? {
+        if expired(supplier.deleted_at) && supplier_repository::purge_supplier(conn, supplier.id).unwrap_or(false) {
+            purged += 1;
+        }
+    }
+
+    // Oldest first: places inside another one were deleted before it.
+    for place in place_repository::list_deleted_places(conn, "")?.into_iter().rev() {
+        if expired(place.deleted_at) && place_repository::purge_place(conn, place.id).unwrap_or(false) {
+            purged += 1;
+        }
+    }
+
+    Ok(purged)
+}
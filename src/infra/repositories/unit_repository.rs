@@ -0,0 +1,170 @@
+use diesel::prelude::*;
+use std::error::Error;
+
+use crate::infra::models::{UnitRow, NewUnitRow, EditUnitRow};
+use crate::infra::repositories::audit_repository;
+use crate::infra::schema::{products, units};
+use crate::domain::audit::{AuditAction, AuditEntity};
+use crate::domain::unit::Unit;
+
+use chrono::Utc;
+
+const NAIVE_DATE_TIME_PATTERN: &str =  "%Y-%m-%d %H:%M:%S";
+
+pub fn list_units(conn: &mut SqliteConnection, search: &str) -> Result<Vec<Unit>, Box<dyn Error>> {
+    let search_like = format!("%{}%", search);
+
+    let unit_list: Vec<UnitRow> = units::table
+        .filter(units::deleted_at.is_null())
+        .filter(units::name.like(&search_like).or(units::symbol.like(&search_like)))
+        .order(units::name.asc())
+        .load(conn)?;
+
+    let units = unit_list.into_iter()
+        .map(Unit::from)
+        .collect();
+
+    Ok(units)
+}
+
+pub fn get_unit_by_id(conn: &mut SqliteConnection, unit_id: i32) -> Result<Unit, Box<dyn Error>> {
+    let unit = units::table
+        .find(unit_id)
+        .first::<UnitRow>(conn)?;
+
+    let unit_item = unit.into();
+
+    Ok(unit_item)
+}
+
+pub fn create_unit(conn: &mut SqliteConnection, new_unit: NewUnitRow) -> Result<Unit, Box<dyn Error>> {
+    check_base_unit(conn, None, new_unit.base_unit_id, new_unit.factor)?;
+
+    conn.transaction::<_, Box<dyn Error>, _>(|conn| {
+        diesel::insert_into(units::table)
+            .values(&new_unit)
+            .execute(conn)?;
+
+        let created_unit = units::table
+            .order(units::id.desc())
+            .first::<UnitRow>(conn)?;
+
+        let unit_item: Unit = created_unit.into();
+
+        audit_repository::record_audit(conn, AuditEntity::Unit, unit_item.id, AuditAction::Create, &[], &unit_item.audit_fields())?;
+
+        Ok(unit_item)
+    })
+}
+
+/// Updates a unit and the symbol cached on the products that use it.
+pub fn edit_unit(conn: &mut SqliteConnection, unit: EditUnitRow) -> Result<Unit, Box<dyn Error>> {
+    let unit_id = unit.id;
+
+    check_base_unit(conn, Some(unit_id), unit.base_unit_id, unit.factor)?;
+
+    conn.transaction::<_, Box<dyn Error>, _>(|conn| {
+        let before = get_unit_by_id(conn, unit_id)?.audit_fields();
+
+        diesel::update(units::table.find(unit_id))
+            .set((
+                &unit,
+                units::updated_at.eq(Utc::now().format(NAIVE_DATE_TIME_PATTERN).to_string())
+            ))
+            .execute(conn)?;
+
+        diesel::update(products::table.filter(products::unit_id.eq(unit_id)))
+            .set(products::unity.eq(&unit.symbol))
+            .execute(conn)?;
+
+        let unit_item = get_unit_by_id(conn, unit_id)?;
+
+        audit_repository::record_audit(conn, AuditEntity::Unit, unit_id, AuditAction::Edit, &before, &unit_item.audit_fields())?;
+
+        Ok(unit_item)
+    })
+}
+
+/// Soft-deletes a unit that no product and no other unit depends on.
+pub fn delete_unit(conn: &mut SqliteConnection, unit_id: i32) -> Result<bool, Box<dyn Error>> {
+    let products_using: i64 = products::table
+        .filter(products::unit_id.eq(unit_id))
+        .filter(products::deleted_at.is_null())
+        .count()
+        .get_result(conn)?;
+
+    if products_using > 0 {
+        return Err(format!("The unit is used by {} product(s)", products_using).into());
+    }
+
+    let derived_units: i64 = units::table
+        .filter(units::base_unit_id.eq(unit_id))
+        .filter(units::deleted_at.is_null())
+        .count()
+        .get_result(conn)?;
+
+    if derived_units > 0 {
+        return Err("Other units are defined in terms of this unit".into());
+    }
+
+    conn.transaction::<_, Box<dyn Error>, _>(|conn| {
+        let before = get_unit_by_id(conn, unit_id)?.audit_fields();
+
+        let deleted = diesel::update(units::table.find(unit_id))
+            .set(units::deleted_at.eq(Some(Utc::now().naive_utc().format(NAIVE_DATE_TIME_PATTERN).to_string())))
+            .execute(conn)?;
+
+        audit_repository::record_audit(conn, AuditEntity::Unit, unit_id, AuditAction::Delete, &before, &[])?;
+
+        Ok(deleted > 0)
+    })
+}
+
+/// Converts a quantity between two units; a missing unit on either side means
+/// the quantity is already in the right unit.
+pub fn convert_quantity(conn: &mut SqliteConnection, quantity: i32, from_unit_id: Option<i32>, to_unit_id: Option<i32>) -> Result<i32, Box<dyn Error>> {
+    match (from_unit_id, to_unit_id) {
+        (Some(from_unit_id), Some(to_unit_id)) if from_unit_id != to_unit_id => {
+            let from_unit = get_unit_by_id(conn, from_unit_id)?;
+            let to_unit = get_unit_by_id(conn, to_unit_id)?;
+
+            from_unit.convert(quantity, &to_unit)
+        }
+        _ => Ok(quantity),
+    }
+}
+
+/// Derived units point straight at a base unit, so conversions never chain.
+fn check_base_unit(conn: &mut SqliteConnection, unit_id: Option<i32>, base_unit_id: Option<i32>, factor: f64) -> Result<(), Box<dyn Error>> {
+    if factor <= 0.0 {
+        return Err("The conversion factor must be greater than zero".into());
+    }
+
+    let Some(base_unit_id) = base_unit_id else {
+        return Ok(());
+    };
+
+    if Some(base_unit_id) == unit_id {
+        return Err("A unit cannot be defined in terms of itself".into());
+    }
+
+    let base_unit = get_unit_by_id(conn, base_unit_id)?;
+
+    if base_unit.base_unit_id.is_some() {
+        return Err(format!("{} is not a base unit", base_unit.symbol).into());
+    }
+
+    if let Some(unit_id) = unit_id {
+        let derived_units: i64 = units::table
+            .filter(units::base_unit_id.eq(unit_id))
+            .filter(units::deleted_at.is_null())
+            .count()
+            .get_result(conn)?;
+
+        if derived_units > 0 {
+            return Err("Other units are defined in terms of this unit, it must stay a base unit".into());
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,41 @@
+use diesel::prelude::*;
+use std::collections::HashMap;
+use std::error::Error;
+
+use crate::infra::models::InventoryTransactionRow;
+use crate::infra::schema::{inventory_transactions, stock_transfers};
+use crate::domain::inventory_transaction::InventoryTransaction;
+use crate::domain::valuation::{value_stock, CostingMethod, StockValuation};
+
+use chrono::{Local, NaiveDate, TimeZone, Utc};
+
+const NAIVE_DATE_TIME_PATTERN: &str =  "%Y-%m-%d %H:%M:%S";
+
+/// Values the stock on hand at the end of `as_of` by replaying the ledger up to
+/// that day with the given costing method. `as_of` is a local date, while
+/// movements are stamped in UTC.
+pub fn list_stock_valuation(conn: &mut SqliteConnection, method: CostingMethod, as_of: NaiveDate) -> Result<Vec<StockValuation>, Box<dyn Error>> {
+    let end_of_day = as_of.and_hms_opt(23, 59, 59)
+        .and_then(|end_of_day| Local.from_local_datetime(&end_of_day).latest())
+        .ok_or_else(|| format!("{} has no end of day in the local time zone", as_of))?;
+
+    let until = end_of_day.with_timezone(&Utc).format(NAIVE_DATE_TIME_PATTERN).to_string();
+
+    let transaction_list: Vec<InventoryTransactionRow> = inventory_transactions::table
+        .filter(inventory_transactions::created_at.le(&until))
+        .order(inventory_transactions::id)
+        .select(InventoryTransactionRow::as_select())
+        .load(conn)?;
+
+    let transactions = transaction_list.into_iter()
+        .map(|transaction| transaction.try_into())
+        .collect::<Result<Vec<InventoryTransaction>, _>>()?;
+
+    let transfer_sources: HashMap<i32, i32> = stock_transfers::table
+        .select((stock_transfers::inbound_transaction_id, stock_transfers::outbound_transaction_id))
+        .load::<(i32, i32)>(conn)?
+        .into_iter()
+        .collect();
+
+    Ok(value_stock(method, &transactions, &transfer_sources))
+}
@@ -1,5 +1,105 @@
 // @generated automatically by Diesel CLI.
 
+diesel::table! {
+    audit_log (id) {
+        id -> Integer,
+        entity -> Text,
+        entity_id -> Integer,
+        action -> Text,
+        changes -> Text,
+        created_at -> Text,
+    }
+}
+
+diesel::table! {
+    categories (id) {
+        id -> Integer,
+        name -> Text,
+        parent_id -> Nullable<Integer>,
+        created_at -> Text,
+        updated_at -> Text,
+        deleted_at -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    goods_receipt_lines (id) {
+        id -> Integer,
+        goods_receipt_id -> Integer,
+        purchase_order_line_id -> Integer,
+        quantity -> Integer,
+        inventory_transaction_id -> Integer,
+    }
+}
+
+diesel::table! {
+    goods_receipts (id) {
+        id -> Integer,
+        purchase_order_id -> Integer,
+        place_id -> Integer,
+        notes -> Nullable<Text>,
+        created_at -> Text,
+    }
+}
+
+diesel::table! {
+    inventory_transactions (id) {
+        id -> Integer,
+        product_id -> Integer,
+        place_id -> Integer,
+        supplier_id -> Nullable<Integer>,
+        quantity -> Integer,
+        transaction_type -> Text,
+        reason -> Nullable<Text>,
+        created_at -> Text,
+        lot_number -> Nullable<Text>,
+        expiry_date -> Nullable<Text>,
+        unit_cost -> Nullable<Double>,
+        reservation_id -> Nullable<Integer>,
+    }
+}
+
+diesel::table! {
+    issue_lines (id) {
+        id -> Integer,
+        issue_id -> Integer,
+        product_id -> Integer,
+        quantity -> Integer,
+        lot_number -> Nullable<Text>,
+        serial_numbers -> Nullable<Text>,
+        inventory_transaction_id -> Nullable<Integer>,
+        created_at -> Text,
+        updated_at -> Text,
+    }
+}
+
+diesel::table! {
+    issues (id) {
+        id -> Integer,
+        place_id -> Integer,
+        requester -> Text,
+        department -> Text,
+        destination -> Nullable<Text>,
+        status -> Text,
+        notes -> Nullable<Text>,
+        posted_at -> Nullable<Text>,
+        created_at -> Text,
+        updated_at -> Text,
+        deleted_at -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    kit_components (id) {
+        id -> Integer,
+        kit_product_id -> Integer,
+        component_product_id -> Integer,
+        quantity -> Integer,
+        created_at -> Text,
+        updated_at -> Text,
+    }
+}
+
 diesel::table! {
     places (id) {
         id -> Integer,
@@ -7,6 +107,35 @@ diesel::table! {
         created_at -> Text,
         updated_at -> Text,
         deleted_at -> Nullable<Text>,
+        parent_id -> Nullable<Integer>,
+        place_type -> Text,
+    }
+}
+
+diesel::table! {
+    product_barcodes (id) {
+        id -> Integer,
+        product_id -> Integer,
+        barcode -> Text,
+        symbology -> Text,
+        created_at -> Text,
+        deleted_at -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    product_suppliers (id) {
+        id -> Integer,
+        product_id -> Integer,
+        supplier_id -> Integer,
+        supplier_sku -> Nullable<Text>,
+        last_unit_cost -> Nullable<Double>,
+        currency -> Text,
+        min_order_quantity -> Integer,
+        lead_time_days -> Nullable<Integer>,
+        created_at -> Text,
+        updated_at -> Text,
+        preferred -> Bool,
     }
 }
 
@@ -21,6 +150,124 @@ diesel::table! {
         created_at -> Text,
         updated_at -> Text,
         deleted_at -> Nullable<Text>,
+        reorder_point -> Integer,
+        safety_stock -> Integer,
+        max_stock -> Integer,
+        tracks_lots -> Bool,
+        serialized -> Bool,
+        unit_id -> Nullable<Integer>,
+        category_id -> Nullable<Integer>,
+        sku -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    purchase_order_lines (id) {
+        id -> Integer,
+        purchase_order_id -> Integer,
+        product_id -> Integer,
+        quantity -> Integer,
+        received_quantity -> Integer,
+        unit_cost -> Nullable<Double>,
+        created_at -> Text,
+        updated_at -> Text,
+        unit_id -> Nullable<Integer>,
+    }
+}
+
+diesel::table! {
+    purchase_orders (id) {
+        id -> Integer,
+        supplier_id -> Integer,
+        status -> Text,
+        expected_date -> Nullable<Text>,
+        notes -> Nullable<Text>,
+        created_at -> Text,
+        updated_at -> Text,
+        deleted_at -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    reservations (id) {
+        id -> Integer,
+        product_id -> Integer,
+        place_id -> Nullable<Integer>,
+        quantity -> Integer,
+        consumed_quantity -> Integer,
+        reference -> Text,
+        notes -> Nullable<Text>,
+        expires_on -> Nullable<Text>,
+        status -> Text,
+        created_at -> Text,
+        updated_at -> Text,
+    }
+}
+
+diesel::table! {
+    serial_number_movements (id) {
+        id -> Integer,
+        serial_number_id -> Integer,
+        inventory_transaction_id -> Integer,
+    }
+}
+
+diesel::table! {
+    serial_numbers (id) {
+        id -> Integer,
+        product_id -> Integer,
+        serial_number -> Text,
+        place_id -> Nullable<Integer>,
+        created_at -> Text,
+        updated_at -> Text,
+    }
+}
+
+diesel::table! {
+    settings (key) {
+        key -> Text,
+        value -> Text,
+        updated_at -> Text,
+    }
+}
+
+diesel::table! {
+    stock_transfers (id) {
+        id -> Integer,
+        product_id -> Integer,
+        from_place_id -> Integer,
+        to_place_id -> Integer,
+        quantity -> Integer,
+        outbound_transaction_id -> Integer,
+        inbound_transaction_id -> Integer,
+        reason -> Nullable<Text>,
+        created_at -> Text,
+    }
+}
+
+diesel::table! {
+    stocktake_lines (id) {
+        id -> Integer,
+        stocktake_id -> Integer,
+        product_id -> Integer,
+        place_id -> Integer,
+        lot_number -> Nullable<Text>,
+        expected_quantity -> Integer,
+        counted_quantity -> Nullable<Integer>,
+        created_at -> Text,
+        updated_at -> Text,
+    }
+}
+
+diesel::table! {
+    stocktakes (id) {
+        id -> Integer,
+        place_id -> Nullable<Integer>,
+        status -> Text,
+        notes -> Nullable<Text>,
+        approved_at -> Nullable<Text>,
+        created_at -> Text,
+        updated_at -> Text,
     }
 }
 
@@ -31,7 +278,87 @@ diesel::table! {
         created_at -> Text,
         updated_at -> Text,
         deleted_at -> Nullable<Text>,
+        tax_id -> Nullable<Text>,
+        contact_name -> Nullable<Text>,
+        phone -> Nullable<Text>,
+        mobile_phone -> Nullable<Text>,
+        email -> Nullable<Text>,
+        address -> Nullable<Text>,
+        payment_terms -> Nullable<Text>,
+        notes -> Nullable<Text>,
+        active -> Bool,
     }
 }
 
-diesel::allow_tables_to_appear_in_same_query!(places, products, suppliers,);
+diesel::table! {
+    units (id) {
+        id -> Integer,
+        name -> Text,
+        symbol -> Text,
+        base_unit_id -> Nullable<Integer>,
+        factor -> Double,
+        created_at -> Text,
+        updated_at -> Text,
+        deleted_at -> Nullable<Text>,
+    }
+}
+
+diesel::joinable!(goods_receipt_lines -> goods_receipts (goods_receipt_id));
+diesel::joinable!(goods_receipt_lines -> inventory_transactions (inventory_transaction_id));
+diesel::joinable!(goods_receipt_lines -> purchase_order_lines (purchase_order_line_id));
+diesel::joinable!(goods_receipts -> places (place_id));
+diesel::joinable!(goods_receipts -> purchase_orders (purchase_order_id));
+diesel::joinable!(inventory_transactions -> places (place_id));
+diesel::joinable!(inventory_transactions -> products (product_id));
+diesel::joinable!(inventory_transactions -> reservations (reservation_id));
+diesel::joinable!(inventory_transactions -> suppliers (supplier_id));
+diesel::joinable!(issue_lines -> inventory_transactions (inventory_transaction_id));
+diesel::joinable!(issue_lines -> issues (issue_id));
+diesel::joinable!(issue_lines -> products (product_id));
+diesel::joinable!(issues -> places (place_id));
+diesel::joinable!(product_barcodes -> products (product_id));
+diesel::joinable!(product_suppliers -> products (product_id));
+diesel::joinable!(product_suppliers -> suppliers (supplier_id));
+diesel::joinable!(products -> categories (category_id));
+diesel::joinable!(products -> units (unit_id));
+diesel::joinable!(purchase_order_lines -> products (product_id));
+diesel::joinable!(purchase_order_lines -> purchase_orders (purchase_order_id));
+diesel::joinable!(purchase_order_lines -> units (unit_id));
+diesel::joinable!(purchase_orders -> suppliers (supplier_id));
+diesel::joinable!(reservations -> places (place_id));
+diesel::joinable!(reservations -> products (product_id));
+diesel::joinable!(serial_number_movements -> inventory_transactions (inventory_transaction_id));
+diesel::joinable!(serial_number_movements -> serial_numbers (serial_number_id));
+diesel::joinable!(serial_numbers -> places (place_id));
+diesel::joinable!(serial_numbers -> products (product_id));
+diesel::joinable!(stock_transfers -> products (product_id));
+diesel::joinable!(stocktake_lines -> places (place_id));
+diesel::joinable!(stocktake_lines -> products (product_id));
+diesel::joinable!(stocktake_lines -> stocktakes (stocktake_id));
+diesel::joinable!(stocktakes -> places (place_id));
+
+diesel::allow_tables_to_appear_in_same_query!(
+    audit_log,
+    categories,
+    goods_receipt_lines,
+    goods_receipts,
+    inventory_transactions,
+    issue_lines,
+    issues,
+    kit_components,
+    places,
+    product_barcodes,
+    product_suppliers,
+    products,
+    purchase_order_lines,
+    purchase_orders,
+    reservations,
+    serial_number_movements,
+    serial_numbers,
+    settings,
+    stock_transfers,
+    stocktake_lines,
+    stocktakes,
+    suppliers,
+    units,
+);
@@ -0,0 +1,75 @@
+use std::{io::Error, path::PathBuf};
+
+use rust_xlsxwriter::*;
+
+use crate::domain::issue::{Issue, IssueLine};
+use crate::domain::product::Product;
+
+pub fn export_issue(
+    issue: &Issue,
+    place_name: &str,
+    lines: &[IssueLine],
+    products: &[Product],
+    path_to_save: PathBuf,
+) -> Result<bool, Error> {
+    let mut workbook = Workbook::new();
+
+    let worksheet = workbook.add_worksheet();
+
+    let _ = worksheet.set_name("Issue");
+
+    let header_values = [
+        ("Issue", format!("#{}", issue.id)),
+        ("Date", issue.posted_at.unwrap_or(issue.created_at).format("%Y-%m-%d %H:%M").to_string()),
+        ("Requester", issue.requester.clone()),
+        ("Department", issue.department.clone()),
+        ("Destination", issue.destination.clone().unwrap_or_default()),
+        ("Issued from", place_name.to_owned()),
+        ("Status", issue.status.label().to_owned()),
+        ("Notes", issue.notes.clone().unwrap_or_default()),
+    ];
+
+    for (row, (label, value)) in header_values.iter().enumerate() {
+        let _ = worksheet.write(row as u32, 0, *label);
+        let _ = worksheet.write(row as u32, 1, value);
+    }
+
+    let first_line_row = (header_values.len() + 1) as u32;
+
+    let column_headers = [
+        "product_id",
+        "product",
+        "quantity",
+        "unit",
+        "lot_number",
+        "serial_numbers",
+    ];
+
+    for (column_number, header_name) in column_headers.iter().enumerate() {
+        let _ = worksheet.write(first_line_row, column_number as u16, *header_name);
+    }
+
+    for (index, line) in lines.iter().enumerate() {
+        let row = first_line_row + (index + 1) as u32;
+
+        let product = products.iter().find(|product| product.id == line.product_id);
+
+        let _ = worksheet.write(row, 0, line.product_id);
+        let _ = worksheet.write(row, 1, product.map(|product| product.name.clone()).unwrap_or_default());
+        let _ = worksheet.write(row, 2, line.quantity);
+        let _ = worksheet.write(row, 3, product.and_then(|product| product.unity.clone()).unwrap_or_default());
+        let _ = worksheet.write(row, 4, line.lot_number.clone().unwrap_or_default());
+        let _ = worksheet.write(row, 5, line.serial_numbers.join(", "));
+    }
+
+    // Left blank to be signed on the printed copy.
+    let signature_row = first_line_row + (lines.len() + 3) as u32;
+
+    for (offset, label) in ["Issued by", "Received by", "Date"].iter().enumerate() {
+        let _ = worksheet.write(signature_row + (offset * 2) as u32, 0, *label);
+    }
+
+    let _ = workbook.save(path_to_save);
+
+    Ok(true)
+}
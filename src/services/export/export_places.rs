@@ -2,16 +2,19 @@ use std::{io::Error, path::PathBuf};
 
 use rust_xlsxwriter::*;
 
-use crate::domain::place::Place;
+use crate::domain::place::{Place, place_path};
 
-pub fn export_places(places: &[Place], path_to_save: PathBuf) -> Result<bool, Error> {
+/// `all_places` resolves the paths of the exported places.
+pub fn export_places(places: &[Place], all_places: &[Place], path_to_save: PathBuf) -> Result<bool, Error> {
     let mut workbook = Workbook::new();
 
     let worksheet = workbook.add_worksheet();
 
     let column_headers = [
         "id",
+        "path",
         "name",
+        "type",
         "created_at",
         "updated_at",
     ];
@@ -27,7 +30,9 @@ pub fn export_places(places: &[Place], path_to_save: PathBuf) -> Result<bool, Er
 
         let values = vec![
             place.id.to_string(),
+            place_path(all_places, place.id),
             place.name.clone(),
+            place.place_type.as_str().to_owned(),
             place.created_at.to_string(),
             place.updated_at.to_string(),
         ];
@@ -41,4 +46,4 @@ pub fn export_places(places: &[Place], path_to_save: PathBuf) -> Result<bool, Er
     let _ = workbook.save(path_to_save);
 
     Ok(true)
-}
\ No newline at end of file
+}
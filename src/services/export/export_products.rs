@@ -2,9 +2,10 @@ use std::{io::Error, path::PathBuf};
 
 use rust_xlsxwriter::*;
 
+use crate::domain::category::{Category, category_path};
 use crate::domain::product::Product;
 
-pub fn export_products(products: &[Product], path_to_save: PathBuf) -> Result<bool, Error> {
+pub fn export_products(products: &[Product], categories: &[Category], path_to_save: PathBuf) -> Result<bool, Error> {
     let mut workbook = Workbook::new();
 
     let worksheet = workbook.add_worksheet();
@@ -15,9 +16,14 @@ pub fn export_products(products: &[Product], path_to_save: PathBuf) -> Result<bo
         "brand",
         "unity",
         "min_stock",
+        "reorder_point",
+        "safety_stock",
+        "max_stock",
         "observation",
         "created_at",
         "updated_at",
+        "category",
+        "sku",
     ];
 
     let _ = worksheet.set_name("Products");
@@ -35,9 +41,16 @@ pub fn export_products(products: &[Product], path_to_save: PathBuf) -> Result<bo
             product.brand.clone().unwrap_or_default(),
             product.unity.clone().unwrap_or_default(),
             product.min_stock.to_string(),
+            product.reorder_point.to_string(),
+            product.safety_stock.to_string(),
+            product.max_stock.to_string(),
             product.observation.clone().unwrap_or_default(),
             product.created_at.to_string(),
             product.updated_at.to_string(),
+            product.category_id
+                .map(|category_id| category_path(categories, category_id))
+                .unwrap_or_default(),
+            product.sku.clone().unwrap_or_default(),
         ];
 
         for (column, value) in values.iter().enumerate() {
@@ -0,0 +1,91 @@
+use std::{io::Error, path::PathBuf};
+
+use rust_xlsxwriter::*;
+
+use crate::domain::product::Product;
+use crate::domain::purchase_order::{PurchaseOrder, PurchaseOrderLine};
+use crate::domain::supplier::Supplier;
+use crate::domain::unit::Unit;
+
+pub fn export_purchase_order(
+    order: &PurchaseOrder,
+    supplier: &Supplier,
+    lines: &[PurchaseOrderLine],
+    products: &[Product],
+    units: &[Unit],
+    path_to_save: PathBuf,
+) -> Result<bool, Error> {
+    let mut workbook = Workbook::new();
+
+    let worksheet = workbook.add_worksheet();
+
+    let _ = worksheet.set_name("Purchase Order");
+
+    let header_values = [
+        ("Purchase Order", format!("#{}", order.id)),
+        ("Supplier", supplier.name.clone()),
+        ("Date", order.created_at.format("%Y-%m-%d").to_string()),
+        ("Expected", order.expected_date.map(|date| date.to_string()).unwrap_or_default()),
+        ("Status", order.status.label().to_owned()),
+        ("Notes", order.notes.clone().unwrap_or_default()),
+    ];
+
+    for (row, (label, value)) in header_values.iter().enumerate() {
+        let _ = worksheet.write(row as u32, 0, *label);
+        let _ = worksheet.write(row as u32, 1, value);
+    }
+
+    let first_line_row = (header_values.len() + 1) as u32;
+
+    let column_headers = [
+        "product_id",
+        "product",
+        "quantity",
+        "unit",
+        "unit_cost",
+        "total",
+    ];
+
+    for (column_number, header_name) in column_headers.iter().enumerate() {
+        let _ = worksheet.write(first_line_row, column_number as u16, *header_name);
+    }
+
+    for (index, line) in lines.iter().enumerate() {
+        let row = first_line_row + (index + 1) as u32;
+
+        let product = products.iter().find(|product| product.id == line.product_id);
+
+        let product_name = product
+            .map(|product| product.name.clone())
+            .unwrap_or_default();
+
+        let unit_symbol = line.unit_id
+            .and_then(|unit_id| units.iter().find(|unit| unit.id == unit_id))
+            .map(|unit| unit.symbol.clone())
+            .or_else(|| product.and_then(|product| product.unity.clone()))
+            .unwrap_or_default();
+
+        let _ = worksheet.write(row, 0, line.product_id);
+        let _ = worksheet.write(row, 1, product_name);
+        let _ = worksheet.write(row, 2, line.quantity);
+        let _ = worksheet.write(row, 3, unit_symbol);
+
+        if let Some(unit_cost) = line.unit_cost {
+            let _ = worksheet.write(row, 4, unit_cost);
+        }
+
+        if let Some(total) = line.total_cost() {
+            let _ = worksheet.write(row, 5, total);
+        }
+    }
+
+    let total_row = first_line_row + (lines.len() + 1) as u32;
+    let order_total: f64 = lines.iter().filter_map(|line| line.total_cost()).sum();
+
+    let _ = worksheet.write(total_row, 4, "Total");
+    let _ = worksheet.write(total_row, 5, order_total);
+
+    let _ = workbook.save(path_to_save);
+
+    Ok(true)
+}
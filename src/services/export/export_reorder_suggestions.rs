@@ -0,0 +1,57 @@
+use std::{io::Error, path::PathBuf};
+
+use rust_xlsxwriter::*;
+
+use crate::domain::reorder_suggestion::ReorderSuggestion;
+
+pub fn export_reorder_suggestions(suggestions: &[ReorderSuggestion], path_to_save: PathBuf) -> Result<bool, Error> {
+    let mut workbook = Workbook::new();
+
+    let worksheet = workbook.add_worksheet();
+
+    let column_headers = [
+        "supplier",
+        "supplier_sku",
+        "product_id",
+        "product",
+        "on_hand",
+        "on_order",
+        "reorder_point",
+        "max_stock",
+        "suggested_quantity",
+        "unit_cost",
+        "total",
+    ];
+
+    let _ = worksheet.set_name("Reorder Suggestions");
+
+    for (column_number, header_name) in column_headers.iter().enumerate() {
+        let _ = worksheet.write(0, column_number as u16, *header_name);
+    }
+
+    for (index, suggestion) in suggestions.iter().enumerate() {
+        let row = (index + 1) as u32;
+
+        let _ = worksheet.write(row, 0, suggestion.supplier_name());
+        let _ = worksheet.write(row, 1, suggestion.supplier_sku.clone().unwrap_or_default());
+        let _ = worksheet.write(row, 2, suggestion.product.id);
+        let _ = worksheet.write(row, 3, &suggestion.product.name);
+        let _ = worksheet.write(row, 4, suggestion.on_hand);
+        let _ = worksheet.write(row, 5, suggestion.on_order);
+        let _ = worksheet.write(row, 6, suggestion.product.reorder_point);
+        let _ = worksheet.write(row, 7, suggestion.product.max_stock);
+        let _ = worksheet.write(row, 8, suggestion.suggested_quantity);
+
+        if let Some(unit_cost) = suggestion.unit_cost {
+            let _ = worksheet.write(row, 9, unit_cost);
+        }
+
+        if let Some(total) = suggestion.total_cost() {
+            let _ = worksheet.write(row, 10, total);
+        }
+    }
+
+    let _ = workbook.save(path_to_save);
+
+    Ok(true)
+}
@@ -0,0 +1,74 @@
+use std::{io::Error, path::PathBuf};
+
+use chrono::NaiveDate;
+use rust_xlsxwriter::*;
+
+use crate::domain::place::{Place, place_path};
+use crate::domain::product::Product;
+use crate::domain::valuation::{CostingMethod, StockValuation};
+
+const TABLE_FIRST_ROW: u32 = 5;
+
+pub fn export_stock_valuation(
+    valuations: &[StockValuation],
+    products: &[Product],
+    places: &[Place],
+    method: CostingMethod,
+    as_of: NaiveDate,
+    path_to_save: PathBuf,
+) -> Result<bool, Error> {
+    let mut workbook = Workbook::new();
+
+    let worksheet = workbook.add_worksheet();
+
+    let _ = worksheet.set_name("Stock Valuation");
+
+    let total: f64 = valuations.iter().map(|valuation| valuation.value).sum();
+
+    let _ = worksheet.write(0, 0, "Stock Valuation");
+    let _ = worksheet.write(1, 0, "As of");
+    let _ = worksheet.write(1, 1, as_of.to_string());
+    let _ = worksheet.write(2, 0, "Method");
+    let _ = worksheet.write(2, 1, method.label());
+    let _ = worksheet.write(3, 0, "Total");
+    let _ = worksheet.write(3, 1, total);
+
+    let column_headers = [
+        "product_id",
+        "product",
+        "sku",
+        "place",
+        "quantity",
+        "unit",
+        "unit_cost",
+        "value",
+    ];
+
+    for (column_number, header_name) in column_headers.iter().enumerate() {
+        let _ = worksheet.write(TABLE_FIRST_ROW, column_number as u16, *header_name);
+    }
+
+    for (index, valuation) in valuations.iter().enumerate() {
+        let row = TABLE_FIRST_ROW + 1 + index as u32;
+
+        let product = products.iter().find(|product| product.id == valuation.product_id);
+        let place = places.iter().find(|place| place.id == valuation.place_id);
+
+        let _ = worksheet.write(row, 0, valuation.product_id);
+        let _ = worksheet.write(row, 1, product.map(|product| product.name.clone()).unwrap_or_default());
+        let _ = worksheet.write(row, 2, product.and_then(|product| product.sku.clone()).unwrap_or_default());
+        let _ = worksheet.write(row, 3, place.map(|place| place_path(places, place.id)).unwrap_or_default());
+        let _ = worksheet.write(row, 4, valuation.quantity as f64);
+        let _ = worksheet.write(row, 5, product.and_then(|product| product.unity.clone()).unwrap_or_default());
+
+        if let Some(unit_cost) = valuation.unit_cost() {
+            let _ = worksheet.write(row, 6, unit_cost);
+        }
+
+        let _ = worksheet.write(row, 7, valuation.value);
+    }
+
+    let _ = workbook.save(path_to_save);
+
+    Ok(true)
+}
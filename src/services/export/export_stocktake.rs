@@ -0,0 +1,67 @@
+use std::{io::Error, path::PathBuf};
+
+use rust_xlsxwriter::*;
+
+use crate::domain::place::{Place, place_path};
+use crate::domain::product::Product;
+use crate::domain::stocktake::StocktakeLine;
+
+/// Writes the count sheet of a stocktake. The "counted" column is filled in on
+/// the floor and the file imported back; rows added at the bottom need the
+/// product_id, place and lot_number and no line_id.
+pub fn export_stocktake(
+    lines: &[StocktakeLine],
+    products: &[Product],
+    places: &[Place],
+    path_to_save: PathBuf,
+) -> Result<bool, Error> {
+    let mut workbook = Workbook::new();
+
+    let worksheet = workbook.add_worksheet();
+
+    let _ = worksheet.set_name("Count Sheet");
+
+    let column_headers = [
+        "line_id",
+        "product_id",
+        "product",
+        "sku",
+        "place",
+        "lot_number",
+        "unit",
+        "expected",
+        "counted",
+    ];
+
+    for (column_number, header_name) in column_headers.iter().enumerate() {
+        let _ = worksheet.write(0, column_number as u16, *header_name);
+    }
+
+    for (index, line) in lines.iter().enumerate() {
+        let row = (index + 1) as u32;
+
+        let product = products.iter().find(|product| product.id == line.product_id);
+
+        let place_name = places.iter()
+            .find(|place| place.id == line.place_id)
+            .map(|place| place_path(places, place.id))
+            .unwrap_or_default();
+
+        let _ = worksheet.write(row, 0, line.id);
+        let _ = worksheet.write(row, 1, line.product_id);
+        let _ = worksheet.write(row, 2, product.map(|product| product.name.clone()).unwrap_or_default());
+        let _ = worksheet.write(row, 3, product.and_then(|product| product.sku.clone()).unwrap_or_default());
+        let _ = worksheet.write(row, 4, place_name);
+        let _ = worksheet.write(row, 5, line.lot_number.clone().unwrap_or_default());
+        let _ = worksheet.write(row, 6, product.and_then(|product| product.unity.clone()).unwrap_or_default());
+        let _ = worksheet.write(row, 7, line.expected_quantity);
+
+        if let Some(counted_quantity) = line.counted_quantity {
+            let _ = worksheet.write(row, 8, counted_quantity);
+        }
+    }
+
+    let _ = workbook.save(path_to_save);
+
+    Ok(true)
+}
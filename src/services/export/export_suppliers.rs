@@ -12,6 +12,15 @@ pub fn export_suppliers(suppliers: &[Supplier], path_to_save: PathBuf) -> Result
     let column_headers = [
         "id",
         "name",
+        "tax_id",
+        "contact_name",
+        "phone",
+        "mobile_phone",
+        "email",
+        "address",
+        "payment_terms",
+        "notes",
+        "active",
         "created_at",
         "updated_at",
     ];
@@ -28,6 +37,15 @@ pub fn export_suppliers(suppliers: &[Supplier], path_to_save: PathBuf) -> Result
         let values = vec![
             supplier.id.to_string(),
             supplier.name.clone(),
+            supplier.formatted_tax_id().unwrap_or_default(),
+            supplier.contact_name.clone().unwrap_or_default(),
+            supplier.phone.clone().unwrap_or_default(),
+            supplier.mobile_phone.clone().unwrap_or_default(),
+            supplier.email.clone().unwrap_or_default(),
+            supplier.address.clone().unwrap_or_default(),
+            supplier.payment_terms.clone().unwrap_or_default(),
+            supplier.notes.clone().unwrap_or_default(),
+            (if supplier.active { "yes" } else { "no" }).to_owned(),
             supplier.created_at.to_string(),
             supplier.updated_at.to_string(),
         ];
@@ -1,3 +1,8 @@
+pub mod export_issue;
 pub mod export_places;
 pub mod export_products;
+pub mod export_purchase_order;
+pub mod export_reorder_suggestions;
+pub mod export_stock_valuation;
+pub mod export_stocktake;
 pub mod export_suppliers;
\ No newline at end of file
@@ -4,32 +4,56 @@ use std::error::Error;
 use calamine::{open_workbook, Xlsx, Reader, RangeDeserializerBuilder};
 use serde::Deserialize;
 
-use crate::infra::models::NewPlaceRow;
+use crate::domain::place::PlaceType;
 
 #[derive(Deserialize)]
 struct PlaceRecord {
-    name: String,
+    #[serde(alias = "name")]
+    path: String,
+    #[serde(rename = "type")]
+    place_type: Option<String>,
 }
 
-pub fn import_places(path: PathBuf) -> Result<Vec<NewPlaceRow>, Box<dyn Error>> {
+/// An imported place as its "WH1/A/03" path, resolved to existing places or
+/// created level by level when imported.
+pub struct ImportedPlace {
+    pub path: String,
+    pub place_type: Option<PlaceType>,
+}
+
+pub fn import_places(path: PathBuf) -> Result<Vec<ImportedPlace>, Box<dyn Error>> {
 
     let mut workbook: Xlsx<_> = open_workbook(path).unwrap();
 
     let range = workbook.worksheet_range("Places")?;
-    
-    let headers = &["name"];
 
-    let iter_records = RangeDeserializerBuilder::with_headers(headers).from_range(&range)?;
+    // Sheets exported by older versions only carry top-level names.
+    let has_header = |name: &str| range.rows().next()
+        .is_some_and(|header_row| header_row.iter().any(|cell| cell.to_string().trim() == name));
+
+    let mut headers = vec![if has_header("path") { "path" } else { "name" }];
+
+    if has_header("type") {
+        headers.push("type");
+    }
+
+    let iter_records = RangeDeserializerBuilder::with_headers(&headers).from_range(&range)?;
     
-    let valid_records = iter_records
-        .filter_map(|result: Result<PlaceRecord, calamine::DeError>| {
-            match result {
-                Ok(record) => {
-                    Some(NewPlaceRow { name: record.name })
-                },
-                _ => None
-            }
-        }).collect();
+    let mut valid_records = Vec::new();
+
+    for result in iter_records {
+        let record: PlaceRecord = match result {
+            Ok(record) => record,
+            Err(_) => continue,
+        };
+
+        let place_type = match record.place_type.as_deref().map(str::trim).filter(|value| !value.is_empty()) {
+            Some(value) => Some(PlaceType::try_from(value)?),
+            None => None,
+        };
+
+        valid_records.push(ImportedPlace { path: record.path, place_type });
+    }
 
     Ok(valid_records)
-}
\ No newline at end of file
+}
@@ -12,29 +12,64 @@ struct ProductRecord {
     brand: Option<String>,
     unity: Option<String>,
     min_stock: Option<i32>,
+    reorder_point: Option<i32>,
+    safety_stock: Option<i32>,
+    max_stock: Option<i32>,
     observation: Option<String>,
+    category: Option<String>,
+    sku: Option<String>,
 }
 
-pub fn import_products(path: PathBuf) -> Result<Vec<NewProductRow>, Box<dyn Error>> {
+/// An imported product with the "Parent > Child" path of its category, resolved
+/// to a category when the products are created.
+pub struct ImportedProduct {
+    pub product: NewProductRow,
+    pub category_path: Option<String>,
+}
+
+pub fn import_products(path: PathBuf) -> Result<Vec<ImportedProduct>, Box<dyn Error>> {
 
     let mut workbook: Xlsx<_> = open_workbook(path).unwrap();
 
     let range = workbook.worksheet_range("Products")?;
     
-    let headers = &["name", "brand", "unity", "min_stock", "observation"];
+    let mut headers = vec!["name", "brand", "unity", "min_stock", "observation"];
+
+    // Sheets exported by older versions lack the newer columns.
+    let has_header = |name: &str| range.rows().next()
+        .is_some_and(|header_row| header_row.iter().any(|cell| cell.to_string().trim() == name));
 
-    let iter_records = RangeDeserializerBuilder::with_headers(headers).from_range(&range)?;
+    for optional_header in ["reorder_point", "safety_stock", "max_stock", "category", "sku"] {
+        if has_header(optional_header) {
+            headers.push(optional_header);
+        }
+    }
+
+    let iter_records = RangeDeserializerBuilder::with_headers(&headers).from_range(&range)?;
     
     let valid_records = iter_records
         .filter_map(|result: Result<ProductRecord, calamine::DeError>| {
             match result {
                 Ok(record) => {
-                    Some(NewProductRow {
+                    let product = NewProductRow {
                         name: record.name,
                         unity: record.unity,
                         brand: record.brand,
                         min_stock: record.min_stock,
                         observation: record.observation,
+                        reorder_point: record.reorder_point,
+                        safety_stock: record.safety_stock,
+                        max_stock: record.max_stock,
+                        tracks_lots: None,
+                        serialized: None,
+                        unit_id: None,
+                        category_id: None,
+                        sku: record.sku.map(|sku| sku.trim().to_owned()).filter(|sku| !sku.is_empty()),
+                    };
+
+                    Some(ImportedProduct {
+                        product,
+                        category_path: record.category.filter(|path| !path.trim().is_empty()),
                     })
                 },
                 _ => None
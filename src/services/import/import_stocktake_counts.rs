@@ -0,0 +1,50 @@
+use std::path::PathBuf;
+use std::error::Error;
+
+use calamine::{open_workbook, Xlsx, Reader, RangeDeserializerBuilder};
+use serde::Deserialize;
+
+use crate::domain::stocktake::StocktakeCount;
+
+#[derive(Deserialize)]
+struct CountRecord {
+    line_id: Option<i32>,
+    product_id: Option<i32>,
+    place: Option<String>,
+    lot_number: Option<String>,
+    counted: Option<i32>,
+}
+
+/// Reads the counts of a count sheet written by `export_stocktake`. Rows left
+/// without a count are skipped.
+pub fn import_stocktake_counts(path: PathBuf) -> Result<Vec<StocktakeCount>, Box<dyn Error>> {
+
+    let mut workbook: Xlsx<_> = open_workbook(path)?;
+
+    let range = workbook.worksheet_range("Count Sheet")?;
+
+    let headers = &["line_id", "product_id", "place", "lot_number", "counted"];
+
+    let iter_records = RangeDeserializerBuilder::with_headers(headers).from_range(&range)?;
+
+    let mut counts = Vec::new();
+
+    for (index, result) in iter_records.enumerate() {
+        let record: CountRecord = result
+            .map_err(|error| format!("Row {} of the count sheet is invalid: {}", index + 2, error))?;
+
+        let Some(counted_quantity) = record.counted else {
+            continue;
+        };
+
+        counts.push(StocktakeCount {
+            line_id: record.line_id,
+            product_id: record.product_id,
+            place_name: record.place,
+            lot_number: record.lot_number,
+            counted_quantity,
+        });
+    }
+
+    Ok(counts)
+}
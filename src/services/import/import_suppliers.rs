@@ -5,10 +5,32 @@ use calamine::{open_workbook, Xlsx, Reader, RangeDeserializerBuilder};
 use serde::Deserialize;
 
 use crate::infra::models::NewSupplierRow;
+use crate::domain::tax_id::normalize_tax_id;
+
+const DETAIL_HEADERS: [&str; 9] = [
+    "tax_id",
+    "contact_name",
+    "phone",
+    "mobile_phone",
+    "email",
+    "address",
+    "payment_terms",
+    "notes",
+    "active",
+];
 
 #[derive(Deserialize)]
 struct SupplierRecord {
     name: String,
+    tax_id: Option<String>,
+    contact_name: Option<String>,
+    phone: Option<String>,
+    mobile_phone: Option<String>,
+    email: Option<String>,
+    address: Option<String>,
+    payment_terms: Option<String>,
+    notes: Option<String>,
+    active: Option<String>,
 }
 
 pub fn import_suppliers(path: PathBuf) -> Result<Vec<NewSupplierRow>, Box<dyn Error>> {
@@ -17,19 +39,46 @@ pub fn import_suppliers(path: PathBuf) -> Result<Vec<NewSupplierRow>, Box<dyn Er
 
     let range = workbook.worksheet_range("Suppliers")?;
     
-    let headers = &["name"];
+    let mut headers = vec!["name"];
+
+    // Sheets exported by older versions only carry the name.
+    let has_header = |name: &str| range.rows().next()
+        .is_some_and(|header_row| header_row.iter().any(|cell| cell.to_string().trim() == name));
+
+    for optional_header in DETAIL_HEADERS {
+        if has_header(optional_header) {
+            headers.push(optional_header);
+        }
+    }
 
-    let iter_records = RangeDeserializerBuilder::with_headers(headers).from_range(&range)?;
+    let iter_records = RangeDeserializerBuilder::with_headers(&headers).from_range(&range)?;
+
+    let optional = |value: Option<String>| value
+        .map(|value| value.trim().to_owned())
+        .filter(|value| !value.is_empty());
     
     let valid_records = iter_records
         .filter_map(|result: Result<SupplierRecord, calamine::DeError>| {
             match result {
                 Ok(record) => {
-                    Some(NewSupplierRow { name: record.name })
+                    Some(NewSupplierRow {
+                        name: record.name,
+                        tax_id: optional(record.tax_id.map(|tax_id| normalize_tax_id(&tax_id))),
+                        contact_name: optional(record.contact_name),
+                        phone: optional(record.phone),
+                        mobile_phone: optional(record.mobile_phone),
+                        email: optional(record.email),
+                        address: optional(record.address),
+                        payment_terms: optional(record.payment_terms),
+                        notes: optional(record.notes),
+                        active: optional(record.active).map(|active| {
+                            !matches!(active.to_lowercase().as_str(), "no" | "false" | "0" | "inactive")
+                        }),
+                    })
                 },
                 _ => None
             }
         }).collect();
 
     Ok(valid_records)
-}
\ No newline at end of file
+}
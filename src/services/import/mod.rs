@@ -1,3 +1,4 @@
 pub mod import_places;
 pub mod import_products;
+pub mod import_stocktake_counts;
 pub mod import_suppliers;
\ No newline at end of file
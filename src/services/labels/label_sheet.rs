@@ -0,0 +1,371 @@
+use std::error::Error;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use qrcode::{Color, QrCode};
+
+const POINTS_PER_MM: f64 = 72.0 / 25.4;
+
+/// Average glyph width of Helvetica as a fraction of the font size, used to
+/// shorten text that would not fit on a label.
+const AVERAGE_CHAR_WIDTH: f64 = 0.55;
+
+/// Page and label geometry, in millimetres. Labels are laid out left to right,
+/// top to bottom, starting at the top-left margin.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LabelLayout {
+    pub page_width: f64,
+    pub page_height: f64,
+    pub label_width: f64,
+    pub label_height: f64,
+    pub columns: u32,
+    pub rows: u32,
+    pub margin_left: f64,
+    pub margin_top: f64,
+    pub gap_x: f64,
+    pub gap_y: f64,
+}
+
+impl LabelLayout {
+    /// Common label stocks.
+    pub const PRESETS: [(&'static str, LabelLayout); 5] = [
+        ("A4, 3 × 8 (70 × 37 mm)", LabelLayout {
+            page_width: 210.0, page_height: 297.0, label_width: 70.0, label_height: 37.0,
+            columns: 3, rows: 8, margin_left: 0.0, margin_top: 0.5, gap_x: 0.0, gap_y: 0.0,
+        }),
+        ("A4, 2 × 7 (99.1 × 38.1 mm)", LabelLayout {
+            page_width: 210.0, page_height: 297.0, label_width: 99.1, label_height: 38.1,
+            columns: 2, rows: 7, margin_left: 4.65, margin_top: 15.15, gap_x: 2.5, gap_y: 0.0,
+        }),
+        ("A4, 4 × 10 (48.5 × 25.4 mm)", LabelLayout {
+            page_width: 210.0, page_height: 297.0, label_width: 48.5, label_height: 25.4,
+            columns: 4, rows: 10, margin_left: 8.0, margin_top: 21.5, gap_x: 0.0, gap_y: 0.0,
+        }),
+        ("Letter, 3 × 10 (66.7 × 25.4 mm)", LabelLayout {
+            page_width: 215.9, page_height: 279.4, label_width: 66.7, label_height: 25.4,
+            columns: 3, rows: 10, margin_left: 4.8, margin_top: 12.7, gap_x: 3.1, gap_y: 0.0,
+        }),
+        ("Thermal roll, 50 × 30 mm", LabelLayout {
+            page_width: 50.0, page_height: 30.0, label_width: 50.0, label_height: 30.0,
+            columns: 1, rows: 1, margin_left: 0.0, margin_top: 0.0, gap_x: 0.0, gap_y: 0.0,
+        }),
+    ];
+
+    pub fn labels_per_page(&self) -> usize {
+        (self.columns * self.rows) as usize
+    }
+
+    /// Checks that the grid of labels fits on the page.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.columns == 0 || self.rows == 0 {
+            return Err("There must be at least one row and one column".into());
+        }
+
+        if self.label_width <= 0.0 || self.label_height <= 0.0 {
+            return Err("Labels must be wider and taller than zero".into());
+        }
+
+        let used_width = self.margin_left + f64::from(self.columns) * self.label_width + f64::from(self.columns - 1) * self.gap_x;
+        let used_height = self.margin_top + f64::from(self.rows) * self.label_height + f64::from(self.rows - 1) * self.gap_y;
+
+        if used_width > self.page_width + 0.01 || used_height > self.page_height + 0.01 {
+            return Err(format!("The labels need {:.1} × {:.1} mm but the page is {:.1} × {:.1} mm", used_width, used_height, self.page_width, self.page_height));
+        }
+
+        Ok(())
+    }
+
+    /// Top-left corner of the label at `index` within its page.
+    fn label_origin(&self, index: usize) -> (f64, f64) {
+        let column = (index % self.columns as usize) as f64;
+        let row = (index / self.columns as usize) as f64;
+
+        (
+            self.margin_left + column * (self.label_width + self.gap_x),
+            self.margin_top + row * (self.label_height + self.gap_y),
+        )
+    }
+}
+
+/// A drawing primitive, positioned in millimetres from the top-left corner of
+/// its label. Text is placed by its baseline.
+#[derive(Debug, Clone)]
+pub enum Shape {
+    Rect { x: f64, y: f64, width: f64, height: f64 },
+    Text { x: f64, y: f64, size: f64, text: String },
+}
+
+/// Collects labels and writes them as a PDF, or as SVG with one file per page,
+/// depending on the extension of the path saved to.
+pub struct LabelSheet {
+    layout: LabelLayout,
+    labels: Vec<Vec<Shape>>,
+}
+
+impl LabelSheet {
+    pub fn new(layout: LabelLayout) -> Self {
+        Self {
+            layout,
+            labels: Vec::new(),
+        }
+    }
+
+    pub fn add_label(&mut self, shapes: Vec<Shape>) {
+        self.labels.push(shapes);
+    }
+
+    pub fn save(&self, path: PathBuf) -> Result<(), Box<dyn Error>> {
+        self.layout.validate()?;
+
+        if self.labels.is_empty() {
+            return Err("There are no labels to print".into());
+        }
+
+        let is_svg = path.extension().is_some_and(|extension| extension.eq_ignore_ascii_case("svg"));
+
+        if is_svg {
+            self.save_svg(&path)
+        } else {
+            fs::write(path, self.pdf_bytes())?;
+            Ok(())
+        }
+    }
+
+    /// Shapes of every page, moved from label to page coordinates.
+    fn pages(&self) -> Vec<Vec<Shape>> {
+        self.labels
+            .chunks(self.layout.labels_per_page())
+            .map(|page_labels| {
+                page_labels.iter().enumerate().flat_map(|(index, shapes)| {
+                    let (left, top) = self.layout.label_origin(index);
+
+                    shapes.iter().map(move |shape| match shape {
+                        Shape::Rect { x, y, width, height } => Shape::Rect { x: x + left, y: y + top, width: *width, height: *height },
+                        Shape::Text { x, y, size, text } => Shape::Text { x: x + left, y: y + top, size: *size, text: text.clone() },
+                    })
+                }).collect()
+            })
+            .collect()
+    }
+
+    /// Writes `labels.svg`, `labels-2.svg`, ... as SVG has no notion of pages.
+    fn save_svg(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        let stem = path.file_stem().map(|stem| stem.to_string_lossy().into_owned()).unwrap_or_else(|| "labels".to_owned());
+
+        for (index, page) in self.pages().iter().enumerate() {
+            let page_path = if index == 0 {
+                path.to_path_buf()
+            } else {
+                path.with_file_name(format!("{}-{}.svg", stem, index + 1))
+            };
+
+            fs::write(page_path, self.svg_page(page))?;
+        }
+
+        Ok(())
+    }
+
+    fn svg_page(&self, shapes: &[Shape]) -> String {
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}mm\" height=\"{h}mm\" viewBox=\"0 0 {w} {h}\">\n<rect width=\"{w}\" height=\"{h}\" fill=\"#ffffff\"/>\n",
+            w = self.layout.page_width,
+            h = self.layout.page_height,
+        );
+
+        for shape in shapes {
+            match shape {
+                Shape::Rect { x, y, width, height } => {
+                    let _ = writeln!(svg, "<rect x=\"{:.3}\" y=\"{:.3}\" width=\"{:.3}\" height=\"{:.3}\" fill=\"#000000\"/>", x, y, width, height);
+                }
+                Shape::Text { x, y, size, text } => {
+                    let _ = writeln!(
+                        svg,
+                        "<text x=\"{:.3}\" y=\"{:.3}\" font-size=\"{:.3}\" font-family=\"Helvetica, Arial, sans-serif\">{}</text>",
+                        x, y, size, escape_xml(text)
+                    );
+                }
+            }
+        }
+
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    /// A minimal PDF: one content stream per page drawn with the built-in
+    /// Helvetica font, so no font files have to be embedded.
+    fn pdf_bytes(&self) -> Vec<u8> {
+        let pages = self.pages();
+        let page_width = self.layout.page_width * POINTS_PER_MM;
+        let page_height = self.layout.page_height * POINTS_PER_MM;
+
+        // Objects 1-3 are the catalog, the page tree and the font; each page
+        // then takes two objects, the page and its content stream.
+        let mut objects: Vec<Vec<u8>> = Vec::new();
+
+        let page_ids: Vec<usize> = (0..pages.len()).map(|index| 4 + index * 2).collect();
+
+        objects.push(b"<< /Type /Catalog /Pages 2 0 R >>".to_vec());
+        objects.push(format!(
+            "<< /Type /Pages /Kids [{}] /Count {} >>",
+            page_ids.iter().map(|id| format!("{} 0 R", id)).collect::<Vec<_>>().join(" "),
+            pages.len()
+        ).into_bytes());
+        objects.push(b"<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>".to_vec());
+
+        for (page, page_id) in pages.iter().zip(&page_ids) {
+            let content = pdf_content(page, page_height);
+
+            objects.push(format!(
+                "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {:.2} {:.2}] /Resources << /Font << /F1 3 0 R >> >> /Contents {} 0 R >>",
+                page_width, page_height, page_id + 1
+            ).into_bytes());
+
+            let mut stream = format!("<< /Length {} >>\nstream\n", content.len()).into_bytes();
+            stream.extend_from_slice(&content);
+            stream.extend_from_slice(b"\nendstream");
+            objects.push(stream);
+        }
+
+        let mut pdf = b"%PDF-1.4\n".to_vec();
+        let mut offsets = Vec::with_capacity(objects.len());
+
+        for (index, object) in objects.iter().enumerate() {
+            offsets.push(pdf.len());
+            pdf.extend_from_slice(format!("{} 0 obj\n", index + 1).as_bytes());
+            pdf.extend_from_slice(object);
+            pdf.extend_from_slice(b"\nendobj\n");
+        }
+
+        let xref_offset = pdf.len();
+        pdf.extend_from_slice(format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1).as_bytes());
+
+        for offset in offsets {
+            pdf.extend_from_slice(format!("{:010} 00000 n \n", offset).as_bytes());
+        }
+
+        pdf.extend_from_slice(format!(
+            "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n",
+            objects.len() + 1,
+            xref_offset
+        ).as_bytes());
+
+        pdf
+    }
+}
+
+fn pdf_content(shapes: &[Shape], page_height: f64) -> Vec<u8> {
+    let mut content = Vec::new();
+
+    for shape in shapes {
+        match shape {
+            Shape::Rect { x, y, width, height } => {
+                // PDF measures from the bottom-left corner.
+                content.extend_from_slice(format!(
+                    "{:.3} {:.3} {:.3} {:.3} re f\n",
+                    x * POINTS_PER_MM,
+                    page_height - (y + height) * POINTS_PER_MM,
+                    width * POINTS_PER_MM,
+                    height * POINTS_PER_MM
+                ).as_bytes());
+            }
+            Shape::Text { x, y, size, text } => {
+                content.extend_from_slice(format!(
+                    "BT /F1 {:.2} Tf {:.3} {:.3} Td (",
+                    size * POINTS_PER_MM,
+                    x * POINTS_PER_MM,
+                    page_height - y * POINTS_PER_MM
+                ).as_bytes());
+                content.extend_from_slice(&pdf_string(text));
+                content.extend_from_slice(b") Tj ET\n");
+            }
+        }
+    }
+
+    content
+}
+
+/// Encodes text for a WinAnsi font, which covers Latin-1; other characters
+/// are printed as '?'.
+fn pdf_string(text: &str) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(text.len());
+
+    for char in text.chars() {
+        match char {
+            '(' | ')' | '\\' => {
+                bytes.push(b'\\');
+                bytes.push(char as u8);
+            }
+            ' '..='~' | '\u{A0}'..='\u{FF}' => bytes.push(char as u32 as u8),
+            _ => bytes.push(b'?'),
+        }
+    }
+
+    bytes
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Shortens `text` with "..." so it fits `max_width` at font `size`.
+pub fn fit_text(text: &str, size: f64, max_width: f64) -> String {
+    let max_chars = (max_width / (size * AVERAGE_CHAR_WIDTH)).floor() as usize;
+
+    if text.chars().count() <= max_chars {
+        return text.to_owned();
+    }
+
+    let kept: String = text.chars().take(max_chars.saturating_sub(3)).collect();
+    format!("{}...", kept.trim_end())
+}
+
+/// Bars of a 1D barcode (1 = bar, 0 = space) drawn into the given box, merging
+/// adjacent bars into a single rectangle.
+pub fn barcode_shapes(modules: &[u8], x: f64, y: f64, width: f64, height: f64) -> Vec<Shape> {
+    let module_width = width / modules.len() as f64;
+    let mut shapes = Vec::new();
+    let mut index = 0;
+
+    while index < modules.len() {
+        if modules[index] == 1 {
+            let start = index;
+
+            while index < modules.len() && modules[index] == 1 {
+                index += 1;
+            }
+
+            shapes.push(Shape::Rect {
+                x: x + start as f64 * module_width,
+                y,
+                width: (index - start) as f64 * module_width,
+                height,
+            });
+        } else {
+            index += 1;
+        }
+    }
+
+    shapes
+}
+
+/// Dark modules of a QR code drawn as a `size` × `size` square, one row of
+/// modules at a time.
+pub fn qr_shapes(code: &QrCode, x: f64, y: f64, size: f64) -> Vec<Shape> {
+    let width = code.width();
+    let module_size = size / width as f64;
+
+    let modules: Vec<u8> = code.to_colors()
+        .iter()
+        .map(|color| u8::from(*color == Color::Dark))
+        .collect();
+
+    modules
+        .chunks(width)
+        .enumerate()
+        .flat_map(|(row, row_modules)| barcode_shapes(row_modules, x, y + row as f64 * module_size, size, module_size))
+        .collect()
+}
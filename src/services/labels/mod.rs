@@ -0,0 +1,4 @@
+pub mod label_sheet;
+pub mod print_issue_receipt;
+pub mod print_place_labels;
+pub mod print_product_labels;
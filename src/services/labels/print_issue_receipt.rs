@@ -0,0 +1,155 @@
+use std::error::Error;
+use std::path::PathBuf;
+
+use crate::domain::issue::{Issue, IssueLine};
+use crate::domain::product::Product;
+use crate::services::labels::label_sheet::{LabelLayout, LabelSheet, Shape, fit_text};
+
+/// One A4 sheet per "label"; the receipt draws its own margins.
+const RECEIPT_LAYOUT: LabelLayout = LabelLayout {
+    page_width: 210.0, page_height: 297.0, label_width: 210.0, label_height: 297.0,
+    columns: 1, rows: 1, margin_left: 0.0, margin_top: 0.0, gap_x: 0.0, gap_y: 0.0,
+};
+
+const MARGIN: f64 = 15.0;
+const TITLE_SIZE: f64 = 6.0;
+const TEXT_SIZE: f64 = 3.5;
+const ROW_HEIGHT: f64 = 6.5;
+const RULE_WIDTH: f64 = 0.3;
+const LINES_PER_PAGE: usize = 22;
+
+const PRODUCT_X: f64 = MARGIN;
+const DETAIL_X: f64 = 105.0;
+const QUANTITY_X: f64 = 160.0;
+const UNIT_X: f64 = 180.0;
+
+/// Receipt sheet for an issue: header, the lines handed out and signature
+/// fields for whoever issues and receives the material. Long issues continue
+/// on further pages; the signatures go on the last one.
+pub fn print_issue_receipt(issue: &Issue, place_name: &str, lines: &[IssueLine], products: &[Product], path: PathBuf) -> Result<(), Box<dyn Error>> {
+    let mut sheet = LabelSheet::new(RECEIPT_LAYOUT);
+
+    let pages: Vec<&[IssueLine]> = if lines.is_empty() {
+        vec![&[]]
+    } else {
+        lines.chunks(LINES_PER_PAGE).collect()
+    };
+
+    for (page_index, page_lines) in pages.iter().enumerate() {
+        let mut shapes = header_shapes(issue, place_name);
+        let mut y = MARGIN + 78.0;
+
+        shapes.extend(table_header_shapes(y));
+        y += ROW_HEIGHT;
+
+        for line in page_lines.iter() {
+            shapes.extend(line_shapes(line, products, y));
+            y += ROW_HEIGHT;
+        }
+
+        if page_index == pages.len() - 1 {
+            shapes.extend(signature_shapes());
+        }
+
+        shapes.push(Shape::Text {
+            x: QUANTITY_X,
+            y: RECEIPT_LAYOUT.page_height - MARGIN / 2.0,
+            size: TEXT_SIZE * 0.8,
+            text: format!("Page {} of {}", page_index + 1, pages.len()),
+        });
+
+        sheet.add_label(shapes);
+    }
+
+    sheet.save(path)
+}
+
+fn header_shapes(issue: &Issue, place_name: &str) -> Vec<Shape> {
+    let mut shapes = vec![Shape::Text {
+        x: MARGIN,
+        y: MARGIN + TITLE_SIZE,
+        size: TITLE_SIZE,
+        text: format!("Material Issue #{}", issue.id),
+    }];
+
+    let header_values = [
+        ("Date", issue.posted_at.unwrap_or(issue.created_at).format("%Y-%m-%d %H:%M").to_string()),
+        ("Requester", issue.requester.clone()),
+        ("Department", issue.department.clone()),
+        ("Destination", issue.destination.clone().unwrap_or_default()),
+        ("Issued from", place_name.to_owned()),
+        ("Status", issue.status.label().to_owned()),
+        ("Notes", issue.notes.clone().unwrap_or_default().replace('\n', " ")),
+    ];
+
+    let value_x = MARGIN + 30.0;
+    let value_width = RECEIPT_LAYOUT.page_width - value_x - MARGIN;
+
+    for (index, (label, value)) in header_values.into_iter().enumerate() {
+        let y = MARGIN + 20.0 + index as f64 * ROW_HEIGHT;
+
+        shapes.push(Shape::Text { x: MARGIN, y, size: TEXT_SIZE, text: label.to_owned() });
+        shapes.push(Shape::Text { x: value_x, y, size: TEXT_SIZE, text: fit_text(&value, TEXT_SIZE, value_width) });
+    }
+
+    shapes
+}
+
+fn table_header_shapes(y: f64) -> Vec<Shape> {
+    vec![
+        Shape::Text { x: PRODUCT_X, y, size: TEXT_SIZE, text: "Product".to_owned() },
+        Shape::Text { x: DETAIL_X, y, size: TEXT_SIZE, text: "Lot / Serial numbers".to_owned() },
+        Shape::Text { x: QUANTITY_X, y, size: TEXT_SIZE, text: "Quantity".to_owned() },
+        Shape::Text { x: UNIT_X, y, size: TEXT_SIZE, text: "Unit".to_owned() },
+        Shape::Rect {
+            x: MARGIN,
+            y: y + 2.0,
+            width: RECEIPT_LAYOUT.page_width - 2.0 * MARGIN,
+            height: RULE_WIDTH,
+        },
+    ]
+}
+
+fn line_shapes(line: &IssueLine, products: &[Product], y: f64) -> Vec<Shape> {
+    let product = products.iter().find(|product| product.id == line.product_id);
+
+    let product_name = product
+        .map(|product| product.name.clone())
+        .unwrap_or_else(|| format!("#{}", line.product_id));
+
+    let detail = match (&line.lot_number, line.serial_numbers.is_empty()) {
+        (Some(lot_number), true) => format!("Lot {}", lot_number),
+        (Some(lot_number), false) => format!("Lot {}, {}", lot_number, line.serial_numbers.join(", ")),
+        (None, false) => line.serial_numbers.join(", "),
+        (None, true) => String::new(),
+    };
+
+    vec![
+        Shape::Text { x: PRODUCT_X, y, size: TEXT_SIZE, text: fit_text(&product_name, TEXT_SIZE, DETAIL_X - PRODUCT_X - 2.0) },
+        Shape::Text { x: DETAIL_X, y, size: TEXT_SIZE, text: fit_text(&detail, TEXT_SIZE, QUANTITY_X - DETAIL_X - 2.0) },
+        Shape::Text { x: QUANTITY_X, y, size: TEXT_SIZE, text: line.quantity.to_string() },
+        Shape::Text {
+            x: UNIT_X,
+            y,
+            size: TEXT_SIZE,
+            text: product.and_then(|product| product.unity.clone()).unwrap_or_default(),
+        },
+    ]
+}
+
+/// Two signature lines side by side near the bottom of the page.
+fn signature_shapes() -> Vec<Shape> {
+    let line_y = RECEIPT_LAYOUT.page_height - MARGIN - 25.0;
+    let line_width = 75.0;
+    let right_x = RECEIPT_LAYOUT.page_width - MARGIN - line_width;
+
+    let mut shapes = Vec::new();
+
+    for (x, caption) in [(MARGIN, "Issued by"), (right_x, "Received by")] {
+        shapes.push(Shape::Rect { x, y: line_y, width: line_width, height: RULE_WIDTH });
+        shapes.push(Shape::Text { x, y: line_y + 5.0, size: TEXT_SIZE, text: caption.to_owned() });
+        shapes.push(Shape::Text { x, y: line_y + 15.0, size: TEXT_SIZE, text: "Date: ____ / ____ / ________".to_owned() });
+    }
+
+    shapes
+}
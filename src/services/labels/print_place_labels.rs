@@ -0,0 +1,50 @@
+use std::error::Error;
+use std::path::PathBuf;
+
+use qrcode::QrCode;
+
+use crate::domain::place::Place;
+use crate::services::labels::label_sheet::{LabelLayout, LabelSheet, Shape, fit_text, qr_shapes};
+
+const PADDING: f64 = 2.5;
+
+/// Bin labels with a QR code of the place on the left and its name beside it.
+pub fn print_place_labels(places: &[Place], layout: &LabelLayout, path: PathBuf) -> Result<(), Box<dyn Error>> {
+    let mut sheet = LabelSheet::new(*layout);
+
+    for place in places {
+        sheet.add_label(place_label(place, layout)?);
+    }
+
+    sheet.save(path)
+}
+
+fn place_label(place: &Place, layout: &LabelLayout) -> Result<Vec<Shape>, Box<dyn Error>> {
+    let code = place.label_code();
+    let qr_code = QrCode::new(code.as_bytes())?;
+
+    let qr_size = (layout.label_height - 2.0 * PADDING).min(layout.label_width / 2.0);
+    let text_left = PADDING + qr_size + PADDING;
+    let text_width = layout.label_width - text_left - PADDING;
+
+    let name_size = (layout.label_height * 0.16).min(6.0);
+    let code_size = name_size * 0.6;
+
+    let mut shapes = qr_shapes(&qr_code, PADDING, (layout.label_height - qr_size) / 2.0, qr_size);
+
+    shapes.push(Shape::Text {
+        x: text_left,
+        y: layout.label_height / 2.0,
+        size: name_size,
+        text: fit_text(&place.name, name_size, text_width),
+    });
+
+    shapes.push(Shape::Text {
+        x: text_left,
+        y: layout.label_height / 2.0 + code_size * 1.8,
+        size: code_size,
+        text: code,
+    });
+
+    Ok(shapes)
+}
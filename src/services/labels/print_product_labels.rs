@@ -0,0 +1,90 @@
+use std::error::Error;
+use std::path::PathBuf;
+
+use barcoders::sym::code128::Code128;
+use barcoders::sym::ean13::EAN13;
+use barcoders::sym::ean8::EAN8;
+
+use crate::domain::barcode::{ProductBarcode, Symbology};
+use crate::domain::product::Product;
+use crate::services::labels::label_sheet::{LabelLayout, LabelSheet, Shape, barcode_shapes, fit_text};
+
+const PADDING: f64 = 2.5;
+
+/// Product labels with the name, the SKU and a barcode. The first barcode of
+/// the product is printed, or its SKU as Code 128 when it has none.
+pub fn print_product_labels(products: &[Product], barcodes: &[ProductBarcode], layout: &LabelLayout, path: PathBuf) -> Result<(), Box<dyn Error>> {
+    let mut sheet = LabelSheet::new(*layout);
+
+    for product in products {
+        let barcode = barcodes.iter().find(|barcode| barcode.product_id == product.id);
+        sheet.add_label(product_label(product, barcode, layout)?);
+    }
+
+    sheet.save(path)
+}
+
+fn product_label(product: &Product, barcode: Option<&ProductBarcode>, layout: &LabelLayout) -> Result<Vec<Shape>, Box<dyn Error>> {
+    let content_width = layout.label_width - 2.0 * PADDING;
+    let name_size = (layout.label_height * 0.12).min(4.0);
+    let detail_size = name_size * 0.75;
+
+    let mut shapes = vec![Shape::Text {
+        x: PADDING,
+        y: PADDING + name_size,
+        size: name_size,
+        text: fit_text(&product.name, name_size, content_width),
+    }];
+
+    let mut top = PADDING + name_size * 1.4;
+
+    if let Some(sku) = &product.sku {
+        shapes.push(Shape::Text {
+            x: PADDING,
+            y: top + detail_size,
+            size: detail_size,
+            text: fit_text(&format!("SKU: {}", sku), detail_size, content_width),
+        });
+
+        top += detail_size * 1.4;
+    }
+
+    let code = match (barcode, &product.sku) {
+        (Some(barcode), _) => Some((barcode.barcode.clone(), barcode.symbology)),
+        (None, Some(sku)) => Some((sku.clone(), Symbology::Code128)),
+        (None, None) => None,
+    };
+
+    if let Some((code, symbology)) = code {
+        let modules = encode_barcode(&code, symbology)?;
+
+        // Human-readable digits go under the bars.
+        let bars_height = layout.label_height - top - PADDING - detail_size * 1.4;
+
+        if bars_height > 0.0 {
+            shapes.extend(barcode_shapes(&modules, PADDING, top, content_width, bars_height));
+        }
+
+        shapes.push(Shape::Text {
+            x: PADDING,
+            y: layout.label_height - PADDING,
+            size: detail_size,
+            text: fit_text(&code, detail_size, content_width),
+        });
+    }
+
+    Ok(shapes)
+}
+
+fn encode_barcode(code: &str, symbology: Symbology) -> Result<Vec<u8>, Box<dyn Error>> {
+    let modules = match symbology {
+        Symbology::Ean13 => EAN13::new(code)?.encode(),
+        // A UPC-A code is an EAN-13 code starting with zero.
+        Symbology::UpcA => EAN13::new(format!("0{}", code))?.encode(),
+        Symbology::Ean8 => EAN8::new(code)?.encode(),
+        // 'Ɓ' selects character set B, which covers printable ASCII.
+        Symbology::Code128 => Code128::new(format!("\u{0181}{}", code))?.encode(),
+    };
+
+    Ok(modules)
+}
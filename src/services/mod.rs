@@ -1,2 +1,3 @@
 pub mod export;
-pub mod import;
\ No newline at end of file
+pub mod import;
+pub mod labels;
\ No newline at end of file